@@ -1,3 +1,6 @@
+-- trigram indexes back the substring search on lists
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
 -- PEOPLE --
 -- people_status = 'Active', 'OnLeave', 'Left'
 CREATE TABLE people (
@@ -21,6 +24,8 @@ CREATE TABLE people (
 	bio TEXT,
 	permission_equipment TEXT NOT NULL,
 	permission_people TEXT NOT NULL,
+	permission_culture TEXT NOT NULL,
+	permission_experiment TEXT NOT NULL,
 	create_date TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP NOT NULL
 );
 
@@ -44,7 +49,9 @@ INSERT INTO
 		specializations,
 		picture,
 		permission_equipment,
-		permission_people
+		permission_people,
+		permission_culture,
+		permission_experiment
 	)
 VALUES
 	(
@@ -66,7 +73,9 @@ VALUES
 		'DNA recombination',
 		'gene_splicer.png',
 		'READ(*)|WRITE(*)|CREATE(true)',
-		'READ(equipment[-1])|WRITE(equipment[-1])|CREATE(false)'
+		'READ(equipment[-1])|WRITE(equipment[-1])|CREATE(false)',
+		'READ(*)|WRITE(*)|CREATE(true)',
+		'READ(*)|WRITE(*)|CREATE(true)'
 	),
 	(
 		'mentelle',
@@ -87,7 +96,9 @@ VALUES
 		'Chemical analysis',
 		'elle_ment.png',
 		'READ(*)|WRITE(*)|CREATE(true)',
-		'READ(equipment[-1])|WRITE(equipment[-1])|CREATE(false)'
+		'READ(equipment[-1])|WRITE(equipment[-1])|CREATE(false)',
+		'READ(*)|WRITE(*)|CREATE(true)',
+		'READ(*)|WRITE(*)|CREATE(true)'
 	),
 	(
 		'adam_zyme',
@@ -108,7 +119,9 @@ VALUES
 		'Enzymology',
 		'adam_zyme.png',
 		'READ(*)|WRITE(*)|CREATE(true)',
-		'READ(equipment[-1])|WRITE(equipment[-1])|CREATE(false)'
+		'READ(equipment[-1])|WRITE(equipment[-1])|CREATE(false)',
+		'READ(*)|WRITE(*)|CREATE(true)',
+		'READ(*)|WRITE(*)|CREATE(true)'
 	),
 	(
 		'ano666',
@@ -129,7 +142,9 @@ VALUES
 		'Antibody production',
 		'ann_tibody.png',
 		'READ(*)|WRITE(*)|CREATE(true)',
-		'READ(equipment[-1])|WRITE(equipment[-1])|CREATE(false)'
+		'READ(equipment[-1])|WRITE(equipment[-1])|CREATE(false)',
+		'READ(*)|WRITE(*)|CREATE(true)',
+		'READ(*)|WRITE(*)|CREATE(true)'
 	),
 	(
 		'filter',
@@ -150,7 +165,9 @@ VALUES
 		'Filtration techniques',
 		'phil_ter.png',
 		'READ(*)|WRITE(*)|CREATE(true)',
-		'READ(equipment[-1])|WRITE(equipment[-1])|CREATE(false)'
+		'READ(equipment[-1])|WRITE(equipment[-1])|CREATE(false)',
+		'READ(*)|WRITE(*)|CREATE(true)',
+		'READ(*)|WRITE(*)|CREATE(true)'
 	),
 	(
 		'ben42',
@@ -171,7 +188,9 @@ VALUES
 		'Soil analysis',
 		'ben_doyle.png',
 		'READ(*)|WRITE(*)|CREATE(true)',
-		'READ(equipment[-1])|WRITE(equipment[-1])|CREATE(false)'
+		'READ(equipment[-1])|WRITE(equipment[-1])|CREATE(false)',
+		'READ(*)|WRITE(*)|CREATE(true)',
+		'READ(*)|WRITE(*)|CREATE(true)'
 	),
 	(
 		'arious2',
@@ -192,7 +211,9 @@ VALUES
 		'Virus research',
 		'ari_ous.png',
 		'READ(*)|WRITE(*)|CREATE(true)',
-		'READ(equipment[-1])|WRITE(equipment[-1])|CREATE(false)'
+		'READ(equipment[-1])|WRITE(equipment[-1])|CREATE(false)',
+		'READ(*)|WRITE(*)|CREATE(true)',
+		'READ(*)|WRITE(*)|CREATE(true)'
 	),
 	(
 		'polly',
@@ -213,7 +234,9 @@ VALUES
 		'PCR techniques',
 		'polly_merase.png',
 		'READ(*)|WRITE(*)|CREATE(true)',
-		'READ(equipment[-1])|WRITE(equipment[-1])|CREATE(false)'
+		'READ(equipment[-1])|WRITE(equipment[-1])|CREATE(false)',
+		'READ(*)|WRITE(*)|CREATE(true)',
+		'READ(*)|WRITE(*)|CREATE(true)'
 	),
 	(
 		'dna',
@@ -234,7 +257,9 @@ VALUES
 		'Genome sequencing',
 		'dee_na.png',
 		'READ(*)|WRITE(*)|CREATE(true)',
-		'READ(equipment[-1])|WRITE(equipment[-1])|CREATE(false)'
+		'READ(equipment[-1])|WRITE(equipment[-1])|CREATE(false)',
+		'READ(*)|WRITE(*)|CREATE(true)',
+		'READ(*)|WRITE(*)|CREATE(true)'
 	),
 	(
 		'hturner',
@@ -255,7 +280,9 @@ VALUES
 		'Protein folding',
 		'helix_turner.png',
 		'READ(*)|WRITE(*)|CREATE(true)',
-		'READ(equipment[-1])|WRITE(equipment[-1])|CREATE(false)'
+		'READ(equipment[-1])|WRITE(equipment[-1])|CREATE(false)',
+		'READ(*)|WRITE(*)|CREATE(true)',
+		'READ(*)|WRITE(*)|CREATE(true)'
 	),
 	(
 		'max1000',
@@ -276,7 +303,9 @@ VALUES
 		'Big data analytics',
 		'max_a_million.png',
 		'READ(*)|WRITE(*)|CREATE(true)',
-		'READ(equipment[-1])|WRITE(equipment[-1])|CREATE(false)'
+		'READ(equipment[-1])|WRITE(equipment[-1])|CREATE(false)',
+		'READ(*)|WRITE(*)|CREATE(true)',
+		'READ(*)|WRITE(*)|CREATE(true)'
 	),
 	(
 		'eve',
@@ -297,7 +326,9 @@ VALUES
 		'Species adaptation',
 		'eve_olution.png',
 		'READ(*)|WRITE(*)|CREATE(true)',
-		'READ(equipment[-1])|WRITE(equipment[-1])|CREATE(false)'
+		'READ(equipment[-1])|WRITE(equipment[-1])|CREATE(false)',
+		'READ(*)|WRITE(*)|CREATE(true)',
+		'READ(*)|WRITE(*)|CREATE(true)'
 	),
 	(
 		'mic',
@@ -318,6 +349,8 @@ VALUES
 		'Species of Dune',
 		'mic.png',
 		'READ(*)|WRITE(*)|CREATE(true)',
+		'READ(*)|WRITE(*)|CREATE(true)',
+		'READ(*)|WRITE(*)|CREATE(true)',
 		'READ(*)|WRITE(*)|CREATE(true)'
 	),
 	(
@@ -339,15 +372,78 @@ VALUES
 		'Compiling things',
 		NULL,
 		'READ(equipment[12],equipment[5],equipment[1],equipment[3],person[14])|WRITE(equipment[12],person[14])|CREATE(false)',
+		'READ(*)|WRITE(*)|CREATE(true)',
+		'READ(*)|WRITE(person[14])|CREATE(true)',
+		'READ(*)|WRITE(person[14])|CREATE(true)'
+	);
+
+-- ROLES --
+-- A person's effective permissions are the union of their own permissions and those of all their roles
+CREATE TABLE roles (
+	id INT PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
+	name TEXT NOT NULL UNIQUE,
+	permission_equipment TEXT NOT NULL,
+	permission_people TEXT NOT NULL,
+	permission_culture TEXT NOT NULL,
+	permission_experiment TEXT NOT NULL,
+	create_date TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP NOT NULL
+);
+
+INSERT INTO
+	roles (name, permission_equipment, permission_people, permission_culture, permission_experiment)
+VALUES
+	(
+		'Technician',
+		'READ(*)|WRITE(*)|CREATE(true)',
+		'READ(equipment[-1])|WRITE(equipment[-1])|CREATE(false)',
+		'READ(*)|WRITE(*)|CREATE(true)',
+		'READ(*)|WRITE(*)|CREATE(true)'
+	),
+	(
+		'Lab Manager',
+		'READ(*)|WRITE(*)|CREATE(true)',
+		'READ(*)|WRITE(*)|CREATE(true)',
+		'READ(*)|WRITE(*)|CREATE(true)',
 		'READ(*)|WRITE(*)|CREATE(true)'
 	);
 
+CREATE TABLE people_roles (
+	person INT NOT NULL REFERENCES people (id) ON DELETE CASCADE,
+	role INT NOT NULL REFERENCES roles (id) ON DELETE CASCADE,
+	PRIMARY KEY (person, role)
+);
+
+CREATE INDEX people_roles_role ON people_roles (role);
+
+INSERT INTO
+	people_roles (person, role)
+VALUES
+	(1, 2),
+	(2, 1),
+	(3, 1);
+
 -- EQUIPMENT --
--- equipment_type = 'Flask', 'Vessel', 'IncubationCabinet'
+-- the kinds of equipment, name is what equipment stores and never changes, label is what people see
+-- icon = 'Equipment', 'Flask', 'Vessel', 'IncubationCabinet'
+CREATE TABLE equipment_types (
+	name TEXT PRIMARY KEY,
+	label TEXT NOT NULL UNIQUE,
+	icon TEXT NOT NULL DEFAULT 'Equipment',
+	create_date TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP NOT NULL
+);
+
+INSERT INTO
+	equipment_types (name, label, icon)
+VALUES
+	('Flask', 'Flask', 'Flask'),
+	('Vessel', 'Vessel', 'Vessel'),
+	('IncubationCabinet', 'Incubation Cabinet', 'IncubationCabinet');
+
+-- equipment_type = a name from equipment_types
 -- status = 'Cleaned', 'Prepared', 'Sterilized', 'InUse', 'Dirty', 'Archived'
 CREATE TABLE equipment (
 	id INT PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
-	equipment_type TEXT NOT NULL,
+	equipment_type TEXT NOT NULL REFERENCES equipment_types (name),
 	qrcode TEXT UNIQUE,
 	person INT NOT NULL REFERENCES people (id),
 	create_date TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP NOT NULL,
@@ -359,9 +455,64 @@ CREATE TABLE equipment (
 	cost_in_cent INT,
 	warranty_expiration_date TIMESTAMPTZ,
 	location TEXT,
-	notes TEXT
+	notes TEXT,
+	-- set while the equipment sits in the trash waiting to be restored or purged
+	deleted_at TIMESTAMPTZ,
+	-- bumped by every edit so stale edits can be rejected
+	version INT NOT NULL DEFAULT 1
 );
 
+-- must match the search document in src/equipment/schema_search.rs
+CREATE INDEX equipment_search ON equipment USING GIN (
+	(
+		coalesce(name, '') || ' ' || coalesce(location, '') || ' ' || coalesce(manufacturer, '') || ' ' || coalesce(vendor, '') || ' ' || coalesce(notes, '')
+	) gin_trgm_ops
+);
+
+CREATE INDEX equipment_status ON equipment (status);
+
+CREATE INDEX equipment_equipment_type ON equipment (equipment_type);
+
+-- the status workflow of each equipment type, status changes may only follow these edges
+CREATE TABLE equipment_status_transitions (
+	equipment_type TEXT NOT NULL REFERENCES equipment_types (name) ON DELETE CASCADE,
+	from_status TEXT NOT NULL,
+	to_status TEXT NOT NULL,
+	PRIMARY KEY (equipment_type, from_status, to_status)
+);
+
+INSERT INTO
+	equipment_status_transitions (equipment_type, from_status, to_status)
+SELECT
+	equipment_type,
+	from_status,
+	to_status
+FROM
+	(
+		VALUES
+			('Flask'),
+			('Vessel'),
+			('IncubationCabinet')
+	) AS types (equipment_type)
+	CROSS JOIN (
+		VALUES
+			('Cleaned', 'Prepared'),
+			('Prepared', 'Sterilized'),
+			('Sterilized', 'InUse'),
+			('InUse', 'Dirty'),
+			('Dirty', 'Cleaned'),
+			-- anything can be marked as dirty or archived and archived equipment comes back dirty
+			('Cleaned', 'Dirty'),
+			('Prepared', 'Dirty'),
+			('Sterilized', 'Dirty'),
+			('Archived', 'Dirty'),
+			('Cleaned', 'Archived'),
+			('Prepared', 'Archived'),
+			('Sterilized', 'Archived'),
+			('InUse', 'Archived'),
+			('Dirty', 'Archived')
+	) AS transitions (from_status, to_status);
+
 INSERT INTO
 	equipment (
 		equipment_type,
@@ -560,6 +711,137 @@ VALUES
 		E'Welcome to the "Spore Spa"—where fungi come to relax, rejuvenate, and sporulate! 🧖‍♂️🍄\nBrought to you by WonderCabinets, this state-of-the-art incubation cabinet is the pinnacle of fungal luxury.\nOur team of intrepid scientists and researchers are on a mission to mold a better future by discovering sustainable solutions that are good for the planet.\n\nSo sit back, relax, and let the spores do the work—because saving the world shouldn\'t be a mushroom for error!'
 	);
 
+-- equipment_type = a name from equipment_types
+CREATE TABLE equipment_templates (
+	id INT PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
+	name TEXT NOT NULL UNIQUE,
+	equipment_type TEXT NOT NULL REFERENCES equipment_types (name),
+	manufacturer TEXT,
+	vendor TEXT,
+	cost_in_cent INT,
+	-- how long the warranty runs from the day an item is created from this template
+	warranty_days INT,
+	location TEXT,
+	person INT NOT NULL REFERENCES people (id),
+	create_date TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP NOT NULL
+);
+
+INSERT INTO
+	equipment_templates (name, equipment_type, manufacturer, vendor, cost_in_cent, warranty_days, location, person)
+VALUES
+	('Standard Flask', 'Flask', 'Flasktastic Labs', 'SupplySidekick', 50000, 730, 'Mystery Lab, Area 7', 1),
+	('Culture Vessel', 'Vessel', 'VesselWorks', 'LabLogix', 120000, 365, NULL, 2);
+
+-- extra fields admins define per equipment type next to the fixed columns of equipment
+-- kind = 'Text', 'Number', 'Date', 'Select', 'Boolean'
+CREATE TABLE equipment_fields (
+	id INT PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
+	equipment_type TEXT NOT NULL REFERENCES equipment_types (name) ON DELETE CASCADE,
+	label TEXT NOT NULL,
+	kind TEXT NOT NULL,
+	-- shown after the value of a number field
+	unit TEXT,
+	-- the choices of a select field
+	options TEXT[] NOT NULL DEFAULT '{}',
+	-- the allowed range of a number field
+	min_value DOUBLE PRECISION,
+	max_value DOUBLE PRECISION,
+	create_date TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP NOT NULL,
+	UNIQUE (equipment_type, label)
+);
+
+INSERT INTO
+	equipment_fields (equipment_type, label, kind, unit, options, min_value, max_value)
+VALUES
+	('IncubationCabinet', 'CO2', 'Number', '%', '{}', 0, 100),
+	('IncubationCabinet', 'Min temperature', 'Number', '°C', '{}', -80, 100),
+	('IncubationCabinet', 'Max temperature', 'Number', '°C', '{}', -80, 100),
+	('IncubationCabinet', 'Shelves', 'Number', NULL, '{}', 0, NULL),
+	('Flask', 'Volume', 'Number', 'ml', '{}', 0, NULL),
+	('Vessel', 'Material', 'Select', NULL, '{"Glass", "Stainless steel", "Plastic"}', NULL, NULL),
+	('Vessel', 'Autoclavable', 'Boolean', NULL, '{}', NULL, NULL);
+
+-- value is the normalized text form, see CustomField::validate in src/equipment/schema_custom_field.rs
+CREATE TABLE equipment_field_values (
+	equipment INT NOT NULL REFERENCES equipment (id) ON DELETE CASCADE,
+	field INT NOT NULL REFERENCES equipment_fields (id) ON DELETE CASCADE,
+	value TEXT NOT NULL,
+	PRIMARY KEY (equipment, field)
+);
+
+CREATE INDEX equipment_field_values_field ON equipment_field_values (field, value);
+
+INSERT INTO
+	equipment_field_values (equipment, field, value)
+SELECT
+	equipment.id,
+	equipment_fields.id,
+	seed.value
+FROM
+	(
+		VALUES
+			('Spore Spa', 'CO2', '5'),
+			('Spore Spa', 'Min temperature', '20'),
+			('Spore Spa', 'Max temperature', '37'),
+			('Spore Spa', 'Shelves', '4'),
+			('Flask-in-a-Box', 'Volume', '500'),
+			('Stevens Flask', 'Volume', '250'),
+			('Britta', 'Material', 'Glass'),
+			('Britta', 'Autoclavable', 'true')
+	) AS seed (equipment, label, value)
+	JOIN equipment ON equipment.name = seed.equipment
+	JOIN equipment_fields ON equipment_fields.label = seed.label
+	AND equipment_fields.equipment_type = equipment.equipment_type;
+
+-- recurring work like calibration or filter changes, a plan covers one item or every item of a type
+-- interval_unit = 'days', 'weeks', 'months', 'years'
+CREATE TABLE equipment_maintenance (
+	id INT PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
+	equipment INT REFERENCES equipment (id) ON DELETE CASCADE,
+	equipment_type TEXT REFERENCES equipment_types (name) ON DELETE CASCADE,
+	name TEXT NOT NULL,
+	interval_count INT NOT NULL CHECK (interval_count > 0),
+	interval_unit TEXT NOT NULL CHECK (interval_unit IN ('days', 'weeks', 'months', 'years')),
+	person INT NOT NULL REFERENCES people (id),
+	-- a plan is first due one interval after it was created or the equipment was added, whichever is later
+	create_date TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP NOT NULL,
+	CHECK ((equipment IS NULL) <> (equipment_type IS NULL))
+);
+
+CREATE INDEX equipment_maintenance_equipment ON equipment_maintenance (equipment);
+
+CREATE INDEX equipment_maintenance_equipment_type ON equipment_maintenance (equipment_type);
+
+INSERT INTO
+	equipment_maintenance (equipment, equipment_type, name, interval_count, interval_unit, person, create_date)
+VALUES
+	(NULL, 'IncubationCabinet', 'Replace HEPA filter', 1, 'years', 1, '2024-01-01T00:00:00Z'),
+	(NULL, 'Flask', 'Check for cracks', 3, 'months', 1, '2024-01-01T00:00:00Z'),
+	(12, NULL, 'Calibrate CO2 sensor', 6, 'months', 2, '2024-01-01T00:00:00Z');
+
+-- sort_order = 'asc', 'desc'
+CREATE TABLE equipment_views (
+	id INT PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
+	person INT NOT NULL REFERENCES people (id) ON DELETE CASCADE,
+	name TEXT NOT NULL,
+	field TEXT NOT NULL,
+	sort_order TEXT NOT NULL,
+	-- comma separated columns
+	filter TEXT NOT NULL,
+	items_per_page SMALLINT NOT NULL,
+	archive BOOLEAN NOT NULL DEFAULT FALSE,
+	-- the search and column filters as percent-encoded URL query pairs
+	search TEXT NOT NULL DEFAULT '',
+	is_default BOOLEAN NOT NULL DEFAULT FALSE,
+	shared BOOLEAN NOT NULL DEFAULT FALSE,
+	create_date TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP NOT NULL,
+	UNIQUE (person, name)
+);
+
+CREATE UNIQUE INDEX equipment_views_default ON equipment_views (person) WHERE is_default;
+
+CREATE INDEX equipment_views_shared ON equipment_views (shared) WHERE shared;
+
 CREATE TABLE equipment_notes (
 	id INT PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
 	equipment INT NOT NULL REFERENCES equipment (id) ON DELETE CASCADE,
@@ -1125,7 +1407,7 @@ VALUES
 		NULL
 	);
 
--- log_type = 'cleaning', 'sterilization', 'preparation', 'edit'
+-- log_type = 'cleaning', 'sterilization', 'preparation', 'edit', 'create', 'maintenance'
 CREATE TABLE equipment_log (
 	id INT PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
 	log_type TEXT NOT NULL,
@@ -1136,6 +1418,13 @@ CREATE TABLE equipment_log (
 	field TEXT,
 	old_value TEXT,
 	new_value TEXT,
+	-- the exact values of an 'edit' entry, dates as RFC 3339 and costs in cent, old_value and new_value are for display
+	old_raw TEXT,
+	new_raw TEXT,
+	-- the maintenance plan a 'Maintenance' entry completed
+	maintenance INT REFERENCES equipment_maintenance (id) ON DELETE SET NULL,
+	-- the earlier 'edit' entry whose value this one restored
+	reverts INT REFERENCES equipment_log (id) ON DELETE SET NULL,
 	media1 TEXT,
 	media2 TEXT,
 	media3 TEXT,
@@ -1706,15 +1995,15 @@ VALUES
 		NULL
 	);
 
--- CULTURE
--- culture_contamination_status = 'Clean', 'Xenic', 'Monoxenic', 'Axenic', 'Contaminated', 'ParentContaminated', 'CleanWasContaminated'
+-- CULTURE --
+-- contamination_status = 'Clean', 'Xenic', 'Monoxenic', 'Axenic', 'Contaminated', 'ParentContaminated', 'CleanWasContaminated'
 CREATE TABLE culture (
 	id INT PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
-	qrcode TEXT NOT NULL UNIQUE,
-	create_date TIMESTAMPTZ DEFAULT CURRENT_DATE,
-	create_by INT REFERENCES people (id),
+	qrcode TEXT UNIQUE,
+	person INT NOT NULL REFERENCES people (id),
+	create_date TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP NOT NULL,
 	name TEXT NOT NULL,
-	parent INT NOT NULL REFERENCES culture (id),
+	parent INT REFERENCES culture (id),
 	culture_method TEXT,
 	species TEXT,
 	genus TEXT,
@@ -1722,6 +2011,391 @@ CREATE TABLE culture (
 	storage_conditions TEXT,
 	equipment INT REFERENCES equipment (id),
 	growth_medium TEXT,
-	contamination_status TEXT,
+	contamination_status TEXT NOT NULL,
+	notes TEXT
+);
+
+CREATE INDEX culture_parent ON culture (parent);
+
+INSERT INTO
+	culture (
+		qrcode,
+		person,
+		create_date,
+		name,
+		parent,
+		culture_method,
+		species,
+		genus,
+		location,
+		storage_conditions,
+		equipment,
+		growth_medium,
+		contamination_status,
+		notes
+	)
+VALUES
+	(
+		'/upload_media/culture/0-5k/1/qr_1.svg',
+		1,
+		'2023-02-01T09:12:00.000000000Z',
+		'Golden Teacher Mother',
+		NULL,
+		'Spore print',
+		'cubensis',
+		'Psilocybe',
+		'Fridge B, Shelf 2',
+		'4°C, dark',
+		NULL,
+		'Malt extract agar',
+		'Clean',
+		'The one that started it all.'
+	),
+	(
+		'/upload_media/culture/0-5k/2/qr_2.svg',
+		1,
+		'2023-02-14T10:30:00.000000000Z',
+		'Golden Teacher G1',
+		1,
+		'Agar transfer',
+		'cubensis',
+		'Psilocybe',
+		'Incubation Cabinet',
+		'24°C, 80% humidity',
+		12,
+		'Malt extract agar',
+		'Axenic',
+		NULL
+	),
+	(
+		'/upload_media/culture/0-5k/3/qr_3.svg',
+		2,
+		'2023-03-02T14:05:00.000000000Z',
+		'Golden Teacher G2 Grain',
+		2,
+		'Grain spawn',
+		'cubensis',
+		'Psilocybe',
+		'Back Row D, Column 27',
+		'24°C',
+		1,
+		'Rye grain',
+		'Clean',
+		E'Colonised in record time.\nShake at 30%.'
+	),
+	(
+		'/upload_media/culture/0-5k/4/qr_4.svg',
+		3,
+		'2023-04-11T08:45:00.000000000Z',
+		'Lions Mane Liquid',
+		NULL,
+		'Liquid culture',
+		'erinaceus',
+		'Hericium',
+		'Fridge A, Door',
+		'4°C',
+		2,
+		'Honey water',
+		'Contaminated',
+		'Cloudy after a week, smells like regret.'
+	),
+	(
+		'/upload_media/culture/0-5k/5/qr_5.svg',
+		3,
+		'2023-04-20T16:20:00.000000000Z',
+		'Lions Mane Plate',
+		4,
+		'Agar transfer',
+		'erinaceus',
+		'Hericium',
+		'Incubation Cabinet',
+		'22°C',
+		NULL,
+		'Potato dextrose agar',
+		'ParentContaminated',
+		NULL
+	),
+	(
+		'/upload_media/culture/0-5k/6/qr_6.svg',
+		14,
+		'2024-01-07T11:00:00.000000000Z',
+		'Oyster Slant',
+		NULL,
+		'Slant',
+		'ostreatus',
+		'Pleurotus',
+		'Fridge B, Shelf 1',
+		'2°C, sealed',
+		NULL,
+		'Malt extract agar',
+		'Xenic',
+		'Long term storage, check every six months.'
+	);
+
+CREATE TABLE culture_notes (
+	id INT PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
+	culture INT NOT NULL REFERENCES culture (id) ON DELETE CASCADE,
+	create_date TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP NOT NULL,
+	person INT NOT NULL REFERENCES people (id),
+	notes TEXT NOT NULL,
+	media1 TEXT,
+	media2 TEXT,
+	media3 TEXT,
+	media4 TEXT,
+	media5 TEXT,
+	media6 TEXT,
+	media7 TEXT,
+	media8 TEXT,
+	media9 TEXT,
+	media10 TEXT
+);
+
+CREATE INDEX culture_notes_culture ON culture_notes (culture);
+
+CREATE INDEX culture_notes_person ON culture_notes (person);
+
+INSERT INTO
+	culture_notes (culture, person, notes)
+VALUES
+	(1, 1, 'Fresh print taken, looks very healthy.'),
+	(2, 2, E'Rhizomorphic growth all the way to the edge.\nReady for grain.'),
+	(4, 3, 'Green spots showing up near the injection port.');
+
+-- log_type = 'edit', 'contamination'
+CREATE TABLE culture_log (
+	id INT PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
+	log_type TEXT NOT NULL,
+	culture INT NOT NULL REFERENCES culture (id) ON DELETE CASCADE,
+	create_date TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP NOT NULL,
+	person INT NOT NULL REFERENCES people (id),
+	notes TEXT,
+	field TEXT,
+	old_value TEXT,
+	new_value TEXT,
+	media1 TEXT,
+	media2 TEXT,
+	media3 TEXT,
+	media4 TEXT,
+	media5 TEXT,
+	media6 TEXT,
+	media7 TEXT,
+	media8 TEXT,
+	media9 TEXT,
+	media10 TEXT
+);
+
+CREATE INDEX culture_log_culture ON culture_log (culture);
+
+CREATE INDEX culture_log_person ON culture_log (person);
+
+INSERT INTO
+	culture_log (log_type, culture, person, notes, field, old_value, new_value)
+VALUES
+	('edit', 2, 1, 'Moved into the incubator', 'location', 'Fridge B, Shelf 2', 'Incubation Cabinet'),
+	('contamination', 4, 3, 'Trichoderma, most likely', 'contamination_status', 'Clean', 'Contaminated'),
+	('contamination', 5, 3, 'Parent culture was contaminated', 'contamination_status', 'Clean', 'ParentContaminated');
+
+-- EXPERIMENT --
+CREATE TABLE experiment (
+	id INT PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
+	person INT NOT NULL REFERENCES people (id),
+	create_date TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP NOT NULL,
+	name TEXT NOT NULL,
+	protocol TEXT,
+	start_date TIMESTAMPTZ NOT NULL,
+	end_date TIMESTAMPTZ,
 	notes TEXT
 );
+
+CREATE INDEX experiment_person ON experiment (person);
+
+INSERT INTO
+	experiment (person, create_date, name, protocol, start_date, end_date, notes)
+VALUES
+	(
+		1,
+		'2023-02-10T09:00:00.000000000Z',
+		'Golden Teacher Colonisation Speed',
+		E'1. Inoculate three jars from G1\n2. Incubate at 24°C\n3. Record colonisation daily',
+		'2023-02-14T09:00:00.000000000Z',
+		'2023-03-14T17:00:00.000000000Z',
+		'Grain won by a mile.'
+	),
+	(
+		3,
+		'2023-04-01T11:30:00.000000000Z',
+		'Lions Mane Liquid Culture Sterility',
+		'Compare sterility of honey water vs. light malt extract after pressure cooking for 30 and 45 minutes.',
+		'2023-04-11T08:00:00.000000000Z',
+		NULL,
+		NULL
+	),
+	(
+		14,
+		'2024-01-05T15:45:00.000000000Z',
+		'Cold Storage Viability',
+		'Store slants at 2°C and test revival every six months.',
+		'2024-01-07T11:00:00.000000000Z',
+		NULL,
+		'Long running, check the fridge log first.'
+	);
+
+CREATE TABLE experiment_equipment (
+	experiment INT NOT NULL REFERENCES experiment (id) ON DELETE CASCADE,
+	equipment INT NOT NULL REFERENCES equipment (id) ON DELETE CASCADE,
+	PRIMARY KEY (experiment, equipment)
+);
+
+CREATE INDEX experiment_equipment_equipment ON experiment_equipment (equipment);
+
+INSERT INTO
+	experiment_equipment (experiment, equipment)
+VALUES
+	(1, 1),
+	(1, 12),
+	(2, 2),
+	(2, 5),
+	(2, 12),
+	(3, 7);
+
+CREATE TABLE experiment_culture (
+	experiment INT NOT NULL REFERENCES experiment (id) ON DELETE CASCADE,
+	culture INT NOT NULL REFERENCES culture (id) ON DELETE CASCADE,
+	PRIMARY KEY (experiment, culture)
+);
+
+CREATE INDEX experiment_culture_culture ON experiment_culture (culture);
+
+INSERT INTO
+	experiment_culture (experiment, culture)
+VALUES
+	(1, 2),
+	(1, 3),
+	(2, 4),
+	(2, 5),
+	(3, 6);
+
+-- AUDIT --
+-- action = 'delete', 'restore', 'purge'
+-- entries outlive the records they describe so there is no foreign key on entity_id
+CREATE TABLE audit_log (
+	id INT PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
+	action TEXT NOT NULL,
+	entity TEXT NOT NULL,
+	entity_id INT NOT NULL,
+	person INT NOT NULL REFERENCES people (id),
+	details TEXT,
+	create_date TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP NOT NULL
+);
+
+CREATE INDEX audit_log_entity ON audit_log (entity, entity_id);
+
+-- JOBS --
+-- one row per registered job, the runner claims a job by setting locked_until so only one instance runs it
+CREATE TABLE jobs (
+	name TEXT PRIMARY KEY,
+	interval_seconds INT NOT NULL CHECK (interval_seconds > 0),
+	next_run TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP NOT NULL,
+	-- failed attempts since the last success, drives the retry backoff
+	attempts INT DEFAULT 0 NOT NULL,
+	locked_until TIMESTAMPTZ,
+	locked_by TEXT
+);
+
+-- status = 'running', 'succeeded', 'failed'
+CREATE TABLE job_runs (
+	id INT PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
+	job TEXT NOT NULL REFERENCES jobs (name) ON DELETE CASCADE,
+	status TEXT NOT NULL,
+	attempt INT NOT NULL,
+	instance TEXT NOT NULL,
+	error TEXT,
+	started_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP NOT NULL,
+	finished_at TIMESTAMPTZ
+);
+
+CREATE INDEX job_runs_job ON job_runs (job, started_at);
+
+-- REMINDERS --
+-- a single row, reminders go out this many days before a warranty expires or maintenance is due
+CREATE TABLE reminder_settings (
+	id INT PRIMARY KEY DEFAULT 1 CHECK (id = 1),
+	reminder_days INT[] DEFAULT '{60,30,7}' NOT NULL,
+	-- sent every reminder on top of the person the equipment belongs to
+	recipients TEXT[] DEFAULT '{}' NOT NULL
+);
+
+INSERT INTO
+	reminder_settings (reminder_days, recipients)
+VALUES
+	('{60,30,7}', '{lab-manager@codon.test}');
+
+-- kind = 'warranty', 'maintenance'
+-- reference is the maintenance plan for maintenance reminders and 0 for warranties
+-- recipient is the address an email went to, '' stands for the in-app notification
+CREATE TABLE sent_reminders (
+	equipment INT NOT NULL REFERENCES equipment (id) ON DELETE CASCADE,
+	kind TEXT NOT NULL,
+	reference INT NOT NULL,
+	due_date DATE NOT NULL,
+	days INT NOT NULL,
+	recipient TEXT NOT NULL,
+	sent_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP NOT NULL,
+	PRIMARY KEY (equipment, kind, reference, due_date, days, recipient)
+);
+
+-- WEBHOOKS --
+-- events = 'status_change', 'field_edit', 'note_created', 'note_deleted', 'equipment_created'
+CREATE TABLE webhooks (
+	id INT PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
+	url TEXT NOT NULL,
+	-- key of the HMAC-SHA256 signature sent with every delivery
+	secret TEXT NOT NULL,
+	events TEXT[] DEFAULT '{}' NOT NULL,
+	enabled BOOLEAN DEFAULT TRUE NOT NULL,
+	person INT REFERENCES people (id) ON DELETE SET NULL,
+	create_date TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP NOT NULL
+);
+
+-- status = 'pending', 'delivered', 'failed'
+CREATE TABLE webhook_deliveries (
+	id INT PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
+	webhook INT NOT NULL REFERENCES webhooks (id) ON DELETE CASCADE,
+	event TEXT NOT NULL,
+	payload TEXT NOT NULL,
+	status TEXT DEFAULT 'pending' NOT NULL,
+	attempts INT DEFAULT 0 NOT NULL,
+	next_attempt TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP NOT NULL,
+	response_status INT,
+	error TEXT,
+	create_date TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP NOT NULL,
+	delivered_at TIMESTAMPTZ
+);
+
+CREATE INDEX webhook_deliveries_pending ON webhook_deliveries (next_attempt) WHERE status = 'pending';
+CREATE INDEX webhook_deliveries_webhook ON webhook_deliveries (webhook, create_date);
+
+-- NOTIFICATIONS --
+-- people who want to hear about equipment they don't own
+CREATE TABLE equipment_watchers (
+	equipment INT NOT NULL REFERENCES equipment (id) ON DELETE CASCADE,
+	person INT NOT NULL REFERENCES people (id) ON DELETE CASCADE,
+	create_date TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP NOT NULL,
+	PRIMARY KEY (equipment, person)
+);
+
+-- kind = 'status', 'note', 'warranty', 'maintenance'
+-- actor is empty for notifications the server raises on its own like reminders
+CREATE TABLE notifications (
+	id INT PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
+	person INT NOT NULL REFERENCES people (id) ON DELETE CASCADE,
+	equipment INT REFERENCES equipment (id) ON DELETE CASCADE,
+	actor INT REFERENCES people (id) ON DELETE SET NULL,
+	kind TEXT NOT NULL,
+	message TEXT NOT NULL,
+	read_at TIMESTAMPTZ,
+	create_date TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP NOT NULL
+);
+
+CREATE INDEX notifications_person ON notifications (person, create_date);
+CREATE INDEX notifications_unread ON notifications (person) WHERE read_at IS NULL;
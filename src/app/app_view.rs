@@ -1,13 +1,19 @@
 use crate::{
 	auth::{Login, Logout, User, get_user},
+	culture::{Culture, CultureAdd, CultureDetail},
 	ds::Ds,
-	equipment::{Equipment, EquipmentAdd, EquipmentDetail},
+	equipment::{Equipment, EquipmentAdd, EquipmentDetail, EquipmentImport, EquipmentTrash, EquipmentTypes, Maintenance},
 	error_template::{AppError, ErrorTemplate},
+	experiment::{Experiment, ExperimentAdd, ExperimentDetail},
 	footer::Footer,
 	header::Header,
 	home::Home,
+	jobs::Jobs,
 	login::Login,
+	notifications::Notifications,
+	people::{People, PeopleAdd, PeopleDetail, RoleDetail, Roles},
 	profile::Profile,
+	webhooks::Webhooks,
 };
 
 use leptos::*;
@@ -21,6 +27,8 @@ pub type ScrollableBody = RwSignal<bool>;
 pub type LoginAction = Action<Login, Result<(), ServerFnError>>;
 pub type LogoutAction = Action<Logout, Result<(), ServerFnError>>;
 pub type UserSignal = RwSignal<Option<User>>;
+/// Bumped whenever notifications are read so the unread count in the nav refreshes
+pub type NotificationsRefetch = RwSignal<usize>;
 
 #[component]
 pub fn App() -> impl IntoView {
@@ -71,6 +79,8 @@ pub fn App() -> impl IntoView {
 	});
 	provide_context::<UserSignal>(user_signal);
 
+	provide_context::<NotificationsRefetch>(create_rw_signal(0));
+
 	view! {
 		<Body class=move || { if is_body_scrollable.get() { "" } else { "not_scrollable" } } />
 		<Stylesheet id="leptos" href="/pkg/codon.css" />
@@ -94,7 +104,10 @@ pub fn App() -> impl IntoView {
 						format!(
 							"{} frame{}",
 							css::main,
-							if location.pathname.get() == "/equipment" { " large" } else { "" },
+							match location.pathname.get().as_str() {
+								"/equipment" | "/culture" | "/experiment" | "/people" => " large",
+								_ => "",
+							},
 						)
 					}>
 						<Routes>
@@ -104,7 +117,25 @@ pub fn App() -> impl IntoView {
 							<Route path="/profile" view=move || view! { <Profile /> } />
 							<Route path="/equipment" view=Equipment />
 							<Route path="/equipment/add" view=EquipmentAdd />
+							<Route path="/equipment/import" view=EquipmentImport />
+							<Route path="/equipment/types" view=EquipmentTypes />
+							<Route path="/equipment/maintenance" view=Maintenance />
+							<Route path="/equipment/trash" view=EquipmentTrash />
 							<Route path="/equipment/:id" view=EquipmentDetail />
+							<Route path="/culture" view=Culture />
+							<Route path="/culture/add" view=CultureAdd />
+							<Route path="/culture/:id" view=CultureDetail />
+							<Route path="/experiment" view=Experiment />
+							<Route path="/experiment/add" view=ExperimentAdd />
+							<Route path="/experiment/:id" view=ExperimentDetail />
+							<Route path="/people" view=People />
+							<Route path="/people/add" view=PeopleAdd />
+							<Route path="/people/roles" view=Roles />
+							<Route path="/people/roles/:id" view=RoleDetail />
+							<Route path="/people/:id" view=PeopleDetail />
+							<Route path="/jobs" view=Jobs />
+							<Route path="/webhooks" view=Webhooks />
+							<Route path="/notifications" view=Notifications />
 						</Routes>
 					</main>
 				</Router>
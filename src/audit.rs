@@ -0,0 +1,22 @@
+use sqlx::PgExecutor;
+
+/// Write an entry to the audit log.
+/// Entries are kept even after the record they describe has been purged.
+pub async fn record_audit<'a, E: PgExecutor<'a>>(
+	executor: E,
+	action: &str,
+	entity: &str,
+	entity_id: i32,
+	person: i32,
+	details: Option<String>,
+) -> Result<(), sqlx::Error> {
+	sqlx::query("INSERT INTO audit_log (action, entity, entity_id, person, details) VALUES ($1, $2, $3, $4, $5)")
+		.bind(action)
+		.bind(entity)
+		.bind(entity_id)
+		.bind(person)
+		.bind(details)
+		.execute(executor)
+		.await
+		.map(|_| ())
+}
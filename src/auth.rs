@@ -1,7 +1,6 @@
-use crate::{
-	equipment::PeopleStatus,
-	permission::{Permission, Permissions, Scope},
-};
+#[cfg(feature = "ssr")]
+use crate::permission::Permission;
+use crate::{equipment::PeopleStatus, permission::Permissions};
 
 use leptos::*;
 use serde::{Deserialize, Serialize};
@@ -19,6 +18,10 @@ pub struct User {
 	pub username: String,
 	pub permission_equipment: Permissions,
 	pub permission_people: Permissions,
+	pub permission_culture: Permissions,
+	pub permission_experiment: Permissions,
+	/// Ids of the roles this person holds, their permissions are already merged into the ones above
+	pub roles: Vec<i32>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -32,19 +35,36 @@ pub struct UserSQL {
 	pub password: String,
 	pub permission_equipment: String,
 	pub permission_people: String,
+	pub permission_culture: String,
+	pub permission_experiment: String,
+}
+
+/// A broken permission string must not lock everyone out so we fall back to no access for that dimension
+#[cfg(feature = "ssr")]
+fn parse_or_none(owner: &str, name: &str, perm: String) -> Permissions {
+	Permission::parse(perm).unwrap_or_else(|error| {
+		eprintln!("Invalid {name} permission for {owner}: {error}");
+		Permissions::none()
+	})
 }
 
 #[cfg(feature = "ssr")]
 impl From<UserSQL> for User {
 	fn from(val: UserSQL) -> Self {
+		let owner = format!("user {}", val.id);
+		let parse = |name: &str, perm: String| parse_or_none(&owner, name, perm);
+
 		User {
 			id: val.id,
 			status: PeopleStatus::parse(val.status),
 			preferred_name: val.preferred_name,
 			picture: val.picture,
 			username: val.username,
-			permission_equipment: Permission::parse(val.permission_equipment).expect("Invalid permission string"),
-			permission_people: Permission::parse(val.permission_people).expect("Invalid permission string"),
+			permission_equipment: parse("equipment", val.permission_equipment),
+			permission_people: parse("people", val.permission_people),
+			permission_culture: parse("culture", val.permission_culture),
+			permission_experiment: parse("experiment", val.permission_experiment),
+			roles: Vec::new(),
 		}
 	}
 }
@@ -65,23 +85,20 @@ impl Default for User {
 			preferred_name: Default::default(),
 			picture: None,
 			username: "Guest".into(),
-			permission_equipment: Permissions::All {
-				read: Permission::Read(vec![Scope::Equipment(-1)]),
-				write: Permission::Write(vec![Scope::Equipment(-1)]),
-				create: Permission::Create(false),
-			},
-			permission_people: Permissions::All {
-				read: Permission::Read(vec![Scope::Equipment(-1)]),
-				write: Permission::Write(vec![Scope::Equipment(-1)]),
-				create: Permission::Create(false),
-			},
+			permission_equipment: Permissions::none(),
+			permission_people: Permissions::none(),
+			permission_culture: Permissions::none(),
+			permission_experiment: Permissions::none(),
+			roles: Vec::new(),
 		}
 	}
 }
 
 #[cfg(feature = "ssr")]
 pub mod ssr {
+	use super::parse_or_none;
 	pub use super::{User, UserPasshash, UserSQL};
+	use crate::permission::Permissions;
 
 	pub use argon2::{
 		self, Argon2,
@@ -96,38 +113,83 @@ pub mod ssr {
 
 	pub type AuthSession = axum_session_auth::AuthSession<User, i32, SessionPgPool, PgPool>;
 
+	/// People who left keep their row for history but lose access immediately
+	pub fn active_user(auth: &AuthSession) -> Option<User> {
+		auth.current_user.clone().filter(|user| user.status != super::PeopleStatus::Left)
+	}
+
 	impl User {
 		pub async fn get_from_id_with_passhash(id: i32, pool: &PgPool) -> Option<(Self, UserPasshash)> {
 			let sqluser = sqlx::query_as::<_, UserSQL>(
-				"SELECT id, status, preferred_name, picture, username, password, permission_equipment, permission_people FROM people WHERE id = $1",
+				"SELECT id, status, preferred_name, picture, username, password, permission_equipment, permission_people, permission_culture, permission_experiment FROM people WHERE id = $1",
 			)
 			.bind(id)
 			.fetch_one(pool)
 			.await
 			.ok()?;
 
-			Some(sqluser.into_user())
+			let (user, passhash) = sqluser.into_user();
+			Some((user.with_roles(&mut *pool.acquire().await.ok()?).await?, passhash))
 		}
 
 		pub async fn get_from_id(id: i32, pool: &PgPool) -> Option<Self> {
 			User::get_from_id_with_passhash(id, pool).await.map(|(user, _)| user)
 		}
 
+		/// Loads a user with their role permissions on a connection that may be inside a transaction
+		pub async fn get_from_id_on(id: i32, connection: &mut sqlx::PgConnection) -> Option<Self> {
+			let sqluser = sqlx::query_as::<_, UserSQL>(
+				"SELECT id, status, preferred_name, picture, username, password, permission_equipment, permission_people, permission_culture, permission_experiment FROM people WHERE id = $1",
+			)
+			.bind(id)
+			.fetch_one(&mut *connection)
+			.await
+			.ok()?;
+
+			let (user, _) = sqluser.into_user();
+			user.with_roles(connection).await
+		}
+
 		pub async fn get_from_username_with_passhash(name: String, pool: &PgPool) -> Option<(Self, UserPasshash)> {
 			let sqluser = sqlx::query_as::<_, UserSQL>(
-				"SELECT id, status, preferred_name, picture, username, password, permission_equipment, permission_people FROM people WHERE username = $1",
+				"SELECT id, status, preferred_name, picture, username, password, permission_equipment, permission_people, permission_culture, permission_experiment FROM people WHERE username = $1",
 			)
 			.bind(name)
 			.fetch_one(pool)
 			.await
 			.ok()?;
 
-			Some(sqluser.into_user())
+			let (user, passhash) = sqluser.into_user();
+			Some((user.with_roles(&mut *pool.acquire().await.ok()?).await?, passhash))
 		}
 
 		pub async fn get_from_username(name: String, pool: &PgPool) -> Option<Self> {
 			User::get_from_username_with_passhash(name, pool).await.map(|(user, _)| user)
 		}
+
+		/// Merges the permissions of every role the user holds into their own
+		async fn with_roles(mut self, connection: &mut sqlx::PgConnection) -> Option<Self> {
+			let roles = sqlx::query_as::<_, (i32, String, String, String, String)>(
+				"SELECT roles.id, roles.permission_equipment, roles.permission_people, roles.permission_culture, roles.permission_experiment FROM roles JOIN people_roles ON people_roles.role = roles.id WHERE people_roles.person = $1",
+			)
+			.bind(self.id)
+			.fetch_all(connection)
+			.await
+			.ok()?;
+
+			for (id, equipment, people, culture, experiment) in roles {
+				let owner = format!("role {id}");
+				let merge = |own: &Permissions, name: &str, perm: String| own.union(&parse_or_none(&owner, name, perm));
+
+				self.permission_equipment = merge(&self.permission_equipment, "equipment", equipment);
+				self.permission_people = merge(&self.permission_people, "people", people);
+				self.permission_culture = merge(&self.permission_culture, "culture", culture);
+				self.permission_experiment = merge(&self.permission_experiment, "experiment", experiment);
+				self.roles.push(id);
+			}
+
+			Some(self)
+		}
 	}
 
 	#[async_trait]
@@ -154,14 +216,14 @@ pub mod ssr {
 /// ![allow_no_get_user]
 #[server(prefix = "/api")]
 pub async fn get_user() -> Result<Option<User>, ServerFnError> {
-	use crate::auth::ssr::AuthSession;
+	use crate::auth::ssr::{AuthSession, active_user};
 
 	let auth = match use_context::<AuthSession>() {
 		Some(auth) => auth,
 		None => return Ok(None),
 	};
 
-	Ok(auth.current_user)
+	Ok(active_user(&auth))
 }
 
 /// ![allow_no_get_user]
@@ -186,6 +248,9 @@ pub async fn login(
 		.map_err(|error| ServerFnError::<NoCustomError>::ServerError(format!("Hash parsing error: {}", error)))?;
 
 	match Argon2::default().verify_password(password.as_bytes(), &parsed_hash) {
+		Ok(_) if user.status == PeopleStatus::Left => {
+			Err(ServerFnError::ServerError("This account has been deactivated.".to_string()))
+		},
 		Ok(_) => {
 			auth.login_user(user.id);
 			auth.remember_user(remember.is_some());
@@ -224,6 +289,10 @@ pub async fn signup(
 		None => return Err(ServerFnError::Request(String::from("User not authenticated"))),
 	};
 
+	if password.trim().is_empty() {
+		return Err(ServerFnError::ServerError("The password can't be empty.".to_string()));
+	}
+
 	if password != password_confirmation {
 		return Err(ServerFnError::ServerError("Passwords did not match.".to_string()));
 	}
@@ -237,12 +306,13 @@ pub async fn signup(
 
 	sqlx::query(
 		"INSERT INTO people
-		(username, password, permission_equipment, permission_people)
+		(username, password, permission_equipment, permission_people, permission_culture, permission_experiment)
 		VALUES
-		($1, $2, 'READ(-1)|WRITE(-1)|CREATE(false)', 'READ(-1)|WRITE(-1)|CREATE(false)')",
+		($1, $2, $3, $3, $3, $3)",
 	)
 	.bind(username.clone())
 	.bind(password_hashed)
+	.bind(Permissions::none().to_string())
 	.execute(&pool)
 	.await?;
 
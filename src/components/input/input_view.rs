@@ -6,7 +6,7 @@ stylance::import_style!(css, "input.module.css");
 pub fn Input(
 	#[prop(optional)] placeholder: &'static str,
 	#[prop(optional)] value: RwSignal<String>,
-	#[prop(optional)] name: &'static str,
+	#[prop(optional, into)] name: MaybeSignal<String>,
 	#[prop(optional, default = "text")] kind: &'static str,
 	#[prop(optional)] disabled: RwSignal<bool>,
 	#[prop(optional)] required: bool,
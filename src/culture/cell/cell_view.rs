@@ -0,0 +1,33 @@
+use crate::{culture::ContaminationStatus, equipment::EquipmentCellView};
+
+use leptos::*;
+
+stylance::import_style!(css, "cell.module.css");
+
+impl EquipmentCellView for ContaminationStatus {
+	fn view(self, _: bool) -> impl IntoView {
+		match self {
+			ContaminationStatus::Clean => {
+				view! { <div class=css::clean>"Clean"</div> }
+			},
+			ContaminationStatus::Xenic => {
+				view! { <div class=css::xenic>"Xenic"</div> }
+			},
+			ContaminationStatus::Monoxenic => {
+				view! { <div class=css::monoxenic>"Monoxenic"</div> }
+			},
+			ContaminationStatus::Axenic => {
+				view! { <div class=css::axenic>"Axenic"</div> }
+			},
+			ContaminationStatus::Contaminated => {
+				view! { <div class=css::contaminated>"Contaminated"</div> }
+			},
+			ContaminationStatus::ParentContaminated => {
+				view! { <div class=css::parent_contaminated>"Parent" "\u{00A0}" "Contaminated"</div> }
+			},
+			ContaminationStatus::CleanWasContaminated => {
+				view! { <div class=css::clean_was_contaminated>"Clean" "\u{00A0}" "(was" "\u{00A0}" "Contaminated)"</div> }
+			},
+		}
+	}
+}
@@ -0,0 +1,155 @@
+use crate::{
+	culture::ContaminationStatus,
+	permission::{Dimension, Permission, Subject},
+};
+
+use leptos::ServerFnError;
+use sqlx::PgConnection;
+
+/// Write a contamination log entry for the culture and update its status
+async fn set_contamination_status(
+	connection: &mut PgConnection,
+	id: i32,
+	person: i32,
+	note: &str,
+	old_value: &str,
+	new_value: ContaminationStatus,
+) -> Result<(), ServerFnError> {
+	sqlx::query(
+		r#"INSERT INTO culture_log
+		(log_type, culture, person, notes, field, old_value, new_value)
+		VALUES
+		($1, $2, $3, $4, $5, $6, $7)"#,
+	)
+	.bind("contamination")
+	.bind(id)
+	.bind(person)
+	.bind(note)
+	.bind("contamination_status")
+	.bind(old_value)
+	.bind(format!("{:#?}", new_value))
+	.execute(&mut *connection)
+	.await
+	.map_err::<ServerFnError, _>(|error| ServerFnError::ServerError(error.to_string()))?;
+
+	sqlx::query("UPDATE culture SET contamination_status = $1 WHERE id = $2")
+		.bind(format!("{:#?}", new_value))
+		.bind(id)
+		.execute(&mut *connection)
+		.await
+		.map_err::<ServerFnError, _>(|error| ServerFnError::ServerError(error.to_string()))?;
+
+	Ok(())
+}
+
+/// Flag every descendant of a contaminated culture as `ParentContaminated`.
+/// This ignores write permissions on the descendants on purpose, a contamination always has to reach the whole lineage.
+pub async fn mark_descendants_parent_contaminated(
+	connection: &mut PgConnection,
+	id: i32,
+	person: i32,
+) -> Result<(), ServerFnError> {
+	let descendants: Vec<(i32, String)> = sqlx::query_as(
+		r#"
+		WITH RECURSIVE descendants AS (
+			SELECT id FROM culture WHERE parent = $1
+			UNION
+			SELECT culture.id FROM culture JOIN descendants ON culture.parent = descendants.id
+		)
+		SELECT id, contamination_status
+		FROM culture
+		WHERE
+			id IN (SELECT id FROM descendants)
+			AND contamination_status NOT IN ('Contaminated', 'ParentContaminated')
+		ORDER BY id"#,
+	)
+	.bind(id)
+	.fetch_all(&mut *connection)
+	.await
+	.map_err::<ServerFnError, _>(|error| ServerFnError::ServerError(error.to_string()))?;
+
+	let note = format!("Culture #{id} in the lineage was marked as contaminated");
+	for (descendant, old_value) in descendants {
+		set_contamination_status(
+			connection,
+			descendant,
+			person,
+			&note,
+			&old_value,
+			ContaminationStatus::ParentContaminated,
+		)
+		.await?;
+	}
+
+	Ok(())
+}
+
+/// Clear a `ParentContaminated` culture and all `ParentContaminated` cultures below it the user can write to.
+/// Fails while any ancestor is still marked as `Contaminated`.
+pub async fn clear_parent_contamination(
+	connection: &mut PgConnection,
+	id: i32,
+	person: i32,
+	perm: &Permission,
+	note: &str,
+) -> Result<(), ServerFnError> {
+	let contaminated_ancestors: i64 = sqlx::query_scalar(
+		r#"
+		WITH RECURSIVE ancestors AS (
+			SELECT parent AS id FROM culture WHERE id = $1
+			UNION
+			SELECT culture.parent FROM culture JOIN ancestors ON culture.id = ancestors.id
+		)
+		SELECT COUNT(*)
+		FROM culture
+		WHERE
+			id IN (SELECT id FROM ancestors)
+			AND contamination_status = 'Contaminated'"#,
+	)
+	.bind(id)
+	.fetch_one(&mut *connection)
+	.await
+	.map_err::<ServerFnError, _>(|error| ServerFnError::ServerError(error.to_string()))?;
+
+	if contaminated_ancestors > 0 {
+		return Err(ServerFnError::Request(String::from("An ancestor of this culture is still contaminated")));
+	}
+
+	let cultures: Vec<i32> = sqlx::query_scalar(
+		r#"
+		WITH RECURSIVE cleared AS (
+			SELECT id FROM culture WHERE id = $1 AND contamination_status = 'ParentContaminated'
+			UNION
+			SELECT culture.id
+			FROM culture JOIN cleared ON culture.parent = cleared.id
+			WHERE culture.contamination_status = 'ParentContaminated'
+		)
+		SELECT id FROM culture WHERE id IN (SELECT id FROM cleared) ORDER BY id"#,
+	)
+	.bind(id)
+	.fetch_all(&mut *connection)
+	.await
+	.map_err::<ServerFnError, _>(|error| ServerFnError::ServerError(error.to_string()))?;
+
+	if !cultures.contains(&id) {
+		return Err(ServerFnError::Request(String::from("Culture is not marked as parent contaminated")));
+	}
+
+	for culture in cultures {
+		if culture == id
+			|| perm.has_permission_on("write", &Subject::load(Dimension::Culture, culture, &mut *connection).await?)
+		{
+			set_contamination_status(
+				connection,
+				culture,
+				person,
+				note,
+				"ParentContaminated",
+				ContaminationStatus::CleanWasContaminated,
+			)
+			.await?;
+		}
+	}
+
+	Ok(())
+}
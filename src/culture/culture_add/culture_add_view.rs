@@ -0,0 +1,321 @@
+use crate::{
+	app::UserSignal,
+	components::{
+		button::Button,
+		input::{Input, TextArea},
+		select::Select,
+	},
+	culture::{ContaminationStatus, Heading},
+	icons::CultureLogo,
+	login::Login,
+	permission::{Permission, Permissions},
+};
+
+use leptos::*;
+use leptos_router::*;
+
+stylance::import_style!(css, "../../equipment/equipment_add/equipment_add.module.css");
+
+#[component]
+pub fn CultureAdd() -> impl IntoView {
+	let add_culture_action = create_server_action::<AddCulture>();
+	let user_signal = use_context::<UserSignal>().expect("No user signal found in context");
+	let query = use_query_map();
+
+	let loading = create_rw_signal(false);
+	let parent = create_rw_signal(query.with_untracked(|p| p.get("parent").cloned().unwrap_or_default()));
+
+	let navigate = use_navigate();
+	create_effect(move |_| {
+		if let Some(Ok(id)) = add_culture_action.value().get() {
+			navigate(&format!("/culture/{id}"), NavigateOptions::default());
+		}
+	});
+
+	create_effect(move |_| {
+		if !add_culture_action.pending().get() {
+			loading.set(false);
+		}
+	});
+
+	view! {
+		<Heading hide_new=true>
+			<CultureLogo />
+			" Add new Culture"
+		</Heading>
+
+		<Suspense fallback=move || {
+			view! { <Login redirect="/culture/add" /> }
+		}>
+			{move || {
+				match user_signal.get() {
+					None => view! { <Login redirect="/culture/add" /> }.into_view(),
+					Some(user) => {
+						let Permissions::All { read: _, write: _, create: perm } = user.permission_culture;
+						if perm != Permission::Create(true) {
+							view! { <span>"You don't have permission to create new Culture"</span> }.into_view()
+						} else {
+							view! {
+								<ActionForm
+									action=add_culture_action
+									class=css::form
+									on:submit=move |_| loading.set(true)
+								>
+									<label class=css::label>
+										<span class=css::text>Name:</span>
+										<span class=css::input>
+											<Input name="name" placeholder="Name" required=true />
+										</span>
+									</label>
+
+									<label class=css::label>
+										<span class=css::text>Parent:</span>
+										<span class=css::input>
+											<Input name="parent" kind="number" placeholder="Parent culture ID" value=parent />
+										</span>
+									</label>
+
+									<label class=css::label>
+										<span class=css::text>Contamination Status:</span>
+										<span class=css::input>
+											<Select name="contamination_status" required=true>
+												{ContaminationStatus::get_fields()
+													.into_iter()
+													.map(|field| {
+														let contamination_status = ContaminationStatus::parse(field);
+														view! {
+															<option value=format!(
+																"{:#?}",
+																contamination_status,
+															)>{format!("{contamination_status}")}</option>
+														}
+													})
+													.collect_view()}
+											</Select>
+										</span>
+									</label>
+
+									<label class=css::label>
+										<span class=css::text>Culture Method:</span>
+										<span class=css::input>
+											<Input name="culture_method" placeholder="Culture Method" />
+										</span>
+									</label>
+
+									<label class=css::label>
+										<span class=css::text>Genus:</span>
+										<span class=css::input>
+											<Input name="genus" placeholder="Genus" />
+										</span>
+									</label>
+
+									<label class=css::label>
+										<span class=css::text>Species:</span>
+										<span class=css::input>
+											<Input name="species" placeholder="Species" />
+										</span>
+									</label>
+
+									<label class=css::label>
+										<span class=css::text>Growth Medium:</span>
+										<span class=css::input>
+											<Input name="growth_medium" placeholder="Growth Medium" />
+										</span>
+									</label>
+
+									<label class=css::label>
+										<span class=css::text>Equipment:</span>
+										<span class=css::input>
+											<Input name="equipment" kind="number" placeholder="Equipment ID" />
+										</span>
+									</label>
+
+									<label class=css::label>
+										<span class=css::text>Location:</span>
+										<span class=css::input>
+											<Input name="location" placeholder="Location" />
+										</span>
+									</label>
+
+									<label class=css::label>
+										<span class=css::text>Storage Conditions:</span>
+										<span class=css::input>
+											<Input name="storage_conditions" placeholder="Storage Conditions" />
+										</span>
+									</label>
+
+									<label class=css::label>
+										<span class=css::text>Notes:</span>
+										<span class=css::input>
+											<TextArea name="notes" placeholder="Notes" />
+										</span>
+									</label>
+
+									<div class=css::btn_row>
+										{move || {
+											if let Some(responds) = add_culture_action.value().get() {
+												match responds {
+													Ok(_) => view! {}.into_view(),
+													Err(error) => {
+														view! {
+															<span class=css::error>
+																{error
+																	.to_string()
+																	.replace(
+																		"error reaching server to call server function: ",
+																		"",
+																	)}
+															</span>
+														}
+															.into_view()
+													}
+												}
+											} else {
+												view! {}.into_view()
+											}
+										}} <Button kind="submit" loading=loading>
+											Add
+										</Button>
+									</div>
+								</ActionForm>
+							}
+								.into_view()
+						}
+					}
+				}
+			}}
+		</Suspense>
+	}
+}
+
+#[allow(clippy::too_many_arguments)]
+#[server(prefix = "/api")]
+pub async fn add_culture(
+	name: String,
+	parent: String,
+	contamination_status: String,
+	culture_method: String,
+	genus: String,
+	species: String,
+	growth_medium: String,
+	equipment: String,
+	location: String,
+	storage_conditions: String,
+	notes: String,
+) -> Result<i32, ServerFnError> {
+	use crate::{
+		auth::get_user,
+		culture::ContaminationStatus,
+		permission::{Permission, Permissions},
+		qrcode::generate_qr,
+		utils::get_culture_base_folder,
+	};
+
+	use chrono::prelude::*;
+	use sqlx::PgPool;
+	use std::{fs, path::PathBuf};
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	let user_id;
+	match user {
+		Some(user) => {
+			let Permissions::All {
+				read: _,
+				write: _,
+				create: perm,
+			} = user.permission_culture;
+			user_id = user.id;
+			if perm != Permission::Create(true) {
+				return Err(ServerFnError::Request(String::from("User not authenticated")));
+			}
+		},
+		None => return Err(ServerFnError::Request(String::from("User not authenticated"))),
+	};
+
+	let parent: Option<i32> = if parent.is_empty() {
+		None
+	} else {
+		match parent.parse::<i32>() {
+			Ok(value) => Some(value),
+			Err(_) => return Err(ServerFnError::Request(String::from("Invalid parent ID"))),
+		}
+	};
+
+	// A culture taken from a contaminated lineage inherits that contamination
+	let mut contamination_status = ContaminationStatus::parse(contamination_status);
+	if let Some(parent) = parent {
+		let parent_status: String = sqlx::query_scalar("SELECT contamination_status FROM culture WHERE id = $1")
+			.bind(parent)
+			.fetch_optional(&pool)
+			.await?
+			.ok_or_else::<ServerFnError, _>(|| ServerFnError::Request(String::from("Invalid parent ID")))?;
+
+		if matches!(
+			ContaminationStatus::parse(parent_status),
+			ContaminationStatus::Contaminated | ContaminationStatus::ParentContaminated
+		) && contamination_status != ContaminationStatus::Contaminated
+		{
+			contamination_status = ContaminationStatus::ParentContaminated;
+		}
+	}
+
+	let equipment: Option<i32> = if equipment.is_empty() {
+		None
+	} else {
+		match equipment.parse::<i32>() {
+			Ok(value) => Some(value),
+			Err(_) => return Err(ServerFnError::Request(String::from("Invalid equipment ID"))),
+		}
+	};
+
+	let id: i32 = sqlx::query_scalar(
+		"INSERT INTO culture\
+		(person, create_date, name, parent, culture_method, species, genus, location, storage_conditions, equipment, growth_medium, contamination_status, notes)\
+		VALUES\
+		($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)\
+		RETURNING id",
+	)
+	.bind(user_id)
+	.bind(Utc::now())
+	.bind(name)
+	.bind(parent)
+	.bind(culture_method)
+	.bind(species)
+	.bind(genus)
+	.bind(location)
+	.bind(storage_conditions)
+	.bind(equipment)
+	.bind(growth_medium)
+	.bind(format!("{:#?}", contamination_status))
+	.bind(notes)
+	.fetch_one(&pool)
+	.await
+	.map_err::<ServerFnError, _>(|error| ServerFnError::ServerError(error.to_string()))?;
+
+	// TODO: make domain configurable
+	let qr_svg = generate_qr(&format!("https://codon.com/culture/{id}"))
+		.map_err::<ServerFnError, _>(|_| ServerFnError::ServerError("Failed to generate QR code".into()))?;
+
+	let base_folder = get_culture_base_folder(id);
+	tokio::fs::create_dir_all(format!("{}public{base_folder}", env!("UPLOAD_ROOT"))).await?;
+	let qr_path = PathBuf::from(format!("{}public{base_folder}qr_{id}.svg", env!("UPLOAD_ROOT")));
+
+	fs::write(&qr_path, qr_svg)
+		.map_err::<ServerFnError, _>(|_| ServerFnError::ServerError("Failed to save QR code to disk".into()))?;
+
+	if let Some(stripped_path) = qr_path.to_string_lossy().strip_prefix(&format!("{}public", env!("UPLOAD_ROOT"))) {
+		sqlx::query("UPDATE culture SET qrcode = $1 WHERE id = $2")
+			.bind(stripped_path)
+			.bind(id)
+			.execute(&pool)
+			.await
+			.map(|_| ())?;
+	} else {
+		return Err(ServerFnError::ServerError("Failed to convert QR code path".into()));
+	}
+
+	Ok(id)
+}
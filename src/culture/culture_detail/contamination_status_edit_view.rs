@@ -0,0 +1,162 @@
+use crate::{
+	app::UserSignal,
+	components::{button::Button, input::TextArea, select::Select},
+	culture::{ContaminationStatus, CultureData, CultureFormToggle},
+};
+
+use leptos::*;
+use leptos_router::*;
+
+stylance::import_style!(css, "../../equipment/equipment_detail/equipment_details_edits.module.css");
+
+#[component]
+pub fn ContaminationStatusEdit(
+	culture: CultureData,
+	user_signal: UserSignal,
+	refetch_resources: RwSignal<usize>,
+) -> impl IntoView {
+	let contamination_status_action = create_server_action::<EditCultureContaminationStatus>();
+
+	view! {
+		<CultureFormToggle
+			user_id=culture.person.id
+			id=culture.id
+			user_signal=user_signal
+			item=culture.contamination_status
+		>
+			{
+				view! {
+					<ActionForm action=contamination_status_action class=css::edit_form>
+						<input type="hidden" name="id" value=culture.id />
+						<Select name="contamination_status">
+							{ContaminationStatus::get_fields()
+								.into_iter()
+								.map(|field| {
+									let contamination_status = ContaminationStatus::parse(field);
+									view! {
+										<option
+											value=format!("{:#?}", contamination_status)
+											selected=culture.contamination_status == contamination_status
+										>
+											{format!("{contamination_status}")}
+										</option>
+									}
+								})
+								.collect_view()}
+						</Select>
+						<TextArea name="note" placeholder="Add a note why you made this change" />
+						<div class=css::btns>
+							{move || {
+								if let Some(responds) = contamination_status_action.value().get() {
+									match responds {
+										Ok(_) => {
+											contamination_status_action.value().set(None);
+											refetch_resources.update(|version| *version += 1);
+											view! {}.into_view()
+										}
+										Err(error) => {
+											view! {
+												<span>
+													{error
+														.to_string()
+														.replace("error reaching server to call server function: ", "")}
+												</span>
+											}
+												.into_view()
+										}
+									}
+								} else {
+									view! {}.into_view()
+								}
+							}} <Button kind="submit">Save</Button>
+						</div>
+					</ActionForm>
+				}
+			}
+		</CultureFormToggle>
+	}
+}
+
+#[server(prefix = "/api")]
+pub async fn edit_culture_contamination_status(
+	id: String,
+	contamination_status: String,
+	note: String,
+) -> Result<(), ServerFnError> {
+	use crate::{
+		auth::get_user,
+		culture::contamination::mark_descendants_parent_contaminated,
+		permission::{Dimension, Permissions, Subject},
+	};
+
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	let id = match id.parse::<i32>() {
+		Ok(value) => value,
+		Err(_) => return Err(ServerFnError::Request(String::from("Invalid ID"))),
+	};
+
+	let user_id;
+	match user {
+		Some(user) => {
+			let Permissions::All {
+				read: _,
+				write: perm,
+				create: _,
+			} = user.permission_culture;
+			user_id = user.id;
+
+			let subject = Subject::load(Dimension::Culture, id, &pool).await?;
+			if !perm.has_permission_on("write", &subject) {
+				return Err(ServerFnError::Request(String::from("User not authenticated")));
+			}
+		},
+		None => return Err(ServerFnError::Request(String::from("User not authenticated"))),
+	};
+
+	let contamination_status = ContaminationStatus::parse(contamination_status);
+
+	let mut transaction = pool.begin().await?;
+
+	let old_value: String = sqlx::query_scalar("SELECT contamination_status FROM culture WHERE id = $1")
+		.bind(id)
+		.fetch_one(&mut *transaction)
+		.await?;
+
+	sqlx::query(
+		r#"INSERT INTO culture_log
+		(log_type, culture, person, notes, field, old_value, new_value)
+		VALUES
+		($1, $2, $3, $4, $5, $6, $7)"#,
+	)
+	.bind("contamination")
+	.bind(id)
+	.bind(user_id)
+	.bind(note)
+	.bind("contamination_status")
+	.bind(&old_value)
+	.bind(format!("{:#?}", contamination_status))
+	.execute(&mut *transaction)
+	.await
+	.map_err::<ServerFnError, _>(|error| ServerFnError::ServerError(error.to_string()))?;
+
+	sqlx::query("UPDATE culture SET contamination_status = $1 WHERE id = $2")
+		.bind(format!("{:#?}", contamination_status))
+		.bind(id)
+		.execute(&mut *transaction)
+		.await?;
+
+	if contamination_status == ContaminationStatus::Contaminated
+		&& ContaminationStatus::parse(old_value) != contamination_status
+	{
+		mark_descendants_parent_contaminated(&mut transaction, id, user_id).await?;
+	}
+
+	transaction.commit().await?;
+
+	Ok(())
+}
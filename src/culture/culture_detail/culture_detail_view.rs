@@ -0,0 +1,430 @@
+use crate::{
+	app::{LoginAction, UserSignal},
+	culture::{
+		ContaminationStatusEdit, CultureData, CultureLogData, CultureMethodEdit, CultureNoteEdit, EquipmentEdit, GenusEdit,
+		GrowthMediumEdit, Heading, Lineage, LocationEdit, Log, NameEdit, Notes, ParentContaminationClear, SpeciesEdit,
+		StorageConditionsEdit, get_log_for_culture,
+	},
+	equipment::EquipmentCell,
+	error_template::ErrorTemplate,
+	icons::CultureLogo,
+	login::Login,
+	permission::{Dimension, provide_subject_signal},
+};
+
+use leptos::*;
+use leptos_router::*;
+
+stylance::import_style!(
+	#[allow(dead_code)]
+	css,
+	"../../equipment/equipment_detail/equipment_details.module.css"
+);
+
+pub type LogAction = Resource<(String, usize), Result<(Vec<CultureLogData>, i64), ServerFnError>>;
+
+#[component]
+pub fn CultureDetail() -> impl IntoView {
+	let params = use_params_map();
+	let query = use_query_map();
+
+	let id = create_rw_signal(String::new());
+	let refetch_resources = create_rw_signal(0);
+	let notes_query_page = create_rw_signal::<u16>(1);
+	let notes_query_ipp = create_rw_signal::<u8>(25);
+	let log_query_page = create_rw_signal::<u16>(1);
+	let log_query_ipp = create_rw_signal::<u8>(25);
+	let tab_query = create_rw_signal(String::from("notes"));
+
+	create_effect(move |_| {
+		id.set(params.with(|p| p.get("id").cloned().unwrap_or_default()));
+	});
+
+	create_effect(move |_| {
+		let (notes_page, notes_ipp, log_page, log_ipp, tab) = query.with(|p| {
+			let notes_page = p.get("notes_page").cloned().unwrap_or(String::from("1")).parse::<u16>().unwrap_or(1);
+			let notes_ipp = p.get("notes_items_per_page").cloned().unwrap_or(String::from("25")).parse::<u8>().unwrap_or(25);
+			let log_page = p.get("log_page").cloned().unwrap_or(String::from("1")).parse::<u16>().unwrap_or(1);
+			let log_ipp = p.get("log_items_per_page").cloned().unwrap_or(String::from("25")).parse::<u8>().unwrap_or(25);
+			let tab = match p.get("tab").cloned().unwrap_or(String::from("notes")).as_str() {
+				"notes" => String::from("notes"),
+				"log" => String::from("log"),
+				_ => String::from("notes"),
+			};
+
+			(notes_page, notes_ipp, log_page, log_ipp, tab)
+		});
+
+		notes_query_page.set(if notes_page > 0 { notes_page } else { 1 });
+		notes_query_ipp.set(if notes_ipp > 0 { notes_ipp } else { 1 });
+		log_query_page.set(if log_page > 0 { log_page } else { 1 });
+		log_query_ipp.set(if log_ipp > 0 { log_ipp } else { 1 });
+		tab_query.set(tab);
+	});
+
+	let login_action = use_context::<LoginAction>().expect("No login action found in context");
+	let user_signal = use_context::<UserSignal>().expect("No user signal found in context");
+	provide_subject_signal(
+		Dimension::Culture,
+		move || params.with(|p| p.get("id").cloned().unwrap_or_default()),
+		refetch_resources,
+	);
+
+	let culture_data = create_resource(
+		move || {
+			(login_action.version().get(), params.with(|p| p.get("id").cloned().unwrap_or_default()), refetch_resources.get())
+		},
+		move |(_, id, _)| get_culture_data_by_id(id),
+	);
+
+	let log_data: LogAction = create_resource(
+		move || (params.with(|p| p.get("id").cloned().unwrap_or_default()), refetch_resources.get()),
+		move |(id, _)| get_log_for_culture(id, log_query_page.get(), log_query_ipp.get()),
+	);
+
+	view! {
+		<Suspense fallback=move || view! { <p>Loading culture...</p> }>
+			<ErrorBoundary fallback=|errors| {
+				view! { <ErrorTemplate errors=errors /> }
+			}>
+				{move || {
+					let culture = {
+						move || {
+							if culture_data.get().is_some() {
+								match culture_data.get().unwrap() {
+									Err(error) => {
+										let error = error.to_string();
+										if error.contains("User not authenticated") {
+											view! { <Login redirect=format!("/culture/{}", id.get()) /> }
+										} else {
+											view! { <pre class="error">Server Error: {error}</pre> }.into_view()
+										}
+									}
+									Ok(culture) => {
+										let title = culture.name.clone();
+										view! {
+											<div class=css::details>
+												<Heading>
+													<CultureLogo />
+													" "
+													{title}
+												</Heading>
+
+												<dl class=css::list>
+													<dt>ID</dt>
+													<dd>
+														<EquipmentCell cell=culture.id />
+													</dd>
+
+													<dt>Name</dt>
+													<dd class=css::edit>
+														<NameEdit
+															culture=culture.clone()
+															user_signal
+															refetch_resources
+														/>
+													</dd>
+
+													<dt>Created By</dt>
+													<dd>
+														<EquipmentCell cell=culture.person.clone() />
+													</dd>
+
+													<dt>Qrcode</dt>
+													<dd>
+														<EquipmentCell cell=culture.qrcode.clone() />
+													</dd>
+
+													<dt>Create Date</dt>
+													<dd>
+														<EquipmentCell cell=culture.create_date />
+													</dd>
+
+													<dt>Parent</dt>
+													<dd>
+														{match culture.parent {
+															Some(parent) => {
+																view! {
+																	<A href=format!("/culture/{parent}")>
+																		<EquipmentCell cell=parent />
+																	</A>
+																}
+																	.into_view()
+															}
+															None => view! { <span>"None"</span> }.into_view(),
+														}}
+													</dd>
+
+													<dt>Contamination Status</dt>
+													<dd class=css::edit>
+														<ContaminationStatusEdit
+															culture=culture.clone()
+															user_signal
+															refetch_resources
+														/>
+														<ParentContaminationClear
+															culture=culture.clone()
+															user_signal
+															refetch_resources
+														/>
+													</dd>
+
+													<dt>Lineage</dt>
+													<dd>
+														<Lineage id=culture.id refetch_resources />
+													</dd>
+
+													<dt>Culture Method</dt>
+													<dd class=css::edit>
+														<CultureMethodEdit
+															culture=culture.clone()
+															user_signal
+															refetch_resources
+														/>
+													</dd>
+
+													<dt>Genus</dt>
+													<dd class=css::edit>
+														<GenusEdit
+															culture=culture.clone()
+															user_signal
+															refetch_resources
+														/>
+													</dd>
+
+													<dt>Species</dt>
+													<dd class=css::edit>
+														<SpeciesEdit
+															culture=culture.clone()
+															user_signal
+															refetch_resources
+														/>
+													</dd>
+
+													<dt>Growth Medium</dt>
+													<dd class=css::edit>
+														<GrowthMediumEdit
+															culture=culture.clone()
+															user_signal
+															refetch_resources
+														/>
+													</dd>
+
+													<dt>Equipment</dt>
+													<dd class=css::edit>
+														<EquipmentEdit
+															culture=culture.clone()
+															user_signal
+															refetch_resources
+														/>
+													</dd>
+
+													<dt>Location</dt>
+													<dd class=css::edit>
+														<LocationEdit
+															culture=culture.clone()
+															user_signal
+															refetch_resources
+														/>
+													</dd>
+
+													<dt>Storage Conditions</dt>
+													<dd class=css::edit>
+														<StorageConditionsEdit
+															culture=culture.clone()
+															user_signal
+															refetch_resources
+														/>
+													</dd>
+
+													<dt>Notes</dt>
+													<dd class=css::edit>
+														<CultureNoteEdit
+															culture=culture.clone()
+															user_signal
+															refetch_resources
+														/>
+													</dd>
+												</dl>
+											</div>
+										}
+											.into_view()
+									}
+								}
+							} else {
+								view! { <div>Nothing found</div> }.into_view()
+							}
+						}
+					};
+					let id_clone = id;
+					let notes_query_page_clone = notes_query_page;
+					let notes_query_ipp_clone = notes_query_ipp;
+					let log_query_page_clone = log_query_page;
+					let log_query_ipp_clone = log_query_ipp;
+					let tab_query_clone = tab_query;
+					view! {
+						<div>
+							{culture}
+							<Suspense fallback=move || {
+								view! { <A href="/login">"Login"</A> }
+							}>
+								{move || {
+									match user_signal.get() {
+										None => view! { <span /> }.into_view(),
+										Some(_) => {
+											view! {
+												<div id="culture_tab" class=css::tab>
+													<form
+														class=if tab_query.get() == *"notes" {
+															"is-selected"
+														} else {
+															""
+														}
+														action=format!("/culture/{}#culture_tab", id.get())
+														method="GET"
+													>
+														<input
+															type="hidden"
+															name="notes_page"
+															value=notes_query_page_clone
+														/>
+														<input
+															type="hidden"
+															name="notes_items_per_page"
+															value=notes_query_ipp_clone
+														/>
+														<input
+															type="hidden"
+															name="log_page"
+															value=log_query_page_clone
+														/>
+														<input
+															type="hidden"
+															name="log_items_per_page"
+															value=log_query_ipp_clone
+														/>
+														<input type="hidden" name="tab" value="notes" />
+														<button class=css::btn>Notes</button>
+													</form>
+													<form
+														class=if tab_query.get() == *"log" { "is-selected" } else { "" }
+														action=format!("/culture/{}#culture_tab", id.get())
+														method="GET"
+													>
+														<input
+															type="hidden"
+															name="notes_page"
+															value=notes_query_page_clone
+														/>
+														<input
+															type="hidden"
+															name="notes_items_per_page"
+															value=notes_query_ipp_clone
+														/>
+														<input
+															type="hidden"
+															name="log_page"
+															value=log_query_page_clone
+														/>
+														<input
+															type="hidden"
+															name="log_items_per_page"
+															value=log_query_ipp_clone
+														/>
+														<input type="hidden" name="tab" value="log" />
+														<button class=css::btn>Log</button>
+													</form>
+												</div>
+												<Show
+													when=move || tab_query.get().as_str() == "log"
+													fallback=move || {
+														view! {
+															<Notes
+																id=id_clone
+																notes_query_page=notes_query_page_clone
+																notes_query_ipp=notes_query_ipp_clone
+																log_query_page=log_query_page_clone
+																log_query_ipp=log_query_ipp_clone
+																tab_query=tab_query_clone
+															/>
+														}
+													}
+												>
+													<Log
+														id=id
+														notes_query_page=notes_query_page
+														notes_query_ipp=notes_query_ipp
+														log_query_page=log_query_page
+														log_query_ipp=log_query_ipp
+														tab_query=tab_query
+														log_data=log_data
+													/>
+												</Show>
+											}
+												.into_view()
+										}
+									}
+								}}
+							</Suspense>
+						</div>
+					}
+				}}
+			</ErrorBoundary>
+		</Suspense>
+	}
+}
+
+#[server(prefix = "/api")]
+pub async fn get_culture_data_by_id(id: String) -> Result<CultureData, ServerFnError> {
+	use crate::{
+		auth::get_user,
+		culture::CultureSQLData,
+		permission::{Dimension, Permissions, Subject},
+	};
+
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	let id = match id.parse::<i32>() {
+		Ok(value) => value,
+		Err(_) => return Err(ServerFnError::Request(String::from("Invalid ID"))),
+	};
+
+	match user {
+		Some(user) => {
+			let Permissions::All {
+				read: perm,
+				write: _,
+				create: _,
+			} = user.permission_culture;
+			let subject = Subject::load(Dimension::Culture, id, &pool).await?;
+			if !perm.has_permission_on("read", &subject) {
+				return Err(ServerFnError::Request(String::from("User not authenticated")));
+			}
+		},
+		None => return Err(ServerFnError::Request(String::from("User not authenticated"))),
+	};
+
+	let culture_sql_data = sqlx::query_as::<_, CultureSQLData>(
+		r#"
+		SELECT
+			culture.*,
+			people.id AS person_id,
+			people.status AS person_status,
+			people.preferred_name AS person_preferred_name,
+			people.picture AS person_picture
+		FROM
+			culture
+			JOIN people ON culture.person = people.id
+		WHERE culture.id = $1"#,
+	)
+	.bind(id)
+	.fetch_one(&pool)
+	.await
+	.map_err::<ServerFnError, _>(|error| ServerFnError::ServerError(error.to_string()))?;
+
+	Ok(culture_sql_data.into())
+}
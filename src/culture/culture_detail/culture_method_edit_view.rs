@@ -0,0 +1,133 @@
+use crate::{
+	app::UserSignal,
+	components::{
+		button::Button,
+		input::{Input, TextArea},
+	},
+	culture::{CultureData, CultureFormToggle},
+};
+
+use leptos::*;
+use leptos_router::*;
+
+stylance::import_style!(css, "../../equipment/equipment_detail/equipment_details_edits.module.css");
+
+#[component]
+pub fn CultureMethodEdit(
+	culture: CultureData,
+	user_signal: UserSignal,
+	refetch_resources: RwSignal<usize>,
+) -> impl IntoView {
+	let culture_method_action = create_server_action::<EditCultureMethod>();
+
+	view! {
+		<CultureFormToggle
+			user_id=culture.person.id
+			id=culture.id
+			user_signal=user_signal
+			item=culture.culture_method.clone()
+		>
+			{
+				let culture_method_clone = culture.culture_method.clone();
+				view! {
+					<ActionForm action=culture_method_action class=css::edit_form>
+						<input type="hidden" name="id" value=culture.id />
+						<Input name="culture_method" value=create_rw_signal(culture_method_clone.unwrap_or_default()) />
+						<TextArea name="note" placeholder="Add a note why you made this change" />
+						<div class=css::btns>
+							{move || {
+								if let Some(responds) = culture_method_action.value().get() {
+									match responds {
+										Ok(_) => {
+											culture_method_action.value().set(None);
+											refetch_resources.update(|version| *version += 1);
+											view! {}.into_view()
+										}
+										Err(error) => {
+											view! {
+												<span>
+													{error
+														.to_string()
+														.replace("error reaching server to call server function: ", "")}
+												</span>
+											}
+												.into_view()
+										}
+									}
+								} else {
+									view! {}.into_view()
+								}
+							}} <Button kind="submit">Save</Button>
+						</div>
+					</ActionForm>
+				}
+			}
+		</CultureFormToggle>
+	}
+}
+
+#[server(prefix = "/api")]
+pub async fn edit_culture_method(id: String, culture_method: String, note: String) -> Result<(), ServerFnError> {
+	use crate::{
+		auth::get_user,
+		permission::{Dimension, Permissions, Subject},
+	};
+
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	let id = match id.parse::<i32>() {
+		Ok(value) => value,
+		Err(_) => return Err(ServerFnError::Request(String::from("Invalid ID"))),
+	};
+
+	let user_id;
+	match user {
+		Some(user) => {
+			let Permissions::All {
+				read: _,
+				write: perm,
+				create: _,
+			} = user.permission_culture;
+			user_id = user.id;
+
+			let subject = Subject::load(Dimension::Culture, id, &pool).await?;
+			if !perm.has_permission_on("write", &subject) {
+				return Err(ServerFnError::Request(String::from("User not authenticated")));
+			}
+		},
+		None => return Err(ServerFnError::Request(String::from("User not authenticated"))),
+	};
+
+	let old_value: Option<String> =
+		sqlx::query_scalar("SELECT culture_method FROM culture WHERE id = $1").bind(id).fetch_one(&pool).await?;
+
+	sqlx::query(
+		r#"INSERT INTO culture_log
+		(log_type, culture, person, notes, field, old_value, new_value)
+		VALUES
+		($1, $2, $3, $4, $5, $6, $7)"#,
+	)
+	.bind("edit")
+	.bind(id)
+	.bind(user_id)
+	.bind(note)
+	.bind("culture_method")
+	.bind(old_value)
+	.bind(culture_method.clone())
+	.execute(&pool)
+	.await
+	.map_err::<ServerFnError, _>(|error| ServerFnError::ServerError(error.to_string()))?;
+
+	sqlx::query("UPDATE culture SET culture_method = $1 WHERE id = $2")
+		.bind(culture_method)
+		.bind(id)
+		.execute(&pool)
+		.await
+		.map(|_| ())?;
+
+	Ok(())
+}
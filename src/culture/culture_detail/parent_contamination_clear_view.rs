@@ -0,0 +1,125 @@
+use crate::{
+	app::UserSignal,
+	components::{
+		button::{Button, ButtonVariant},
+		input::TextArea,
+	},
+	culture::{ContaminationStatus, CultureData},
+	permission::{Permissions, Subject, SubjectSignal},
+};
+
+use leptos::*;
+use leptos_router::*;
+
+stylance::import_style!(css, "../../equipment/equipment_detail/equipment_details_edits.module.css");
+
+#[component]
+pub fn ParentContaminationClear(
+	culture: CultureData,
+	user_signal: UserSignal,
+	refetch_resources: RwSignal<usize>,
+) -> impl IntoView {
+	let clear_action = create_server_action::<ClearCultureParentContamination>();
+	let subject_signal = use_context::<SubjectSignal>();
+
+	view! {
+		<Suspense fallback=move || view! { <span /> }>
+			{move || {
+				match user_signal.get() {
+					None => view! { <span /> }.into_view(),
+					Some(user) => {
+						let Permissions::All { read: _, write: perm, create: _ } = user.permission_culture;
+						view! {
+							<Show when=move || {
+								culture.contamination_status == ContaminationStatus::ParentContaminated
+									&& perm.has_permission_on("write", &Subject::resolve(subject_signal, culture.id, culture.person.id))
+							}>
+								<ActionForm action=clear_action class=css::edit_form>
+									<input type="hidden" name="id" value=culture.id />
+									<TextArea
+										name="note"
+										placeholder="Why is it safe to clear this culture and its descendants?"
+									/>
+									<div class=css::btns>
+										{move || {
+											if let Some(responds) = clear_action.value().get() {
+												match responds {
+													Ok(_) => {
+														clear_action.value().set(None);
+														refetch_resources.update(|version| *version += 1);
+														view! {}.into_view()
+													}
+													Err(error) => {
+														view! {
+															<span>
+																{error
+																	.to_string()
+																	.replace(
+																		"error reaching server to call server function: ",
+																		"",
+																	)}
+															</span>
+														}
+															.into_view()
+													}
+												}
+											} else {
+												view! {}.into_view()
+											}
+										}} <Button kind="submit" variant=ButtonVariant::Outlined>
+											Clear parent contamination
+										</Button>
+									</div>
+								</ActionForm>
+							</Show>
+						}
+							.into_view()
+					}
+				}
+			}}
+		</Suspense>
+	}
+}
+
+#[server(prefix = "/api")]
+pub async fn clear_culture_parent_contamination(id: String, note: String) -> Result<(), ServerFnError> {
+	use crate::{
+		auth::get_user,
+		culture::contamination::clear_parent_contamination,
+		permission::{Dimension, Permissions, Subject},
+	};
+
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	let id = match id.parse::<i32>() {
+		Ok(value) => value,
+		Err(_) => return Err(ServerFnError::Request(String::from("Invalid ID"))),
+	};
+
+	let (user_id, perm) = match user {
+		Some(user) => {
+			let Permissions::All {
+				read: _,
+				write: perm,
+				create: _,
+			} = user.permission_culture;
+
+			let subject = Subject::load(Dimension::Culture, id, &pool).await?;
+			if !perm.has_permission_on("write", &subject) {
+				return Err(ServerFnError::Request(String::from("User not authenticated")));
+			}
+			(user.id, perm)
+		},
+		None => return Err(ServerFnError::Request(String::from("User not authenticated"))),
+	};
+
+	let mut transaction = pool.begin().await?;
+	clear_parent_contamination(&mut transaction, id, user_id, &perm, &note).await?;
+	transaction.commit().await?;
+
+	Ok(())
+}
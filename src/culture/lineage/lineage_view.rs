@@ -0,0 +1,198 @@
+use crate::{
+	culture::{ContaminationStatus, CultureLineageData, CultureLineageLayout, CultureLineageNode},
+	error_template::ErrorTemplate,
+};
+
+use leptos::*;
+
+stylance::import_style!(css, "lineage.module.css");
+
+const NODE_WIDTH: f32 = 180.0;
+const NODE_HEIGHT: f32 = 44.0;
+const GAP_X: f32 = 24.0;
+const GAP_Y: f32 = 48.0;
+const NAME_LENGTH: usize = 20;
+
+fn node_x(node: &CultureLineageNode) -> f32 {
+	node.column * (NODE_WIDTH + GAP_X)
+}
+
+fn node_y(node: &CultureLineageNode) -> f32 {
+	node.depth as f32 * (NODE_HEIGHT + GAP_Y)
+}
+
+fn status_class(status: ContaminationStatus) -> &'static str {
+	match status {
+		ContaminationStatus::Clean | ContaminationStatus::Axenic => css::clean,
+		ContaminationStatus::Xenic | ContaminationStatus::Monoxenic => css::xenic,
+		ContaminationStatus::Contaminated => css::contaminated,
+		ContaminationStatus::ParentContaminated => css::parent_contaminated,
+		ContaminationStatus::CleanWasContaminated => css::clean_was_contaminated,
+	}
+}
+
+#[component]
+pub fn Lineage(id: i32, refetch_resources: RwSignal<usize>) -> impl IntoView {
+	let lineage_data = create_resource(move || refetch_resources.get(), move |_| get_culture_lineage(id.to_string()));
+
+	view! {
+		<Suspense fallback=move || view! { <p>Loading lineage...</p> }>
+			<ErrorBoundary fallback=|errors| {
+				view! { <ErrorTemplate errors=errors /> }
+			}>
+				{move || {
+					if lineage_data.get().is_some() {
+						match lineage_data.get().unwrap() {
+							Err(error) => {
+								view! { <pre class="error">Lineage Server Error: {error.to_string()}</pre> }.into_view()
+							}
+							Ok(lineage) => {
+								view! { <LineageTree layout=CultureLineageLayout::new(lineage) current=id /> }.into_view()
+							}
+						}
+					} else {
+						view! {}.into_view()
+					}
+				}}
+			</ErrorBoundary>
+		</Suspense>
+	}
+}
+
+#[component]
+pub fn LineageTree(layout: CultureLineageLayout, current: i32) -> impl IntoView {
+	let width = (layout.columns as f32 * (NODE_WIDTH + GAP_X) - GAP_X).max(NODE_WIDTH);
+	let height = (layout.rows as f32 * (NODE_HEIGHT + GAP_Y) - GAP_Y).max(NODE_HEIGHT);
+
+	let edges = layout
+		.nodes
+		.iter()
+		.filter_map(|node| {
+			let parent = layout.get(node.culture.parent?)?;
+			let (from_x, from_y) = (node_x(parent) + NODE_WIDTH / 2.0, node_y(parent) + NODE_HEIGHT);
+			let (to_x, to_y) = (node_x(node) + NODE_WIDTH / 2.0, node_y(node));
+			let middle_y = from_y + GAP_Y / 2.0;
+			Some(format!("M{from_x},{from_y} C{from_x},{middle_y} {to_x},{middle_y} {to_x},{to_y}"))
+		})
+		.collect::<Vec<String>>();
+
+	view! {
+		<div class=css::lineage>
+			<svg
+				class=css::tree
+				viewBox=format!("-2 -2 {} {}", width + 4.0, height + 4.0)
+				width=width + 4.0
+				height=height + 4.0
+				role="img"
+				aria-label="Culture lineage"
+			>
+				{edges
+					.into_iter()
+					.map(|edge| {
+						view! {
+							<g>
+								<path class=css::edge d=edge />
+							</g>
+						}
+					})
+					.collect_view()}
+				{layout
+					.nodes
+					.into_iter()
+					.map(|node| {
+						let CultureLineageData { id, name, parent: _, contamination_status } = node.culture.clone();
+						let name = if name.chars().count() > NAME_LENGTH {
+							format!("{}…", name.chars().take(NAME_LENGTH - 1).collect::<String>())
+						} else {
+							name
+						};
+						view! {
+							<g
+								class=format!(
+									"{} {}",
+									status_class(contamination_status),
+									if id == current { css::current } else { "" },
+								)
+								transform=format!("translate({},{})", node_x(&node), node_y(&node))
+							>
+								<a href=format!("/culture/{id}")>
+									<rect class=css::node width=NODE_WIDTH height=NODE_HEIGHT rx="6" />
+									<text class=css::name x="10" y="18">
+										{name}
+									</text>
+									<text class=css::status x="10" y="35">
+										{format!("#{id} {contamination_status}")}
+									</text>
+								</a>
+							</g>
+						}
+					})
+					.collect_view()}
+			</svg>
+		</div>
+	}
+}
+
+#[server(prefix = "/api")]
+pub async fn get_culture_lineage(id: String) -> Result<Vec<CultureLineageData>, ServerFnError> {
+	use crate::{
+		auth::get_user,
+		culture::CultureLineageSQLData,
+		permission::{BindFilter, Dimension, Permissions, Subject},
+	};
+
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	let id = match id.parse::<i32>() {
+		Ok(value) => value,
+		Err(_) => return Err(ServerFnError::Request(String::from("Invalid ID"))),
+	};
+
+	let auth_filter = match user {
+		Some(user) => {
+			let Permissions::All {
+				read: perm,
+				write: _,
+				create: _,
+			} = user.permission_culture;
+			let subject = Subject::load(Dimension::Culture, id, &pool).await?;
+			if !perm.has_permission_on("read", &subject) {
+				return Err(ServerFnError::Request(String::from("User not authenticated")));
+			}
+			perm.get_query_filter(Dimension::Culture, "culture.id")
+		},
+		None => return Err(ServerFnError::Request(String::from("User not authenticated"))),
+	};
+
+	let auth_query = auth_filter.and_clause(2);
+	let lineage_sql_data = sqlx::query_as::<_, CultureLineageSQLData>(&format!(
+		r#"
+		WITH RECURSIVE ancestors AS (
+			SELECT id, parent FROM culture WHERE id = $1
+			UNION
+			SELECT culture.id, culture.parent FROM culture JOIN ancestors ON culture.id = ancestors.parent
+		),
+		descendants AS (
+			SELECT id FROM culture WHERE id = $1
+			UNION
+			SELECT culture.id FROM culture JOIN descendants ON culture.parent = descendants.id
+		)
+		SELECT id, name, parent, contamination_status
+		FROM culture
+		WHERE
+			(id IN (SELECT id FROM ancestors) OR id IN (SELECT id FROM descendants))
+			AND id IN (SELECT culture.id FROM culture WHERE culture.id IS NOT NULL {auth_query})
+		ORDER BY id"#
+	))
+	.bind(id)
+	.bind_filter(&auth_filter)
+	.fetch_all(&pool)
+	.await
+	.map_err::<ServerFnError, _>(|error| ServerFnError::ServerError(error.to_string()))?;
+
+	Ok(lineage_sql_data.into_iter().map(Into::into).collect())
+}
@@ -0,0 +1,85 @@
+pub mod cell {
+	pub mod cell_view;
+}
+
+pub mod culture_view;
+pub use culture_view::*;
+
+pub mod culture_detail {
+	pub mod contamination_status_edit_view;
+	pub mod culture_detail_view;
+	pub mod culture_form_toggle_view;
+	pub mod culture_method_edit_view;
+	pub mod equipment_edit_view;
+	pub mod genus_edit_view;
+	pub mod growth_medium_edit_view;
+	pub mod location_edit_view;
+	pub mod name_edit_view;
+	pub mod note_edit_view;
+	pub mod parent_contamination_clear_view;
+	pub mod species_edit_view;
+	pub mod storage_conditions_edit_view;
+}
+pub use culture_detail::contamination_status_edit_view::*;
+pub use culture_detail::culture_detail_view::*;
+pub use culture_detail::culture_form_toggle_view::*;
+pub use culture_detail::culture_method_edit_view::*;
+pub use culture_detail::equipment_edit_view::*;
+pub use culture_detail::genus_edit_view::*;
+pub use culture_detail::growth_medium_edit_view::*;
+pub use culture_detail::location_edit_view::*;
+pub use culture_detail::name_edit_view::*;
+pub use culture_detail::note_edit_view::*;
+pub use culture_detail::parent_contamination_clear_view::*;
+pub use culture_detail::species_edit_view::*;
+pub use culture_detail::storage_conditions_edit_view::*;
+
+pub mod lineage {
+	pub mod lineage_view;
+}
+pub use lineage::lineage_view::*;
+
+pub mod notes_form {
+	pub mod notes_form_view;
+}
+pub use notes_form::notes_form_view::*;
+
+pub mod log {
+	pub mod log_view;
+}
+pub use log::log_view::*;
+
+pub mod notes {
+	pub mod notes_view;
+}
+pub use notes::notes_view::*;
+
+pub mod culture_add {
+	pub mod culture_add_view;
+}
+pub use culture_add::culture_add_view::*;
+
+pub mod heading {
+	pub mod heading_view;
+}
+pub use heading::heading_view::*;
+
+pub mod row {
+	pub mod row_view;
+}
+pub use row::row_view::*;
+
+pub mod schema;
+pub use schema::*;
+
+#[cfg(feature = "ssr")]
+pub mod contamination;
+
+pub mod schema_lineage;
+pub use schema_lineage::*;
+
+pub mod schema_log;
+pub use schema_log::*;
+
+pub mod schema_notes;
+pub use schema_notes::*;
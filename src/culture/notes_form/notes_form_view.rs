@@ -0,0 +1,247 @@
+use crate::{
+	app::UserSignal,
+	components::{button::Button, file_input::FileInput, input::TextArea},
+	permission::{Permissions, Subject, SubjectSignal},
+};
+
+use leptos::*;
+use server_fn::codec::{MultipartData, MultipartFormData};
+use web_sys::{FormData, SubmitEvent};
+
+stylance::import_style!(css, "../../equipment/notes_form/notes_form.module.css");
+
+#[component]
+pub fn CultureNotesForm(
+	id: String,
+	person_id: i32,
+	notes_upload_action: Action<FormData, Result<(), ServerFnError>>,
+) -> impl IntoView {
+	let user_signal = use_context::<UserSignal>().expect("No user signal found in context");
+	let subject_signal = use_context::<SubjectSignal>();
+
+	let form_ref = create_node_ref::<html::Form>();
+
+	let media1 = create_rw_signal(String::new());
+	let media2 = create_rw_signal(String::new());
+	let media3 = create_rw_signal(String::new());
+	let media4 = create_rw_signal(String::new());
+	let media5 = create_rw_signal(String::new());
+	let media6 = create_rw_signal(String::new());
+	let media7 = create_rw_signal(String::new());
+	let media8 = create_rw_signal(String::new());
+	let media9 = create_rw_signal(String::new());
+	let media10 = create_rw_signal(String::new());
+	let loading = create_rw_signal(false);
+	let id = create_rw_signal(id);
+
+	view! {
+		<Suspense fallback=move || {
+			view! { <span /> }
+		}>
+			{move || {
+				match user_signal.get() {
+					None => view! { <span /> }.into_view(),
+					Some(user) => {
+						let Permissions::All { read: _, write: perm, create: _ } = user.permission_culture;
+						let id_num = id.get().parse::<i32>().unwrap_or(-1);
+						if !perm.has_permission_on("write", &Subject::resolve(subject_signal, id_num, person_id)) {
+							view! { <span /> }.into_view()
+						} else {
+							view! {
+								<form
+									ref=form_ref
+									class=css::form
+									method="post"
+									action="#"
+									enctype="multipart/form-data"
+									on:submit=move |event: SubmitEvent| {
+										event.prevent_default();
+										let form = form_ref.get().unwrap();
+										let form_data = match FormData::new_with_form(&form) {
+											Ok(fd) => fd,
+											Err(error) => {
+												logging::log!("Failed to create FormData");
+												logging::log!("{error:?}");
+												return;
+											}
+										};
+										notes_upload_action.dispatch(form_data);
+									}
+								>
+									<h3>Add a Note</h3>
+									<input type="hidden" name="id" value=id.get() />
+									<TextArea
+										name="notes"
+										value=create_rw_signal(String::from(""))
+										placeholder="Your note"
+										required=true
+									/>
+									<div class=css::file_inputs>
+										<Show when=move || !media9.get().is_empty()>
+											<FileInput name="media10" value=media10 />
+										</Show>
+										<Show when=move || !media8.get().is_empty()>
+											<FileInput name="media9" value=media9 />
+										</Show>
+										<Show when=move || !media7.get().is_empty()>
+											<FileInput name="media8" value=media8 />
+										</Show>
+										<Show when=move || !media6.get().is_empty()>
+											<FileInput name="media7" value=media7 />
+										</Show>
+										<Show when=move || !media5.get().is_empty()>
+											<FileInput name="media6" value=media6 />
+										</Show>
+										<Show when=move || !media4.get().is_empty()>
+											<FileInput name="media5" value=media5 />
+										</Show>
+										<Show when=move || !media3.get().is_empty()>
+											<FileInput name="media4" value=media4 />
+										</Show>
+										<Show when=move || !media2.get().is_empty()>
+											<FileInput name="media3" value=media3 />
+										</Show>
+										<Show when=move || !media1.get().is_empty()>
+											<FileInput name="media2" value=media2 />
+										</Show>
+										<FileInput name="media1" value=media1 />
+									</div>
+									<div class=css::btn_line>
+										<Button kind="submit" loading>
+											Save
+										</Button>
+										<span>
+											{move || {
+												if notes_upload_action.input().get().is_none()
+													&& notes_upload_action.value().get().is_none()
+												{
+													view! {}.into_view()
+												} else if notes_upload_action.pending().get() {
+													loading.set(true);
+													view! {}.into_view()
+												} else if let Some(Ok(_)) = notes_upload_action.value().get() {
+													loading.set(false);
+													view! { <span class=css::success>Saved successfully</span> }
+														.into_view()
+												} else {
+													loading.set(false);
+													view! {
+														<span class=css::error>
+															{format!("Error: {:?}", notes_upload_action.value().get())}
+														</span>
+													}
+														.into_view()
+												}
+											}}
+										</span>
+									</div>
+								</form>
+							}
+								.into_view()
+						}
+					}
+				}
+			}}
+		</Suspense>
+	}
+}
+
+#[server(input = MultipartFormData, prefix = "/api")]
+pub async fn save_culture_notes(data: MultipartData) -> Result<(), ServerFnError> {
+	use crate::{
+		auth::get_user,
+		components::file_upload::{file_upload, remove_temp_files},
+		permission::{Dimension, Permissions, Subject},
+		utils::{get_culture_base_folder, get_equipment_notes_folder, move_file},
+	};
+
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	let result = file_upload(data, |id| format!("{}temp/", get_culture_base_folder(id))).await?;
+
+	let user_id;
+	match user {
+		Some(user) => {
+			let Permissions::All {
+				read: _,
+				write: perm,
+				create: _,
+			} = user.permission_culture;
+			user_id = user.id;
+
+			let subject = Subject::load(Dimension::Culture, result.id, &pool).await?;
+			if !perm.has_permission_on("write", &subject) {
+				remove_temp_files(result).await?;
+				return Err(ServerFnError::Request(String::from("User not authenticated")));
+			}
+		},
+		None => {
+			remove_temp_files(result).await?;
+			return Err(ServerFnError::Request(String::from("User not authenticated")));
+		},
+	};
+
+	let mut notes = None;
+	for (name, value) in &result.additional_fields {
+		if name.as_str() == "notes" {
+			notes = Some(value)
+		}
+	}
+
+	let note_id: i32 =
+		sqlx::query_scalar(r#"INSERT INTO culture_notes (culture, person, notes) VALUES ($1, $2, $3) RETURNING id"#)
+			.bind(result.id)
+			.bind(user_id)
+			.bind(notes)
+			.fetch_one(&pool)
+			.await
+			.map_err::<ServerFnError, _>(|error| ServerFnError::ServerError(error.to_string()))?;
+
+	let notes_folder = get_equipment_notes_folder(note_id);
+
+	let media1 = move_file(result.media1, &notes_folder).await?;
+	let media2 = move_file(result.media2, &notes_folder).await?;
+	let media3 = move_file(result.media3, &notes_folder).await?;
+	let media4 = move_file(result.media4, &notes_folder).await?;
+	let media5 = move_file(result.media5, &notes_folder).await?;
+	let media6 = move_file(result.media6, &notes_folder).await?;
+	let media7 = move_file(result.media7, &notes_folder).await?;
+	let media8 = move_file(result.media8, &notes_folder).await?;
+	let media9 = move_file(result.media9, &notes_folder).await?;
+	let media10 = move_file(result.media10, &notes_folder).await?;
+
+	sqlx::query(
+		r#"UPDATE culture_notes set
+			media1 = $1,
+			media2 = $2,
+			media3 = $3,
+			media4 = $4,
+			media5 = $5,
+			media6 = $6,
+			media7 = $7,
+			media8 = $8,
+			media9 = $9,
+			media10 = $10
+		WHERE id = $11"#,
+	)
+	.bind(media1)
+	.bind(media2)
+	.bind(media3)
+	.bind(media4)
+	.bind(media5)
+	.bind(media6)
+	.bind(media7)
+	.bind(media8)
+	.bind(media9)
+	.bind(media10)
+	.bind(note_id)
+	.execute(&pool)
+	.await
+	.map_err::<ServerFnError, _>(|error| ServerFnError::ServerError(error.to_string()))?;
+
+	Ok(())
+}
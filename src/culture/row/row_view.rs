@@ -0,0 +1,221 @@
+use crate::{culture::CultureData, equipment::EquipmentCell};
+
+use leptos::*;
+use leptos_router::*;
+
+stylance::import_style!(css, "row.module.css");
+
+#[component]
+pub fn Row(culture: Vec<CultureData>, query_filter: RwSignal<Vec<String>>) -> impl IntoView {
+	culture
+		.into_iter()
+		.map(move |culture| {
+			view! {
+				<tr>
+					<Show when=move || { query_filter.get().contains(&String::from("id")) }>
+						<td class="culture_listing_id">
+							<A href=format!("/culture/{}", culture.id) class="none">
+								<EquipmentCell cell=culture.id table_view=true />
+							</A>
+						</td>
+					</Show>
+					<Show when=move || {
+						query_filter.get().contains(&String::from("person"))
+					}>
+						{
+							let culture_person = culture.person.clone();
+							view! {
+								<td class="culture_listing_person">
+									<A href=format!("/culture/{}", culture.id) class="none">
+										<EquipmentCell cell=culture_person table_view=true />
+									</A>
+								</td>
+							}
+						}
+					</Show>
+					<Show when=move || {
+						query_filter.get().contains(&String::from("qrcode"))
+					}>
+						{
+							let culture_qrcode = culture.qrcode.clone();
+							view! {
+								<td class="culture_listing_qrcode">
+									<A href=format!("/culture/{}", culture.id) class="none">
+										<EquipmentCell cell=culture_qrcode table_view=true />
+									</A>
+								</td>
+							}
+						}
+					</Show>
+					<Show when=move || {
+						query_filter.get().contains(&String::from("create_date"))
+					}>
+						{
+							view! {
+								<td class="culture_listing_create_date">
+									<A href=format!("/culture/{}", culture.id) class="none">
+										<EquipmentCell cell=culture.create_date table_view=true />
+									</A>
+								</td>
+							}
+						}
+					</Show>
+					<Show when=move || {
+						query_filter.get().contains(&String::from("name"))
+					}>
+						{
+							let culture_name = culture.name.clone();
+							view! {
+								<td class="culture_listing_name">
+									<A href=format!("/culture/{}", culture.id) class="none">
+										<EquipmentCell cell=culture_name table_view=true />
+									</A>
+								</td>
+							}
+						}
+					</Show>
+					<Show when=move || {
+						query_filter.get().contains(&String::from("parent"))
+					}>
+						{
+							view! {
+								<td class="culture_listing_parent">
+									<A href=format!("/culture/{}", culture.id) class="none">
+										<EquipmentCell cell=culture.parent table_view=true />
+									</A>
+								</td>
+							}
+						}
+					</Show>
+					<Show when=move || {
+						query_filter.get().contains(&String::from("culture_method"))
+					}>
+						{
+							let culture_culture_method = culture.culture_method.clone();
+							view! {
+								<td class="culture_listing_culture_method">
+									<A href=format!("/culture/{}", culture.id) class="none">
+										<EquipmentCell cell=culture_culture_method table_view=true />
+									</A>
+								</td>
+							}
+						}
+					</Show>
+					<Show when=move || {
+						query_filter.get().contains(&String::from("species"))
+					}>
+						{
+							let culture_species = culture.species.clone();
+							view! {
+								<td class="culture_listing_species">
+									<A href=format!("/culture/{}", culture.id) class="none">
+										<EquipmentCell cell=culture_species table_view=true />
+									</A>
+								</td>
+							}
+						}
+					</Show>
+					<Show when=move || {
+						query_filter.get().contains(&String::from("genus"))
+					}>
+						{
+							let culture_genus = culture.genus.clone();
+							view! {
+								<td class="culture_listing_genus">
+									<A href=format!("/culture/{}", culture.id) class="none">
+										<EquipmentCell cell=culture_genus table_view=true />
+									</A>
+								</td>
+							}
+						}
+					</Show>
+					<Show when=move || {
+						query_filter.get().contains(&String::from("location"))
+					}>
+						{
+							let culture_location = culture.location.clone();
+							view! {
+								<td class="culture_listing_location">
+									<A href=format!("/culture/{}", culture.id) class="none">
+										<EquipmentCell cell=culture_location table_view=true />
+									</A>
+								</td>
+							}
+						}
+					</Show>
+					<Show when=move || {
+						query_filter.get().contains(&String::from("storage_conditions"))
+					}>
+						{
+							let culture_storage_conditions = culture.storage_conditions.clone();
+							view! {
+								<td class="culture_listing_storage_conditions">
+									<A href=format!("/culture/{}", culture.id) class="none">
+										<EquipmentCell cell=culture_storage_conditions table_view=true />
+									</A>
+								</td>
+							}
+						}
+					</Show>
+					<Show when=move || {
+						query_filter.get().contains(&String::from("equipment"))
+					}>
+						{
+							view! {
+								<td class="culture_listing_equipment">
+									<A href=format!("/culture/{}", culture.id) class="none">
+										<EquipmentCell cell=culture.equipment table_view=true />
+									</A>
+								</td>
+							}
+						}
+					</Show>
+					<Show when=move || {
+						query_filter.get().contains(&String::from("growth_medium"))
+					}>
+						{
+							let culture_growth_medium = culture.growth_medium.clone();
+							view! {
+								<td class="culture_listing_growth_medium">
+									<A href=format!("/culture/{}", culture.id) class="none">
+										<EquipmentCell cell=culture_growth_medium table_view=true />
+									</A>
+								</td>
+							}
+						}
+					</Show>
+					<Show when=move || {
+						query_filter.get().contains(&String::from("contamination_status"))
+					}>
+						{
+							view! {
+								<td class="culture_listing_contamination_status">
+									<A href=format!("/culture/{}", culture.id) class="none">
+										<EquipmentCell cell=culture.contamination_status table_view=true />
+									</A>
+								</td>
+							}
+						}
+					</Show>
+					<Show when=move || {
+						query_filter.get().contains(&String::from("notes"))
+					}>
+						{
+							let culture_notes = culture.notes.clone();
+							view! {
+								<td class="culture_listing_notes">
+									<A href=format!("/culture/{}", culture.id) class="none">
+										<EquipmentCell cell=culture_notes table_view=true />
+									</A>
+								</td>
+							}
+						}
+					</Show>
+					<td class="culture_listing_details_link">
+						<A href=format!("/culture/{}", culture.id)>Details</A>
+					</td>
+				</tr>
+			}
+		})
+		.collect_view()
+}
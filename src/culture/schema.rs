@@ -0,0 +1,206 @@
+use crate::equipment::{AvatarData, AvatarSQLData, Notes, QRCode};
+
+use chrono::prelude::*;
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "ssr")]
+use sqlx::Row;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum ContaminationStatus {
+	#[default]
+	Clean,
+	Xenic,
+	Monoxenic,
+	Axenic,
+	Contaminated,
+	ParentContaminated,
+	CleanWasContaminated,
+}
+
+impl ContaminationStatus {
+	pub fn parse(input: String) -> Self {
+		match input.to_lowercase().as_str() {
+			"clean" => ContaminationStatus::Clean,
+			"xenic" => ContaminationStatus::Xenic,
+			"monoxenic" => ContaminationStatus::Monoxenic,
+			"axenic" => ContaminationStatus::Axenic,
+			"contaminated" => ContaminationStatus::Contaminated,
+			"parentcontaminated" => ContaminationStatus::ParentContaminated,
+			"cleanwascontaminated" => ContaminationStatus::CleanWasContaminated,
+			_ => Default::default(),
+		}
+	}
+}
+
+impl std::fmt::Display for ContaminationStatus {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			ContaminationStatus::Clean => write!(f, "Clean"),
+			ContaminationStatus::Xenic => write!(f, "Xenic"),
+			ContaminationStatus::Monoxenic => write!(f, "Monoxenic"),
+			ContaminationStatus::Axenic => write!(f, "Axenic"),
+			ContaminationStatus::Contaminated => write!(f, "Contaminated"),
+			ContaminationStatus::ParentContaminated => {
+				// Alt formatter for how the value looks in SQL
+				if f.alternate() {
+					write!(f, "ParentContaminated")
+				} else {
+					write!(f, "Parent Contaminated")
+				}
+			},
+			ContaminationStatus::CleanWasContaminated => {
+				if f.alternate() {
+					write!(f, "CleanWasContaminated")
+				} else {
+					write!(f, "Clean (was Contaminated)")
+				}
+			},
+		}
+	}
+}
+
+impl ContaminationStatus {
+	pub fn get_fields() -> Vec<String> {
+		vec![
+			String::from("Clean"),
+			String::from("Xenic"),
+			String::from("Monoxenic"),
+			String::from("Axenic"),
+			String::from("Contaminated"),
+			String::from("ParentContaminated"),
+			String::from("CleanWasContaminated"),
+		]
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CultureSQLData {
+	pub id: i32,
+	pub person: AvatarSQLData,
+	pub qrcode: String,
+	pub create_date: DateTime<Utc>,
+	pub name: String,
+	pub parent: Option<i32>,
+	pub culture_method: Option<String>,
+	pub species: Option<String>,
+	pub genus: Option<String>,
+	pub location: Option<String>,
+	pub storage_conditions: Option<String>,
+	pub equipment: Option<i32>,
+	pub growth_medium: Option<String>,
+	pub contamination_status: String,
+	pub notes: Option<String>,
+}
+
+#[cfg(feature = "ssr")]
+impl sqlx::FromRow<'_, sqlx::postgres::PgRow> for CultureSQLData {
+	fn from_row(row: &sqlx::postgres::PgRow) -> Result<Self, sqlx::Error> {
+		Ok(CultureSQLData {
+			id: row.try_get("id")?,
+			person: AvatarSQLData {
+				id: row.try_get("person_id")?,
+				status: row.try_get("person_status")?,
+				preferred_name: row.try_get("person_preferred_name")?,
+				picture: row.try_get("person_picture")?,
+			},
+			qrcode: row.try_get("qrcode")?,
+			create_date: row.try_get("create_date")?,
+			name: row.try_get("name")?,
+			parent: row.try_get("parent")?,
+			culture_method: row.try_get("culture_method")?,
+			species: row.try_get("species")?,
+			genus: row.try_get("genus")?,
+			location: row.try_get("location")?,
+			storage_conditions: row.try_get("storage_conditions")?,
+			equipment: row.try_get("equipment")?,
+			growth_medium: row.try_get("growth_medium")?,
+			contamination_status: row.try_get("contamination_status")?,
+			notes: row.try_get("notes")?,
+		})
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CultureData {
+	pub id: i32,
+	pub person: AvatarData,
+	pub qrcode: QRCode,
+	pub create_date: DateTime<Utc>,
+	pub name: String,
+	pub parent: Option<i32>,
+	pub culture_method: Option<String>,
+	pub species: Option<String>,
+	pub genus: Option<String>,
+	pub location: Option<String>,
+	pub storage_conditions: Option<String>,
+	pub equipment: Option<i32>,
+	pub growth_medium: Option<String>,
+	pub contamination_status: ContaminationStatus,
+	pub notes: Option<Notes>,
+}
+
+impl CultureData {
+	pub fn get_fields() -> Vec<(String, String)> {
+		vec![
+			(String::from("id"), String::from("ID")),
+			(String::from("person"), String::from("Person")),
+			(String::from("qrcode"), String::from("QRCode")),
+			(String::from("create_date"), String::from("Create")),
+			(String::from("name"), String::from("Name")),
+			(String::from("parent"), String::from("Parent")),
+			(String::from("culture_method"), String::from("Method")),
+			(String::from("species"), String::from("Species")),
+			(String::from("genus"), String::from("Genus")),
+			(String::from("location"), String::from("Location")),
+			(String::from("storage_conditions"), String::from("Storage Conditions")),
+			(String::from("equipment"), String::from("Equipment")),
+			(String::from("growth_medium"), String::from("Growth Medium")),
+			(String::from("contamination_status"), String::from("Contamination")),
+			(String::from("notes"), String::from("Notes")),
+		]
+	}
+}
+
+impl std::default::Default for CultureData {
+	fn default() -> Self {
+		CultureData {
+			id: Default::default(),
+			person: Default::default(),
+			qrcode: Default::default(),
+			create_date: Utc::now(),
+			name: Default::default(),
+			parent: None,
+			culture_method: None,
+			species: None,
+			genus: None,
+			location: None,
+			storage_conditions: None,
+			equipment: None,
+			growth_medium: None,
+			contamination_status: Default::default(),
+			notes: None,
+		}
+	}
+}
+
+impl From<CultureSQLData> for CultureData {
+	fn from(val: CultureSQLData) -> Self {
+		CultureData {
+			id: val.id,
+			person: val.person.into(),
+			qrcode: val.qrcode.into(),
+			create_date: val.create_date,
+			name: val.name,
+			parent: val.parent,
+			culture_method: val.culture_method,
+			species: val.species,
+			genus: val.genus,
+			location: val.location,
+			storage_conditions: val.storage_conditions,
+			equipment: val.equipment,
+			growth_medium: val.growth_medium,
+			contamination_status: ContaminationStatus::parse(val.contamination_status),
+			notes: val.notes.map(Into::into),
+		}
+	}
+}
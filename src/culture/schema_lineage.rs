@@ -0,0 +1,168 @@
+use crate::culture::ContaminationStatus;
+
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "ssr")]
+use sqlx::FromRow;
+use std::collections::{HashMap, HashSet};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ssr", derive(FromRow))]
+pub struct CultureLineageSQLData {
+	pub id: i32,
+	pub name: String,
+	pub parent: Option<i32>,
+	pub contamination_status: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CultureLineageData {
+	pub id: i32,
+	pub name: String,
+	pub parent: Option<i32>,
+	pub contamination_status: ContaminationStatus,
+}
+
+impl From<CultureLineageSQLData> for CultureLineageData {
+	fn from(val: CultureLineageSQLData) -> Self {
+		CultureLineageData {
+			id: val.id,
+			name: val.name,
+			parent: val.parent,
+			contamination_status: ContaminationStatus::parse(val.contamination_status),
+		}
+	}
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CultureLineageNode {
+	pub culture: CultureLineageData,
+	pub column: f32,
+	pub depth: usize,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CultureLineageLayout {
+	pub nodes: Vec<CultureLineageNode>,
+	pub columns: usize,
+	pub rows: usize,
+}
+
+impl CultureLineageLayout {
+	/// Lays cultures out as a tree where leaves take one column each and parents sit centered above their children.
+	/// Cultures whose parent isn't part of the list (e.g. hidden by permissions) are treated as roots.
+	pub fn new(cultures: Vec<CultureLineageData>) -> Self {
+		let ids = cultures.iter().map(|culture| culture.id).collect::<HashSet<i32>>();
+		let mut children: HashMap<i32, Vec<usize>> = HashMap::new();
+		let mut roots = Vec::new();
+
+		for (index, culture) in cultures.iter().enumerate() {
+			match culture.parent {
+				Some(parent) if ids.contains(&parent) => children.entry(parent).or_default().push(index),
+				_ => roots.push(index),
+			}
+		}
+
+		let mut layout = CultureLineageLayout::default();
+		let mut visited = HashSet::new();
+		let mut next_column = 0;
+
+		for root in roots {
+			layout.place(&cultures, &children, root, 0, &mut next_column, &mut visited);
+		}
+
+		layout.columns = next_column;
+		layout.rows = layout.nodes.iter().map(|node| node.depth + 1).max().unwrap_or(0);
+		layout.nodes.sort_by_key(|node| node.culture.id);
+
+		layout
+	}
+
+	fn place(
+		&mut self,
+		cultures: &[CultureLineageData],
+		children: &HashMap<i32, Vec<usize>>,
+		index: usize,
+		depth: usize,
+		next_column: &mut usize,
+		visited: &mut HashSet<i32>,
+	) -> f32 {
+		let culture = &cultures[index];
+		visited.insert(culture.id);
+
+		let mut child_columns = Vec::new();
+		for &child in children.get(&culture.id).map(Vec::as_slice).unwrap_or_default() {
+			if !visited.contains(&cultures[child].id) {
+				child_columns.push(self.place(cultures, children, child, depth + 1, next_column, visited));
+			}
+		}
+
+		let column = match (child_columns.first(), child_columns.last()) {
+			(Some(first), Some(last)) => (first + last) / 2.0,
+			_ => {
+				let column = *next_column as f32;
+				*next_column += 1;
+				column
+			},
+		};
+
+		self.nodes.push(CultureLineageNode {
+			culture: culture.clone(),
+			column,
+			depth,
+		});
+
+		column
+	}
+
+	pub fn get(&self, id: i32) -> Option<&CultureLineageNode> {
+		self.nodes.iter().find(|node| node.culture.id == id)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn culture(id: i32, parent: Option<i32>) -> CultureLineageData {
+		CultureLineageData {
+			id,
+			name: format!("Culture {id}"),
+			parent,
+			contamination_status: ContaminationStatus::Clean,
+		}
+	}
+
+	#[test]
+	fn lineage_layout_test() {
+		let layout = CultureLineageLayout::new(vec![
+			culture(1, None),
+			culture(2, Some(1)),
+			culture(3, Some(2)),
+			culture(4, Some(2)),
+		]);
+
+		assert_eq!(layout.columns, 2);
+		assert_eq!(layout.rows, 3);
+		assert_eq!(layout.get(3).map(|node| (node.column, node.depth)), Some((0.0, 2)));
+		assert_eq!(layout.get(4).map(|node| (node.column, node.depth)), Some((1.0, 2)));
+		assert_eq!(layout.get(2).map(|node| (node.column, node.depth)), Some((0.5, 1)));
+		assert_eq!(layout.get(1).map(|node| (node.column, node.depth)), Some((0.5, 0)));
+	}
+
+	#[test]
+	fn lineage_layout_missing_parent_test() {
+		let layout = CultureLineageLayout::new(vec![culture(2, Some(1)), culture(3, Some(2)), culture(5, Some(4))]);
+
+		assert_eq!(layout.columns, 2);
+		assert_eq!(layout.rows, 2);
+		assert_eq!(layout.get(2).map(|node| (node.column, node.depth)), Some((0.0, 0)));
+		assert_eq!(layout.get(5).map(|node| (node.column, node.depth)), Some((1.0, 0)));
+	}
+
+	#[test]
+	fn lineage_layout_cycle_test() {
+		let layout = CultureLineageLayout::new(vec![culture(1, None), culture(2, Some(1)), culture(3, Some(3))]);
+
+		assert_eq!(layout.nodes.len(), 2);
+	}
+}
@@ -0,0 +1,225 @@
+use chrono::prelude::*;
+use std::{future::Future, time::Duration};
+use tokio::{
+	io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+	net::TcpStream,
+};
+
+/// How long connecting or a single SMTP round-trip may take before the send is given up
+const SMTP_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Where mail is delivered, read from `SMTP_HOST`, `SMTP_PORT` and `SMTP_FROM`.
+/// The server talks plain SMTP so it expects a relay on a trusted network or a catcher like MailHog.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmailConfig {
+	pub host: String,
+	pub port: u16,
+	pub from: String,
+}
+
+impl EmailConfig {
+	/// `None` when no SMTP host is configured which turns sending mail off
+	pub fn from_env() -> Option<Self> {
+		let host = std::env::var("SMTP_HOST").ok().filter(|host| !host.is_empty())?;
+		Some(EmailConfig {
+			host,
+			port: std::env::var("SMTP_PORT").ok().and_then(|port| port.parse().ok()).unwrap_or(25),
+			from: std::env::var("SMTP_FROM").unwrap_or_else(|_| String::from("codon@localhost")),
+		})
+	}
+}
+
+/// The address links in emails point to, read from `APP_URL`
+pub fn app_url() -> String {
+	std::env::var("APP_URL").unwrap_or_else(|_| String::from("http://localhost:3000")).trim_end_matches('/').to_string()
+}
+
+/// Whether an address can go into an SMTP command and a header as-is.
+/// Line breaks would inject commands or headers and angle brackets would close the `<...>` around it.
+pub fn is_valid_address(address: &str) -> bool {
+	match address.split_once('@') {
+		Some((local, domain)) => {
+			!local.is_empty()
+				&& !domain.is_empty()
+				&& !domain.contains('@')
+				&& !address
+					.chars()
+					.any(|char| char.is_whitespace() || char.is_control() || matches!(char, '<' | '>' | ',' | ';'))
+		},
+		None => false,
+	}
+}
+
+/// Encodes a header value as RFC 2047 words when it isn't plain ASCII, split so no word is longer than 75 characters.
+/// Line breaks become spaces either way so they can't start a new header.
+pub fn encode_header(value: &str) -> String {
+	let value = value.replace(['\r', '\n'], " ");
+	if value.is_ascii() {
+		return value;
+	}
+
+	const PREFIX: &str = "=?UTF-8?Q?";
+	const SUFFIX: &str = "?=";
+	let mut words = Vec::new();
+	let mut word = String::new();
+	for char in value.chars() {
+		let mut buffer = [0; 4];
+		let encoded = char
+			.encode_utf8(&mut buffer)
+			.bytes()
+			.map(|byte| match byte {
+				b' ' => String::from("_"),
+				b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'!' | b'*' | b'+' | b'-' | b'/' => (byte as char).to_string(),
+				_ => format!("={byte:02X}"),
+			})
+			.collect::<String>();
+		if PREFIX.len() + word.len() + encoded.len() + SUFFIX.len() > 75 {
+			words.push(format!("{PREFIX}{word}{SUFFIX}"));
+			word.clear();
+		}
+		word.push_str(&encoded);
+	}
+	words.push(format!("{PREFIX}{word}{SUFFIX}"));
+
+	words.join("\r\n ")
+}
+
+/// Builds the message with headers, CRLF line endings and lines starting with a dot escaped
+pub fn format_message(from: &str, to: &[String], subject: &str, body: &str, date: DateTime<Utc>) -> String {
+	let headers = [
+		format!("From: {from}"),
+		format!("To: {}", to.join(", ")),
+		format!("Subject: {}", encode_header(subject)),
+		format!("Date: {}", date.to_rfc2822()),
+		String::from("MIME-Version: 1.0"),
+		String::from("Content-Type: text/plain; charset=utf-8"),
+		String::from("Content-Transfer-Encoding: 8bit"),
+	];
+	let body = body
+		.lines()
+		.map(|line| {
+			if line.starts_with('.') {
+				format!(".{line}")
+			} else {
+				line.to_string()
+			}
+		})
+		.collect::<Vec<_>>()
+		.join("\r\n");
+
+	format!("{}\r\n\r\n{body}\r\n", headers.join("\r\n"))
+}
+
+/// Sends a plain text email to all recipients in one SMTP transaction
+pub async fn send_email(config: &EmailConfig, to: &[String], subject: &str, body: &str) -> Result<(), String> {
+	if to.is_empty() {
+		return Ok(());
+	}
+	if let Some(address) = std::iter::once(&config.from).chain(to).find(|address| !is_valid_address(address)) {
+		return Err(format!("\"{}\" is not a valid email address", address.escape_debug()));
+	}
+
+	let stream = with_timeout(TcpStream::connect((config.host.as_str(), config.port)))
+		.await?
+		.map_err(|error| format!("Connecting to {}:{} failed: {error}", config.host, config.port))?;
+	let (reader, mut writer) = stream.into_split();
+	let mut reader = BufReader::new(reader);
+
+	expect_reply(&mut reader, 220).await?;
+	command(&mut writer, &mut reader, "EHLO codon", 250).await?;
+	command(&mut writer, &mut reader, &format!("MAIL FROM:<{}>", config.from), 250).await?;
+	for recipient in to {
+		command(&mut writer, &mut reader, &format!("RCPT TO:<{recipient}>"), 250).await?;
+	}
+	command(&mut writer, &mut reader, "DATA", 354).await?;
+	let message = format_message(&config.from, to, subject, body, Utc::now());
+	command(&mut writer, &mut reader, &format!("{message}."), 250).await?;
+	command(&mut writer, &mut reader, "QUIT", 221).await?;
+
+	Ok(())
+}
+
+async fn command(
+	writer: &mut tokio::net::tcp::OwnedWriteHalf,
+	reader: &mut BufReader<tokio::net::tcp::OwnedReadHalf>,
+	line: &str,
+	expected: u16,
+) -> Result<(), String> {
+	with_timeout(writer.write_all(format!("{line}\r\n").as_bytes())).await?.map_err(|error| error.to_string())?;
+	expect_reply(reader, expected).await
+}
+
+/// Stops a hung mail server from blocking the job runner
+async fn with_timeout<T>(future: impl Future<Output = T>) -> Result<T, String> {
+	tokio::time::timeout(SMTP_TIMEOUT, future)
+		.await
+		.map_err(|_| format!("The mail server didn't answer within {} seconds", SMTP_TIMEOUT.as_secs()))
+}
+
+/// Reads a possibly multi line reply and checks its code, 2xx replies also accept 251 for forwarded recipients
+async fn expect_reply(reader: &mut BufReader<tokio::net::tcp::OwnedReadHalf>, expected: u16) -> Result<(), String> {
+	loop {
+		let mut line = String::new();
+		if with_timeout(reader.read_line(&mut line)).await?.map_err(|error| error.to_string())? == 0 {
+			return Err(String::from("The mail server closed the connection"));
+		}
+
+		let code = line.get(..3).and_then(|code| code.parse::<u16>().ok());
+		// "250-" continues a multi line reply, "250 " ends it
+		if line.as_bytes().get(3) == Some(&b'-') {
+			continue;
+		}
+
+		return match code {
+			Some(code) if code == expected || (expected == 250 && code == 251) => Ok(()),
+			_ => Err(format!("The mail server answered \"{}\"", line.trim_end())),
+		};
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn is_valid_address_test() {
+		assert!(is_valid_address("a@lab.test"));
+		assert!(is_valid_address("first.last+tag@lab.test"));
+		assert!(!is_valid_address("lab.test"));
+		assert!(!is_valid_address("@lab.test"));
+		assert!(!is_valid_address("a@"));
+		assert!(!is_valid_address("a@b@lab.test"));
+		assert!(!is_valid_address("a@lab.test>\r\nRCPT TO:<b@lab.test"));
+		assert!(!is_valid_address("a@lab.test\nBcc: b@lab.test"));
+		assert!(!is_valid_address("a b@lab.test"));
+		assert!(!is_valid_address("a@lab.test, b@lab.test"));
+	}
+
+	#[test]
+	fn format_message_test() {
+		let date = Utc.with_ymd_and_hms(2024, 5, 1, 8, 0, 0).unwrap();
+		let message = format_message(
+			"codon@lab.test",
+			&[String::from("a@lab.test"), String::from("b@lab.test")],
+			"Warranty\nexpires",
+			"Hello\n.hidden\nBye",
+			date,
+		);
+
+		assert!(message.starts_with("From: codon@lab.test\r\nTo: a@lab.test, b@lab.test\r\nSubject: Warranty expires\r\n"));
+		assert!(message.contains("Date: Wed, 1 May 2024 08:00:00 +0000\r\n"));
+		assert!(message.ends_with("\r\n\r\nHello\r\n..hidden\r\nBye\r\n"));
+	}
+
+	#[test]
+	fn encode_header_test() {
+		assert_eq!(encode_header("Warranty\r\nexpires"), "Warranty  expires");
+		assert_eq!(encode_header("Spülmaschine is due"), "=?UTF-8?Q?Sp=C3=BClmaschine_is_due?=");
+
+		let encoded = encode_header(&"ü".repeat(30));
+		assert!(
+			encoded.split("\r\n ").all(|word| word.len() <= 75 && word.starts_with("=?UTF-8?Q?") && word.ends_with("?="))
+		);
+		assert_eq!(encoded.matches("=C3=BC").count(), 30);
+	}
+}
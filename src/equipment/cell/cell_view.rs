@@ -1,7 +1,9 @@
 use crate::{
 	components::{avatar::Avatar, multiline::MultiLine},
-	equipment::{AvatarData, Cost, EquipmentStatus, EquipmentType, Notes, QRCode},
-	icons::{Flask, IncubationCabinet, Vessel},
+	equipment::{
+		AvatarData, Cost, CustomFieldValue, EquipmentStatus, EquipmentType, EquipmentTypeIcon, MaintenanceDue, Notes,
+		QRCode,
+	},
 };
 
 use chrono::prelude::*;
@@ -23,26 +25,8 @@ impl EquipmentCellView for EquipmentType {
 	fn view(self, _: bool) -> impl IntoView {
 		view! {
 			<div class=css::equipment_type>
-				{match self {
-					EquipmentType::Flask => {
-						view! {
-							<Flask />
-							<span>" Flask"</span>
-						}
-					}
-					EquipmentType::Vessel => {
-						view! {
-							<Vessel />
-							<span>" Vessel"</span>
-						}
-					}
-					EquipmentType::IncubationCabinet => {
-						view! {
-							<IncubationCabinet />
-							<span>" Incubation Cabinet"</span>
-						}
-					}
-				}}
+				<EquipmentTypeIcon icon=self.icon />
+				<span>" " {self.label}</span>
 			</div>
 		}
 	}
@@ -85,6 +69,12 @@ impl EquipmentCellView for String {
 	}
 }
 
+impl EquipmentCellView for CustomFieldValue {
+	fn view(self, _: bool) -> impl IntoView {
+		view! { <StringCell item=self.to_string() /> }
+	}
+}
+
 impl EquipmentCellView for QRCode {
 	fn view(self, _: bool) -> impl IntoView {
 		view! { <img src=format!("{self}") alt="The QR code" class=css::qrcode /> }
@@ -100,6 +90,15 @@ impl EquipmentCellView for Option<String> {
 	}
 }
 
+impl EquipmentCellView for Option<i32> {
+	fn view(self, table_view: bool) -> impl IntoView {
+		match self {
+			Some(value) => value.view(table_view).into_view(),
+			None => view! { <span /> }.into_view(),
+		}
+	}
+}
+
 impl EquipmentCellView for Option<Cost> {
 	fn view(self, _: bool) -> impl IntoView {
 		match self {
@@ -129,6 +128,32 @@ impl EquipmentCellView for Option<Notes> {
 	}
 }
 
+impl EquipmentCellView for Vec<MaintenanceDue> {
+	fn view(self, _: bool) -> impl IntoView {
+		let now = Utc::now();
+		view! {
+			<ul class=css::maintenance>
+				{self
+					.into_iter()
+					.map(|item| {
+						let due = format!("due {}", item.due_date.format("%d %b %Y"));
+						view! {
+							<li>
+								{item.plan.name.clone()} " " <small>"(" {item.plan.interval()} ")"</small> " "
+								<span class=if item.is_overdue(now) {
+									css::overdue
+								} else {
+									""
+								}>{due}</span>
+							</li>
+						}
+					})
+					.collect_view()}
+			</ul>
+		}
+	}
+}
+
 #[component]
 fn StringCell(item: String) -> impl IntoView {
 	view! { <span>{item}</span> }
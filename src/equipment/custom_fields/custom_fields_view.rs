@@ -0,0 +1,285 @@
+use crate::{
+	components::{
+		button::{Button, ButtonVariant},
+		input::Input,
+		select::Select,
+	},
+	equipment::{CustomField, CustomFieldKind},
+};
+
+use leptos::*;
+use leptos_router::*;
+
+stylance::import_style!(css, "custom_fields.module.css");
+
+/// The custom fields of one equipment type and, for people who can create equipment, a form to add more
+#[component]
+pub fn CustomFields(equipment_type: String, can_create: Signal<bool>) -> impl IntoView {
+	let add_field_action = create_server_action::<AddCustomField>();
+	let delete_field_action = create_server_action::<DeleteCustomField>();
+
+	let fields_data = create_resource(
+		move || (add_field_action.version().get(), delete_field_action.version().get()),
+		move |_| get_custom_fields(),
+	);
+
+	let error = move || {
+		[add_field_action.value().get(), delete_field_action.value().get()]
+			.into_iter()
+			.find_map(|value| value.and_then(Result::err))
+			.map(|error| {
+				view! {
+					<span class=css::error>
+						{error.to_string().replace("error reaching server to call server function: ", "")}
+					</span>
+				}
+			})
+	};
+
+	let equipment_type = store_value(equipment_type);
+	view! {
+		<div class=css::fields>
+			<Transition>
+				{move || {
+					let type_name = equipment_type.get_value();
+					fields_data
+						.get()
+						.and_then(Result::ok)
+						.map(|fields| {
+							fields
+								.into_iter()
+								.filter(|field| field.equipment_type == type_name)
+								.map(|field| {
+									let id = field.id;
+									view! {
+										<div class=css::field>
+											<span class=css::label>{field.label.clone()}</span>
+											<span class=css::rules>{field_rules(&field)}</span>
+											<Show when=move || can_create.get()>
+												<Button
+													variant=ButtonVariant::Text
+													on_click=Callback::new(move |_: ev::MouseEvent| {
+														delete_field_action.dispatch(DeleteCustomField { id });
+													})
+												>
+													Remove
+												</Button>
+											</Show>
+										</div>
+									}
+								})
+								.collect_view()
+						})
+				}}
+			</Transition>
+
+			<Show when=move || can_create.get()>
+				<ActionForm action=add_field_action class=css::form>
+					<input type="hidden" name="equipment_type" value=equipment_type.get_value() />
+					<Input name="label" placeholder="Field name" required=true />
+					<Select name="kind" required=true>
+						{CustomFieldKind::get_fields()
+							.into_iter()
+							.map(|kind| {
+								let label = CustomFieldKind::parse(kind.clone()).to_string();
+								view! { <option value=kind>{label}</option> }
+							})
+							.collect_view()}
+					</Select>
+					<Input name="unit" placeholder="Unit" />
+					<Input name="options" placeholder="Choices, comma separated" />
+					<Input name="min_value" placeholder="Min" />
+					<Input name="max_value" placeholder="Max" />
+					<Button kind="submit" variant=ButtonVariant::Outlined>
+						Add field
+					</Button>
+				</ActionForm>
+			</Show>
+			{error}
+		</div>
+	}
+}
+
+/// A short summary of what a field accepts
+fn field_rules(field: &CustomField) -> String {
+	let mut rules = vec![field.kind.to_string()];
+	if let Some(unit) = &field.unit {
+		rules.push(format!("in {unit}"));
+	}
+	if !field.options.is_empty() {
+		rules.push(field.options.join(" / "));
+	}
+	match (field.min_value, field.max_value) {
+		(Some(min), Some(max)) => rules.push(format!("{min} to {max}")),
+		(Some(min), None) => rules.push(format!("at least {min}")),
+		(None, Some(max)) => rules.push(format!("at most {max}")),
+		(None, None) => {},
+	}
+	rules.join(", ")
+}
+
+/// All custom fields ordered by type and the order they were added in
+#[cfg(feature = "ssr")]
+pub async fn load_custom_fields(pool: &sqlx::PgPool) -> Result<Vec<CustomField>, ServerFnError> {
+	Ok(
+		sqlx::query_as::<_, CustomField>("SELECT * FROM equipment_fields ORDER BY equipment_type, id")
+			.fetch_all(pool)
+			.await?,
+	)
+}
+
+/// Fills in the custom fields of each equipment's type with the values stored for it
+#[cfg(feature = "ssr")]
+pub async fn attach_custom_fields(
+	pool: &sqlx::PgPool,
+	custom_fields: &[CustomField],
+	equipment: &mut [crate::equipment::EquipmentData],
+) -> Result<(), ServerFnError> {
+	use crate::equipment::CustomFieldValue;
+
+	let ids: Vec<i32> = equipment.iter().map(|item| item.id).collect();
+	let values: Vec<(i32, i32, String)> =
+		sqlx::query_as("SELECT equipment, field, value FROM equipment_field_values WHERE equipment = ANY($1)")
+			.bind(&ids)
+			.fetch_all(pool)
+			.await?;
+
+	for item in equipment.iter_mut() {
+		item.custom_fields = custom_fields
+			.iter()
+			.filter(|field| field.equipment_type == item.equipment_type.name)
+			.map(|field| CustomFieldValue {
+				field: field.clone(),
+				value: values
+					.iter()
+					.find(|(equipment, id, _)| *equipment == item.id && *id == field.id)
+					.map(|(_, _, value)| value.clone()),
+			})
+			.collect();
+	}
+
+	Ok(())
+}
+
+#[server(prefix = "/api")]
+pub async fn get_custom_fields() -> Result<Vec<CustomField>, ServerFnError> {
+	use crate::auth::get_user;
+
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	if user.is_none() {
+		return Err(ServerFnError::Request(String::from("User not authenticated")));
+	}
+
+	load_custom_fields(&pool).await
+}
+
+#[server(prefix = "/api")]
+pub async fn add_custom_field(
+	equipment_type: String,
+	label: String,
+	kind: String,
+	unit: String,
+	options: String,
+	min_value: String,
+	max_value: String,
+) -> Result<(), ServerFnError> {
+	use crate::{
+		auth::get_user,
+		equipment::{check_equipment_create, find_equipment_type},
+	};
+
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	check_equipment_create(user)?;
+
+	let equipment_type = find_equipment_type(&pool, &equipment_type).await?.name;
+
+	let label = label.trim().to_string();
+	if label.is_empty() {
+		return Err(ServerFnError::Request(String::from("The field name can't be empty")));
+	}
+	if !CustomFieldKind::get_fields().contains(&kind) {
+		return Err(ServerFnError::Request(String::from("Unknown field kind")));
+	}
+	let kind = CustomFieldKind::parse(kind);
+
+	let unit = Some(unit.trim().to_string()).filter(|unit| !unit.is_empty() && kind == CustomFieldKind::Number);
+	let options: Vec<String> = options
+		.split(',')
+		.map(|option| option.trim().to_string())
+		.filter(|option| !option.is_empty() && kind == CustomFieldKind::Select)
+		.collect();
+	if kind == CustomFieldKind::Select && options.is_empty() {
+		return Err(ServerFnError::Request(String::from("A select field needs at least one choice")));
+	}
+
+	let number = |value: &str, name: &str| -> Result<Option<f64>, ServerFnError> {
+		if value.trim().is_empty() || kind != CustomFieldKind::Number {
+			return Ok(None);
+		}
+		value.trim().parse::<f64>().map(Some).map_err(|_| ServerFnError::Request(format!("The {name} must be a number")))
+	};
+	let min_value = number(&min_value, "min")?;
+	let max_value = number(&max_value, "max")?;
+	if matches!((min_value, max_value), (Some(min), Some(max)) if min > max) {
+		return Err(ServerFnError::Request(String::from("The min can't be larger than the max")));
+	}
+
+	let exists: bool =
+		sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM equipment_fields WHERE equipment_type = $1 AND label = $2)")
+			.bind(&equipment_type)
+			.bind(&label)
+			.fetch_one(&pool)
+			.await?;
+	if exists {
+		return Err(ServerFnError::Request(format!("\"{label}\" already exists for this type")));
+	}
+
+	sqlx::query(
+		r#"
+		INSERT INTO equipment_fields (equipment_type, label, kind, unit, options, min_value, max_value)
+		VALUES ($1, $2, $3, $4, $5, $6, $7)
+		"#,
+	)
+	.bind(equipment_type)
+	.bind(label)
+	.bind(format!("{kind:?}"))
+	.bind(unit)
+	.bind(options)
+	.bind(min_value)
+	.bind(max_value)
+	.execute(&pool)
+	.await?;
+
+	Ok(())
+}
+
+/// Removes a custom field together with every value stored for it
+#[server(prefix = "/api")]
+pub async fn delete_custom_field(id: i32) -> Result<(), ServerFnError> {
+	use crate::{auth::get_user, equipment::check_equipment_create};
+
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	check_equipment_create(user)?;
+
+	let result = sqlx::query("DELETE FROM equipment_fields WHERE id = $1").bind(id).execute(&pool).await?;
+	if result.rows_affected() == 0 {
+		return Err(ServerFnError::Request(String::from("Unknown custom field")));
+	}
+
+	Ok(())
+}
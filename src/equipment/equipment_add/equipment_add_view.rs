@@ -7,7 +7,7 @@ use crate::{
 		select::Select,
 		timezone_offset::Timezone,
 	},
-	equipment::{EquipmentType, Heading},
+	equipment::{EquipmentFromTemplate, Heading, get_equipment_types},
 	icons::EquipmentLogo,
 	login::Login,
 	permission::{Permission, Permissions},
@@ -24,6 +24,7 @@ pub fn EquipmentAdd() -> impl IntoView {
 	let user_signal = use_context::<UserSignal>().expect("No user signal found in context");
 
 	let loading = create_rw_signal(false);
+	let types = create_resource(|| (), move |_| get_equipment_types());
 
 	let navigate = use_navigate();
 	create_effect(move |_| {
@@ -67,10 +68,25 @@ pub fn EquipmentAdd() -> impl IntoView {
 										<span class=css::text>Equipment Type:</span>
 										<span class=css::input>
 											<Select name="equipment_type" required=true>
-												{EquipmentType::get_fields()
-													.iter()
-													.map(|name| view! { <option value=name>{name}</option> })
-													.collect_view()}
+												<Suspense>
+													{move || {
+														types
+															.get()
+															.and_then(Result::ok)
+															.map(|types| {
+																types
+																	.into_iter()
+																	.map(|equipment_type| {
+																		view! {
+																			<option value=equipment_type.name>
+																				{equipment_type.label}
+																			</option>
+																		}
+																	})
+																	.collect_view()
+															})
+													}}
+												</Suspense>
 											</Select>
 										</span>
 									</label>
@@ -161,6 +177,8 @@ pub fn EquipmentAdd() -> impl IntoView {
 										</Button>
 									</div>
 								</ActionForm>
+
+								<EquipmentFromTemplate />
 							}
 								.into_view()
 						}
@@ -187,15 +205,12 @@ pub async fn add_equipment(
 ) -> Result<i32, ServerFnError> {
 	use crate::{
 		auth::get_user,
-		equipment::{EquipmentStatus, EquipmentType},
+		equipment::{EquipmentStatus, find_equipment_type},
 		permission::{Permission, Permissions},
-		qrcode::generate_qr,
-		utils::get_equipment_base_folder,
 	};
 
 	use chrono::prelude::*;
 	use sqlx::PgPool;
-	use std::{fs, path::PathBuf};
 
 	let pool = use_context::<PgPool>()
 		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
@@ -250,50 +265,107 @@ pub async fn add_equipment(
 	let cost_in_cent: Option<i32> =
 		cost_in_cent.parse::<f64>().ok().map(|cost_in_cent_f64| (cost_in_cent_f64 * 100.0) as i32);
 
-	let row = sqlx::query!(
+	let equipment_type = find_equipment_type(&pool, &equipment_type).await?.name;
+
+	let mut transaction = pool.begin().await?;
+	let id = insert_equipment(
+		&mut transaction,
+		user_id,
+		NewEquipment {
+			equipment_type,
+			name,
+			status: EquipmentStatus::Dirty,
+			manufacturer: Some(manufacturer),
+			purchase_date,
+			vendor: Some(vendor),
+			cost_in_cent,
+			warranty_expiration_date,
+			location: Some(location),
+			notes: Some(notes),
+		},
+		"Created",
+	)
+	.await?;
+	transaction.commit().await?;
+
+	Ok(id)
+}
+
+/// The fields of an equipment item that hasn't been stored yet
+#[cfg(feature = "ssr")]
+pub struct NewEquipment {
+	/// The name of one of the `equipment_types`
+	pub equipment_type: String,
+	pub name: String,
+	pub status: crate::equipment::EquipmentStatus,
+	pub manufacturer: Option<String>,
+	pub purchase_date: Option<chrono::DateTime<chrono::Utc>>,
+	pub vendor: Option<String>,
+	pub cost_in_cent: Option<i32>,
+	pub warranty_expiration_date: Option<chrono::DateTime<chrono::Utc>>,
+	pub location: Option<String>,
+	pub notes: Option<String>,
+}
+
+/// Stores new equipment, writes its QR code and records the creation in its log
+#[cfg(feature = "ssr")]
+pub async fn insert_equipment(
+	connection: &mut sqlx::PgConnection,
+	person: i32,
+	equipment: NewEquipment,
+	log_note: &str,
+) -> Result<i32, ServerFnError> {
+	use crate::{
+		equipment::EquipmentLogType, qrcode::generate_qr, utils::get_equipment_base_folder, webhooks::queue_log_webhooks,
+	};
+
+	let id: i32 = sqlx::query_scalar(
 		"INSERT INTO equipment\
-		(equipment_type, person, create_date, name, status, manufacturer, purchase_date, vendor, cost_in_cent, warranty_expiration_date, location, notes)\
+		(equipment_type, person, name, status, manufacturer, purchase_date, vendor, cost_in_cent, warranty_expiration_date, location, notes)\
 		VALUES\
-		($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)\
+		($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)\
 		RETURNING id",
-		EquipmentType::parse(equipment_type.clone()).to_string(),
-		user_id,
-		Utc::now(),
-		name,
-		EquipmentStatus::Dirty.to_string(),
-		manufacturer,
-		purchase_date,
-		vendor,
-		cost_in_cent,
-		warranty_expiration_date,
-		location,
-		notes,
 	)
-	.fetch_one(&pool)
+	.bind(equipment.equipment_type)
+	.bind(person)
+	.bind(equipment.name)
+	.bind(format!("{:?}", equipment.status))
+	.bind(equipment.manufacturer)
+	.bind(equipment.purchase_date)
+	.bind(equipment.vendor)
+	.bind(equipment.cost_in_cent)
+	.bind(equipment.warranty_expiration_date)
+	.bind(equipment.location)
+	.bind(equipment.notes)
+	.fetch_one(&mut *connection)
 	.await
-	.map_err::<ServerFnError, _>(|error| {
-		ServerFnError::ServerError(error.to_string())
-	})?;
+	.map_err::<ServerFnError, _>(|error| ServerFnError::ServerError(error.to_string()))?;
 
 	// TODO: make domain configurable
-	let qr_svg = generate_qr(&format!("https://codon.com/equipment/{}", row.id))
+	let qr_svg = generate_qr(&format!("https://codon.com/equipment/{id}"))
 		.map_err::<ServerFnError, _>(|_| ServerFnError::ServerError("Failed to generate QR code".into()))?;
 
-	let base_folder = get_equipment_base_folder(row.id);
+	let base_folder = get_equipment_base_folder(id);
 	tokio::fs::create_dir_all(format!("{}public{base_folder}", env!("UPLOAD_ROOT"))).await?;
-	let qr_path = PathBuf::from(format!("{}public{base_folder}qr_{}.svg", env!("UPLOAD_ROOT"), row.id));
+	let qrcode = format!("{base_folder}qr_{id}.svg");
 
-	fs::write(&qr_path, qr_svg)
+	tokio::fs::write(format!("{}public{qrcode}", env!("UPLOAD_ROOT")), qr_svg)
+		.await
 		.map_err::<ServerFnError, _>(|_| ServerFnError::ServerError("Failed to save QR code to disk".into()))?;
 
-	if let Some(stripped_path) = qr_path.to_string_lossy().strip_prefix(&format!("{}public", env!("UPLOAD_ROOT"))) {
-		sqlx::query!("UPDATE equipment SET qrcode = $1 WHERE id = $2", &stripped_path, row.id)
-			.execute(&pool)
-			.await
-			.map(|_| ())?;
-	} else {
-		return Err(ServerFnError::ServerError("Failed to convert QR code path".into()));
-	}
+	sqlx::query("UPDATE equipment SET qrcode = $1 WHERE id = $2").bind(qrcode).bind(id).execute(&mut *connection).await?;
+
+	let log_id: i32 = sqlx::query_scalar(
+		"INSERT INTO equipment_log (log_type, equipment, person, notes) VALUES ($1, $2, $3, $4) RETURNING id",
+	)
+	.bind(EquipmentLogType::Create.to_string())
+	.bind(id)
+	.bind(person)
+	.bind(log_note)
+	.fetch_one(&mut *connection)
+	.await?;
+
+	queue_log_webhooks(connection, log_id).await?;
 
-	Ok(row.id)
+	Ok(id)
 }
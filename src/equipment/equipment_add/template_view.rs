@@ -0,0 +1,330 @@
+use crate::{
+	components::{
+		button::{Button, ButtonVariant},
+		input::Input,
+		select::Select,
+	},
+	equipment::EquipmentTemplateData,
+};
+
+use leptos::*;
+use leptos_router::*;
+
+stylance::import_style!(css, "equipment_add.module.css");
+stylance::import_style!(template_css, "equipment_template.module.css");
+
+/// The most equipment a single batch may create
+pub const EQUIPMENT_BATCH_LIMIT: i32 = 100;
+
+/// Creates a batch of equipment from a saved template
+#[component]
+pub fn EquipmentFromTemplate() -> impl IntoView {
+	let create_action = create_server_action::<AddEquipmentFromTemplate>();
+	let delete_action = create_server_action::<DeleteEquipmentTemplate>();
+
+	let templates = create_resource(move || delete_action.version().get(), move |_| get_equipment_templates());
+
+	let navigate = use_navigate();
+	create_effect(move |_| {
+		if let Some(Ok(ids)) = create_action.value().get() {
+			match ids.as_slice() {
+				[id] => navigate(&format!("/equipment/{id}"), NavigateOptions::default()),
+				_ => navigate("/equipment", NavigateOptions::default()),
+			}
+		}
+	});
+
+	let error = move || {
+		[
+			create_action.value().get().map(|value| value.map(|_| ())),
+			delete_action.value().get(),
+		]
+		.into_iter()
+		.find_map(|value| value.and_then(Result::err))
+		.map(|error| {
+			view! {
+				<span class=css::error>
+					{error.to_string().replace("error reaching server to call server function: ", "")}
+				</span>
+			}
+		})
+	};
+
+	view! {
+		<Suspense fallback=move || view! { <p>Loading templates...</p> }>
+			{move || {
+				templates
+					.get()
+					.map(|data| match data {
+						Err(error) => view! { <pre class="error">Server Error: {error.to_string()}</pre> }.into_view(),
+						Ok(templates) if templates.is_empty() => view! {}.into_view(),
+						Ok(templates) => {
+							let options = templates
+								.iter()
+								.map(|template| view! { <option value=template.id>{template.name.clone()}</option> })
+								.collect_view();
+							view! {
+								<h2 class=template_css::subheading>From a template</h2>
+								<ActionForm action=create_action class=css::form>
+									<label class=css::label>
+										<span class=css::text>Template:</span>
+										<span class=css::input>
+											<Select name="template" required=true>
+												{options}
+											</Select>
+										</span>
+									</label>
+
+									<label class=css::label>
+										<span class=css::text>Name:</span>
+										<span class=css::input>
+											<Input name="name" placeholder="Name" required=true />
+										</span>
+									</label>
+
+									<label class=css::label>
+										<span class=css::text>Count:</span>
+										<span class=css::input>
+											<Input
+												name="count"
+												kind="number"
+												value=create_rw_signal(String::from("1"))
+												required=true
+											/>
+										</span>
+									</label>
+
+									<div class=css::btn_row>
+										{error} <Button kind="submit">Create from template</Button>
+									</div>
+								</ActionForm>
+
+								<ul class=template_css::templates>
+									{templates
+										.into_iter()
+										.map(|template| {
+											view! { <TemplateItem template delete_action /> }
+										})
+										.collect_view()}
+								</ul>
+							}
+								.into_view()
+						}
+					})
+			}}
+		</Suspense>
+	}
+}
+
+#[component]
+fn TemplateItem(
+	template: EquipmentTemplateData,
+	delete_action: Action<DeleteEquipmentTemplate, Result<(), ServerFnError>>,
+) -> impl IntoView {
+	let mut details = vec![template.equipment_type.to_string()];
+	details.extend(template.manufacturer);
+	details.extend(template.vendor);
+	details.extend(template.cost_in_cent.map(|cost| format!("${:.2}", cost as f64 / 100.0)));
+	details.extend(template.warranty_days.map(|days| format!("{days} days warranty")));
+	details.extend(template.location);
+
+	view! {
+		<li>
+			<ActionForm action=delete_action>
+				<input type="hidden" name="id" value=template.id />
+				<strong>{template.name}</strong>
+				" "
+				<small>{details.join(", ")}</small>
+				" "
+				<Button kind="submit" variant=ButtonVariant::Text>
+					Delete
+				</Button>
+			</ActionForm>
+		</li>
+	}
+}
+
+/// Only people who may create equipment get to use or manage templates
+#[cfg(feature = "ssr")]
+pub(crate) fn check_equipment_create(user: Option<crate::auth::User>) -> Result<crate::auth::User, ServerFnError> {
+	use crate::permission::{Permission, Permissions};
+
+	match user {
+		Some(user) => {
+			let Permissions::All {
+				read: _,
+				write: _,
+				create: perm,
+			} = &user.permission_equipment;
+			if *perm != Permission::Create(true) {
+				return Err(ServerFnError::Request(String::from("User not authenticated")));
+			}
+			Ok(user)
+		},
+		None => Err(ServerFnError::Request(String::from("User not authenticated"))),
+	}
+}
+
+/// Numbers the names of a batch so each item stays distinguishable
+#[cfg(feature = "ssr")]
+pub(crate) fn batch_names(name: &str, count: &str) -> Result<Vec<String>, ServerFnError> {
+	let count = match count.parse::<i32>() {
+		Ok(value) if (1..=EQUIPMENT_BATCH_LIMIT).contains(&value) => value,
+		_ => {
+			return Err(ServerFnError::Request(format!("Count must be a number between 1 and {EQUIPMENT_BATCH_LIMIT}")));
+		},
+	};
+
+	if name.trim().is_empty() {
+		return Err(ServerFnError::Request(String::from("Name can't be empty")));
+	}
+
+	if count == 1 {
+		Ok(vec![name.to_string()])
+	} else {
+		Ok((1..=count).map(|n| format!("{name} {n}")).collect())
+	}
+}
+
+#[server(prefix = "/api")]
+pub async fn get_equipment_templates() -> Result<Vec<EquipmentTemplateData>, ServerFnError> {
+	use crate::{auth::get_user, equipment::EquipmentTemplateSQLData};
+
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	check_equipment_create(user)?;
+
+	let templates = sqlx::query_as::<_, EquipmentTemplateSQLData>(
+		r#"
+		SELECT
+			equipment_templates.id,
+			equipment_templates.name,
+			equipment_templates.equipment_type,
+			equipment_types.label AS equipment_type_label,
+			equipment_types.icon AS equipment_type_icon,
+			equipment_templates.manufacturer,
+			equipment_templates.vendor,
+			equipment_templates.cost_in_cent,
+			equipment_templates.warranty_days,
+			equipment_templates.location
+		FROM
+			equipment_templates
+			JOIN equipment_types ON equipment_templates.equipment_type = equipment_types.name
+		ORDER BY equipment_templates.name
+		"#,
+	)
+	.fetch_all(&pool)
+	.await?;
+
+	Ok(templates.into_iter().map(Into::into).collect())
+}
+
+#[server(prefix = "/api")]
+pub async fn delete_equipment_template(id: String) -> Result<(), ServerFnError> {
+	use crate::auth::get_user;
+
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	check_equipment_create(user)?;
+
+	let id = match id.parse::<i32>() {
+		Ok(value) => value,
+		Err(_) => return Err(ServerFnError::Request(String::from("Invalid ID"))),
+	};
+
+	sqlx::query("DELETE FROM equipment_templates WHERE id = $1").bind(id).execute(&pool).await?;
+
+	Ok(())
+}
+
+/// Creates `count` items from a template, each with its own QR code and creation log entry
+#[server(prefix = "/api")]
+pub async fn add_equipment_from_template(
+	template: String,
+	name: String,
+	count: String,
+) -> Result<Vec<i32>, ServerFnError> {
+	use crate::{
+		auth::get_user,
+		equipment::{EquipmentStatus, EquipmentTemplateSQLData, NewEquipment, insert_equipment},
+	};
+
+	use chrono::{Duration, Utc};
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	let user_id = check_equipment_create(user)?.id;
+
+	let template_id = match template.parse::<i32>() {
+		Ok(value) => value,
+		Err(_) => return Err(ServerFnError::Request(String::from("Invalid template"))),
+	};
+
+	let names = batch_names(&name, &count)?;
+
+	let template: EquipmentTemplateData = sqlx::query_as::<_, EquipmentTemplateSQLData>(
+		r#"
+		SELECT
+			equipment_templates.id,
+			equipment_templates.name,
+			equipment_templates.equipment_type,
+			equipment_types.label AS equipment_type_label,
+			equipment_types.icon AS equipment_type_icon,
+			equipment_templates.manufacturer,
+			equipment_templates.vendor,
+			equipment_templates.cost_in_cent,
+			equipment_templates.warranty_days,
+			equipment_templates.location
+		FROM
+			equipment_templates
+			JOIN equipment_types ON equipment_templates.equipment_type = equipment_types.name
+		WHERE equipment_templates.id = $1
+		"#,
+	)
+	.bind(template_id)
+	.fetch_optional(&pool)
+	.await?
+	.ok_or_else::<ServerFnError, _>(|| ServerFnError::Request(String::from("Template not found")))?
+	.into();
+
+	let now = Utc::now();
+	let log_note = format!("Created from template \"{}\"", template.name);
+
+	let mut transaction = pool.begin().await?;
+	let mut ids = Vec::with_capacity(names.len());
+	for name in names {
+		let id = insert_equipment(
+			&mut transaction,
+			user_id,
+			NewEquipment {
+				equipment_type: template.equipment_type.name.clone(),
+				name,
+				status: EquipmentStatus::Dirty,
+				manufacturer: template.manufacturer.clone(),
+				purchase_date: Some(now),
+				vendor: template.vendor.clone(),
+				cost_in_cent: template.cost_in_cent,
+				warranty_expiration_date: template.warranty_days.map(|days| now + Duration::days(days as i64)),
+				location: template.location.clone(),
+				notes: None,
+			},
+			&log_note,
+		)
+		.await?;
+		ids.push(id);
+	}
+	transaction.commit().await?;
+
+	Ok(ids)
+}
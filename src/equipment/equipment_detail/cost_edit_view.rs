@@ -32,6 +32,7 @@ pub fn CostEdit(
 				view! {
 					<ActionForm action=cost_in_cent_action class=css::edit_form>
 						<input type="hidden" name="id" value=equipment.id />
+						<input type="hidden" name="version" value=equipment.version />
 						<MoneyInput
 							name="cost_in_cent"
 							value=create_rw_signal(cost_in_cent_clone.unwrap_or_default().to_string())
@@ -70,8 +71,17 @@ pub fn CostEdit(
 }
 
 #[server(prefix = "/api")]
-pub async fn edit_cost_in_cent(id: String, cost_in_cent: f32, note: String) -> Result<(), ServerFnError> {
-	use crate::{auth::get_user, permission::Permissions};
+pub async fn edit_cost_in_cent(
+	id: String,
+	version: i32,
+	cost_in_cent: String,
+	note: String,
+) -> Result<(), ServerFnError> {
+	use crate::{
+		auth::get_user,
+		equipment::{Cost, FieldEdit, apply_field_edits},
+		permission::{Dimension, Permissions, Subject},
+	};
 
 	use sqlx::PgPool;
 
@@ -94,42 +104,23 @@ pub async fn edit_cost_in_cent(id: String, cost_in_cent: f32, note: String) -> R
 			} = user.permission_equipment;
 			user_id = user.id;
 
-			let person: i32 =
-				sqlx::query_scalar("SELECT person FROM equipment WHERE id = $1").bind(id).fetch_one(&pool).await?;
-			if !perm.has_permission("write", id, person) {
+			let subject = Subject::load(Dimension::Equipment, id, &pool).await?;
+			if !perm.has_permission_on("write", &subject) {
 				return Err(ServerFnError::Request(String::from("User not authenticated")));
 			}
 		},
 		None => return Err(ServerFnError::Request(String::from("User not authenticated"))),
 	};
 
-	let cost_in_cent = (cost_in_cent * 100.0) as i32;
-
-	let old_value: i32 =
-		sqlx::query_scalar("SELECT cost_in_cent FROM equipment WHERE id = $1").bind(id).fetch_one(&pool).await?;
-	let old_value = format!("{}", (old_value as f32 / 100.0));
-
-	sqlx::query!(
-		r#"INSERT INTO equipment_log
-		(log_type, equipment, person, notes, field, old_value, new_value)
-		VALUES
-		($1, $2, $3, $4, $5, $6, $7)"#,
-		"edit",
+	apply_field_edits(
+		&pool,
 		id,
+		version,
 		user_id,
-		note,
-		"cost_in_cent",
-		old_value,
-		format!("{:.2}", (cost_in_cent as f32 / 100.0)),
+		&note,
+		vec![FieldEdit::CostInCent(
+			Cost::parse_cents(&cost_in_cent).map_err::<ServerFnError, _>(ServerFnError::Request)?,
+		)],
 	)
-	.execute(&pool)
 	.await
-	.map_err::<ServerFnError, _>(|error| ServerFnError::ServerError(error.to_string()))?;
-
-	sqlx::query!("UPDATE equipment SET cost_in_cent = $1 WHERE id = $2", cost_in_cent, id)
-		.execute(&pool)
-		.await
-		.map(|_| ())?;
-
-	Ok(())
 }
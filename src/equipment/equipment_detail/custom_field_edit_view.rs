@@ -0,0 +1,157 @@
+use crate::{
+	app::UserSignal,
+	components::{
+		button::Button,
+		input::{Input, TextArea},
+		select::Select,
+	},
+	equipment::{CustomFieldKind, CustomFieldValue, EquipmentData, EquipmentFormToggle},
+};
+
+use leptos::*;
+use leptos_router::*;
+
+stylance::import_style!(css, "equipment_details_edits.module.css");
+
+#[component]
+pub fn CustomFieldEdit(
+	equipment: EquipmentData,
+	custom_field: CustomFieldValue,
+	user_signal: UserSignal,
+	refetch_resources: RwSignal<usize>,
+) -> impl IntoView {
+	let custom_field_action = create_server_action::<EditCustomField>();
+
+	view! {
+		<EquipmentFormToggle
+			user_id=equipment.person.id
+			id=equipment.id
+			user_signal=user_signal
+			item=custom_field.clone()
+		>
+			{
+				let field = custom_field.field.clone();
+				let value = custom_field.value.clone().unwrap_or_default();
+				let input = match field.kind {
+					CustomFieldKind::Text | CustomFieldKind::Number => {
+						view! { <Input name="value" value=create_rw_signal(value) /> }.into_view()
+					}
+					CustomFieldKind::Date => {
+						view! { <Input name="value" kind="date" value=create_rw_signal(value) /> }.into_view()
+					}
+					CustomFieldKind::Select | CustomFieldKind::Boolean => {
+						let options = if field.kind == CustomFieldKind::Boolean {
+							vec![(String::from("true"), String::from("Yes")), (String::from("false"), String::from("No"))]
+						} else {
+							field.options.iter().map(|option| (option.clone(), option.clone())).collect()
+						};
+						view! {
+							<Select name="value">
+								<option value="">"–"</option>
+								{options
+									.into_iter()
+									.map(|(option, label)| {
+										let selected = option == value;
+										view! {
+											<option value=option selected=selected>
+												{label}
+											</option>
+										}
+									})
+									.collect_view()}
+							</Select>
+						}
+							.into_view()
+					}
+				};
+				view! {
+					<ActionForm action=custom_field_action class=css::edit_form>
+						<input type="hidden" name="id" value=equipment.id />
+						<input type="hidden" name="version" value=equipment.version />
+						<input type="hidden" name="field" value=field.id />
+						{input}
+						<TextArea name="note" placeholder="Add a note why you made this change" />
+						<div class=css::btns>
+							{move || {
+								if let Some(responds) = custom_field_action.value().get() {
+									match responds {
+										Ok(_) => {
+											custom_field_action.value().set(None);
+											refetch_resources.update(|version| *version += 1);
+											view! {}.into_view()
+										}
+										Err(error) => {
+											view! {
+												<span>
+													{error
+														.to_string()
+														.replace("error reaching server to call server function: ", "")}
+												</span>
+											}
+												.into_view()
+										}
+									}
+								} else {
+									view! {}.into_view()
+								}
+							}} <Button kind="submit">Save</Button>
+						</div>
+					</ActionForm>
+				}
+			}
+		</EquipmentFormToggle>
+	}
+}
+
+#[server(prefix = "/api")]
+pub async fn edit_custom_field(
+	id: String,
+	version: i32,
+	field: String,
+	value: String,
+	note: String,
+) -> Result<(), ServerFnError> {
+	use crate::{
+		auth::get_user,
+		equipment::{FieldEdit, apply_field_edits, find_equipment_custom_field},
+		permission::{Dimension, Permissions, Subject},
+	};
+
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	let id = match id.parse::<i32>() {
+		Ok(value) => value,
+		Err(_) => return Err(ServerFnError::Request(String::from("Invalid ID"))),
+	};
+	let field_id = match field.parse::<i32>() {
+		Ok(value) => value,
+		Err(_) => return Err(ServerFnError::Request(String::from("Invalid field"))),
+	};
+
+	let user_id;
+	match user {
+		Some(user) => {
+			let Permissions::All {
+				read: _,
+				write: perm,
+				create: _,
+			} = user.permission_equipment;
+			user_id = user.id;
+
+			let subject = Subject::load(Dimension::Equipment, id, &pool).await?;
+			if !perm.has_permission_on("write", &subject) {
+				return Err(ServerFnError::Request(String::from("User not authenticated")));
+			}
+		},
+		None => return Err(ServerFnError::Request(String::from("User not authenticated"))),
+	};
+
+	let field = find_equipment_custom_field(&pool, id, field_id).await?;
+	let value = field.validate(&value).map_err::<ServerFnError, _>(ServerFnError::Request)?;
+
+	apply_field_edits(&pool, id, version, user_id, &note, vec![FieldEdit::CustomField { field, value }]).await
+}
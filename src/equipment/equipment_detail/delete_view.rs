@@ -0,0 +1,343 @@
+use crate::{
+	app::UserSignal,
+	components::button::{Button, ButtonVariant},
+	equipment::EquipmentData,
+	permission::{Permissions, Subject, SubjectSignal},
+};
+
+use chrono::{Duration, Utc};
+use leptos::*;
+use leptos_router::*;
+
+stylance::import_style!(css, "equipment_details_edits.module.css");
+
+/// How many days deleted equipment can still be restored from the trash
+pub const EQUIPMENT_RESTORE_DAYS: i64 = 30;
+
+/// Moves equipment into the trash, restores it within the restore window or purges it for good
+#[component]
+pub fn EquipmentDelete(
+	equipment: EquipmentData,
+	user_signal: UserSignal,
+	refetch_resources: RwSignal<usize>,
+) -> impl IntoView {
+	let delete_action = create_server_action::<DeleteEquipment>();
+	let restore_action = create_server_action::<RestoreEquipment>();
+	let purge_action = create_server_action::<PurgeEquipment>();
+	let subject_signal = use_context::<SubjectSignal>();
+
+	let navigate = use_navigate();
+	create_effect(move |_| {
+		if let Some(Ok(_)) = purge_action.value().get() {
+			navigate("/equipment", NavigateOptions::default());
+		}
+	});
+
+	create_effect(move |_| {
+		if matches!(delete_action.value().get(), Some(Ok(_))) || matches!(restore_action.value().get(), Some(Ok(_))) {
+			delete_action.value().set(None);
+			restore_action.value().set(None);
+			refetch_resources.update(|version| *version += 1);
+		}
+	});
+
+	let id = equipment.id;
+	let person = equipment.person.id;
+	let can_write = move || match user_signal.get() {
+		Some(user) => {
+			let Permissions::All {
+				read: _,
+				write: perm,
+				create: _,
+			} = user.permission_equipment;
+			perm.has_permission_on("write", &Subject::resolve(subject_signal, id, person))
+		},
+		None => false,
+	};
+
+	let error = move || {
+		[
+			delete_action.value().get(),
+			restore_action.value().get(),
+			purge_action.value().get(),
+		]
+		.into_iter()
+		.find_map(|value| value.and_then(Result::err))
+		.map(
+			|error| view! { <span>{error.to_string().replace("error reaching server to call server function: ", "")}</span> },
+		)
+	};
+
+	match equipment.deleted_at {
+		None => view! {
+			<Show when=can_write>
+				<ActionForm action=delete_action class=css::edit_form>
+					<input type="hidden" name="id" value=id />
+					<div class=css::btns>
+						{error} <Button kind="submit" variant=ButtonVariant::Outlined>
+							Move to trash
+						</Button>
+					</div>
+				</ActionForm>
+			</Show>
+		}
+		.into_view(),
+		Some(deleted_at) => {
+			let restore_until = deleted_at + Duration::days(EQUIPMENT_RESTORE_DAYS);
+			view! {
+				<div class=css::edit_form>
+					<span>
+						"In the trash since " {deleted_at.format("%d %b %Y").to_string()}
+						{if Utc::now() < restore_until {
+							format!(", can be restored until {}", restore_until.format("%d %b %Y"))
+						} else {
+							String::from(", the restore window has passed")
+						}}
+					</span>
+					<Show when=can_write>
+						<div class=css::btns>
+							{error} <Show when=move || Utc::now() < restore_until>
+								<ActionForm action=restore_action>
+									<input type="hidden" name="id" value=id />
+									<Button kind="submit" variant=ButtonVariant::Outlined>
+										Restore
+									</Button>
+								</ActionForm>
+							</Show> <ActionForm action=purge_action>
+								<input type="hidden" name="id" value=id />
+								<Button
+									kind="submit"
+									variant=ButtonVariant::Text
+									on_click=Callback::new(|event: ev::MouseEvent| {
+										let confirmed = window()
+											.confirm_with_message(
+												"Purging removes this equipment with all its notes, log entries and media for good. Continue?",
+											)
+											.unwrap_or(false);
+										if !confirmed {
+											event.prevent_default();
+										}
+									})
+								>
+									Purge permanently
+								</Button>
+							</ActionForm>
+						</div>
+					</Show>
+				</div>
+			}
+			.into_view()
+		},
+	}
+}
+
+#[cfg(feature = "ssr")]
+async fn check_equipment_write(
+	user: Option<crate::auth::User>,
+	id: i32,
+	pool: &sqlx::PgPool,
+) -> Result<i32, ServerFnError> {
+	use crate::permission::Dimension;
+
+	match user {
+		Some(user) => {
+			let Permissions::All {
+				read: _,
+				write: perm,
+				create: _,
+			} = user.permission_equipment;
+			let subject = Subject::load(Dimension::Equipment, id, pool).await?;
+			if !perm.has_permission_on("write", &subject) {
+				return Err(ServerFnError::Request(String::from("User not authenticated")));
+			}
+			Ok(user.id)
+		},
+		None => Err(ServerFnError::Request(String::from("User not authenticated"))),
+	}
+}
+
+#[server(prefix = "/api")]
+pub async fn delete_equipment(id: String) -> Result<(), ServerFnError> {
+	use crate::{audit::record_audit, auth::get_user};
+
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	let id = match id.parse::<i32>() {
+		Ok(value) => value,
+		Err(_) => return Err(ServerFnError::Request(String::from("Invalid ID"))),
+	};
+
+	let user_id = check_equipment_write(user, id, &pool).await?;
+
+	let mut transaction = pool.begin().await?;
+
+	let deleted = sqlx::query("UPDATE equipment SET deleted_at = now() WHERE id = $1 AND deleted_at IS NULL")
+		.bind(id)
+		.execute(&mut *transaction)
+		.await?
+		.rows_affected();
+	if deleted == 0 {
+		return Err(ServerFnError::Request(String::from("This equipment is already in the trash")));
+	}
+
+	record_audit(&mut *transaction, "delete", "equipment", id, user_id, None).await?;
+	transaction.commit().await?;
+
+	Ok(())
+}
+
+#[server(prefix = "/api")]
+pub async fn restore_equipment(id: String) -> Result<(), ServerFnError> {
+	use crate::{audit::record_audit, auth::get_user};
+
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	let id = match id.parse::<i32>() {
+		Ok(value) => value,
+		Err(_) => return Err(ServerFnError::Request(String::from("Invalid ID"))),
+	};
+
+	let user_id = check_equipment_write(user, id, &pool).await?;
+
+	let mut transaction = pool.begin().await?;
+
+	let restored = sqlx::query(
+		"UPDATE equipment SET deleted_at = NULL WHERE id = $1 AND deleted_at > now() - make_interval(days => $2)",
+	)
+	.bind(id)
+	.bind(EQUIPMENT_RESTORE_DAYS as i32)
+	.execute(&mut *transaction)
+	.await?
+	.rows_affected();
+	if restored == 0 {
+		return Err(ServerFnError::Request(String::from("This equipment can't be restored anymore")));
+	}
+
+	record_audit(&mut *transaction, "restore", "equipment", id, user_id, None).await?;
+	transaction.commit().await?;
+
+	Ok(())
+}
+
+#[server(prefix = "/api")]
+pub async fn purge_equipment(id: String) -> Result<(), ServerFnError> {
+	use crate::auth::get_user;
+
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	let id = match id.parse::<i32>() {
+		Ok(value) => value,
+		Err(_) => return Err(ServerFnError::Request(String::from("Invalid ID"))),
+	};
+
+	let user_id = check_equipment_write(user, id, &pool).await?;
+
+	purge_trashed_equipment(&pool, id, Some(user_id)).await
+}
+
+/// Purges everything that has been in the trash for longer than the restore window
+#[cfg(feature = "ssr")]
+pub async fn purge_expired_equipment(pool: sqlx::PgPool) -> Result<(), String> {
+	let ids: Vec<i32> =
+		sqlx::query_scalar("SELECT id FROM equipment WHERE deleted_at <= now() - make_interval(days => $1)")
+			.bind(EQUIPMENT_RESTORE_DAYS as i32)
+			.fetch_all(&pool)
+			.await
+			.map_err(|error| error.to_string())?;
+
+	let mut failed = Vec::new();
+	for id in ids {
+		if let Err(error) = purge_trashed_equipment(&pool, id, None).await {
+			failed.push(format!("{id} ({error})"));
+		}
+	}
+
+	if failed.is_empty() {
+		Ok(())
+	} else {
+		Err(format!("Could not purge equipment {}", failed.join(", ")))
+	}
+}
+
+/// Removes trashed equipment with its notes, log, links and every file stored for it.
+/// Purges without a person are recorded under whoever moved the equipment to the trash.
+#[cfg(feature = "ssr")]
+async fn purge_trashed_equipment(pool: &sqlx::PgPool, id: i32, person: Option<i32>) -> Result<(), ServerFnError> {
+	use crate::{audit::record_audit, utils::get_equipment_base_folder};
+
+	use std::path::PathBuf;
+
+	let mut transaction = pool.begin().await?;
+
+	// Locking the row keeps a concurrent restore from bringing back equipment we are about to remove
+	let (name, qrcode, deleted_by): (String, Option<String>, i32) = sqlx::query_as(
+		r#"
+		SELECT
+			name,
+			qrcode,
+			COALESCE(
+				(SELECT person FROM audit_log WHERE entity = 'equipment' AND entity_id = equipment.id AND action = 'delete' ORDER BY create_date DESC LIMIT 1),
+				person
+			)
+		FROM equipment
+		WHERE id = $1 AND deleted_at IS NOT NULL
+		FOR UPDATE"#,
+	)
+	.bind(id)
+	.fetch_optional(&mut *transaction)
+	.await?
+	.ok_or_else::<ServerFnError, _>(|| ServerFnError::Request(String::from("Only equipment in the trash can be purged")))?;
+
+	sqlx::query("DELETE FROM equipment_notes WHERE equipment = $1").bind(id).execute(&mut *transaction).await?;
+	sqlx::query("DELETE FROM equipment_log WHERE equipment = $1").bind(id).execute(&mut *transaction).await?;
+	sqlx::query("DELETE FROM experiment_equipment WHERE equipment = $1").bind(id).execute(&mut *transaction).await?;
+	sqlx::query("UPDATE culture SET equipment = NULL WHERE equipment = $1").bind(id).execute(&mut *transaction).await?;
+	sqlx::query("DELETE FROM equipment WHERE id = $1").bind(id).execute(&mut *transaction).await?;
+
+	record_audit(&mut *transaction, "purge", "equipment", id, person.unwrap_or(deleted_by), Some(name)).await?;
+
+	// Files can't be part of the transaction so we move them aside first and only remove them once the rows are gone
+	let upload_root = format!("{}public", env!("UPLOAD_ROOT"));
+	let base_folder = PathBuf::from(format!("{upload_root}{}", get_equipment_base_folder(id)));
+	let purge_folder = PathBuf::from(format!("{upload_root}{}", get_equipment_base_folder(id).trim_end_matches('/')))
+		.with_extension("purge");
+	let moved = if base_folder.exists() {
+		tokio::fs::rename(&base_folder, &purge_folder).await?;
+		true
+	} else {
+		false
+	};
+
+	if let Err(error) = transaction.commit().await {
+		if moved {
+			tokio::fs::rename(&purge_folder, &base_folder).await?;
+		}
+		return Err(error.into());
+	}
+
+	if moved {
+		tokio::fs::remove_dir_all(&purge_folder).await?;
+	}
+
+	// Older QR codes may live outside of the equipment folder
+	if let Some(qrcode) = qrcode {
+		let qr_path = PathBuf::from(format!("{upload_root}{qrcode}"));
+		if qr_path.exists() {
+			tokio::fs::remove_file(&qr_path).await?;
+		}
+	}
+
+	Ok(())
+}
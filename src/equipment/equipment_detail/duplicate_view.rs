@@ -0,0 +1,242 @@
+use crate::{
+	app::UserSignal,
+	components::{
+		button::{Button, ButtonVariant},
+		input::Input,
+	},
+	equipment::EquipmentData,
+	permission::{Permission, Permissions},
+};
+
+use leptos::*;
+use leptos_router::*;
+
+stylance::import_style!(
+	#[allow(dead_code)]
+	css,
+	"equipment_details_edits.module.css"
+);
+
+/// Copies equipment into new items or saves its shared fields as a template
+#[component]
+pub fn EquipmentDuplicate(equipment: EquipmentData, user_signal: UserSignal) -> impl IntoView {
+	let duplicate_action = create_server_action::<DuplicateEquipment>();
+	let template_action = create_server_action::<SaveEquipmentTemplate>();
+
+	let navigate = use_navigate();
+	create_effect(move |_| {
+		if let Some(Ok(ids)) = duplicate_action.value().get() {
+			match ids.as_slice() {
+				[id] => navigate(&format!("/equipment/{id}"), NavigateOptions::default()),
+				_ => navigate("/equipment", NavigateOptions::default()),
+			}
+		}
+	});
+
+	let trashed = equipment.deleted_at.is_some();
+	let can_create = move || match user_signal.get() {
+		Some(_) if trashed => false,
+		Some(user) => {
+			let Permissions::All {
+				read: _,
+				write: _,
+				create: perm,
+			} = user.permission_equipment;
+			perm == Permission::Create(true)
+		},
+		None => false,
+	};
+
+	let error = move || {
+		[
+			duplicate_action.value().get().map(|value| value.map(|_| ())),
+			template_action.value().get(),
+		]
+		.into_iter()
+		.find_map(|value| value.and_then(Result::err))
+		.map(
+			|error| view! { <span>{error.to_string().replace("error reaching server to call server function: ", "")}</span> },
+		)
+	};
+
+	let id = equipment.id;
+	let template_name = create_rw_signal(equipment.name.clone());
+
+	view! {
+		<Show when=can_create>
+			<div class=css::edit_form>
+				<ActionForm action=duplicate_action class=css::edit_form>
+					<input type="hidden" name="id" value=id />
+					<Input name="count" kind="number" value=create_rw_signal(String::from("1")) required=true />
+					<div class=css::btns>
+						<Button kind="submit" variant=ButtonVariant::Outlined>
+							Duplicate
+						</Button>
+					</div>
+				</ActionForm>
+				<ActionForm action=template_action class=css::edit_form>
+					<input type="hidden" name="id" value=id />
+					<Input name="name" placeholder="Template name" value=template_name required=true />
+					<div class=css::btns>
+						{error}
+						{move || {
+							matches!(template_action.value().get(), Some(Ok(_))).then_some(view! { <span>Saved</span> })
+						}} <Button kind="submit" variant=ButtonVariant::Outlined>
+							Save as template
+						</Button>
+					</div>
+				</ActionForm>
+			</div>
+		</Show>
+	}
+}
+
+/// Checks that the user may create equipment and read the item being copied
+#[cfg(feature = "ssr")]
+async fn check_equipment_copy(
+	user: Option<crate::auth::User>,
+	id: i32,
+	pool: &sqlx::PgPool,
+) -> Result<i32, ServerFnError> {
+	use crate::{
+		equipment::check_equipment_create,
+		permission::{Dimension, Subject},
+	};
+
+	let user = check_equipment_create(user)?;
+	let Permissions::All {
+		read: perm,
+		write: _,
+		create: _,
+	} = user.permission_equipment;
+	let subject = Subject::load(Dimension::Equipment, id, pool).await?;
+	if !perm.has_permission_on("read", &subject) {
+		return Err(ServerFnError::Request(String::from("User not authenticated")));
+	}
+
+	Ok(user.id)
+}
+
+/// Creates `count` copies of an equipment item, each with its own QR code and creation log entry
+#[server(prefix = "/api")]
+pub async fn duplicate_equipment(id: String, count: String) -> Result<Vec<i32>, ServerFnError> {
+	use crate::{
+		auth::get_user,
+		equipment::{EquipmentStatus, NewEquipment, batch_names, insert_equipment},
+	};
+
+	use chrono::{DateTime, Utc};
+	use sqlx::{PgPool, Row};
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	let id = match id.parse::<i32>() {
+		Ok(value) => value,
+		Err(_) => return Err(ServerFnError::Request(String::from("Invalid ID"))),
+	};
+
+	let user_id = check_equipment_copy(user, id, &pool).await?;
+
+	let row = sqlx::query(
+		"SELECT equipment_type, name, manufacturer, purchase_date, vendor, cost_in_cent, warranty_expiration_date, location, notes \
+		FROM equipment WHERE id = $1 AND deleted_at IS NULL",
+	)
+	.bind(id)
+	.fetch_optional(&pool)
+	.await?
+	.ok_or_else::<ServerFnError, _>(|| ServerFnError::Request(String::from("Equipment not found")))?;
+
+	let name: String = row.try_get("name")?;
+	let names = batch_names(&format!("{name} (copy)"), &count)?;
+	let log_note = format!("Duplicated from \"{name}\" (#{id})");
+
+	let mut transaction = pool.begin().await?;
+	let mut ids = Vec::with_capacity(names.len());
+	for name in names {
+		let new_id = insert_equipment(
+			&mut transaction,
+			user_id,
+			NewEquipment {
+				equipment_type: row.try_get("equipment_type")?,
+				name,
+				status: EquipmentStatus::Dirty,
+				manufacturer: row.try_get("manufacturer")?,
+				purchase_date: row.try_get::<Option<DateTime<Utc>>, _>("purchase_date")?,
+				vendor: row.try_get("vendor")?,
+				cost_in_cent: row.try_get("cost_in_cent")?,
+				warranty_expiration_date: row.try_get::<Option<DateTime<Utc>>, _>("warranty_expiration_date")?,
+				location: row.try_get("location")?,
+				notes: row.try_get("notes")?,
+			},
+			&log_note,
+		)
+		.await?;
+		sqlx::query(
+			"INSERT INTO equipment_field_values (equipment, field, value) SELECT $1, field, value FROM equipment_field_values WHERE equipment = $2",
+		)
+		.bind(new_id)
+		.bind(id)
+		.execute(&mut *transaction)
+		.await?;
+		ids.push(new_id);
+	}
+	transaction.commit().await?;
+
+	Ok(ids)
+}
+
+/// Stores the fields equipment shares with its siblings so more of it can be created later
+#[server(prefix = "/api")]
+pub async fn save_equipment_template(id: String, name: String) -> Result<(), ServerFnError> {
+	use crate::auth::get_user;
+
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	let id = match id.parse::<i32>() {
+		Ok(value) => value,
+		Err(_) => return Err(ServerFnError::Request(String::from("Invalid ID"))),
+	};
+
+	let user_id = check_equipment_copy(user, id, &pool).await?;
+
+	let name = name.trim();
+	if name.is_empty() {
+		return Err(ServerFnError::Request(String::from("Template name can't be empty")));
+	}
+
+	// The warranty is stored as a length so new items get a warranty counted from their own purchase
+	let inserted = sqlx::query(
+		r#"INSERT INTO equipment_templates
+			(name, equipment_type, manufacturer, vendor, cost_in_cent, warranty_days, location, person)
+		SELECT
+			$1,
+			equipment_type,
+			manufacturer,
+			vendor,
+			cost_in_cent,
+			EXTRACT(DAY FROM warranty_expiration_date - purchase_date)::INT,
+			location,
+			$2
+		FROM equipment
+		WHERE id = $3
+		ON CONFLICT (name) DO NOTHING"#,
+	)
+	.bind(name)
+	.bind(user_id)
+	.bind(id)
+	.execute(&pool)
+	.await?
+	.rows_affected();
+
+	if inserted == 0 {
+		return Err(ServerFnError::Request(format!("A template named \"{name}\" already exists")));
+	}
+
+	Ok(())
+}
@@ -1,13 +1,16 @@
 use crate::{
 	app::{LoginAction, UserSignal},
 	equipment::{
-		CostEdit, EquipmentCell, EquipmentData, EquipmentLogData, EquipmentNoteEdit, EquipmentType, Heading, LocationEdit,
-		Log, ManufacturerEdit, NameEdit, Notes, PurchaseDateEdit, StatusEdit, TypeEdit, VendorEdit,
+		CostEdit, CustomFieldEdit, EquipmentCell, EquipmentData, EquipmentDelete, EquipmentDuplicate, EquipmentLogData,
+		EquipmentNoteEdit, EquipmentTypeIcon, FieldHistory, FieldsEdit, Heading, LiveUpdates, LocationEdit, Log,
+		MaintenanceEdit, ManufacturerEdit, NameEdit, Notes, PurchaseDateEdit, StatusEdit, TypeEdit, VendorEdit,
 		WarrantyExpirationDateEdit, get_log_for_equipment,
 	},
 	error_template::ErrorTemplate,
-	icons::{FlaskLogo, IncubationCabinetLogo, VesselLogo},
+	experiment::EquipmentExperiments,
 	login::Login,
+	notifications::WatchToggle,
+	permission::{Dimension, provide_subject_signal},
 };
 
 use leptos::*;
@@ -58,6 +61,11 @@ pub fn EquipmentDetail() -> impl IntoView {
 
 	let login_action = use_context::<LoginAction>().expect("No login action found in context");
 	let user_signal = use_context::<UserSignal>().expect("No user signal found in context");
+	provide_subject_signal(
+		Dimension::Equipment,
+		move || params.with(|p| p.get("id").cloned().unwrap_or_default()),
+		refetch_resources,
+	);
 
 	let equipment_data = create_resource(
 		move || {
@@ -72,6 +80,7 @@ pub fn EquipmentDetail() -> impl IntoView {
 	);
 
 	view! {
+		<LiveUpdates id refetch_resources />
 		<Suspense fallback=move || view! { <p>Loading equipment...</p> }>
 			<ErrorBoundary fallback=|errors| {
 				view! { <ErrorTemplate errors=errors /> }
@@ -91,16 +100,20 @@ pub fn EquipmentDetail() -> impl IntoView {
 									}
 									Ok(equipment) => {
 										let title = equipment.name.clone();
+										let now = chrono::Utc::now();
+										let overdue = equipment.maintenance.iter().filter(|item| item.is_overdue(now)).count();
 										view! {
 											<div class=css::details>
 												<Heading>
-													{match equipment.equipment_type {
-														EquipmentType::Flask => view! { <FlaskLogo /> }.into_view(),
-														EquipmentType::Vessel => view! { <VesselLogo /> }.into_view(),
-														EquipmentType::IncubationCabinet => {
-															view! { <IncubationCabinetLogo /> }.into_view()
-														}
-													}} " " {title}
+													<EquipmentTypeIcon icon=equipment.equipment_type.icon logo=true /> " " {title}
+													{(overdue > 0)
+														.then(|| {
+															view! {
+																<span class=css::maintenance_badge>
+																	{format!("{overdue} maintenance overdue")}
+																</span>
+															}
+														})}
 												</Heading>
 
 												<dl class=css::list>
@@ -109,7 +122,15 @@ pub fn EquipmentDetail() -> impl IntoView {
 														<EquipmentCell cell=equipment.id />
 													</dd>
 
-													<dt>Name</dt>
+													<dt>
+														"Name"
+														<FieldHistory
+															equipment=equipment.clone()
+															field="name"
+															user_signal
+															refetch_resources
+														/>
+													</dt>
 													<dd class=css::edit>
 														<NameEdit
 															equipment=equipment.clone()
@@ -118,7 +139,15 @@ pub fn EquipmentDetail() -> impl IntoView {
 														/>
 													</dd>
 
-													<dt>Equipment Type</dt>
+													<dt>
+														"Equipment Type"
+														<FieldHistory
+															equipment=equipment.clone()
+															field="type"
+															user_signal
+															refetch_resources
+														/>
+													</dt>
 													<dd class=css::edit>
 														<TypeEdit
 															equipment=equipment.clone()
@@ -151,7 +180,15 @@ pub fn EquipmentDetail() -> impl IntoView {
 														/>
 													</dd>
 
-													<dt>Manufacturer</dt>
+													<dt>
+														"Manufacturer"
+														<FieldHistory
+															equipment=equipment.clone()
+															field="manufacturer"
+															user_signal
+															refetch_resources
+														/>
+													</dt>
 													<dd class=css::edit>
 														<ManufacturerEdit
 															equipment=equipment.clone()
@@ -160,7 +197,15 @@ pub fn EquipmentDetail() -> impl IntoView {
 														/>
 													</dd>
 
-													<dt>Purchase Date</dt>
+													<dt>
+														"Purchase Date"
+														<FieldHistory
+															equipment=equipment.clone()
+															field="purchase_date"
+															user_signal
+															refetch_resources
+														/>
+													</dt>
 													<dd class=css::edit>
 														<PurchaseDateEdit
 															equipment=equipment.clone()
@@ -169,7 +214,15 @@ pub fn EquipmentDetail() -> impl IntoView {
 														/>
 													</dd>
 
-													<dt>Vendor</dt>
+													<dt>
+														"Vendor"
+														<FieldHistory
+															equipment=equipment.clone()
+															field="vendor"
+															user_signal
+															refetch_resources
+														/>
+													</dt>
 													<dd class=css::edit>
 														<VendorEdit
 															equipment=equipment.clone()
@@ -178,7 +231,15 @@ pub fn EquipmentDetail() -> impl IntoView {
 														/>
 													</dd>
 
-													<dt>Cost</dt>
+													<dt>
+														"Cost"
+														<FieldHistory
+															equipment=equipment.clone()
+															field="cost_in_cent"
+															user_signal
+															refetch_resources
+														/>
+													</dt>
 													<dd class=css::edit>
 														<CostEdit
 															equipment=equipment.clone()
@@ -187,7 +248,15 @@ pub fn EquipmentDetail() -> impl IntoView {
 														/>
 													</dd>
 
-													<dt>Warranty Expiration Date</dt>
+													<dt>
+														"Warranty Expiration Date"
+														<FieldHistory
+															equipment=equipment.clone()
+															field="warranty_expiration_date"
+															user_signal
+															refetch_resources
+														/>
+													</dt>
 													<dd class=css::edit>
 														<WarrantyExpirationDateEdit
 															equipment=equipment.clone()
@@ -196,7 +265,15 @@ pub fn EquipmentDetail() -> impl IntoView {
 														/>
 													</dd>
 
-													<dt>Location</dt>
+													<dt>
+														"Location"
+														<FieldHistory
+															equipment=equipment.clone()
+															field="location"
+															user_signal
+															refetch_resources
+														/>
+													</dt>
 													<dd class=css::edit>
 														<LocationEdit
 															equipment=equipment.clone()
@@ -205,7 +282,15 @@ pub fn EquipmentDetail() -> impl IntoView {
 														/>
 													</dd>
 
-													<dt>Notes</dt>
+													<dt>
+														"Notes"
+														<FieldHistory
+															equipment=equipment.clone()
+															field="notes"
+															user_signal
+															refetch_resources
+														/>
+													</dt>
 													<dd class=css::edit>
 														<EquipmentNoteEdit
 															equipment=equipment.clone()
@@ -213,6 +298,80 @@ pub fn EquipmentDetail() -> impl IntoView {
 															refetch_resources
 														/>
 													</dd>
+
+													<dt>Edit Fields</dt>
+													<dd class=css::edit>
+														<FieldsEdit
+															equipment=equipment.clone()
+															user_signal
+															refetch_resources
+														/>
+													</dd>
+
+													{equipment
+														.custom_fields
+														.clone()
+														.into_iter()
+														.map(|custom_field| {
+															view! {
+																<dt>
+																	{custom_field.field.label.clone()}
+																	<FieldHistory
+																		equipment=equipment.clone()
+																		field=custom_field.field.column()
+																		user_signal
+																		refetch_resources
+																	/>
+																</dt>
+																<dd class=css::edit>
+																	<CustomFieldEdit
+																		equipment=equipment.clone()
+																		custom_field
+																		user_signal
+																		refetch_resources
+																	/>
+																</dd>
+															}
+														})
+														.collect_view()}
+
+													{(!equipment.maintenance.is_empty())
+														.then(|| {
+															view! {
+																<dt>Maintenance</dt>
+																<dd class=css::edit>
+																	<MaintenanceEdit
+																		equipment=equipment.clone()
+																		user_signal
+																		refetch_resources
+																	/>
+																</dd>
+															}
+														})}
+
+													<dt>Watch</dt>
+													<dd>
+														<WatchToggle id=equipment.id />
+													</dd>
+
+													<dt>Experiments</dt>
+													<dd>
+														<EquipmentExperiments id=equipment.id />
+													</dd>
+
+													<dt>Duplicate</dt>
+													<dd>
+														<EquipmentDuplicate equipment=equipment.clone() user_signal />
+													</dd>
+
+													<dt>Trash</dt>
+													<dd>
+														<EquipmentDelete
+															equipment=equipment.clone()
+															user_signal
+															refetch_resources
+														/>
+													</dd>
 												</dl>
 											</div>
 										}
@@ -314,6 +473,7 @@ pub fn EquipmentDetail() -> impl IntoView {
 																log_query_page=log_query_page_clone
 																log_query_ipp=log_query_ipp_clone
 																tab_query=tab_query_clone
+																refetch_resources
 															/>
 														}
 													}
@@ -344,7 +504,11 @@ pub fn EquipmentDetail() -> impl IntoView {
 
 #[server(prefix = "/api")]
 pub async fn get_equipment_data_by_id(id: String) -> Result<EquipmentData, ServerFnError> {
-	use crate::{auth::get_user, equipment::EquipmentSQLData, permission::Permissions};
+	use crate::{
+		auth::get_user,
+		equipment::{EquipmentSQLData, attach_custom_fields, load_custom_fields, load_equipment_maintenance},
+		permission::{Dimension, Permissions, Subject},
+	};
 
 	use sqlx::PgPool;
 
@@ -364,9 +528,8 @@ pub async fn get_equipment_data_by_id(id: String) -> Result<EquipmentData, Serve
 				write: _,
 				create: _,
 			} = user.permission_equipment;
-			let person: i32 =
-				sqlx::query_scalar("SELECT person FROM equipment WHERE id = $1").bind(id).fetch_one(&pool).await?;
-			if !perm.has_permission("read", id, person) {
+			let subject = Subject::load(Dimension::Equipment, id, &pool).await?;
+			if !perm.has_permission_on("read", &subject) {
 				return Err(ServerFnError::Request(String::from("User not authenticated")));
 			}
 		},
@@ -380,10 +543,13 @@ pub async fn get_equipment_data_by_id(id: String) -> Result<EquipmentData, Serve
 			people.id AS person_id,
 			people.status AS person_status,
 			people.preferred_name AS person_preferred_name,
-			people.picture AS person_picture
+			people.picture AS person_picture,
+			equipment_types.label AS equipment_type_label,
+			equipment_types.icon AS equipment_type_icon
 		FROM
 			equipment
 			JOIN people ON equipment.person = people.id
+			JOIN equipment_types ON equipment.equipment_type = equipment_types.name
 		WHERE equipment.id = $1"#,
 	)
 	.bind(id)
@@ -391,5 +557,10 @@ pub async fn get_equipment_data_by_id(id: String) -> Result<EquipmentData, Serve
 	.await
 	.map_err::<ServerFnError, _>(|error| ServerFnError::ServerError(error.to_string()))?;
 
-	Ok(equipment_sql_data.into())
+	let mut equipment: EquipmentData = equipment_sql_data.into();
+	let custom_fields = load_custom_fields(&pool).await?;
+	attach_custom_fields(&pool, &custom_fields, std::slice::from_mut(&mut equipment)).await?;
+	equipment.maintenance = load_equipment_maintenance(&pool, id).await?;
+
+	Ok(equipment)
 }
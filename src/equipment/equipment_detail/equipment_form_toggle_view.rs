@@ -2,7 +2,7 @@ use crate::{
 	app::UserSignal,
 	components::button::{Button, ButtonVariant},
 	equipment::{EquipmentCell, EquipmentCellView},
-	permission::Permissions,
+	permission::{Permissions, Subject, SubjectSignal},
 };
 
 use leptos::*;
@@ -17,6 +17,7 @@ pub fn EquipmentFormToggle<T: EquipmentCellView + Clone + 'static>(
 	children: ChildrenFn,
 ) -> impl IntoView {
 	let toggle = create_rw_signal(false);
+	let subject_signal = use_context::<SubjectSignal>();
 	view! {
 		<Show when=move || toggle.get() fallback=move || view! { <EquipmentCell cell=item.clone() /> }>
 			{children()}
@@ -31,7 +32,9 @@ pub fn EquipmentFormToggle<T: EquipmentCellView + Clone + 'static>(
 					Some(user) => {
 						let Permissions::All { read: _, write: perm, create: _ } = user.permission_equipment;
 						view! {
-							<Show when=move || perm.has_permission("write", id, user_id)>
+							<Show when=move || {
+								perm.has_permission_on("write", &Subject::resolve(subject_signal, id, user_id))
+							}>
 								<Button
 									variant=ButtonVariant::Text
 									on_click=move |_| toggle.update(|toggle| *toggle = !*toggle)
@@ -0,0 +1,382 @@
+#[cfg(feature = "ssr")]
+use crate::equipment::FieldEdit;
+use crate::{
+	app::UserSignal,
+	components::{
+		avatar::Avatar,
+		button::{Button, ButtonVariant},
+	},
+	equipment::{EquipmentData, EquipmentLogData},
+	permission::{Permissions, Subject, SubjectSignal},
+};
+
+use leptos::*;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "ssr")]
+use chrono::prelude::*;
+#[cfg(feature = "ssr")]
+use sqlx::PgPool;
+
+stylance::import_style!(css, "field_history.module.css");
+
+/// How many past values the history of a field lists
+pub const FIELD_HISTORY_SHOWN: i64 = 50;
+
+/// One past value of a field and whether it can be brought back
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldHistoryEntry {
+	pub log: EquipmentLogData,
+	pub revertable: bool,
+}
+
+/// A popover with the past values of one field, `field` being the name the log stores edits of it under
+#[component]
+pub fn FieldHistory(
+	equipment: EquipmentData,
+	#[prop(into)] field: String,
+	user_signal: UserSignal,
+	refetch_resources: RwSignal<usize>,
+) -> impl IntoView {
+	let open = create_rw_signal(false);
+	let field = store_value(field);
+
+	view! {
+		<div class=css::field_history>
+			<Button
+				variant=ButtonVariant::Text
+				on_click=Callback::new(move |_: ev::MouseEvent| open.update(|open| *open = !*open))
+			>
+				History
+			</Button>
+			<Show when=move || open.get()>
+				<FieldHistoryList
+					equipment=equipment.clone()
+					field=field.get_value()
+					user_signal
+					refetch_resources
+				/>
+			</Show>
+		</div>
+	}
+}
+
+#[component]
+fn FieldHistoryList(
+	equipment: EquipmentData,
+	field: String,
+	user_signal: UserSignal,
+	refetch_resources: RwSignal<usize>,
+) -> impl IntoView {
+	let id = equipment.id;
+	let version = equipment.version;
+	let owner = equipment.person.id;
+
+	let revert_action = create_server_action::<RevertEquipmentField>();
+	let subject_signal = use_context::<SubjectSignal>();
+	let history_data = create_resource(|| (), move |_| get_field_history(id, field.clone()));
+
+	let can_revert = move || match user_signal.get() {
+		Some(user) => {
+			let Permissions::All {
+				read: _,
+				write: perm,
+				create: _,
+			} = user.permission_equipment;
+			perm.has_permission_on("write", &Subject::resolve(subject_signal, id, owner))
+		},
+		None => false,
+	};
+
+	view! {
+		<div class=css::popover>
+			<Transition fallback=move || view! { <p>Loading history...</p> }>
+				{move || {
+					history_data
+						.get()
+						.map(|data| match data {
+							Err(error) => view! { <pre class="error">Server Error: {error.to_string()}</pre> }.into_view(),
+							Ok(history) if history.is_empty() => {
+								view! { <p class=css::muted>"This field was never edited"</p> }.into_view()
+							}
+							Ok(history) => {
+								view! {
+									<ul class=css::history>
+										{history
+											.into_iter()
+											.enumerate()
+											.map(|(index, FieldHistoryEntry { log, revertable })| {
+												let log_id = log.id;
+												// The newest entry is the current value so there is nothing to go back to
+												let revertable = revertable && index != 0;
+												view! {
+													<li>
+														<Avatar data=log.person.clone() tiny=true />
+														<span class=css::value>
+															{log.new_value.clone().unwrap_or(String::from("–"))}
+														</span>
+														<span class=css::muted>
+															{format!(
+																"{} on {}",
+																log.person.preferred_name,
+																log.create_date.format("%d %b %Y %H:%M"),
+															)}
+															{log.reverts.map(|_| " (revert)")}
+														</span>
+														<Show when=move || revertable && can_revert()>
+															<Button
+																variant=ButtonVariant::Text
+																on_click=Callback::new(move |_: ev::MouseEvent| {
+																	revert_action
+																		.dispatch(RevertEquipmentField {
+																			id,
+																			version,
+																			log_id,
+																		});
+																})
+															>
+																Revert to this value
+															</Button>
+														</Show>
+													</li>
+												}
+											})
+											.collect_view()}
+									</ul>
+								}
+									.into_view()
+							}
+						})
+				}}
+			</Transition>
+			{move || match revert_action.value().get() {
+				Some(Ok(_)) => {
+					revert_action.value().set(None);
+					refetch_resources.update(|version| *version += 1);
+					view! {}.into_view()
+				}
+				Some(Err(error)) => {
+					view! {
+						<span class="error">
+							{error.to_string().replace("error reaching server to call server function: ", "")}
+						</span>
+					}
+						.into_view()
+				}
+				None => view! {}.into_view(),
+			}}
+		</div>
+	}
+}
+
+/// Reads a date back from the exact RFC 3339 value edits keep in the log
+#[cfg(feature = "ssr")]
+fn parse_logged_date(value: &str) -> Result<DateTime<Utc>, ServerFnError> {
+	DateTime::parse_from_rfc3339(value)
+		.map(|date| date.with_timezone(&Utc))
+		.map_err(|error| ServerFnError::Request(format!("Invalid date: {error}")))
+}
+
+/// The value to restore from a log entry, `None` when the entry doesn't keep enough to restore it.
+/// Entries from before exact values were logged only have what was shown which is exact for text fields alone.
+#[cfg(feature = "ssr")]
+fn logged_raw_value(field: &str, new_value: Option<String>, new_raw: Option<String>) -> Option<Option<String>> {
+	match (new_raw, new_value) {
+		(Some(raw), _) => Some(Some(raw)),
+		(None, None) => Some(None),
+		(None, Some(value)) if matches!(field, "name" | "type" | "manufacturer" | "vendor" | "location" | "notes") => {
+			Some(Some(value))
+		},
+		(None, Some(_)) => None,
+	}
+}
+
+/// Turns the exact value from the log back into an edit of the field it was logged for
+#[cfg(feature = "ssr")]
+async fn parse_logged_value(
+	pool: &PgPool,
+	id: i32,
+	field: &str,
+	value: Option<String>,
+) -> Result<FieldEdit, ServerFnError> {
+	use crate::equipment::{find_equipment_custom_field, find_equipment_type};
+
+	let text = || {
+		value
+			.clone()
+			.ok_or_else::<ServerFnError, _>(|| ServerFnError::Request(String::from("There is no value to revert to")))
+	};
+
+	Ok(match field {
+		"name" => FieldEdit::Name(text()?),
+		"type" => FieldEdit::EquipmentType(find_equipment_type(pool, &text()?).await?.name),
+		"manufacturer" => FieldEdit::Manufacturer(text()?),
+		"purchase_date" => FieldEdit::PurchaseDate(value.as_deref().map(parse_logged_date).transpose()?),
+		"vendor" => FieldEdit::Vendor(text()?),
+		"cost_in_cent" => match text()?.parse::<i32>() {
+			Ok(cost_in_cent) => FieldEdit::CostInCent(cost_in_cent),
+			Err(_) => return Err(ServerFnError::Request(String::from("Invalid cost"))),
+		},
+		"warranty_expiration_date" => {
+			FieldEdit::WarrantyExpirationDate(value.as_deref().map(parse_logged_date).transpose()?)
+		},
+		"location" => FieldEdit::Location(text()?),
+		"notes" => FieldEdit::Notes(text()?),
+		// Custom fields are logged under their column
+		field => match field.strip_prefix("custom_").and_then(|field| field.parse::<i32>().ok()) {
+			Some(field) => {
+				let field = find_equipment_custom_field(pool, id, field).await?;
+				let value = match value {
+					Some(value) => field.validate(&value).map_err::<ServerFnError, _>(ServerFnError::Request)?,
+					None => None,
+				};
+				FieldEdit::CustomField { field, value }
+			},
+			None => return Err(ServerFnError::Request(format!("\"{field}\" can't be reverted"))),
+		},
+	})
+}
+
+#[server(prefix = "/api")]
+pub async fn get_field_history(id: i32, field: String) -> Result<Vec<FieldHistoryEntry>, ServerFnError> {
+	use crate::{
+		auth::get_user,
+		equipment::EquipmentLogSQLData,
+		permission::{Dimension, Permissions, Subject},
+	};
+
+	use sqlx::{FromRow, Row};
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	match user {
+		Some(user) => {
+			let Permissions::All {
+				read: perm,
+				write: _,
+				create: _,
+			} = user.permission_equipment;
+
+			let subject = Subject::load(Dimension::Equipment, id, &pool).await?;
+			if !perm.has_permission_on("read", &subject) {
+				return Err(ServerFnError::Request(String::from("User not authenticated")));
+			}
+		},
+		None => return Err(ServerFnError::Request(String::from("User not authenticated"))),
+	};
+
+	let history = sqlx::query(
+		r#"SELECT
+			equipment_log.*,
+			people.id AS person_id,
+			people.status AS person_status,
+			people.preferred_name AS person_preferred_name,
+			people.picture AS person_picture
+		FROM
+			equipment_log
+			JOIN people ON equipment_log.person = people.id
+		WHERE
+			equipment_log.equipment = $1
+			AND equipment_log.log_type = 'edit'
+			AND equipment_log.field = $2
+		ORDER BY equipment_log.id DESC
+		LIMIT $3"#,
+	)
+	.bind(id)
+	.bind(&field)
+	.bind(FIELD_HISTORY_SHOWN)
+	.fetch_all(&pool)
+	.await?;
+
+	history
+		.iter()
+		.map(|row| {
+			let new_raw: Option<String> = row.try_get("new_raw")?;
+			let log: EquipmentLogData = EquipmentLogSQLData::from_row(row)?.into();
+			Ok(FieldHistoryEntry {
+				revertable: logged_raw_value(&field, log.new_value.clone(), new_raw).is_some(),
+				log,
+			})
+		})
+		.collect::<Result<Vec<_>, sqlx::Error>>()
+		.map_err(Into::into)
+}
+
+/// Sets a field back to the value a past edit gave it
+#[server(prefix = "/api")]
+pub async fn revert_equipment_field(id: i32, version: i32, log_id: i32) -> Result<(), ServerFnError> {
+	use crate::{
+		auth::get_user,
+		equipment::revert_field_edit,
+		permission::{Dimension, Permissions, Subject},
+	};
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	let user_id;
+	match user {
+		Some(user) => {
+			let Permissions::All {
+				read: _,
+				write: perm,
+				create: _,
+			} = user.permission_equipment;
+			user_id = user.id;
+
+			let subject = Subject::load(Dimension::Equipment, id, &pool).await?;
+			if !perm.has_permission_on("write", &subject) {
+				return Err(ServerFnError::Request(String::from("User not authenticated")));
+			}
+		},
+		None => return Err(ServerFnError::Request(String::from("User not authenticated"))),
+	};
+
+	let (field, new_value, new_raw, create_date): (String, Option<String>, Option<String>, DateTime<Utc>) =
+		sqlx::query_as(
+			r#"
+		SELECT field, new_value, new_raw, create_date FROM equipment_log
+		WHERE id = $1 AND equipment = $2 AND log_type = 'edit' AND field IS NOT NULL
+		"#,
+		)
+		.bind(log_id)
+		.bind(id)
+		.fetch_optional(&pool)
+		.await?
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::Request(String::from("Log entry not found")))?;
+
+	let value = logged_raw_value(&field, new_value, new_raw).ok_or_else::<ServerFnError, _>(|| {
+		ServerFnError::Request(String::from("This entry was logged before its exact value was kept and can't be reverted"))
+	})?;
+	let edit = parse_logged_value(&pool, id, &field, value).await?;
+	let note = format!("Reverted to the value from {}", create_date.format("%d %b %Y %H:%M"));
+
+	revert_field_edit(&pool, id, version, user_id, &note, edit, log_id).await
+}
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn parse_logged_date_test() {
+		// Midnight in UTC+02:00 is still the evening before in UTC and has to come back as that instant
+		let date = Utc.with_ymd_and_hms(2024, 2, 29, 22, 0, 0).unwrap();
+		assert_eq!(parse_logged_date(&date.to_rfc3339()).unwrap(), date);
+		assert_eq!(parse_logged_date("2024-03-01T00:00:00+02:00").unwrap(), date);
+		assert!(parse_logged_date("01 Mar 2024").is_err());
+		assert!(parse_logged_date("2024-03-01").is_err());
+	}
+
+	#[test]
+	fn logged_raw_value_test() {
+		let text = |value: &str| Some(String::from(value));
+		assert_eq!(logged_raw_value("cost_in_cent", text("12.50"), text("1250")), Some(text("1250")));
+		assert_eq!(logged_raw_value("vendor", text("Acme"), None), Some(text("Acme")));
+		assert_eq!(logged_raw_value("purchase_date", text("01 Mar 2024"), None), None);
+		assert_eq!(logged_raw_value("purchase_date", None, None), Some(None));
+	}
+}
@@ -0,0 +1,617 @@
+#[cfg(feature = "ssr")]
+use crate::equipment::CustomField;
+use crate::{
+	app::UserSignal,
+	components::{
+		button::{Button, ButtonVariant},
+		datepicker::DatePicker,
+		input::{Input, MoneyInput, TextArea},
+		timezone_offset::Timezone,
+	},
+	equipment::EquipmentData,
+	permission::{Permissions, Subject, SubjectSignal},
+};
+
+use chrono::prelude::*;
+use leptos::*;
+use leptos_router::*;
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "ssr")]
+use sqlx::{PgConnection, PgPool, Postgres};
+use web_sys::{FormData, SubmitEvent};
+
+stylance::import_style!(css, "equipment_details_edits.module.css");
+
+/// One field of an equipment item to change, named like the columns of the equipment list
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EquipmentFieldChange {
+	pub field: String,
+	pub value: String,
+}
+
+/// A validated change to one field of an equipment item
+#[cfg(feature = "ssr")]
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldEdit {
+	Name(String),
+	EquipmentType(String),
+	Manufacturer(String),
+	PurchaseDate(Option<DateTime<Utc>>),
+	Vendor(String),
+	CostInCent(i32),
+	WarrantyExpirationDate(Option<DateTime<Utc>>),
+	Location(String),
+	Notes(String),
+	CustomField { field: CustomField, value: Option<String> },
+}
+
+#[cfg(feature = "ssr")]
+impl FieldEdit {
+	/// The key that keeps two changes to the same field out of one edit
+	fn key(&self) -> String {
+		match self {
+			FieldEdit::Name(_) => String::from("name"),
+			FieldEdit::EquipmentType(_) => String::from("equipment_type"),
+			FieldEdit::Manufacturer(_) => String::from("manufacturer"),
+			FieldEdit::PurchaseDate(_) => String::from("purchase_date"),
+			FieldEdit::Vendor(_) => String::from("vendor"),
+			FieldEdit::CostInCent(_) => String::from("cost_in_cent"),
+			FieldEdit::WarrantyExpirationDate(_) => String::from("warranty_expiration_date"),
+			FieldEdit::Location(_) => String::from("location"),
+			FieldEdit::Notes(_) => String::from("notes"),
+			FieldEdit::CustomField { field, .. } => field.column(),
+		}
+	}
+}
+
+/// Turns a date picked in the browser into the start of that day in the browser's timezone
+#[cfg(feature = "ssr")]
+pub fn parse_local_date(date: &str, timezone_offset: i32) -> Result<DateTime<Utc>, ServerFnError> {
+	// JavaScript reports the offset in minutes west of UTC
+	let offset = FixedOffset::west_opt(timezone_offset * 60)
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::Request(String::from("Invalid timezone offset")))?;
+	let date = NaiveDate::parse_from_str(date, "%Y-%m-%d")
+		.map_err::<ServerFnError, _>(|error| ServerFnError::Request(format!("Invalid date: {}", error)))?;
+
+	date
+		.and_hms_opt(0, 0, 0)
+		.and_then(|date| offset.from_local_datetime(&date).single())
+		.map(|date| date.with_timezone(&Utc))
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::Request(String::from("Invalid date")))
+}
+
+/// Like `parse_local_date` but an empty date clears the field
+#[cfg(feature = "ssr")]
+pub fn parse_optional_local_date(date: &str, timezone_offset: i32) -> Result<Option<DateTime<Utc>>, ServerFnError> {
+	if date.trim().is_empty() {
+		Ok(None)
+	} else {
+		parse_local_date(date, timezone_offset).map(Some)
+	}
+}
+
+/// Finds a custom field of the equipment's own type, the only ones that can be set on it
+#[cfg(feature = "ssr")]
+pub async fn find_equipment_custom_field(pool: &PgPool, id: i32, field: i32) -> Result<CustomField, ServerFnError> {
+	sqlx::query_as::<_, CustomField>(
+		r#"
+		SELECT equipment_fields.*
+		FROM equipment_fields
+		JOIN equipment ON equipment.equipment_type = equipment_fields.equipment_type
+		WHERE equipment_fields.id = $1 AND equipment.id = $2
+		"#,
+	)
+	.bind(field)
+	.bind(id)
+	.fetch_optional(pool)
+	.await?
+	.ok_or_else::<ServerFnError, _>(|| ServerFnError::Request(String::from("Unknown custom field")))
+}
+
+/// Validates a change coming from a form or the API
+#[cfg(feature = "ssr")]
+pub async fn parse_field_change(
+	pool: &PgPool,
+	id: i32,
+	change: &EquipmentFieldChange,
+	timezone_offset: i32,
+) -> Result<FieldEdit, ServerFnError> {
+	use crate::equipment::{Cost, find_equipment_type};
+
+	let value = change.value.clone();
+	Ok(match change.field.as_str() {
+		"name" => FieldEdit::Name(value),
+		"equipment_type" => FieldEdit::EquipmentType(find_equipment_type(pool, &value).await?.name),
+		"manufacturer" => FieldEdit::Manufacturer(value),
+		"purchase_date" => FieldEdit::PurchaseDate(parse_optional_local_date(&value, timezone_offset)?),
+		"vendor" => FieldEdit::Vendor(value),
+		"cost_in_cent" => {
+			FieldEdit::CostInCent(Cost::parse_cents(&value).map_err::<ServerFnError, _>(ServerFnError::Request)?)
+		},
+		"warranty_expiration_date" => {
+			FieldEdit::WarrantyExpirationDate(parse_optional_local_date(&value, timezone_offset)?)
+		},
+		"location" => FieldEdit::Location(value),
+		"notes" => FieldEdit::Notes(value),
+		field => match field.strip_prefix("custom_").and_then(|field| field.parse::<i32>().ok()) {
+			Some(field) => {
+				let field = find_equipment_custom_field(pool, id, field).await?;
+				let value = field.validate(&value).map_err::<ServerFnError, _>(ServerFnError::Request)?;
+				FieldEdit::CustomField { field, value }
+			},
+			None => return Err(ServerFnError::Request(format!("Unknown field \"{field}\""))),
+		},
+	})
+}
+
+#[cfg(feature = "ssr")]
+async fn replace_column<T>(
+	connection: &mut PgConnection,
+	id: i32,
+	column: &str,
+	value: T,
+) -> Result<Option<T>, sqlx::Error>
+where
+	T: for<'q> sqlx::Encode<'q, Postgres> + for<'r> sqlx::Decode<'r, Postgres> + sqlx::Type<Postgres> + Send + Unpin,
+{
+	let old_value: Option<T> = sqlx::query_scalar(&format!("SELECT {column} FROM equipment WHERE id = $1"))
+		.bind(id)
+		.fetch_one(&mut *connection)
+		.await?;
+	sqlx::query(&format!("UPDATE equipment SET {column} = $1 WHERE id = $2"))
+		.bind(value)
+		.bind(id)
+		.execute(&mut *connection)
+		.await?;
+
+	Ok(old_value)
+}
+
+/// What the log records about one change, the raw values are what a revert restores
+#[cfg(feature = "ssr")]
+#[derive(Debug, Clone, PartialEq)]
+struct LoggedChange {
+	field: String,
+	old_value: Option<String>,
+	new_value: Option<String>,
+	old_raw: Option<String>,
+	new_raw: Option<String>,
+}
+
+#[cfg(feature = "ssr")]
+impl LoggedChange {
+	/// A change to a text field where what the log shows is the exact value
+	fn text(field: &str, old_value: Option<String>, new_value: Option<String>) -> Self {
+		LoggedChange {
+			field: field.to_string(),
+			old_raw: old_value.clone(),
+			new_raw: new_value.clone(),
+			old_value,
+			new_value,
+		}
+	}
+
+	/// A change to a date field, shown as a day and kept as RFC 3339 so the exact instant comes back on revert
+	fn date(field: &str, old_value: Option<DateTime<Utc>>, new_value: Option<DateTime<Utc>>) -> Self {
+		let format_date = |date: DateTime<Utc>| date.format("%d %b %Y").to_string();
+		LoggedChange {
+			field: field.to_string(),
+			old_value: old_value.map(format_date),
+			new_value: new_value.map(format_date),
+			old_raw: old_value.map(|date| date.to_rfc3339()),
+			new_raw: new_value.map(|date| date.to_rfc3339()),
+		}
+	}
+}
+
+/// Writes one change and returns what the log records about it
+#[cfg(feature = "ssr")]
+async fn apply_field_edit(
+	connection: &mut PgConnection,
+	id: i32,
+	edit: FieldEdit,
+) -> Result<LoggedChange, sqlx::Error> {
+	Ok(match edit {
+		FieldEdit::Name(value) => {
+			LoggedChange::text("name", replace_column(connection, id, "name", value.clone()).await?, Some(value))
+		},
+		FieldEdit::EquipmentType(value) => {
+			LoggedChange::text("type", replace_column(connection, id, "equipment_type", value.clone()).await?, Some(value))
+		},
+		FieldEdit::Manufacturer(value) => LoggedChange::text(
+			"manufacturer",
+			replace_column(connection, id, "manufacturer", value.clone()).await?,
+			Some(value),
+		),
+		FieldEdit::PurchaseDate(value) => LoggedChange::date(
+			"purchase_date",
+			replace_column(connection, id, "purchase_date", value).await?.flatten(),
+			value,
+		),
+		FieldEdit::Vendor(value) => {
+			LoggedChange::text("vendor", replace_column(connection, id, "vendor", value.clone()).await?, Some(value))
+		},
+		FieldEdit::CostInCent(value) => {
+			let old_value = replace_column(connection, id, "cost_in_cent", value).await?;
+			LoggedChange {
+				field: String::from("cost_in_cent"),
+				old_value: old_value.map(|old_value| format!("{:.2}", old_value as f64 / 100.0)),
+				new_value: Some(format!("{:.2}", value as f64 / 100.0)),
+				old_raw: old_value.map(|old_value| old_value.to_string()),
+				new_raw: Some(value.to_string()),
+			}
+		},
+		FieldEdit::WarrantyExpirationDate(value) => LoggedChange::date(
+			"warranty_expiration_date",
+			replace_column(connection, id, "warranty_expiration_date", value).await?.flatten(),
+			value,
+		),
+		FieldEdit::Location(value) => {
+			LoggedChange::text("location", replace_column(connection, id, "location", value.clone()).await?, Some(value))
+		},
+		FieldEdit::Notes(value) => {
+			LoggedChange::text("notes", replace_column(connection, id, "notes", value.clone()).await?, Some(value))
+		},
+		FieldEdit::CustomField { field, value } => {
+			let old_value: Option<String> =
+				sqlx::query_scalar("SELECT value FROM equipment_field_values WHERE equipment = $1 AND field = $2")
+					.bind(id)
+					.bind(field.id)
+					.fetch_optional(&mut *connection)
+					.await?;
+
+			match &value {
+				Some(value) => {
+					sqlx::query(
+						r#"
+						INSERT INTO equipment_field_values (equipment, field, value) VALUES ($1, $2, $3)
+						ON CONFLICT (equipment, field) DO UPDATE SET value = EXCLUDED.value
+						"#,
+					)
+					.bind(id)
+					.bind(field.id)
+					.bind(value)
+					.execute(&mut *connection)
+					.await?;
+				},
+				None => {
+					sqlx::query("DELETE FROM equipment_field_values WHERE equipment = $1 AND field = $2")
+						.bind(id)
+						.bind(field.id)
+						.execute(&mut *connection)
+						.await?;
+				},
+			}
+
+			// Labels aren't unique and can shadow built-in fields so custom fields are logged under their column
+			LoggedChange::text(&field.column(), old_value, value)
+		},
+	})
+}
+
+/// Applies all edits in one transaction with a log entry each, failing when someone else edited the
+/// equipment since `version` was read
+#[cfg(feature = "ssr")]
+pub async fn apply_field_edits(
+	pool: &PgPool,
+	id: i32,
+	version: i32,
+	person: i32,
+	note: &str,
+	edits: Vec<FieldEdit>,
+) -> Result<(), ServerFnError> {
+	write_field_edits(pool, id, version, person, note, edits, None).await
+}
+
+/// Restores a value from the log like a normal edit whose log entry points back at the restored one
+#[cfg(feature = "ssr")]
+pub async fn revert_field_edit(
+	pool: &PgPool,
+	id: i32,
+	version: i32,
+	person: i32,
+	note: &str,
+	edit: FieldEdit,
+	reverts: i32,
+) -> Result<(), ServerFnError> {
+	write_field_edits(pool, id, version, person, note, vec![edit], Some(reverts)).await
+}
+
+#[cfg(feature = "ssr")]
+async fn write_field_edits(
+	pool: &PgPool,
+	id: i32,
+	version: i32,
+	person: i32,
+	note: &str,
+	edits: Vec<FieldEdit>,
+	reverts: Option<i32>,
+) -> Result<(), ServerFnError> {
+	use crate::{
+		equipment::{EquipmentEventKind, publish_equipment_event},
+		webhooks::queue_log_webhooks,
+	};
+
+	if edits.is_empty() {
+		return Err(ServerFnError::Request(String::from("Nothing to change")));
+	}
+	let mut keys: Vec<String> = edits.iter().map(FieldEdit::key).collect();
+	keys.sort();
+	if keys.windows(2).any(|pair| pair[0] == pair[1]) {
+		return Err(ServerFnError::Request(String::from("Each field can only be changed once per edit")));
+	}
+
+	let mut transaction = pool.begin().await?;
+
+	let current_version: i32 =
+		sqlx::query_scalar("SELECT version FROM equipment WHERE id = $1 AND deleted_at IS NULL FOR UPDATE")
+			.bind(id)
+			.fetch_optional(&mut *transaction)
+			.await?
+			.ok_or_else::<ServerFnError, _>(|| ServerFnError::Request(String::from("Equipment not found or in the trash")))?;
+	if current_version != version {
+		return Err(ServerFnError::Request(String::from(
+			"Someone else changed this equipment in the meantime, reload it and try again",
+		)));
+	}
+
+	for edit in edits {
+		let change = apply_field_edit(&mut transaction, id, edit).await?;
+
+		let log_id: i32 = sqlx::query_scalar(
+			r#"
+			INSERT INTO equipment_log
+			(log_type, equipment, person, notes, field, old_value, new_value, old_raw, new_raw, reverts)
+			VALUES
+			($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+			RETURNING id"#,
+		)
+		.bind("edit")
+		.bind(id)
+		.bind(person)
+		.bind(note)
+		.bind(change.field)
+		.bind(change.old_value)
+		.bind(change.new_value)
+		.bind(change.old_raw)
+		.bind(change.new_raw)
+		.bind(reverts)
+		.fetch_one(&mut *transaction)
+		.await?;
+
+		queue_log_webhooks(&mut transaction, log_id).await?;
+	}
+
+	sqlx::query("UPDATE equipment SET version = version + 1 WHERE id = $1").bind(id).execute(&mut *transaction).await?;
+	publish_equipment_event(&mut transaction, id, EquipmentEventKind::Log).await?;
+
+	transaction.commit().await?;
+
+	Ok(())
+}
+
+/// One form for the common fields that saves everything that was changed in a single edit
+#[component]
+pub fn FieldsEdit(
+	equipment: EquipmentData,
+	user_signal: UserSignal,
+	refetch_resources: RwSignal<usize>,
+) -> impl IntoView {
+	let fields_action = create_server_action::<EditEquipmentFields>();
+	let toggle = create_rw_signal(false);
+	let subject_signal = use_context::<SubjectSignal>();
+	let form_ref = create_node_ref::<html::Form>();
+
+	let (id, user_id, version) = (equipment.id, equipment.person.id, equipment.version);
+	let format_date = |date: Option<DateTime<Utc>>| date.map(|date| date.date_naive().to_string()).unwrap_or_default();
+	// The values the form starts with, only fields that differ from these are sent
+	let original: Vec<(&'static str, String)> = vec![
+		("name", equipment.name.clone()),
+		("manufacturer", equipment.manufacturer.clone().unwrap_or_default()),
+		("vendor", equipment.vendor.clone().unwrap_or_default()),
+		("location", equipment.location.clone().unwrap_or_default()),
+		("cost_in_cent", equipment.cost_in_cent.clone().map(|cost| cost.to_string()).unwrap_or_default()),
+		("purchase_date", format_date(equipment.purchase_date)),
+		("warranty_expiration_date", format_date(equipment.warranty_expiration_date)),
+	];
+
+	let on_submit = move |event: SubmitEvent| {
+		event.prevent_default();
+		let form = form_ref.get().unwrap();
+		let form_data = match FormData::new_with_form(&form) {
+			Ok(fd) => fd,
+			Err(error) => {
+				logging::log!("Failed to create FormData");
+				logging::log!("{error:?}");
+				return;
+			},
+		};
+		let value = |name: &str| form_data.get(name).as_string().unwrap_or_default();
+
+		let changes = original
+			.iter()
+			.filter(|(field, start)| value(field) != *start)
+			.map(|(field, _)| EquipmentFieldChange {
+				field: field.to_string(),
+				value: value(field),
+			})
+			.collect();
+		fields_action.dispatch(EditEquipmentFields {
+			id,
+			version,
+			changes,
+			timezone_offset: value("timezone_offset").parse().unwrap_or_default(),
+			note: value("note"),
+		});
+	};
+
+	view! {
+		<Show when=move || toggle.get()>
+			<form ref=form_ref class=css::edit_form on:submit=on_submit.clone()>
+				<Timezone />
+				<label>
+					"Name" <Input name="name" value=create_rw_signal(equipment.name.clone()) />
+				</label>
+				<label>
+					"Manufacturer"
+					<Input
+						name="manufacturer"
+						value=create_rw_signal(equipment.manufacturer.clone().unwrap_or_default())
+					/>
+				</label>
+				<label>
+					"Vendor" <Input name="vendor" value=create_rw_signal(equipment.vendor.clone().unwrap_or_default()) />
+				</label>
+				<label>
+					"Location"
+					<Input name="location" value=create_rw_signal(equipment.location.clone().unwrap_or_default()) />
+				</label>
+				<label>
+					"Cost"
+					<MoneyInput
+						name="cost_in_cent"
+						value=create_rw_signal(
+							equipment.cost_in_cent.clone().map(|cost| cost.to_string()).unwrap_or_default(),
+						)
+					/>
+				</label>
+				<label>
+					"Purchase Date"
+					<DatePicker
+						attr:name="purchase_date"
+						value=create_rw_signal(equipment.purchase_date.map(|date| date.date_naive()))
+					/>
+				</label>
+				<label>
+					"Warranty Expiration Date"
+					<DatePicker
+						attr:name="warranty_expiration_date"
+						value=create_rw_signal(equipment.warranty_expiration_date.map(|date| date.date_naive()))
+					/>
+				</label>
+				<TextArea name="note" placeholder="Add a note why you made these changes" />
+				<div class=css::btns>
+					{move || {
+						if let Some(responds) = fields_action.value().get() {
+							match responds {
+								Ok(_) => {
+									fields_action.value().set(None);
+									toggle.set(false);
+									refetch_resources.update(|version| *version += 1);
+									view! {}.into_view()
+								}
+								Err(error) => {
+									view! {
+										<span>
+											{error.to_string().replace("error reaching server to call server function: ", "")}
+										</span>
+									}
+										.into_view()
+								}
+							}
+						} else {
+							view! {}.into_view()
+						}
+					}} <Button kind="submit">Save all</Button>
+				</div>
+			</form>
+		</Show>
+
+		<Suspense fallback=move || {
+			view! { <A href="/login">"Login"</A> }
+		}>
+			{move || {
+				match user_signal.get() {
+					None => view! { <span /> }.into_view(),
+					Some(user) => {
+						let Permissions::All { read: _, write: perm, create: _ } = user.permission_equipment;
+						view! {
+							<Show when=move || {
+								perm.has_permission_on("write", &Subject::resolve(subject_signal, id, user_id))
+							}>
+								<Button
+									variant=ButtonVariant::Text
+									on_click=move |_| toggle.update(|toggle| *toggle = !*toggle)
+								>
+									{move || if toggle.get() { "Cancel" } else { "Edit several fields" }}
+								</Button>
+							</Show>
+						}
+							.into_view()
+					}
+				}
+			}}
+		</Suspense>
+	}
+}
+
+/// Changes several fields of an equipment item at once, all or nothing
+#[server(prefix = "/api")]
+pub async fn edit_equipment_fields(
+	id: i32,
+	version: i32,
+	changes: Vec<EquipmentFieldChange>,
+	timezone_offset: i32,
+	note: String,
+) -> Result<(), ServerFnError> {
+	use crate::{
+		auth::get_user,
+		permission::{Dimension, Permissions, Subject},
+	};
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	let user_id;
+	match user {
+		Some(user) => {
+			let Permissions::All {
+				read: _,
+				write: perm,
+				create: _,
+			} = user.permission_equipment;
+			user_id = user.id;
+
+			let subject = Subject::load(Dimension::Equipment, id, &pool).await?;
+			if !perm.has_permission_on("write", &subject) {
+				return Err(ServerFnError::Request(String::from("User not authenticated")));
+			}
+		},
+		None => return Err(ServerFnError::Request(String::from("User not authenticated"))),
+	};
+
+	let mut edits = Vec::with_capacity(changes.len());
+	for change in &changes {
+		edits.push(parse_field_change(&pool, id, change, timezone_offset).await?);
+	}
+
+	apply_field_edits(&pool, id, version, user_id, &note, edits).await
+}
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn parse_local_date_test() {
+		assert_eq!(parse_local_date("2024-03-01", 0).unwrap(), Utc.with_ymd_and_hms(2024, 3, 1, 0, 0, 0).unwrap());
+		// -90 is UTC+01:30 where the day starts before midnight UTC
+		assert_eq!(parse_local_date("2024-03-01", -90).unwrap(), Utc.with_ymd_and_hms(2024, 2, 29, 22, 30, 0).unwrap());
+		assert_eq!(parse_local_date("2024-03-01", -30).unwrap(), Utc.with_ymd_and_hms(2024, 2, 29, 23, 30, 0).unwrap());
+		assert_eq!(parse_local_date("2024-03-01", 300).unwrap(), Utc.with_ymd_and_hms(2024, 3, 1, 5, 0, 0).unwrap());
+		assert!(parse_local_date("01.03.2024", 0).is_err());
+		assert!(parse_local_date("2024-03-01", 24 * 60).is_err());
+	}
+
+	#[test]
+	fn parse_optional_local_date_test() {
+		assert_eq!(parse_optional_local_date("", 0).unwrap(), None);
+		assert_eq!(parse_optional_local_date(" ", 0).unwrap(), None);
+		assert_eq!(
+			parse_optional_local_date("2024-03-01", 0).unwrap(),
+			Some(Utc.with_ymd_and_hms(2024, 3, 1, 0, 0, 0).unwrap())
+		);
+		assert!(parse_optional_local_date("01.03.2024", 0).is_err());
+	}
+}
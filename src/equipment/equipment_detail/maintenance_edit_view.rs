@@ -0,0 +1,292 @@
+use crate::{
+	app::UserSignal,
+	components::{button::Button, file_input::FileInput, input::TextArea, select::Select},
+	equipment::{EquipmentData, EquipmentFormToggle},
+};
+
+use leptos::*;
+use server_fn::codec::{MultipartData, MultipartFormData};
+use web_sys::{FormData, SubmitEvent};
+
+stylance::import_style!(css, "equipment_details_edits.module.css");
+
+#[component]
+pub fn MaintenanceEdit(
+	equipment: EquipmentData,
+	user_signal: UserSignal,
+	refetch_resources: RwSignal<usize>,
+) -> impl IntoView {
+	let maintenance_action = create_action(|data: &FormData| complete_maintenance(data.clone().into()));
+	let plans = store_value(equipment.maintenance.clone());
+
+	view! {
+		<EquipmentFormToggle
+			user_id=equipment.person.clone().id
+			id=equipment.id
+			user_signal=user_signal
+			item=equipment.maintenance.clone()
+		>
+			{
+				let form_ref = create_node_ref::<html::Form>();
+				let media1 = create_rw_signal(String::new());
+				let media2 = create_rw_signal(String::new());
+				let media3 = create_rw_signal(String::new());
+				let media4 = create_rw_signal(String::new());
+				let media5 = create_rw_signal(String::new());
+				let media6 = create_rw_signal(String::new());
+				let media7 = create_rw_signal(String::new());
+				let media8 = create_rw_signal(String::new());
+				let media9 = create_rw_signal(String::new());
+				let media10 = create_rw_signal(String::new());
+				let loading = create_rw_signal(false);
+				view! {
+					<form
+						ref=form_ref
+						class=css::edit_form
+						method="post"
+						action="#"
+						enctype="multipart/form-data"
+						on:submit=move |event: SubmitEvent| {
+							event.prevent_default();
+							loading.set(true);
+							let form = form_ref.get().unwrap();
+							let form_data = match FormData::new_with_form(&form) {
+								Ok(fd) => fd,
+								Err(error) => {
+									logging::error!("Failed to create FormData");
+									logging::error!("{error:?}");
+									return;
+								}
+							};
+							maintenance_action.dispatch(form_data);
+						}
+					>
+						<input type="hidden" name="id" value=equipment.id />
+						<Select name="plan" required=true>
+							{plans
+								.get_value()
+								.into_iter()
+								.map(|item| {
+									view! { <option value=item.plan.id>{item.plan.name.clone()}</option> }
+								})
+								.collect_view()}
+						</Select>
+						<TextArea name="note" placeholder="Add a note about the work you did" />
+						<div class=css::btns>
+							<FileInput name="media1" value=media1 />
+							<Show when=move || !media1.get().is_empty()>
+								<FileInput name="media2" value=media2 />
+							</Show>
+							<Show when=move || !media2.get().is_empty()>
+								<FileInput name="media3" value=media3 />
+							</Show>
+							<Show when=move || !media3.get().is_empty()>
+								<FileInput name="media4" value=media4 />
+							</Show>
+							<Show when=move || !media4.get().is_empty()>
+								<FileInput name="media5" value=media5 />
+							</Show>
+							<Show when=move || !media5.get().is_empty()>
+								<FileInput name="media6" value=media6 />
+							</Show>
+							<Show when=move || !media6.get().is_empty()>
+								<FileInput name="media7" value=media7 />
+							</Show>
+							<Show when=move || !media7.get().is_empty()>
+								<FileInput name="media8" value=media8 />
+							</Show>
+							<Show when=move || !media8.get().is_empty()>
+								<FileInput name="media9" value=media9 />
+							</Show>
+							<Show when=move || !media9.get().is_empty()>
+								<FileInput name="media10" value=media10 />
+							</Show>
+						</div>
+						<div class=css::btns>
+							<span>
+								{move || {
+									if let Some(responds) = maintenance_action.value().get() {
+										loading.set(false);
+										match responds {
+											Ok(_) => {
+												maintenance_action.value().set(None);
+												refetch_resources.update(|version| *version += 1);
+												view! {}.into_view()
+											}
+											Err(error) => {
+												view! {
+													<span>
+														{error
+															.to_string()
+															.replace(
+																"error reaching server to call server function: ",
+																"",
+															)}
+													</span>
+												}
+													.into_view()
+											}
+										}
+									} else {
+										view! {}.into_view()
+									}
+								}}
+							</span>
+							<Button kind="submit" loading>
+								Mark as done
+							</Button>
+						</div>
+					</form>
+				}
+			}
+		</EquipmentFormToggle>
+	}
+}
+
+/// The maintenance plans of one piece of equipment with the date each is next due
+#[cfg(feature = "ssr")]
+pub async fn load_equipment_maintenance(
+	pool: &sqlx::PgPool,
+	id: i32,
+) -> Result<Vec<crate::equipment::MaintenanceDue>, ServerFnError> {
+	use crate::equipment::{MaintenanceDue, maintenance_due_query};
+
+	Ok(
+		sqlx::query_as::<_, MaintenanceDue>(&maintenance_due_query("AND equipment.id = $1"))
+			.bind(id)
+			.fetch_all(pool)
+			.await?,
+	)
+}
+
+/// Records that the work of a maintenance plan was done which starts its next interval
+#[server(input = MultipartFormData, prefix = "/api")]
+pub async fn complete_maintenance(data: MultipartData) -> Result<(), ServerFnError> {
+	use crate::{
+		auth::get_user,
+		components::file_upload::{file_upload, remove_temp_files},
+		equipment::{EquipmentEventKind, EquipmentLogType, publish_equipment_event},
+		permission::{Dimension, Permissions, Subject},
+		utils::{get_equipment_base_folder, get_equipment_log_folder, move_file},
+	};
+
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	let result = file_upload(data, |id| format!("{}temp/", get_equipment_base_folder(id))).await?;
+
+	let user_id;
+	match user {
+		Some(user) => {
+			let Permissions::All {
+				read: _,
+				write: perm,
+				create: _,
+			} = user.permission_equipment;
+			user_id = user.id;
+
+			let subject = Subject::load(Dimension::Equipment, result.id, &pool).await?;
+			if !perm.has_permission_on("write", &subject) {
+				remove_temp_files(result).await?;
+				return Err(ServerFnError::Request(String::from("User not authenticated")));
+			}
+		},
+		None => {
+			remove_temp_files(result).await?;
+			return Err(ServerFnError::Request(String::from("User not authenticated")));
+		},
+	};
+
+	let mut plan = None;
+	let mut note = None;
+
+	for (name, value) in &result.additional_fields {
+		match name.as_str() {
+			"plan" => plan = Some(value),
+			"note" => note = Some(value),
+			_ => {},
+		}
+	}
+
+	let Some(note) = note.cloned() else {
+		remove_temp_files(result).await?;
+		return Err(ServerFnError::Request(String::from("Missing note field")));
+	};
+
+	// Only plans that apply to this equipment can be completed on it
+	let plan = match plan.and_then(|plan| plan.parse::<i32>().ok()) {
+		Some(plan) => load_equipment_maintenance(&pool, result.id).await?.into_iter().find(|item| item.plan.id == plan),
+		None => None,
+	};
+	let Some(due) = plan else {
+		remove_temp_files(result).await?;
+		return Err(ServerFnError::Request(String::from("Unknown maintenance plan")));
+	};
+
+	let log_id: i32 = sqlx::query_scalar(
+		r#"
+		INSERT INTO equipment_log
+		(log_type, equipment, person, notes, field, maintenance)
+		VALUES
+		($1, $2, $3, $4, $5, $6)
+		RETURNING id"#,
+	)
+	.bind(EquipmentLogType::Maintenance.to_string())
+	.bind(result.id)
+	.bind(user_id)
+	.bind(note)
+	.bind(due.plan.name)
+	.bind(due.plan.id)
+	.fetch_one(&pool)
+	.await
+	.map_err::<ServerFnError, _>(|error| ServerFnError::ServerError(error.to_string()))?;
+
+	let log_folder = get_equipment_log_folder(log_id);
+
+	let media1 = move_file(result.media1, &log_folder).await?;
+	let media2 = move_file(result.media2, &log_folder).await?;
+	let media3 = move_file(result.media3, &log_folder).await?;
+	let media4 = move_file(result.media4, &log_folder).await?;
+	let media5 = move_file(result.media5, &log_folder).await?;
+	let media6 = move_file(result.media6, &log_folder).await?;
+	let media7 = move_file(result.media7, &log_folder).await?;
+	let media8 = move_file(result.media8, &log_folder).await?;
+	let media9 = move_file(result.media9, &log_folder).await?;
+	let media10 = move_file(result.media10, &log_folder).await?;
+
+	sqlx::query(
+		r#"UPDATE equipment_log set
+			media1 = $1,
+			media2 = $2,
+			media3 = $3,
+			media4 = $4,
+			media5 = $5,
+			media6 = $6,
+			media7 = $7,
+			media8 = $8,
+			media9 = $9,
+			media10 = $10
+		WHERE id = $11"#,
+	)
+	.bind(media1)
+	.bind(media2)
+	.bind(media3)
+	.bind(media4)
+	.bind(media5)
+	.bind(media6)
+	.bind(media7)
+	.bind(media8)
+	.bind(media9)
+	.bind(media10)
+	.bind(log_id)
+	.execute(&pool)
+	.await
+	.map_err::<ServerFnError, _>(|error| ServerFnError::ServerError(error.to_string()))?;
+
+	publish_equipment_event(&mut *pool.acquire().await?, result.id, EquipmentEventKind::Log).await?;
+
+	Ok(())
+}
@@ -32,6 +32,7 @@ pub fn ManufacturerEdit(
 				view! {
 					<ActionForm action=manufacturer_action class=css::edit_form>
 						<input type="hidden" name="id" value=equipment.id />
+						<input type="hidden" name="version" value=equipment.version />
 						<Input name="manufacturer" value=create_rw_signal(manufacturer_clone.unwrap_or_default()) />
 						<TextArea name="note" placeholder="Add a note why you made this change" />
 						<div class=css::btns>
@@ -67,8 +68,17 @@ pub fn ManufacturerEdit(
 }
 
 #[server(prefix = "/api")]
-pub async fn edit_manufacturer(id: String, manufacturer: String, note: String) -> Result<(), ServerFnError> {
-	use crate::{auth::get_user, permission::Permissions};
+pub async fn edit_manufacturer(
+	id: String,
+	version: i32,
+	manufacturer: String,
+	note: String,
+) -> Result<(), ServerFnError> {
+	use crate::{
+		auth::get_user,
+		equipment::{FieldEdit, apply_field_edits},
+		permission::{Dimension, Permissions, Subject},
+	};
 
 	use sqlx::PgPool;
 
@@ -91,39 +101,13 @@ pub async fn edit_manufacturer(id: String, manufacturer: String, note: String) -
 			} = user.permission_equipment;
 			user_id = user.id;
 
-			let person: i32 =
-				sqlx::query_scalar("SELECT person FROM equipment WHERE id = $1").bind(id).fetch_one(&pool).await?;
-			if !perm.has_permission("write", id, person) {
+			let subject = Subject::load(Dimension::Equipment, id, &pool).await?;
+			if !perm.has_permission_on("write", &subject) {
 				return Err(ServerFnError::Request(String::from("User not authenticated")));
 			}
 		},
 		None => return Err(ServerFnError::Request(String::from("User not authenticated"))),
 	};
 
-	let old_value: String =
-		sqlx::query_scalar("SELECT manufacturer FROM equipment WHERE id = $1").bind(id).fetch_one(&pool).await?;
-
-	sqlx::query!(
-		r#"INSERT INTO equipment_log
-		(log_type, equipment, person, notes, field, old_value, new_value)
-		VALUES
-		($1, $2, $3, $4, $5, $6, $7)"#,
-		"edit",
-		id,
-		user_id,
-		note,
-		"manufacturer",
-		old_value,
-		manufacturer,
-	)
-	.execute(&pool)
-	.await
-	.map_err::<ServerFnError, _>(|error| ServerFnError::ServerError(error.to_string()))?;
-
-	sqlx::query!("UPDATE equipment SET manufacturer = $1 WHERE id = $2", manufacturer, id)
-		.execute(&pool)
-		.await
-		.map(|_| ())?;
-
-	Ok(())
+	apply_field_edits(&pool, id, version, user_id, &note, vec![FieldEdit::Manufacturer(manufacturer)]).await
 }
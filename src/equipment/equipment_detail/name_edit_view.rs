@@ -32,6 +32,7 @@ pub fn NameEdit(
 				view! {
 					<ActionForm action=name_action class=css::edit_form>
 						<input type="hidden" name="id" value=equipment.id />
+						<input type="hidden" name="version" value=equipment.version />
 						<Input name="name" value=create_rw_signal(name_clone) />
 						<TextArea name="note" placeholder="Add a note why you made this change" />
 						<div class=css::btns>
@@ -67,8 +68,12 @@ pub fn NameEdit(
 }
 
 #[server(prefix = "/api")]
-pub async fn edit_name(id: String, name: String, note: String) -> Result<(), ServerFnError> {
-	use crate::{auth::get_user, permission::Permissions};
+pub async fn edit_name(id: String, version: i32, name: String, note: String) -> Result<(), ServerFnError> {
+	use crate::{
+		auth::get_user,
+		equipment::{FieldEdit, apply_field_edits},
+		permission::{Dimension, Permissions, Subject},
+	};
 
 	use sqlx::PgPool;
 
@@ -91,37 +96,13 @@ pub async fn edit_name(id: String, name: String, note: String) -> Result<(), Ser
 			} = user.permission_equipment;
 			user_id = user.id;
 
-			let person: i32 =
-				sqlx::query_scalar("SELECT person FROM equipment WHERE id = $1").bind(id).fetch_one(&pool).await?;
-			if !perm.has_permission("write", id, person) {
+			let subject = Subject::load(Dimension::Equipment, id, &pool).await?;
+			if !perm.has_permission_on("write", &subject) {
 				return Err(ServerFnError::Request(String::from("User not authenticated")));
 			}
 		},
 		None => return Err(ServerFnError::Request(String::from("User not authenticated"))),
 	};
 
-	let old_value: String =
-		sqlx::query_scalar("SELECT name FROM equipment WHERE id = $1").bind(id).fetch_one(&pool).await?;
-
-	sqlx::query(
-		r#"
-		INSERT INTO equipment_log
-		(log_type, equipment, person, notes, field, old_value, new_value)
-		VALUES
-		($1, $2, $3, $4, $5, $6, $7)"#,
-	)
-	.bind("edit")
-	.bind(id)
-	.bind(user_id)
-	.bind(note)
-	.bind("name")
-	.bind(old_value)
-	.bind(name.clone())
-	.execute(&pool)
-	.await
-	.map_err::<ServerFnError, _>(|error| ServerFnError::ServerError(error.to_string()))?;
-
-	sqlx::query("UPDATE equipment SET name = $1 WHERE id = $2").bind(name).bind(id).execute(&pool).await.map(|_| ())?;
-
-	Ok(())
+	apply_field_edits(&pool, id, version, user_id, &note, vec![FieldEdit::Name(name)]).await
 }
@@ -30,6 +30,7 @@ pub fn EquipmentNoteEdit(
 				view! {
 					<ActionForm action=notes_action class=css::edit_form>
 						<input type="hidden" name="id" value=equipment.id />
+						<input type="hidden" name="version" value=equipment.version />
 						<TextArea
 							class=notes_css::notes.to_string()
 							name="notes"
@@ -69,8 +70,12 @@ pub fn EquipmentNoteEdit(
 }
 
 #[server(prefix = "/api")]
-pub async fn edit_notes(id: String, notes: String, note: String) -> Result<(), ServerFnError> {
-	use crate::{auth::get_user, permission::Permissions};
+pub async fn edit_notes(id: String, version: i32, notes: String, note: String) -> Result<(), ServerFnError> {
+	use crate::{
+		auth::get_user,
+		equipment::{FieldEdit, apply_field_edits},
+		permission::{Dimension, Permissions, Subject},
+	};
 
 	use sqlx::PgPool;
 
@@ -93,36 +98,13 @@ pub async fn edit_notes(id: String, notes: String, note: String) -> Result<(), S
 			} = user.permission_equipment;
 			user_id = user.id;
 
-			let person: i32 =
-				sqlx::query_scalar("SELECT person FROM equipment WHERE id = $1").bind(id).fetch_one(&pool).await?;
-			if !perm.has_permission("write", id, person) {
+			let subject = Subject::load(Dimension::Equipment, id, &pool).await?;
+			if !perm.has_permission_on("write", &subject) {
 				return Err(ServerFnError::Request(String::from("User not authenticated")));
 			}
 		},
 		None => return Err(ServerFnError::Request(String::from("User not authenticated"))),
 	};
 
-	let old_value: String =
-		sqlx::query_scalar("SELECT notes FROM equipment WHERE id = $1").bind(id).fetch_one(&pool).await?;
-
-	sqlx::query!(
-		r#"INSERT INTO equipment_log
-		(log_type, equipment, person, notes, field, old_value, new_value)
-		VALUES
-		($1, $2, $3, $4, $5, $6, $7)"#,
-		"edit",
-		id,
-		user_id,
-		note,
-		"notes",
-		old_value,
-		notes,
-	)
-	.execute(&pool)
-	.await
-	.map_err::<ServerFnError, _>(|error| ServerFnError::ServerError(error.to_string()))?;
-
-	sqlx::query!("UPDATE equipment SET notes = $1 WHERE id = $2", notes, id).execute(&pool).await.map(|_| ())?;
-
-	Ok(())
+	apply_field_edits(&pool, id, version, user_id, &note, vec![FieldEdit::Notes(notes)]).await
 }
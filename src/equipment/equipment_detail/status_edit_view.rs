@@ -22,7 +22,10 @@ pub fn StatusEdit(
 ) -> impl IntoView {
 	let status_action = create_action(|data: &FormData| edit_status(data.clone().into()));
 	let is_archived = equipment.status == EquipmentStatus::Archived;
-	let is_dirty = equipment.status == EquipmentStatus::Dirty;
+
+	let equipment_type = equipment.equipment_type.name.clone();
+	let status = equipment.status;
+	let transitions = create_resource(|| (), move |_| get_status_transitions(equipment_type.clone(), status));
 
 	view! {
 		<EquipmentFormToggle
@@ -68,7 +71,8 @@ pub fn StatusEdit(
 						}
 					>
 						<input type="hidden" name="id" value=equipment.id />
-						<input ref=action_ref type="hidden" name="action" value="next_status" />
+						<input type="hidden" name="version" value=equipment.version />
+						<input ref=action_ref type="hidden" name="action" value="" />
 						<TextArea name="note" placeholder="Add a note why you made this change" />
 						<div class=css::btns>
 							<FileInput name="media1" value=media1 />
@@ -130,53 +134,51 @@ pub fn StatusEdit(
 									}
 								}}
 							</span>
-							<Show when=move || !is_archived>
-								<Button
-									kind="submit"
-									variant=ButtonVariant::Outlined
-									loading
-									on:click=move |_| {
-										if let Some(action_element) = action_ref.get() {
-											let _ = action_element.set_attribute("value", "archive");
-										}
-									}
-								>
-									Archive
-								</Button>
-							</Show>
-							<Show when=move || !is_dirty>
-								<Button
-									kind="submit"
-									variant=ButtonVariant::Outlined
-									loading
-									on:click=move |_| {
-										if let Some(action_element) = action_ref.get() {
-											let _ = action_element.set_attribute("value", "dirty");
-										}
-									}
-								>
-									Mark as Dirty
-								</Button>
-							</Show>
-							<Button
-								kind="submit"
-								loading
-								on:click=move |_| {
-									if let Some(action_element) = action_ref.get() {
-										let _ = action_element.set_attribute("value", "next_status");
-									}
-								}
-							>
-								{if equipment.status == EquipmentStatus::Archived {
-									"Unarchive and mark"
-								} else {
-									"Mark"
+							<Suspense fallback=move || view! { <span>Loading...</span> }>
+								{move || {
+									transitions
+										.get()
+										.map(|result| match result {
+											Err(error) => view! { <span>{error.to_string()}</span> }.into_view(),
+											Ok(targets) => {
+												targets
+													.into_iter()
+													.map(|target| {
+														let value = format!("{target:?}");
+														let label = match target {
+															EquipmentStatus::Archived => String::from("Archive"),
+															_ if is_archived => format!("Unarchive and mark as \"{target}\""),
+															_ => format!("Mark as \"{target}\""),
+														};
+														let on_click = move |_| {
+															if let Some(action_element) = action_ref.get() {
+																let _ = action_element.set_attribute("value", &value);
+															}
+														};
+														if matches!(target, EquipmentStatus::Archived | EquipmentStatus::Dirty) {
+															view! {
+																<Button
+																	kind="submit"
+																	variant=ButtonVariant::Outlined
+																	loading
+																	on:click=on_click
+																>
+																	{label}
+																</Button>
+															}
+														} else {
+															view! {
+																<Button kind="submit" loading on:click=on_click>
+																	{label}
+																</Button>
+															}
+														}
+													})
+													.collect_view()
+											}
+										})
 								}}
-								" as \""
-								{EquipmentStatus::get_next_status(equipment.status, equipment.equipment_type)
-									.to_string()}
-								"\""
-							</Button>
+							</Suspense>
 						</div>
 					</form>
 				}
@@ -185,14 +187,61 @@ pub fn StatusEdit(
 	}
 }
 
+/// The statuses equipment of a type may move to from its current status
+#[cfg(feature = "ssr")]
+pub async fn status_transitions(
+	pool: &sqlx::PgPool,
+	equipment_type: &str,
+	status: EquipmentStatus,
+) -> Result<Vec<EquipmentStatus>, ServerFnError> {
+	let targets: Vec<String> = sqlx::query_scalar(
+		"SELECT to_status FROM equipment_status_transitions WHERE equipment_type = $1 AND from_status = $2",
+	)
+	.bind(equipment_type)
+	.bind(format!("{status:?}"))
+	.fetch_all(pool)
+	.await?;
+
+	// Archive and Dirty are the ways out of the cycle so they come first like they always did
+	let mut targets: Vec<EquipmentStatus> =
+		targets.iter().filter_map(|target| EquipmentStatus::try_parse(target)).collect();
+	targets.sort_by_key(|target| match target {
+		EquipmentStatus::Archived => 0,
+		EquipmentStatus::Dirty => 1,
+		_ => 2,
+	});
+
+	Ok(targets)
+}
+
+#[server(prefix = "/api")]
+pub async fn get_status_transitions(
+	equipment_type: String,
+	status: EquipmentStatus,
+) -> Result<Vec<EquipmentStatus>, ServerFnError> {
+	use crate::auth::get_user;
+
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	if get_user().await?.is_none() {
+		return Err(ServerFnError::Request(String::from("User not authenticated")));
+	}
+
+	status_transitions(&pool, &equipment_type, status).await
+}
+
 #[server(input = MultipartFormData, prefix = "/api")]
 pub async fn edit_status(data: MultipartData) -> Result<(), ServerFnError> {
 	use crate::{
 		auth::get_user,
 		components::file_upload::{file_upload, remove_temp_files},
-		equipment::{EquipmentLogType, EquipmentType},
-		permission::Permissions,
+		equipment::{EquipmentEventKind, EquipmentLogType, publish_equipment_event},
+		notifications::{NotificationKind, notify_equipment},
+		permission::{Dimension, Permissions, Subject},
 		utils::{get_equipment_base_folder, get_equipment_log_folder, move_file},
+		webhooks::queue_log_webhooks,
 	};
 
 	use sqlx::PgPool;
@@ -203,62 +252,83 @@ pub async fn edit_status(data: MultipartData) -> Result<(), ServerFnError> {
 
 	let result = file_upload(data, |id| format!("{}temp/", get_equipment_base_folder(id))).await?;
 
-	let user_id;
-	match user {
-		Some(user) => {
-			let Permissions::All {
-				read: _,
-				write: perm,
-				create: _,
-			} = user.permission_equipment;
-			user_id = user.id;
-
-			let person: i32 =
-				sqlx::query_scalar("SELECT person FROM equipment WHERE id = $1").bind(result.id).fetch_one(&pool).await?;
-			if !perm.has_permission("write", result.id, person) {
-				remove_temp_files(result).await?;
-				return Err(ServerFnError::Request(String::from("User not authenticated")));
+	// Anything that stops the edit before the files are moved has to clean up the upload
+	let checked = async {
+		let user_id = match user {
+			Some(user) => {
+				let Permissions::All {
+					read: _,
+					write: perm,
+					create: _,
+				} = user.permission_equipment;
+
+				let subject = Subject::load(Dimension::Equipment, result.id, &pool).await?;
+				if !perm.has_permission_on("write", &subject) {
+					return Err(ServerFnError::Request(String::from("User not authenticated")));
+				}
+				user.id
+			},
+			None => return Err(ServerFnError::Request(String::from("User not authenticated"))),
+		};
+
+		let mut action = None;
+		let mut note = None;
+		let mut version = None;
+
+		for (name, value) in &result.additional_fields {
+			match name.as_str() {
+				"action" => action = Some(value),
+				"note" => note = Some(value),
+				"version" => version = value.parse::<i32>().ok(),
+				_ => {},
 			}
-		},
-		None => {
-			remove_temp_files(result).await?;
-			return Err(ServerFnError::Request(String::from("User not authenticated")));
-		},
-	};
+		}
 
-	let mut action = None;
-	let mut note = None;
+		let Some(action) = action else {
+			return Err(ServerFnError::Request(String::from("Missing button action field")));
+		};
+		let Some(note) = note else {
+			return Err(ServerFnError::Request(String::from("Missing note field")));
+		};
+		let Some(version) = version else {
+			return Err(ServerFnError::Request(String::from("Missing version field")));
+		};
 
-	for (name, value) in &result.additional_fields {
-		match name.as_str() {
-			"action" => action = Some(value),
-			"note" => note = Some(value),
-			_ => {},
+		// Locking the row keeps a concurrent edit from changing the status between the check and the update
+		let mut transaction = pool.begin().await?;
+		let (old_status, equipment_type, current_version): (String, String, i32) = sqlx::query_as(
+			"SELECT status, equipment_type, version FROM equipment WHERE id = $1 AND deleted_at IS NULL FOR UPDATE",
+		)
+		.bind(result.id)
+		.fetch_optional(&mut *transaction)
+		.await?
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::Request(String::from("Equipment not found or in the trash")))?;
+		if current_version != version {
+			return Err(ServerFnError::Request(String::from(
+				"Someone else changed this equipment in the meantime, reload it and try again",
+			)));
 		}
-	}
 
-	if action.is_none() {
-		return Err(ServerFnError::Request(String::from("Missing button action field")));
-	}
+		let current_status = EquipmentStatus::parse(old_status.clone());
+		let next_status = match EquipmentStatus::try_parse(action) {
+			Some(next_status) if status_transitions(&pool, &equipment_type, current_status).await?.contains(&next_status) => {
+				next_status
+			},
+			_ => {
+				return Err(ServerFnError::Request(format!("Equipment can't go from \"{current_status}\" to \"{action}\"")));
+			},
+		};
 
-	if note.is_none() {
-		return Err(ServerFnError::Request(String::from("Missing note field")));
+		Ok((transaction, user_id, note.clone(), version, old_status, next_status))
 	}
+	.await;
 
-	let action = action.unwrap();
-	let note = note.unwrap();
-
-	let (old_status, equipment_type): (String, String) =
-		sqlx::query_as::<_, (String, String)>("SELECT status, equipment_type FROM equipment WHERE id = $1")
-			.bind(result.id)
-			.fetch_one(&pool)
-			.await?;
-	let next_status = if action == "next_status" {
-		EquipmentStatus::get_next_status(EquipmentStatus::parse(old_status.clone()), EquipmentType::parse(equipment_type))
-	} else if action == "dirty" {
-		EquipmentStatus::Dirty
-	} else {
-		EquipmentStatus::Archived
+	let (mut transaction, user_id, note, version, old_status, next_status) = match checked {
+		Ok(checked) => checked,
+		Err(error) => {
+			remove_temp_files(result).await?;
+			return Err(error);
+		},
 	};
 
 	let log = sqlx::query!(
@@ -271,10 +341,10 @@ pub async fn edit_status(data: MultipartData) -> Result<(), ServerFnError> {
 		EquipmentLogType::from(next_status).to_string(),
 		result.id,
 		user_id,
-		note.to_string(),
+		note,
 		old_status
 	)
-	.fetch_one(&pool)
+	.fetch_one(&mut *transaction)
 	.await
 	.map_err::<ServerFnError, _>(|error| ServerFnError::ServerError(error.to_string()))?;
 
@@ -316,16 +386,32 @@ pub async fn edit_status(data: MultipartData) -> Result<(), ServerFnError> {
 		media10,
 		log.id,
 	)
-	.execute(&pool)
+	.execute(&mut *transaction)
 	.await
 	.map_err::<ServerFnError, _>(|error| ServerFnError::ServerError(error.to_string()))?;
 
-	sqlx::query("UPDATE equipment SET status = $1 WHERE id = $2")
-		.bind(format!("{next_status:#?}"))
-		.bind(result.id)
-		.execute(&pool)
-		.await
-		.map(|_| ())?;
+	sqlx::query(
+		"UPDATE equipment SET status = $1, version = version + 1 WHERE id = $2 AND version = $3 AND deleted_at IS NULL",
+	)
+	.bind(format!("{next_status:#?}"))
+	.bind(result.id)
+	.bind(version)
+	.execute(&mut *transaction)
+	.await
+	.map(|_| ())?;
+
+	queue_log_webhooks(&mut transaction, log.id).await?;
+	notify_equipment(
+		&mut transaction,
+		result.id,
+		Some(user_id),
+		NotificationKind::Status,
+		&format!("changed the status to {next_status}"),
+	)
+	.await?;
+	publish_equipment_event(&mut transaction, result.id, EquipmentEventKind::Status).await?;
+
+	transaction.commit().await?;
 
 	Ok(())
 }
@@ -1,7 +1,7 @@
 use crate::{
 	app::UserSignal,
 	components::{button::Button, input::TextArea, select::Select},
-	equipment::{EquipmentData, EquipmentFormToggle, EquipmentType},
+	equipment::{EquipmentData, EquipmentFormToggle, get_equipment_types},
 };
 
 use leptos::*;
@@ -16,6 +16,8 @@ pub fn TypeEdit(
 	refetch_resources: RwSignal<usize>,
 ) -> impl IntoView {
 	let equipment_type_action = create_server_action::<EditType>();
+	let types = create_resource(|| (), move |_| get_equipment_types());
+	let current_type = store_value(equipment.equipment_type.name.clone());
 
 	view! {
 		<EquipmentFormToggle
@@ -28,21 +30,33 @@ pub fn TypeEdit(
 				view! {
 					<ActionForm action=equipment_type_action class=css::edit_form>
 						<input type="hidden" name="id" value=equipment.id />
+						<input type="hidden" name="version" value=equipment.version />
 						<Select name="equipment_type">
-							{EquipmentType::get_fields()
-								.into_iter()
-								.map(|field| {
-									let equipment_type = EquipmentType::parse(field);
-									view! {
-										<option
-											value=format!("{:#?}", equipment_type)
-											selected=equipment.equipment_type == equipment_type
-										>
-											{format!("{equipment_type}")}
-										</option>
+							<Suspense>
+								{
+									move || {
+										let current_type = current_type.get_value();
+										types
+											.get()
+											.and_then(Result::ok)
+											.map(|types| {
+												types
+													.into_iter()
+													.map(|equipment_type| {
+														view! {
+															<option
+																value=equipment_type.name.clone()
+																selected=equipment_type.name == current_type
+															>
+																{equipment_type.label}
+															</option>
+														}
+													})
+													.collect_view()
+											})
 									}
-								})
-								.collect_view()}
+								}
+							</Suspense>
 						</Select>
 						<TextArea name="note" placeholder="Add a note why you made this change" />
 						<div class=css::btns>
@@ -78,8 +92,12 @@ pub fn TypeEdit(
 }
 
 #[server(prefix = "/api")]
-pub async fn edit_type(id: String, equipment_type: String, note: String) -> Result<(), ServerFnError> {
-	use crate::{auth::get_user, permission::Permissions};
+pub async fn edit_type(id: String, version: i32, equipment_type: String, note: String) -> Result<(), ServerFnError> {
+	use crate::{
+		auth::get_user,
+		equipment::{FieldEdit, apply_field_edits, find_equipment_type},
+		permission::{Dimension, Permissions, Subject},
+	};
 
 	use sqlx::PgPool;
 
@@ -102,41 +120,23 @@ pub async fn edit_type(id: String, equipment_type: String, note: String) -> Resu
 			} = user.permission_equipment;
 			user_id = user.id;
 
-			let person: i32 =
-				sqlx::query_scalar("SELECT person FROM equipment WHERE id = $1").bind(id).fetch_one(&pool).await?;
-			if !perm.has_permission("write", id, person) {
+			let subject = Subject::load(Dimension::Equipment, id, &pool).await?;
+			if !perm.has_permission_on("write", &subject) {
 				return Err(ServerFnError::Request(String::from("User not authenticated")));
 			}
 		},
 		None => return Err(ServerFnError::Request(String::from("User not authenticated"))),
 	};
 
-	let old_value: String =
-		sqlx::query_scalar("SELECT equipment_type FROM equipment WHERE id = $1").bind(id).fetch_one(&pool).await?;
-
-	sqlx::query(
-		r#"INSERT INTO equipment_log
-		(log_type, equipment, person, notes, field, old_value, new_value)
-		VALUES
-		($1, $2, $3, $4, $5, $6, $7)"#,
+	apply_field_edits(
+		&pool,
+		id,
+		version,
+		user_id,
+		&note,
+		vec![FieldEdit::EquipmentType(
+			find_equipment_type(&pool, &equipment_type).await?.name,
+		)],
 	)
-	.bind("edit")
-	.bind(id)
-	.bind(user_id)
-	.bind(note)
-	.bind("type")
-	.bind(old_value)
-	.bind(equipment_type.clone())
-	.execute(&pool)
 	.await
-	.map_err::<ServerFnError, _>(|error| ServerFnError::ServerError(error.to_string()))?;
-
-	sqlx::query("UPDATE equipment SET equipment_type = $1 WHERE id = $2")
-		.bind(equipment_type)
-		.bind(id)
-		.execute(&pool)
-		.await
-		.map(|_| ())?;
-
-	Ok(())
 }
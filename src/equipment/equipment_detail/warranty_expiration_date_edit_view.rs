@@ -29,6 +29,7 @@ pub fn WarrantyExpirationDateEdit(
 				view! {
 					<ActionForm action=warranty_expiration_date_action class=css::edit_form>
 						<input type="hidden" name="id" value=equipment.id />
+						<input type="hidden" name="version" value=equipment.version />
 						<Timezone />
 						<DatePicker
 							attr:name="warranty_expiration_date"
@@ -72,13 +73,17 @@ pub fn WarrantyExpirationDateEdit(
 #[server(prefix = "/api")]
 pub async fn edit_warranty_expiration_date(
 	id: String,
+	version: i32,
 	warranty_expiration_date: String,
 	timezone_offset: i32,
 	note: String,
 ) -> Result<(), ServerFnError> {
-	use crate::{auth::get_user, permission::Permissions};
+	use crate::{
+		auth::get_user,
+		equipment::{FieldEdit, apply_field_edits, parse_optional_local_date},
+		permission::{Dimension, Permissions, Subject},
+	};
 
-	use chrono::prelude::*;
 	use sqlx::PgPool;
 
 	let pool = use_context::<PgPool>()
@@ -100,54 +105,24 @@ pub async fn edit_warranty_expiration_date(
 			} = user.permission_equipment;
 			user_id = user.id;
 
-			let person: i32 =
-				sqlx::query_scalar("SELECT person FROM equipment WHERE id = $1").bind(id).fetch_one(&pool).await?;
-			if !perm.has_permission("write", id, person) {
+			let subject = Subject::load(Dimension::Equipment, id, &pool).await?;
+			if !perm.has_permission_on("write", &subject) {
 				return Err(ServerFnError::Request(String::from("User not authenticated")));
 			}
 		},
 		None => return Err(ServerFnError::Request(String::from("User not authenticated"))),
 	};
 
-	let hours = timezone_offset / 60;
-	let minutes = timezone_offset % 60;
-	let offset_str = format!("{:+03}:{:02}", hours, minutes.abs());
-	let warranty_expiration_date_with_tz = format!("{}T00:00:00{}", warranty_expiration_date, offset_str);
-
-	let warranty_expiration_date: DateTime<Utc> =
-		match DateTime::parse_from_str(&warranty_expiration_date_with_tz, "%Y-%m-%dT%H:%M:%S%z") {
-			Ok(date) => date,
-			Err(error) => return Err(ServerFnError::Request(format!("Invalid date: {}", error))),
-		}
-		.with_timezone(&Utc);
-
-	let old_value: Option<DateTime<Utc>> =
-		sqlx::query_scalar("SELECT warranty_expiration_date FROM equipment WHERE id = $1")
-			.bind(id)
-			.fetch_one(&pool)
-			.await?;
-
-	sqlx::query!(
-		r#"INSERT INTO equipment_log
-		(log_type, equipment, person, notes, field, old_value, new_value)
-		VALUES
-		($1, $2, $3, $4, $5, $6, $7)"#,
-		"edit",
+	apply_field_edits(
+		&pool,
 		id,
+		version,
 		user_id,
-		note,
-		"warranty_expiration_date",
-		old_value.unwrap_or_default().format("%d %b %Y").to_string(),
-		warranty_expiration_date.format("%d %b %Y").to_string(),
+		&note,
+		vec![FieldEdit::WarrantyExpirationDate(parse_optional_local_date(
+			&warranty_expiration_date,
+			timezone_offset,
+		)?)],
 	)
-	.execute(&pool)
 	.await
-	.map_err::<ServerFnError, _>(|error| ServerFnError::ServerError(error.to_string()))?;
-
-	sqlx::query!("UPDATE equipment SET warranty_expiration_date = $1 WHERE id = $2", warranty_expiration_date, id)
-		.execute(&pool)
-		.await
-		.map(|_| ())?;
-
-	Ok(())
 }
@@ -0,0 +1,444 @@
+use crate::equipment::EquipmentSearch;
+
+use leptos::*;
+
+stylance::import_style!(css, "equipment_export.module.css");
+
+/// The formats the equipment list can be downloaded in
+pub const EXPORT_FORMATS: [(&str, &str); 3] = [("csv", "CSV"), ("xlsx", "XLSX"), ("json", "JSON")];
+
+/// Text starting with one of these is prefixed with `'` on export,
+/// a leading `'` itself is escaped too so the import can always tell the prefix from the data
+#[cfg(feature = "ssr")]
+const CSV_ESCAPED_STARTS: [char; 7] = ['=', '+', '-', '@', '\t', '\r', '\''];
+
+/// Prefixes text a spreadsheet would run as a formula with `'` so it opens as plain text
+#[cfg(feature = "ssr")]
+pub fn escape_csv_formula(text: &str) -> String {
+	if text.starts_with(CSV_ESCAPED_STARTS) {
+		format!("'{text}")
+	} else {
+		text.to_string()
+	}
+}
+
+/// Drops the `'` that `escape_csv_formula` added so exported text comes back unchanged on import
+#[cfg(feature = "ssr")]
+pub fn unescape_csv_formula(text: &str) -> String {
+	match text.strip_prefix('\'') {
+		Some(rest) if rest.starts_with(CSV_ESCAPED_STARTS) => rest.to_string(),
+		_ => text.to_string(),
+	}
+}
+
+/// Download links for every matching equipment row with the columns, order and archive toggle of the table
+#[component]
+pub fn EquipmentExport(
+	query_field: RwSignal<String>,
+	query_order: RwSignal<String>,
+	query_filter: RwSignal<Vec<String>>,
+	query_archive: RwSignal<bool>,
+	query_search: RwSignal<EquipmentSearch>,
+) -> impl IntoView {
+	view! {
+		<div class=css::export>
+			"Export: "
+			{EXPORT_FORMATS
+				.into_iter()
+				.map(|(format, label)| {
+					let href = move || {
+						let search = query_search.with(|search| {
+							if search.is_empty() { String::new() } else { format!("&{}", search.to_query_string()) }
+						});
+						format!(
+							"/export/equipment?format={format}&field={}&order={}&filter={}&archive={}{search}",
+							query_field.get(),
+							query_order.get(),
+							query_filter.get().join(","),
+							query_archive.get(),
+						)
+					};
+					view! {
+						<a href=href rel="external" download>
+							{label}
+						</a>
+					}
+				})
+				.collect_view()}
+		</div>
+	}
+}
+
+#[cfg(feature = "ssr")]
+pub mod ssr {
+	use crate::{
+		auth::ssr::{AuthSession, active_user},
+		equipment::{
+			BindSearch, CustomField, CustomFieldKind, EquipmentData, EquipmentSQLData, EquipmentSearch, EquipmentStatus,
+			SearchFilter, equipment_list_query, equipment_read_filter, load_custom_fields,
+		},
+		permission::BindFilter,
+	};
+
+	use axum::{
+		body::Body,
+		extract::{Query, State},
+		http::{StatusCode, header},
+		response::{IntoResponse, Response},
+	};
+	use futures::{SinkExt, StreamExt, channel::mpsc};
+	use leptos::ServerFnError;
+	use serde::Deserialize;
+	use sqlx::{FromRow, PgPool, Row, postgres::PgRow};
+
+	/// How many rows go into one chunk of a streamed export
+	const EXPORT_CHUNK_ROWS: usize = 100;
+
+	#[derive(Debug, Deserialize)]
+	pub struct ExportParams {
+		format: String,
+		field: Option<String>,
+		order: Option<String>,
+		filter: Option<String>,
+		archive: Option<bool>,
+		#[serde(flatten)]
+		search: EquipmentSearch,
+	}
+
+	#[derive(Debug, Clone, Copy, PartialEq)]
+	enum ExportFormat {
+		Csv,
+		Xlsx,
+		Json,
+	}
+
+	#[derive(Clone)]
+	enum ExportValue {
+		Text(String),
+		Number(i32),
+		Decimal(f64),
+		Cost(i32),
+		Empty,
+	}
+
+	impl ExportValue {
+		fn text(value: Option<String>) -> Self {
+			value.map(ExportValue::Text).unwrap_or(ExportValue::Empty)
+		}
+
+		fn date(value: Option<chrono::DateTime<chrono::Utc>>) -> Self {
+			ExportValue::text(value.map(|date| date.format("%Y-%m-%d").to_string()))
+		}
+
+		fn to_csv(&self) -> String {
+			match self {
+				ExportValue::Text(text) => super::escape_csv_formula(text),
+				ExportValue::Number(number) => number.to_string(),
+				ExportValue::Decimal(number) => number.to_string(),
+				ExportValue::Cost(cents) => format!("{:.2}", *cents as f64 / 100.0),
+				ExportValue::Empty => String::new(),
+			}
+		}
+
+		fn to_json(&self) -> serde_json::Value {
+			match self {
+				ExportValue::Text(text) => serde_json::Value::from(text.as_str()),
+				ExportValue::Number(number) | ExportValue::Cost(number) => serde_json::Value::from(*number),
+				ExportValue::Decimal(number) => serde_json::Value::from(*number),
+				ExportValue::Empty => serde_json::Value::Null,
+			}
+		}
+	}
+
+	/// An equipment row together with the values of the exported custom fields keyed by their column
+	struct ExportRow {
+		equipment: EquipmentSQLData,
+		custom_values: Vec<(String, ExportValue)>,
+	}
+
+	impl ExportRow {
+		fn from_row(row: &PgRow, custom_fields: &[CustomField]) -> Result<Self, sqlx::Error> {
+			let custom_values = custom_fields
+				.iter()
+				.map(|custom_field| {
+					let column = custom_field.column();
+					let value = match custom_field.kind {
+						CustomFieldKind::Number => {
+							row.try_get::<Option<f64>, _>(column.as_str())?.map(ExportValue::Decimal).unwrap_or(ExportValue::Empty)
+						},
+						CustomFieldKind::Date => ExportValue::text(
+							row
+								.try_get::<Option<chrono::NaiveDate>, _>(column.as_str())?
+								.map(|date| date.format("%Y-%m-%d").to_string()),
+						),
+						_ => ExportValue::text(row.try_get::<Option<String>, _>(column.as_str())?),
+					};
+					Ok((column, value))
+				})
+				.collect::<Result<Vec<(String, ExportValue)>, sqlx::Error>>()?;
+
+			Ok(ExportRow {
+				equipment: EquipmentSQLData::from_row(row)?,
+				custom_values,
+			})
+		}
+	}
+
+	/// Reads a column out of a row in a shape that survives a round trip through the CSV import
+	fn export_value(row: &ExportRow, field: &str) -> ExportValue {
+		let equipment = &row.equipment;
+		match field {
+			"id" => ExportValue::Number(equipment.id),
+			"equipment_type" => ExportValue::Text(equipment.equipment_type_label.clone()),
+			"person" => ExportValue::Text(equipment.person.preferred_name.clone()),
+			"qrcode" => ExportValue::Text(equipment.qrcode.clone()),
+			"create_date" => ExportValue::Text(equipment.create_date.to_rfc3339()),
+			"name" => ExportValue::Text(equipment.name.clone()),
+			"status" => ExportValue::Text(EquipmentStatus::parse(equipment.status.clone()).to_string()),
+			"manufacturer" => ExportValue::text(equipment.manufacturer.clone()),
+			"purchase_date" => ExportValue::date(equipment.purchase_date),
+			"vendor" => ExportValue::text(equipment.vendor.clone()),
+			"cost_in_cent" => equipment.cost_in_cent.map(ExportValue::Cost).unwrap_or(ExportValue::Empty),
+			"warranty_expiration_date" => ExportValue::date(equipment.warranty_expiration_date),
+			"location" => ExportValue::text(equipment.location.clone()),
+			"notes" => ExportValue::text(equipment.notes.clone()),
+			column => row
+				.custom_values
+				.iter()
+				.find(|(custom_column, _)| custom_column == column)
+				.map(|(_, value)| value.clone())
+				.unwrap_or(ExportValue::Empty),
+		}
+	}
+
+	fn write_csv<I, T>(output: &mut Vec<u8>, record: I) -> Result<(), csv::Error>
+	where
+		I: IntoIterator<Item = T>,
+		T: AsRef<[u8]>,
+	{
+		let mut writer = csv::Writer::from_writer(output);
+		writer.write_record(record)?;
+		writer.flush()?;
+		Ok(())
+	}
+
+	/// Turns rows into chunks of bytes for the formats that can be streamed
+	struct ChunkWriter {
+		format: ExportFormat,
+		columns: Vec<(String, String)>,
+		output: Vec<u8>,
+		rows: usize,
+	}
+
+	impl ChunkWriter {
+		fn new(format: ExportFormat, columns: Vec<(String, String)>) -> Self {
+			ChunkWriter {
+				format,
+				columns,
+				output: Vec::new(),
+				rows: 0,
+			}
+		}
+
+		fn start(&mut self) -> Result<(), csv::Error> {
+			match self.format {
+				ExportFormat::Csv => write_csv(&mut self.output, self.columns.iter().map(|(_, name)| name)),
+				_ => {
+					self.output.push(b'[');
+					Ok(())
+				},
+			}
+		}
+
+		fn write(&mut self, equipment: &ExportRow) -> Result<(), csv::Error> {
+			let values = self.columns.iter().map(|(id, _)| (id, export_value(equipment, id)));
+			match self.format {
+				ExportFormat::Csv => write_csv(&mut self.output, values.map(|(_, value)| value.to_csv()))?,
+				_ => {
+					let object: serde_json::Map<String, serde_json::Value> =
+						values.map(|(id, value)| (id.clone(), value.to_json())).collect();
+					if self.rows > 0 {
+						self.output.push(b',');
+					}
+					serde_json::to_writer(&mut self.output, &object).map_err(std::io::Error::from)?;
+				},
+			}
+			self.rows += 1;
+			Ok(())
+		}
+
+		fn finish(&mut self) {
+			if self.format == ExportFormat::Json {
+				self.output.push(b']');
+			}
+		}
+
+		fn take(&mut self) -> Vec<u8> {
+			std::mem::take(&mut self.output)
+		}
+	}
+
+	fn xlsx(columns: &[(String, String)], rows: &[ExportRow]) -> Result<Vec<u8>, rust_xlsxwriter::XlsxError> {
+		use rust_xlsxwriter::{Format, Workbook};
+
+		let mut workbook = Workbook::new();
+		let sheet = workbook.add_worksheet();
+		let bold = Format::new().set_bold();
+		let cost = Format::new().set_num_format("0.00");
+
+		for (column, (_, name)) in columns.iter().enumerate() {
+			sheet.write_string_with_format(0, column as u16, name, &bold)?;
+		}
+
+		for (row, equipment) in rows.iter().enumerate() {
+			let row = row as u32 + 1;
+			for (column, (id, _)) in columns.iter().enumerate() {
+				let column = column as u16;
+				match export_value(equipment, id) {
+					ExportValue::Text(text) => sheet.write_string(row, column, text)?,
+					ExportValue::Number(number) => sheet.write_number(row, column, number)?,
+					ExportValue::Decimal(number) => sheet.write_number(row, column, number)?,
+					ExportValue::Cost(cents) => sheet.write_number_with_format(row, column, cents as f64 / 100.0, &cost)?,
+					ExportValue::Empty => sheet,
+				};
+			}
+		}
+
+		workbook.save_to_buffer()
+	}
+
+	fn error_response(error: ServerFnError) -> Response {
+		match error {
+			ServerFnError::Request(message) if message == "User not authenticated" => {
+				(StatusCode::UNAUTHORIZED, message).into_response()
+			},
+			ServerFnError::Request(message) => (StatusCode::BAD_REQUEST, message).into_response(),
+			error => (StatusCode::INTERNAL_SERVER_ERROR, error.to_string()).into_response(),
+		}
+	}
+
+	/// Exports every equipment row the user may read with the sorting, columns and archive toggle of the table
+	pub async fn export_equipment(
+		auth_session: AuthSession,
+		State(pool): State<PgPool>,
+		Query(params): Query<ExportParams>,
+	) -> Response {
+		let auth_filter = match equipment_read_filter(active_user(&auth_session)) {
+			Ok(filter) => filter,
+			Err(error) => return error_response(error),
+		};
+
+		let format = match params.format.as_str() {
+			"csv" => ExportFormat::Csv,
+			"xlsx" => ExportFormat::Xlsx,
+			"json" => ExportFormat::Json,
+			_ => return error_response(ServerFnError::Request(String::from("Unknown export format"))),
+		};
+
+		let custom_fields = match load_custom_fields(&pool).await {
+			Ok(custom_fields) => custom_fields,
+			Err(error) => return error_response(error),
+		};
+
+		let filter = params.filter.unwrap_or(String::from("id,equipment_type,name,status,location,notes"));
+		let selected: Vec<&str> = filter.split(',').collect();
+		let columns: Vec<(String, String)> = EquipmentData::get_fields()
+			.into_iter()
+			.chain(custom_fields.iter().map(|custom_field| (custom_field.column(), custom_field.label.clone())))
+			.filter(|(id, _)| selected.contains(&id.as_str()))
+			.collect();
+		if columns.is_empty() {
+			return error_response(ServerFnError::Request(String::from("No columns selected")));
+		}
+		let value_fields: Vec<CustomField> =
+			custom_fields.iter().filter(|custom_field| selected.contains(&custom_field.column().as_str())).cloned().collect();
+		let search = match SearchFilter::parse(&params.search, &custom_fields) {
+			Ok(search) => search,
+			Err(error) => return error_response(error),
+		};
+
+		let query = equipment_list_query(
+			&params.field.unwrap_or(String::from("id")),
+			&params.order.unwrap_or(String::from("asc")),
+			params.archive.unwrap_or_default(),
+			&search,
+			&auth_filter,
+			&custom_fields,
+			&value_fields,
+			false,
+		);
+
+		let (content_type, extension) = match format {
+			ExportFormat::Csv => ("text/csv; charset=utf-8", "csv"),
+			ExportFormat::Xlsx => ("application/vnd.openxmlformats-officedocument.spreadsheetml.sheet", "xlsx"),
+			ExportFormat::Json => ("application/json", "json"),
+		};
+		let headers = [
+			(header::CONTENT_TYPE, content_type.to_string()),
+			(header::CONTENT_DISPOSITION, format!("attachment; filename=\"equipment.{extension}\"")),
+		];
+
+		// XLSX is a zip archive which can only be written once all rows are known
+		if format == ExportFormat::Xlsx {
+			let rows =
+				match sqlx::query(&query).bind_search(&search).bind_filter(&auth_filter).fetch_all(&pool).await.and_then(
+					|rows| rows.iter().map(|row| ExportRow::from_row(row, &value_fields)).collect::<Result<Vec<ExportRow>, _>>(),
+				) {
+					Ok(rows) => rows,
+					Err(error) => return error_response(error.into()),
+				};
+			return match xlsx(&columns, &rows) {
+				Ok(buffer) => (headers, buffer).into_response(),
+				Err(error) => error_response(ServerFnError::ServerError(error.to_string())),
+			};
+		}
+
+		let (mut sender, receiver) = mpsc::channel::<Result<Vec<u8>, std::io::Error>>(4);
+		tokio::spawn(async move {
+			let mut writer = ChunkWriter::new(format, columns);
+			let mut rows = sqlx::query(&query).bind_search(&search).bind_filter(&auth_filter).fetch(&pool);
+
+			let result: Result<(), std::io::Error> = async {
+				writer.start()?;
+				while let Some(row) = rows.next().await {
+					let row = row.and_then(|row| ExportRow::from_row(&row, &value_fields)).map_err(std::io::Error::other)?;
+					writer.write(&row)?;
+					if writer.rows.is_multiple_of(EXPORT_CHUNK_ROWS) {
+						sender.send(Ok(writer.take())).await.map_err(std::io::Error::other)?;
+					}
+				}
+				writer.finish();
+				sender.send(Ok(writer.take())).await.map_err(std::io::Error::other)
+			}
+			.await;
+
+			// An error mid-stream aborts the download so a truncated file is never mistaken for a complete one
+			if let Err(error) = result {
+				let _ = sender.send(Err(error)).await;
+			}
+		});
+
+		(headers, Body::from_stream(receiver)).into_response()
+	}
+}
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn escape_csv_formula_test() {
+		assert_eq!(escape_csv_formula("=HYPERLINK(\"http://evil.test\")"), "'=HYPERLINK(\"http://evil.test\")");
+		assert_eq!(escape_csv_formula("+1"), "'+1");
+		assert_eq!(escape_csv_formula("-2"), "'-2");
+		assert_eq!(escape_csv_formula("@SUM(A1)"), "'@SUM(A1)");
+		assert_eq!(escape_csv_formula("\tcmd"), "'\tcmd");
+		assert_eq!(escape_csv_formula("'quoted"), "''quoted");
+		assert_eq!(escape_csv_formula("Lab 2"), "Lab 2");
+		assert_eq!(escape_csv_formula(""), "");
+
+		for text in ["=1+1", "-2", "'quoted", "'=x", "'-20", "''", "'", "Lab 2", ""] {
+			assert_eq!(unescape_csv_formula(&escape_csv_formula(text)), text);
+		}
+	}
+}
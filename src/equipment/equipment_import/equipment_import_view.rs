@@ -0,0 +1,592 @@
+use crate::{
+	app::UserSignal,
+	components::button::Button,
+	equipment::{EquipmentData, Heading},
+	icons::EquipmentLogo,
+	login::Login,
+	permission::{Permission, Permissions},
+};
+
+use leptos::*;
+use leptos_router::*;
+use serde::{Deserialize, Serialize};
+
+stylance::import_style!(css, "equipment_import.module.css");
+
+/// The most rows a single CSV import may contain
+pub const EQUIPMENT_IMPORT_LIMIT: usize = 1000;
+
+/// A CSV column and the equipment field it is read into, an empty field means the column is ignored
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImportColumn {
+	pub header: String,
+	pub field: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImportRow {
+	pub line: u64,
+	pub name: String,
+	pub errors: Vec<String>,
+}
+
+/// The dry-run result of an import, nothing is stored until every row is free of errors
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImportReport {
+	pub columns: Vec<ImportColumn>,
+	pub errors: Vec<String>,
+	pub rows: Vec<ImportRow>,
+}
+
+impl ImportReport {
+	pub fn is_valid(&self) -> bool {
+		self.errors.is_empty() && !self.rows.is_empty() && self.rows.iter().all(|row| row.errors.is_empty())
+	}
+}
+
+/// The equipment fields a CSV column can be mapped to
+pub fn get_import_fields() -> Vec<(String, String)> {
+	EquipmentData::get_fields()
+		.into_iter()
+		.filter(|(id, _)| !matches!(id.as_str(), "id" | "person" | "qrcode" | "create_date"))
+		.collect()
+}
+
+#[component]
+pub fn EquipmentImport() -> impl IntoView {
+	let user_signal = use_context::<UserSignal>().expect("No user signal found in context");
+	let check_action = create_server_action::<CheckEquipmentImport>();
+	let import_action = create_server_action::<ImportEquipment>();
+
+	let input_ref = create_node_ref::<html::Input>();
+	let csv = create_rw_signal(String::new());
+	let file_name = create_rw_signal(String::new());
+	let mapping = create_rw_signal(Vec::<String>::new());
+
+	// A check adopts whatever mapping the server settled on so the selects start from the detected columns
+	create_effect(move |_| {
+		if let Some(Ok(report)) = check_action.value().get() {
+			mapping.set(report.columns.into_iter().map(|column| column.field).collect());
+		}
+	});
+
+	let loading = create_rw_signal(false);
+	create_effect(move |_| loading.set(import_action.pending().get()));
+
+	let navigate = use_navigate();
+	create_effect(move |_| {
+		if let Some(Ok(_)) = import_action.value().get() {
+			navigate("/equipment", NavigateOptions::default());
+		}
+	});
+
+	let check = move || {
+		import_action.value().set(None);
+		check_action.dispatch(CheckEquipmentImport {
+			csv: csv.get_untracked(),
+			mapping: mapping.get_untracked().join(","),
+			timezone_offset: js_sys::Date::new_0().get_timezone_offset() as i32,
+		});
+	};
+
+	let on_file_change = move |_| {
+		let Some(file) = input_ref.get().and_then(|input| input.files()).and_then(|files| files.item(0)) else {
+			return;
+		};
+		file_name.set(file.name());
+		mapping.set(Vec::new());
+		check_action.value().set(None);
+		spawn_local(async move {
+			if let Ok(text) = wasm_bindgen_futures::JsFuture::from(file.text()).await {
+				csv.set(text.as_string().unwrap_or_default());
+				check();
+			}
+		});
+	};
+
+	let error = move || {
+		[
+			check_action.value().get().map(|value| value.map(|_| ())),
+			import_action.value().get().map(|value| value.map(|_| ())),
+		]
+		.into_iter()
+		.find_map(|value| value.and_then(Result::err))
+		.map(|error| {
+			view! {
+				<span class=css::error>
+					{error.to_string().replace("error reaching server to call server function: ", "")}
+				</span>
+			}
+		})
+	};
+
+	view! {
+		<Heading hide_new=true>
+			<EquipmentLogo />
+			" Import Equipment"
+		</Heading>
+
+		<Suspense fallback=move || {
+			view! { <Login redirect="/equipment/import" /> }
+		}>
+			{move || {
+				match user_signal.get() {
+					None => view! { <Login redirect="/equipment/import" /> }.into_view(),
+					Some(user) => {
+						let Permissions::All { read: _, write: _, create: perm } = user.permission_equipment;
+						if perm != Permission::Create(true) {
+							view! { <span>"You don't have permission to import Equipment"</span> }.into_view()
+						} else {
+							view! {
+								<div class=css::import>
+									<p>
+										"Upload a CSV file with a header row. Each row becomes one item and needs at least a name and a type. "
+										"Dates are read as YYYY-MM-DD and costs in dollars."
+									</p>
+									<label class=format!("input_shadow {}", css::file_input)>
+										<input
+											ref=input_ref
+											type="file"
+											accept=".csv,text/csv"
+											on:change=on_file_change
+										/>
+										<span>
+											{move || {
+												if file_name.get().is_empty() {
+													String::from("Choose CSV file")
+												} else {
+													file_name.get()
+												}
+											}}
+										</span>
+									</label>
+									{move || {
+										check_action
+											.value()
+											.get()
+											.and_then(Result::ok)
+											.map(|report| view! { <Report report mapping check /> })
+									}}
+									<div class=css::btns>
+										{error}
+										<Show when=move || {
+											matches!(check_action.value().get(), Some(Ok(report)) if report.is_valid())
+										}>
+											<Button
+												loading
+												on_click=Callback::new(move |_: ev::MouseEvent| {
+													import_action
+														.dispatch(ImportEquipment {
+															csv: csv.get_untracked(),
+															mapping: mapping.get_untracked().join(","),
+															timezone_offset: js_sys::Date::new_0().get_timezone_offset()
+																as i32,
+														});
+												})
+											>
+												{move || match check_action.value().get() {
+													Some(Ok(report)) => format!("Import {} items", report.rows.len()),
+													_ => String::from("Import"),
+												}}
+											</Button>
+										</Show>
+									</div>
+								</div>
+							}
+								.into_view()
+						}
+					}
+				}
+			}}
+		</Suspense>
+	}
+}
+
+#[component]
+fn Report(report: ImportReport, mapping: RwSignal<Vec<String>>, check: impl Fn() + Copy + 'static) -> impl IntoView {
+	let fields = get_import_fields();
+
+	view! {
+		<div class=css::columns>
+			{report
+				.columns
+				.into_iter()
+				.enumerate()
+				.map(|(index, column)| {
+					let fields = fields.clone();
+					view! {
+						<label class=css::column>
+							<strong>{column.header}</strong>
+							<select
+								class="input_shadow"
+								on:change=move |event| {
+									let field = event_target_value(&event);
+									mapping
+										.update(|mapping| {
+											if let Some(slot) = mapping.get_mut(index) {
+												*slot = field;
+											}
+										});
+									check();
+								}
+							>
+								<option value="" selected=column.field.is_empty()>
+									"Ignore"
+								</option>
+								{fields
+									.into_iter()
+									.map(|(id, name)| {
+										let selected = id == column.field;
+										view! {
+											<option value=id selected=selected>
+												{name}
+											</option>
+										}
+									})
+									.collect_view()}
+							</select>
+						</label>
+					}
+				})
+				.collect_view()}
+		</div>
+		<ul class=css::errors>
+			{report.errors.into_iter().map(|error| view! { <li>{error}</li> }).collect_view()}
+		</ul>
+		<div class=css::table_wrapper>
+			<table class=css::table>
+				<thead>
+					<tr>
+						<th>Line</th>
+						<th>Name</th>
+						<th>Result</th>
+					</tr>
+				</thead>
+				<tbody>
+					{report
+						.rows
+						.into_iter()
+						.map(|row| {
+							let ok = row.errors.is_empty();
+							view! {
+								<tr class=if ok { css::row_ok } else { css::row_error }>
+									<td>{row.line}</td>
+									<td>{row.name}</td>
+									<td>{if ok { String::from("Ready") } else { row.errors.join(", ") }}</td>
+								</tr>
+							}
+						})
+						.collect_view()}
+				</tbody>
+			</table>
+		</div>
+	}
+}
+
+/// Reads the CSV into equipment, collecting every problem per row instead of stopping at the first one
+#[cfg(feature = "ssr")]
+pub fn parse_import(
+	csv: &str,
+	mapping: &str,
+	timezone_offset: i32,
+	types: &[crate::equipment::EquipmentType],
+) -> Result<(ImportReport, Vec<crate::equipment::NewEquipment>), ServerFnError> {
+	use crate::equipment::{Cost, EquipmentStatus, EquipmentType, NewEquipment};
+
+	use chrono::{DateTime, FixedOffset, NaiveDate, TimeZone, Utc};
+
+	// JavaScript reports the offset in minutes west of UTC
+	let offset = FixedOffset::west_opt(timezone_offset * 60)
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::Request(String::from("Invalid timezone offset")))?;
+	let parse_date = |value: &str| -> Option<DateTime<Utc>> {
+		let date = NaiveDate::parse_from_str(value, "%Y-%m-%d").ok()?;
+		offset.from_local_datetime(&date.and_hms_opt(0, 0, 0)?).single().map(|date| date.with_timezone(&Utc))
+	};
+
+	let mut reader = csv::ReaderBuilder::new().flexible(true).trim(csv::Trim::All).from_reader(csv.as_bytes());
+	let headers = reader
+		.headers()
+		.map_err::<ServerFnError, _>(|error| ServerFnError::Request(format!("Could not read the CSV header: {error}")))?
+		.clone();
+
+	let fields = get_import_fields();
+	let columns: Vec<ImportColumn> = if mapping.is_empty() {
+		let mut taken = Vec::new();
+		headers
+			.iter()
+			.map(|header| {
+				let key = header.to_lowercase().replace(' ', "_");
+				let field = fields
+					.iter()
+					.find(|(id, name)| *id == key || name.to_lowercase().replace(' ', "_") == key)
+					.map(|(id, _)| id.clone())
+					.filter(|id| !taken.contains(id))
+					.unwrap_or_default();
+				if !field.is_empty() {
+					taken.push(field.clone());
+				}
+				ImportColumn {
+					header: header.to_string(),
+					field,
+				}
+			})
+			.collect()
+	} else {
+		let mapping: Vec<&str> = mapping.split(',').collect();
+		if mapping.len() != headers.len() {
+			return Err(ServerFnError::Request(String::from("The column mapping doesn't match the CSV columns")));
+		}
+		headers
+			.iter()
+			.zip(mapping)
+			.map(|(header, field)| {
+				if field.is_empty() || fields.iter().any(|(id, _)| id == field) {
+					Ok(ImportColumn {
+						header: header.to_string(),
+						field: field.to_string(),
+					})
+				} else {
+					Err(ServerFnError::Request(format!("Unknown field \"{field}\"")))
+				}
+			})
+			.collect::<Result<_, ServerFnError>>()?
+	};
+
+	let mut errors = Vec::new();
+	for (id, name) in &fields {
+		let mapped = columns.iter().filter(|column| column.field == *id).count();
+		if mapped > 1 {
+			errors.push(format!("More than one column is mapped to {name}"));
+		}
+		if mapped == 0 && (id == "name" || id == "equipment_type") {
+			errors.push(format!("No column is mapped to {name}"));
+		}
+	}
+
+	let mut rows = Vec::new();
+	let mut equipment = Vec::new();
+	for record in reader.records() {
+		let record =
+			record.map_err::<ServerFnError, _>(|error| ServerFnError::Request(format!("Could not read the CSV: {error}")))?;
+		if record.iter().all(str::is_empty) {
+			continue;
+		}
+		if rows.len() == EQUIPMENT_IMPORT_LIMIT {
+			return Err(ServerFnError::Request(format!("An import can't contain more than {EQUIPMENT_IMPORT_LIMIT} rows")));
+		}
+
+		let value = |field: &str| -> Option<String> {
+			columns
+				.iter()
+				.position(|column| column.field == field)
+				.and_then(|index| record.get(index))
+				.filter(|value| !value.is_empty())
+				.map(crate::equipment::unescape_csv_formula)
+		};
+
+		let mut row_errors = Vec::new();
+
+		let name = value("name").unwrap_or_default();
+		if name.is_empty() {
+			row_errors.push(String::from("Name is missing"));
+		}
+
+		let equipment_type = match value("equipment_type") {
+			Some(value) => {
+				EquipmentType::find(types, &value).map(|equipment_type| equipment_type.name.clone()).or_else(|| {
+					row_errors.push(format!("Unknown type \"{value}\""));
+					None
+				})
+			},
+			None => {
+				row_errors.push(String::from("Type is missing"));
+				None
+			},
+		};
+
+		let status = match value("status") {
+			Some(value) => EquipmentStatus::try_parse(&value).or_else(|| {
+				row_errors.push(format!("Unknown status \"{value}\""));
+				None
+			}),
+			None => Some(EquipmentStatus::Dirty),
+		};
+
+		let mut date = |field: &str, label: &str| -> Option<DateTime<Utc>> {
+			let value = value(field)?;
+			parse_date(&value).or_else(|| {
+				row_errors.push(format!("Invalid {label} \"{value}\""));
+				None
+			})
+		};
+		let purchase_date = date("purchase_date", "purchase date");
+		let warranty_expiration_date = date("warranty_expiration_date", "warranty expiration date");
+
+		let cost_in_cent = value("cost_in_cent").and_then(|value| {
+			match Cost::parse_cents(&value.trim_start_matches('$').replace(',', "")) {
+				Ok(cost) if cost >= 0 => Some(cost),
+				_ => {
+					row_errors.push(format!("Invalid cost \"{value}\""));
+					None
+				},
+			}
+		});
+
+		if let (true, Some(equipment_type), Some(status)) = (row_errors.is_empty(), equipment_type, status) {
+			equipment.push(NewEquipment {
+				equipment_type,
+				name: name.clone(),
+				status,
+				manufacturer: value("manufacturer"),
+				purchase_date,
+				vendor: value("vendor"),
+				cost_in_cent,
+				warranty_expiration_date,
+				location: value("location"),
+				notes: value("notes"),
+			});
+		}
+
+		rows.push(ImportRow {
+			line: record.position().map(|position| position.line()).unwrap_or_default(),
+			name,
+			errors: row_errors,
+		});
+	}
+
+	Ok((ImportReport { columns, errors, rows }, equipment))
+}
+
+#[server(prefix = "/api")]
+pub async fn check_equipment_import(
+	csv: String,
+	mapping: String,
+	timezone_offset: i32,
+) -> Result<ImportReport, ServerFnError> {
+	use crate::{
+		auth::get_user,
+		equipment::{check_equipment_create, load_equipment_types},
+	};
+
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+	check_equipment_create(user)?;
+
+	let types = load_equipment_types(&pool).await?;
+	let (report, _) = parse_import(&csv, &mapping, timezone_offset, &types)?;
+
+	Ok(report)
+}
+
+/// Stores every row of a CSV that passed the dry run, or nothing if any row has an error
+#[server(prefix = "/api")]
+pub async fn import_equipment(csv: String, mapping: String, timezone_offset: i32) -> Result<usize, ServerFnError> {
+	use crate::{
+		auth::get_user,
+		equipment::{check_equipment_create, insert_equipment, load_equipment_types},
+	};
+
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	let user_id = check_equipment_create(user)?.id;
+
+	let types = load_equipment_types(&pool).await?;
+	let (report, equipment) = parse_import(&csv, &mapping, timezone_offset, &types)?;
+	if !report.is_valid() {
+		return Err(ServerFnError::Request(String::from("The import has errors, please fix them and check again")));
+	}
+
+	let count = equipment.len();
+	let mut transaction = pool.begin().await?;
+	for item in equipment {
+		insert_equipment(&mut transaction, user_id, item, "Imported from CSV").await?;
+	}
+	transaction.commit().await?;
+
+	Ok(count)
+}
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+	use super::*;
+	use crate::equipment::{EquipmentStatus, EquipmentType};
+
+	fn types() -> Vec<EquipmentType> {
+		vec![
+			EquipmentType::new(String::from("Flask"), String::from("Flask"), String::from("Flask")),
+			EquipmentType::new(String::from("Vessel"), String::from("Vessel"), String::from("Vessel")),
+		]
+	}
+
+	#[test]
+	fn parse_import_detects_columns_test() {
+		let (report, equipment) =
+			parse_import("Name,Type,Status,Cost,Purchased\nFlask A,flask,In Use,\"$1,250.50\",2024-03-01\n", "", 0, &types())
+				.unwrap();
+
+		assert_eq!(
+			report.columns.iter().map(|column| column.field.as_str()).collect::<Vec<_>>(),
+			vec!["name", "equipment_type", "status", "cost_in_cent", "purchase_date"]
+		);
+		assert!(report.errors.is_empty());
+		assert_eq!(equipment.len(), 1);
+		assert_eq!(equipment[0].equipment_type, "Flask");
+		assert_eq!(equipment[0].status, EquipmentStatus::InUse);
+		assert_eq!(equipment[0].cost_in_cent, Some(125050));
+		assert_eq!(equipment[0].purchase_date.unwrap().to_rfc3339(), "2024-03-01T00:00:00+00:00");
+	}
+
+	#[test]
+	fn parse_import_rejects_unknown_values_test() {
+		let (report, equipment) =
+			parse_import("name,equipment_type,status\nA,Flask,\nB,Bucket,Broken\n,Vessel,Dirty\n", "", 0, &types()).unwrap();
+
+		assert!(!report.is_valid());
+		assert_eq!(equipment.len(), 1);
+		assert!(report.rows[0].errors.is_empty());
+		assert_eq!(
+			report.rows[1].errors,
+			vec![
+				String::from("Unknown type \"Bucket\""),
+				String::from("Unknown status \"Broken\"")
+			]
+		);
+		assert_eq!(report.rows[2].errors, vec![String::from("Name is missing")]);
+	}
+
+	#[test]
+	fn parse_import_rejects_invalid_costs_test() {
+		let (report, equipment) = parse_import(
+			"name,equipment_type,cost_in_cent\nA,Flask,$12.5\nB,Flask,99999999\nC,Flask,-1\nD,Flask,1.005\n",
+			"",
+			0,
+			&types(),
+		)
+		.unwrap();
+
+		assert_eq!(equipment.len(), 1);
+		assert_eq!(equipment[0].cost_in_cent, Some(1250));
+		assert_eq!(report.rows[1].errors, vec![String::from("Invalid cost \"99999999\"")]);
+		assert_eq!(report.rows[2].errors, vec![String::from("Invalid cost \"-1\"")]);
+		assert_eq!(report.rows[3].errors, vec![String::from("Invalid cost \"1.005\"")]);
+	}
+
+	#[test]
+	fn parse_import_uses_mapping_test() {
+		let (report, _) = parse_import("Item,Kind,Room\nA,Vessel,Lab 1\n", "name,equipment_type,", 0, &types()).unwrap();
+		assert!(report.is_valid());
+		assert_eq!(report.columns[2].field, "");
+
+		let (report, _) = parse_import("Item,Kind\nA,Vessel\n", "name,", 0, &types()).unwrap();
+		assert_eq!(report.errors, vec![String::from("No column is mapped to Type")]);
+
+		assert!(parse_import("Item,Kind\nA,Vessel\n", "name", 0, &types()).is_err());
+	}
+}
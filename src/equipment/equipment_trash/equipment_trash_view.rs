@@ -0,0 +1,209 @@
+use crate::{
+	app::LoginAction,
+	components::button::{Button, ButtonVariant},
+	equipment::{EQUIPMENT_RESTORE_DAYS, Heading, PurgeEquipment, RestoreEquipment},
+	error_template::ErrorTemplate,
+	icons::EquipmentLogo,
+	login::Login,
+};
+
+use chrono::{DateTime, Duration, Utc};
+use leptos::*;
+use leptos_router::*;
+use serde::{Deserialize, Serialize};
+
+stylance::import_style!(css, "equipment_trash.module.css");
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TrashedEquipmentData {
+	pub id: i32,
+	pub name: String,
+	pub deleted_at: DateTime<Utc>,
+	pub can_write: bool,
+}
+
+#[cfg(feature = "ssr")]
+#[derive(sqlx::FromRow)]
+struct TrashedEquipmentSQLData {
+	#[sqlx(flatten)]
+	subject: crate::permission::Subject,
+	name: String,
+	deleted_at: DateTime<Utc>,
+}
+
+/// Lists everything in the trash so it can be restored within the restore window or purged
+#[component]
+pub fn EquipmentTrash() -> impl IntoView {
+	let restore_action = create_server_action::<RestoreEquipment>();
+	let purge_action = create_server_action::<PurgeEquipment>();
+
+	let login_action = use_context::<LoginAction>().expect("No login action found in context");
+
+	let trash_data = create_resource(
+		move || (login_action.version().get(), restore_action.version().get(), purge_action.version().get()),
+		move |_| get_trashed_equipment(),
+	);
+
+	let error = move || {
+		[restore_action.value().get(), purge_action.value().get()]
+			.into_iter()
+			.find_map(|value| value.and_then(Result::err))
+			.map(|error| {
+				view! {
+					<span class=css::error>
+						{error.to_string().replace("error reaching server to call server function: ", "")}
+					</span>
+				}
+			})
+	};
+
+	view! {
+		<Heading hide_new=true>
+			<EquipmentLogo />
+			" Trash"
+		</Heading>
+
+		{error}
+		<Suspense fallback=move || view! { <p>Loading trash...</p> }>
+			<ErrorBoundary fallback=|errors| {
+				view! { <ErrorTemplate errors=errors /> }
+			}>
+				{move || {
+					trash_data
+						.get()
+						.map(|data| match data {
+							Err(error) => {
+								let error = error.to_string();
+								if error.contains("User not authenticated") {
+									view! { <Login redirect="/equipment/trash" /> }.into_view()
+								} else {
+									view! { <pre class="error">Server Error: {error}</pre> }.into_view()
+								}
+							}
+							Ok(equipment) if equipment.is_empty() => {
+								view! { <span class=css::empty>"The trash is empty"</span> }.into_view()
+							}
+							Ok(equipment) => {
+								view! {
+									<div class=css::items>
+										{equipment
+											.into_iter()
+											.map(|item| {
+												let restore_until = item.deleted_at
+													+ Duration::days(EQUIPMENT_RESTORE_DAYS);
+												let restorable = Utc::now() < restore_until;
+												view! {
+													<div class=css::item>
+														<A href=format!("/equipment/{}", item.id)>{item.name}</A>
+														<span class=css::date>
+															{if restorable {
+																format!(
+																	"Restorable until {}",
+																	restore_until.format("%d %b %Y"),
+																)
+															} else {
+																String::from("Waiting to be purged")
+															}}
+														</span>
+														<Show when=move || item.can_write>
+															<Show when=move || restorable>
+																<ActionForm action=restore_action>
+																	<input type="hidden" name="id" value=item.id />
+																	<Button kind="submit" variant=ButtonVariant::Outlined>
+																		Restore
+																	</Button>
+																</ActionForm>
+															</Show>
+															<ActionForm action=purge_action>
+																<input type="hidden" name="id" value=item.id />
+																<Button
+																	kind="submit"
+																	variant=ButtonVariant::Text
+																	on_click=Callback::new(|event: ev::MouseEvent| {
+																		let confirmed = window()
+																			.confirm_with_message(
+																				"Purging removes this equipment with all its notes, log entries and media for good. Continue?",
+																			)
+																			.unwrap_or(false);
+																		if !confirmed {
+																			event.prevent_default();
+																		}
+																	})
+																>
+																	Purge
+																</Button>
+															</ActionForm>
+														</Show>
+													</div>
+												}
+											})
+											.collect_view()}
+									</div>
+								}
+									.into_view()
+							}
+						})
+				}}
+			</ErrorBoundary>
+		</Suspense>
+	}
+}
+
+#[server(prefix = "/api")]
+pub async fn get_trashed_equipment() -> Result<Vec<TrashedEquipmentData>, ServerFnError> {
+	use crate::{
+		auth::get_user,
+		permission::{BindFilter, Dimension, Permissions},
+	};
+
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	let (read, write) = match user {
+		Some(user) => {
+			let Permissions::All { read, write, create: _ } = user.permission_equipment;
+			(read, write)
+		},
+		None => return Err(ServerFnError::Request(String::from("User not authenticated"))),
+	};
+
+	let auth_filter = read.get_query_filter(Dimension::Equipment, "equipment.id");
+	let trash_sql_data = sqlx::query_as::<_, TrashedEquipmentSQLData>(&format!(
+		r#"
+		SELECT
+			equipment.id,
+			equipment.person,
+			equipment.equipment_type,
+			equipment.location,
+			people.department,
+			equipment.name,
+			equipment.deleted_at
+		FROM
+			equipment
+			JOIN people ON people.id = equipment.person
+		WHERE
+			equipment.deleted_at IS NOT NULL
+			{}
+		ORDER BY equipment.deleted_at DESC"#,
+		auth_filter.and_clause(1)
+	))
+	.bind_filter(&auth_filter)
+	.fetch_all(&pool)
+	.await
+	.map_err::<ServerFnError, _>(|error| ServerFnError::ServerError(error.to_string()))?;
+
+	Ok(
+		trash_sql_data
+			.into_iter()
+			.map(|item| TrashedEquipmentData {
+				id: item.subject.id,
+				name: item.name,
+				deleted_at: item.deleted_at,
+				can_write: write.has_permission_on("write", &item.subject),
+			})
+			.collect(),
+	)
+}
@@ -0,0 +1,302 @@
+use crate::{
+	app::{LoginAction, UserSignal},
+	components::{
+		button::{Button, ButtonVariant},
+		input::Input,
+		select::Select,
+	},
+	equipment::{CustomFields, EquipmentIcon, EquipmentType, Heading},
+	error_template::ErrorTemplate,
+	icons::{Equipment, EquipmentLogo, Flask, FlaskLogo, IncubationCabinet, IncubationCabinetLogo, Vessel, VesselLogo},
+	login::Login,
+	permission::{Permission, Permissions},
+};
+
+use leptos::*;
+use leptos_router::*;
+
+stylance::import_style!(css, "equipment_types.module.css");
+
+/// The icon of an equipment type, `logo` picks the large version used in headings
+#[component]
+pub fn EquipmentTypeIcon(icon: EquipmentIcon, #[prop(optional)] logo: bool) -> impl IntoView {
+	match (icon, logo) {
+		(EquipmentIcon::Equipment, false) => view! { <Equipment /> }.into_view(),
+		(EquipmentIcon::Equipment, true) => view! { <EquipmentLogo /> }.into_view(),
+		(EquipmentIcon::Flask, false) => view! { <Flask /> }.into_view(),
+		(EquipmentIcon::Flask, true) => view! { <FlaskLogo /> }.into_view(),
+		(EquipmentIcon::Vessel, false) => view! { <Vessel /> }.into_view(),
+		(EquipmentIcon::Vessel, true) => view! { <VesselLogo /> }.into_view(),
+		(EquipmentIcon::IncubationCabinet, false) => view! { <IncubationCabinet /> }.into_view(),
+		(EquipmentIcon::IncubationCabinet, true) => view! { <IncubationCabinetLogo /> }.into_view(),
+	}
+}
+
+/// Lists the equipment types with their custom fields and lets people who can create equipment add new ones
+#[component]
+pub fn EquipmentTypes() -> impl IntoView {
+	let add_type_action = create_server_action::<AddEquipmentType>();
+	let delete_type_action = create_server_action::<DeleteEquipmentType>();
+
+	let login_action = use_context::<LoginAction>().expect("No login action found in context");
+	let user_signal = use_context::<UserSignal>().expect("No user signal found in context");
+
+	let types_data = create_resource(
+		move || (login_action.version().get(), add_type_action.version().get(), delete_type_action.version().get()),
+		move |_| get_equipment_types(),
+	);
+
+	let can_create = move || match user_signal.get() {
+		Some(user) => {
+			let Permissions::All {
+				read: _,
+				write: _,
+				create: perm,
+			} = user.permission_equipment;
+			perm == Permission::Create(true)
+		},
+		None => false,
+	};
+
+	let error = move || {
+		[
+			add_type_action.value().get().map(|value| value.map(|_| ())),
+			delete_type_action.value().get(),
+		]
+		.into_iter()
+		.find_map(|value| value.and_then(Result::err))
+		.map(|error| {
+			view! {
+				<span class=css::error>
+					{error.to_string().replace("error reaching server to call server function: ", "")}
+				</span>
+			}
+		})
+	};
+
+	view! {
+		<Heading hide_new=true>
+			<EquipmentLogo />
+			" Equipment Types"
+		</Heading>
+
+		<Suspense fallback=move || view! { <p>Loading equipment types...</p> }>
+			<ErrorBoundary fallback=|errors| {
+				view! { <ErrorTemplate errors=errors /> }
+			}>
+				{move || {
+					types_data
+						.get()
+						.map(|data| match data {
+							Err(error) => {
+								let error = error.to_string();
+								if error.contains("User not authenticated") {
+									view! { <Login redirect="/equipment/types" /> }.into_view()
+								} else {
+									view! { <pre class="error">Server Error: {error}</pre> }.into_view()
+								}
+							}
+							Ok(types) => {
+								let workflows = store_value(
+									types
+										.iter()
+										.map(|equipment_type| {
+											(equipment_type.name.clone(), format!("Workflow of {}", equipment_type.label))
+										})
+										.collect::<Vec<_>>(),
+								);
+								view! {
+									<div class=css::types>
+										{types
+											.into_iter()
+											.map(|equipment_type| {
+												let name = equipment_type.name.clone();
+												let fields_type = equipment_type.name.clone();
+												view! {
+													<div class=css::equipment_type>
+														<EquipmentTypeIcon icon=equipment_type.icon />
+														<span class=css::label>{equipment_type.label}</span>
+														<code class=css::name>{equipment_type.name}</code>
+														<Show when=can_create>
+															<Button
+																variant=ButtonVariant::Text
+																on_click=Callback::new({
+																	let name = name.clone();
+																	move |_: ev::MouseEvent| {
+																		delete_type_action
+																			.dispatch(DeleteEquipmentType {
+																				name: name.clone(),
+																			});
+																	}
+																})
+															>
+																Delete
+															</Button>
+														</Show>
+														<CustomFields
+															equipment_type=fields_type
+															can_create=Signal::derive(can_create)
+														/>
+													</div>
+												}
+											})
+											.collect_view()}
+									</div>
+
+									<Show when=can_create>
+										<ActionForm action=add_type_action class=css::form>
+											<Input name="label" placeholder="Type name" required=true />
+											<Select name="icon" required=true>
+												{EquipmentIcon::get_fields()
+													.into_iter()
+													.map(|icon| {
+														let label = EquipmentIcon::parse(icon.clone()).to_string();
+														view! { <option value=icon>{label}</option> }
+													})
+													.collect_view()}
+											</Select>
+											<Select name="workflow" required=true>
+												{workflows
+												.get_value()
+												.into_iter()
+												.map(|(name, label)| view! { <option value=name>{label}</option> })
+												.collect_view()}
+											</Select>
+											<Button kind="submit" variant=ButtonVariant::Outlined>
+												Add type
+											</Button>
+										</ActionForm>
+									</Show>
+									{error}
+								}
+									.into_view()
+							}
+						})
+				}}
+			</ErrorBoundary>
+		</Suspense>
+	}
+}
+
+/// All equipment types ordered by their label
+#[cfg(feature = "ssr")]
+pub async fn load_equipment_types(pool: &sqlx::PgPool) -> Result<Vec<EquipmentType>, ServerFnError> {
+	let types: Vec<(String, String, String)> =
+		sqlx::query_as("SELECT name, label, icon FROM equipment_types ORDER BY label").fetch_all(pool).await?;
+
+	Ok(types.into_iter().map(|(name, label, icon)| EquipmentType::new(name, label, icon)).collect())
+}
+
+/// Looks up a type by name or label and rejects types that don't exist
+#[cfg(feature = "ssr")]
+pub async fn find_equipment_type(pool: &sqlx::PgPool, input: &str) -> Result<EquipmentType, ServerFnError> {
+	EquipmentType::find(&load_equipment_types(pool).await?, input)
+		.cloned()
+		.ok_or_else(|| ServerFnError::Request(format!("Unknown equipment type \"{input}\"")))
+}
+
+#[server(prefix = "/api")]
+pub async fn get_equipment_types() -> Result<Vec<EquipmentType>, ServerFnError> {
+	use crate::auth::get_user;
+
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	if user.is_none() {
+		return Err(ServerFnError::Request(String::from("User not authenticated")));
+	}
+
+	load_equipment_types(&pool).await
+}
+
+/// Adds a type which starts out with a copy of the status workflow of an existing type
+#[server(prefix = "/api")]
+pub async fn add_equipment_type(label: String, icon: String, workflow: String) -> Result<(), ServerFnError> {
+	use crate::{auth::get_user, equipment::check_equipment_create};
+
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	check_equipment_create(user)?;
+
+	let label = label.trim().to_string();
+	if label.is_empty() {
+		return Err(ServerFnError::Request(String::from("The type name can't be empty")));
+	}
+	if !EquipmentIcon::get_fields().contains(&icon) {
+		return Err(ServerFnError::Request(String::from("Unknown icon")));
+	}
+
+	let types = load_equipment_types(&pool).await?;
+	if EquipmentType::find(&types, &label).is_some() {
+		return Err(ServerFnError::Request(String::from("A type with this name already exists")));
+	}
+	let workflow = EquipmentType::find(&types, &workflow)
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::Request(String::from("Unknown workflow")))?;
+
+	let mut transaction = pool.begin().await?;
+
+	// The label doubles as the stored name so new types read well in exports and permission scopes
+	sqlx::query("INSERT INTO equipment_types (name, label, icon) VALUES ($1, $1, $2)")
+		.bind(&label)
+		.bind(icon)
+		.execute(&mut *transaction)
+		.await?;
+
+	sqlx::query(
+		r#"
+		INSERT INTO equipment_status_transitions (equipment_type, from_status, to_status)
+		SELECT $1, from_status, to_status FROM equipment_status_transitions WHERE equipment_type = $2
+		"#,
+	)
+	.bind(&label)
+	.bind(&workflow.name)
+	.execute(&mut *transaction)
+	.await?;
+
+	transaction.commit().await?;
+
+	Ok(())
+}
+
+/// Deletes a type that no equipment or template uses anymore
+#[server(prefix = "/api")]
+pub async fn delete_equipment_type(name: String) -> Result<(), ServerFnError> {
+	use crate::{auth::get_user, equipment::check_equipment_create};
+
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	check_equipment_create(user)?;
+
+	// Trashed equipment still counts since it can be restored
+	let used: i64 = sqlx::query_scalar(
+		r#"
+		SELECT
+			(SELECT COUNT(*) FROM equipment WHERE equipment_type = $1)
+			+ (SELECT COUNT(*) FROM equipment_templates WHERE equipment_type = $1)
+		"#,
+	)
+	.bind(&name)
+	.fetch_one(&pool)
+	.await?;
+	if used > 0 {
+		return Err(ServerFnError::Request(format!("\"{name}\" is still used by {used} equipment or templates")));
+	}
+
+	let result = sqlx::query("DELETE FROM equipment_types WHERE name = $1").bind(&name).execute(&pool).await?;
+	if result.rows_affected() == 0 {
+		return Err(ServerFnError::Request(format!("Unknown equipment type \"{name}\"")));
+	}
+
+	Ok(())
+}
@@ -5,7 +5,10 @@ use crate::{
 		pagination::Pagination,
 		select::{MultiSelect, MultiSelectOption},
 	},
-	equipment::{EquipmentData, Heading, Row, THead},
+	equipment::{
+		EquipmentData, EquipmentExport, EquipmentSearch, EquipmentSearchForm, Heading, Row, SavedViews, SearchHiddenFields,
+		THead, get_custom_fields,
+	},
 	error_template::ErrorTemplate,
 	icons::EquipmentLogo,
 	login::Login,
@@ -18,8 +21,6 @@ stylance::import_style!(css, "equipment.module.css");
 
 #[component]
 pub fn Equipment() -> impl IntoView {
-	// let delete_equipment = create_server_action::<DeleteEquipment>();
-
 	let query = use_query_map();
 
 	let query_field = create_rw_signal(String::from("id"));
@@ -35,9 +36,10 @@ pub fn Equipment() -> impl IntoView {
 	let query_page = create_rw_signal::<u16>(1);
 	let query_ipp = create_rw_signal::<u8>(25);
 	let query_archive = create_rw_signal(false);
+	let query_search = create_rw_signal(EquipmentSearch::default());
 
 	create_effect(move |_| {
-		let (field, order, filter, page, ipp, archive) = query.with(|p| {
+		let (field, order, filter, page, ipp, archive, search) = query.with(|p| {
 			let field = p.get("field").cloned().unwrap_or(String::from("id"));
 			let order = p.get("order").cloned().unwrap_or(String::from("asc"));
 			let filter = p
@@ -51,7 +53,9 @@ pub fn Equipment() -> impl IntoView {
 			let ipp = p.get("items_per_page").cloned().unwrap_or(String::from("25")).parse::<u8>().unwrap_or(25);
 			let archive = p.get("archive").cloned().unwrap_or(String::from("false")).parse::<bool>().unwrap_or_default();
 
-			(field, order, filter, page, ipp, archive)
+			let search = EquipmentSearch::from_query(p);
+
+			(field, order, filter, page, ipp, archive, search)
 		});
 
 		query_field.set(field);
@@ -60,31 +64,36 @@ pub fn Equipment() -> impl IntoView {
 		query_page.set(if page > 0 { page } else { 1 });
 		query_ipp.set(if ipp > 0 { ipp } else { 1 });
 		query_archive.set(archive);
+		query_search.set(search);
 	});
 
 	let login_action = use_context::<LoginAction>().expect("No login action found in context");
 
 	let equipment_data = create_resource(
 		move || {
-			let (field, order, page, ipp, archive) = query.with(|p| {
+			let (field, order, page, ipp, archive, search) = query.with(|p| {
 				let field = p.get("field").cloned().unwrap_or(String::from("id"));
 				let order = p.get("order").cloned().unwrap_or(String::from("asc"));
 				let page = p.get("page").cloned().unwrap_or(String::from("1")).parse::<u16>().unwrap_or(1);
 				let ipp = p.get("items_per_page").cloned().unwrap_or(String::from("25")).parse::<u8>().unwrap_or(25);
 				let archive = p.get("archive").cloned().unwrap_or(String::from("false")).parse::<bool>().unwrap_or_default();
 
-				(field, order, page, ipp, archive)
+				let search = EquipmentSearch::from_query(p);
+
+				(field, order, page, ipp, archive, search)
 			});
-			(/*delete_equipment.version().get(),*/ login_action.version().get(), field, order, page, ipp, archive)
+			(login_action.version().get(), field, order, page, ipp, archive, search)
 		},
-		move |(_, field, order, page, ipp, archive)| get_equipment_data(field, order, page, ipp, archive),
+		move |(_, field, order, page, ipp, archive, search)| get_equipment_data(field, order, page, ipp, archive, search),
 	);
+	let custom_fields_data = create_resource(move || login_action.version().get(), move |_| get_custom_fields());
 
 	view! {
 		<Heading>
 			<EquipmentLogo />
 			" Equipment"
 		</Heading>
+		<SavedViews query_field query_order query_filter query_ipp query_archive query_search />
 		<Suspense fallback=move || view! { <p>Loading equipment...</p> }>
 			<ErrorBoundary fallback=|errors| {
 				view! { <ErrorTemplate errors /> }
@@ -102,13 +111,31 @@ pub fn Equipment() -> impl IntoView {
 									}
 								}
 								Ok((equipment, row_count)) => {
-									let hidden_fields = vec![
+									let custom_fields = custom_fields_data.get().and_then(Result::ok).unwrap_or_default();
+									let columns: Vec<(String, String)> = EquipmentData::get_fields()
+										.into_iter()
+										.chain(
+											custom_fields
+												.iter()
+												.map(|custom_field| (custom_field.column(), custom_field.label.clone())),
+										)
+										.collect();
+									let mut hidden_fields = vec![
 										(String::from("field"), query_field.get()),
 										(String::from("order"), query_order.get()),
 										(String::from("filter"), query_filter.get().join(",")),
 										(String::from("archive"), query_archive.get().to_string()),
 									];
+									hidden_fields.extend(query_search.get().to_query());
 									view! {
+										<EquipmentSearchForm
+											query_field
+											query_order
+											query_filter
+											query_ipp
+											query_archive
+											query_search
+										/>
 										<Pagination
 											action=String::from("/equipment")
 											page_key="page"
@@ -123,7 +150,8 @@ pub fn Equipment() -> impl IntoView {
 											<MultiSelect
 												value=query_filter
 												options=create_rw_signal(
-													EquipmentData::get_fields()
+													columns
+														.clone()
 														.into_iter()
 														.map(|(id, name)| MultiSelectOption::new(name, id))
 														.collect::<Vec<MultiSelectOption<String>>>(),
@@ -131,14 +159,17 @@ pub fn Equipment() -> impl IntoView {
 											/> <div class=css::btns>
 												<Button
 													variant=ButtonVariant::Outlined
-													on_click=move |_| {
-														query_filter
-															.set(
-																EquipmentData::get_fields()
-																	.into_iter()
-																	.map(|(id, _)| id)
-																	.collect::<Vec<String>>(),
-															);
+													on_click={
+														let columns = columns.clone();
+														move |_| {
+															query_filter
+																.set(
+																	columns
+																		.iter()
+																		.map(|(id, _)| id.clone())
+																		.collect::<Vec<String>>(),
+																);
+														}
 													}
 												>
 													All
@@ -153,6 +184,7 @@ pub fn Equipment() -> impl IntoView {
 														name="archive"
 														value=(!query_archive.get()).to_string()
 													/>
+													<SearchHiddenFields query_search />
 													<button type="submit">
 														Archived:
 														<div class=format!(
@@ -166,13 +198,14 @@ pub fn Equipment() -> impl IntoView {
 												</form>
 											</div>
 										</div>
+										<EquipmentExport query_field query_order query_filter query_archive query_search />
 										<div class=css::table_wrapper>
 											<table class=css::table>
 												<thead>
 													<tr>
 														<THead
 															action="/equipment"
-															items=EquipmentData::get_fields()
+															items=columns.clone()
 															query_field
 															query_order
 															query_filter
@@ -193,6 +226,7 @@ pub fn Equipment() -> impl IntoView {
 																name="archive"
 																value=query_archive.get().to_string()
 															/>
+															<SearchHiddenFields query_search />
 														</THead>
 													</tr>
 												</thead>
@@ -200,13 +234,12 @@ pub fn Equipment() -> impl IntoView {
 													{if equipment.is_empty() {
 														view! {
 															<tr>
-																<td colspan=EquipmentData::get_fields()
-																	.len()>"No equipment found."</td>
+																<td colspan=columns.len()>"No equipment found."</td>
 															</tr>
 														}
 															.into_view()
 													} else {
-														view! { <Row equipment query_filter /> }.into_view()
+														view! { <Row equipment custom_fields query_filter /> }.into_view()
 													}}
 												</tbody>
 											</table>
@@ -232,34 +265,39 @@ pub fn Equipment() -> impl IntoView {
 	}
 }
 
-#[server(prefix = "/api")]
-pub async fn get_equipment_data(
-	field: String,
-	order: String,
-	page: u16,
-	items_per_page: u8,
-	show_archived: bool,
-) -> Result<(Vec<EquipmentData>, i64), ServerFnError> {
-	use crate::{auth::get_user, equipment::EquipmentSQLData, permission::Permissions};
-
-	use sqlx::PgPool;
-
-	let pool = use_context::<PgPool>()
-		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
-	let user = get_user().await?;
+/// The permission filter for the equipment a user may read
+#[cfg(feature = "ssr")]
+pub fn equipment_read_filter(user: Option<crate::auth::User>) -> Result<crate::permission::QueryFilter, ServerFnError> {
+	use crate::permission::{Dimension, Permissions};
 
-	let auth_query = match user {
+	match user {
 		Some(user) => {
 			let Permissions::All {
 				read: perm,
 				write: _,
 				create: _,
 			} = user.permission_equipment;
-			perm.get_query_select_without_where("equipment.id")
+			Ok(perm.get_query_filter(Dimension::Equipment, "equipment.id"))
 		},
-		None => return Err(ServerFnError::Request(String::from("User not authenticated"))),
-	};
+		None => Err(ServerFnError::Request(String::from("User not authenticated"))),
+	}
+}
 
+/// The query behind the equipment table so every consumer sees the same rows in the same order.
+/// A paged query takes `LIMIT $1 OFFSET $2` followed by the search and then the permission parameters.
+/// The values of `value_fields` are selected next to the equipment columns under their `column()` name.
+#[cfg(feature = "ssr")]
+#[allow(clippy::too_many_arguments)]
+pub fn equipment_list_query(
+	field: &str,
+	order: &str,
+	show_archived: bool,
+	search: &crate::equipment::SearchFilter,
+	auth_filter: &crate::permission::QueryFilter,
+	custom_fields: &[crate::equipment::CustomField],
+	value_fields: &[crate::equipment::CustomField],
+	paged: bool,
+) -> String {
 	let order_sanitized = match order.to_lowercase().as_str() {
 		"asc" => "ASC",
 		"desc" => "DESC",
@@ -268,7 +306,6 @@ pub async fn get_equipment_data(
 
 	let field_sanitized = match field.to_lowercase().as_str() {
 		f @ "id"
-		| f @ "equipment_type"
 		| f @ "qrcode"
 		| f @ "create_date"
 		| f @ "name"
@@ -280,91 +317,108 @@ pub async fn get_equipment_data(
 		| f @ "warranty_expiration_date"
 		| f @ "location"
 		| f @ "notes" => String::from(f),
+		"equipment_type" => String::from("equipment_type_label"),
 		"person" => String::from("person_preferred_name"),
-		_ => String::from("id"),
+		column => match custom_fields.iter().find(|custom_field| custom_field.column() == column) {
+			Some(custom_field) => custom_field.sql_value(),
+			None => String::from("id"),
+		},
 	};
 
-	let limit = items_per_page as i64;
-	let offset = (page as i64 - 1) * items_per_page as i64;
 	let status_where = if show_archived {
 		""
 	} else {
 		"AND equipment.status IS DISTINCT FROM 'Archived'"
 	};
 
-	let query = format!(
+	let (first_param, limit) = if paged { (3, "LIMIT $1 OFFSET $2") } else { (1, "") };
+	let search_query = search.and_clause(first_param);
+	let auth_query = auth_filter.and_clause(first_param + search.len());
+	let value_columns = value_fields
+		.iter()
+		.map(|custom_field| format!(",\n\t\t\t\t{} AS {}", custom_field.sql_value(), custom_field.column()))
+		.collect::<String>();
+
+	format!(
 		r#"
 			SELECT
 				equipment.*,
 				people.id AS person_id,
 				people.status AS person_status,
 				people.preferred_name AS person_preferred_name,
-				people.picture AS person_picture
+				people.picture AS person_picture,
+				equipment_types.label AS equipment_type_label,
+				equipment_types.icon AS equipment_type_icon{value_columns}
 			FROM
 				equipment
 				JOIN people ON equipment.person = people.id
+				JOIN equipment_types ON equipment.equipment_type = equipment_types.name
 			WHERE
-				equipment.id IS NOT NULL
+				equipment.deleted_at IS NULL
 				{status_where}
+				{search_query}
 				{auth_query}
-				{status_where}
-			ORDER BY {field_sanitized} {order_sanitized}
-			LIMIT $1 OFFSET $2
+			ORDER BY {field_sanitized} {order_sanitized}, equipment.id
+			{limit}
 			"#
-	);
+	)
+}
+
+#[server(prefix = "/api")]
+pub async fn get_equipment_data(
+	field: String,
+	order: String,
+	page: u16,
+	items_per_page: u8,
+	show_archived: bool,
+	search: EquipmentSearch,
+) -> Result<(Vec<EquipmentData>, i64), ServerFnError> {
+	use crate::{
+		auth::get_user,
+		equipment::{BindSearch, EquipmentSQLData, SearchFilter, attach_custom_fields, load_custom_fields},
+		permission::BindFilter,
+	};
+
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	let auth_filter = equipment_read_filter(user)?;
+	let custom_fields = load_custom_fields(&pool).await?;
+	let search = SearchFilter::parse(&search, &custom_fields)?;
+
+	let limit = items_per_page as i64;
+	let offset = (page as i64 - 1) * items_per_page as i64;
+
+	let query = equipment_list_query(&field, &order, show_archived, &search, &auth_filter, &custom_fields, &[], true);
 	let equipment_sql_data = sqlx::query_as::<_, EquipmentSQLData>(&query)
 		.bind(limit)
 		.bind(offset)
+		.bind_search(&search)
+		.bind_filter(&auth_filter)
 		.fetch_all(&pool)
 		.await
 		.map_err::<ServerFnError, _>(|error| ServerFnError::ServerError(error.to_string()))?;
 
-	let equipment_data: Vec<EquipmentData> = equipment_sql_data.into_iter().map(Into::into).collect();
+	let mut equipment_data: Vec<EquipmentData> = equipment_sql_data.into_iter().map(Into::into).collect();
+	attach_custom_fields(&pool, &custom_fields, &mut equipment_data).await?;
 
-	let row_count: i64 =
-		sqlx::query_scalar(&format!("SELECT COUNT(*) FROM equipment WHERE id IS NOT NULL {status_where} {auth_query}"))
-			.fetch_one(&pool)
-			.await?;
+	let status_where = if show_archived {
+		""
+	} else {
+		"AND equipment.status IS DISTINCT FROM 'Archived'"
+	};
+	let search_query = search.and_clause(1);
+	let auth_query = auth_filter.and_clause(1 + search.len());
+	let row_count: i64 = sqlx::query_scalar(&format!(
+		"SELECT COUNT(*) FROM equipment WHERE deleted_at IS NULL {status_where} {search_query} {auth_query}"
+	))
+	.bind_search(&search)
+	.bind_filter(&auth_filter)
+	.fetch_one(&pool)
+	.await?;
 
 	Ok((equipment_data, row_count))
 }
-
-// #[server(prefix = "/api")]
-// pub async fn delete_equipment(id: i32) -> Result<(), ServerFnError> {
-// 	use crate::{auth::get_user, permission::Permissions};
-
-// 	use server_fn::error::NoCustomError;
-// 	use sqlx::PgPool;
-// 	use std::{fs, path::PathBuf};
-
-// 	let pool = use_context::<PgPool>().ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
-// 	let user = get_user().await?;
-
-// 	match user {
-// 		Some(user) => {
-// 			let Permissions::All {
-// 				read: _,
-// 				write: perm,
-// 				create: _,
-// 			} = user.permission_equipment;
-// 			let person: i32 =
-// 				sqlx::query_scalar("SELECT person FROM equipment WHERE id = $1").bind(id).fetch_one(&pool).await?;
-// 			if !perm.has_permission("read", id, person) {
-// 				return Err(ServerFnError::Request(String::from("User not authenticated")));
-// 			}
-// 		},
-// 		None => return Err(ServerFnError::Request(String::from("User not authenticated"))),
-// 	};
-
-// 	let qrcode_path: String =
-// 		sqlx::query_scalar("SELECT qrcode FROM equipment WHERE id = $1").bind(id).fetch_one(&pool).await?;
-// 	// TODO: delete all logs and notes as well
-
-// 	let file_path = PathBuf::from(format!("{}/public/qrcodes/{qrcode_path}", env!("UPLOAD_ROOT")));
-
-// 	if file_path.exists() {
-// 		fs::remove_file(&file_path).map_err(|error| ServerFnError::<NoCustomError>::ServerError(error.to_string()))?;
-// 	}
-
-// 	Ok(sqlx::query("DELETE FROM equipment WHERE id = $1").bind(id).execute(&pool).await.map(|_| ())?)
-// }
@@ -38,7 +38,50 @@ pub fn Heading(children: Children, #[prop(optional)] hide_new: bool) -> impl Int
 											</svg>
 											Add new
 										</A>
+										<A href="/equipment/import">
+											<svg
+												xmlns="http://www.w3.org/2000/svg"
+												viewBox="0 0 24 24"
+												fill="currentColor"
+											>
+												<path d="M11 4v9.59l-3.3-3.3-1.4 1.42L12 17.4l5.7-5.7-1.4-1.41-3.3 3.3V4z" />
+												<path d="M4 18v2h16v-2z" />
+											</svg>
+											Import
+										</A>
+										<A href="/equipment/types">
+											<svg
+												xmlns="http://www.w3.org/2000/svg"
+												viewBox="0 0 24 24"
+												fill="currentColor"
+											>
+												<path d="M4 4h7v7H4zm2 2v3h3V6zm7-2h7v7h-7zm2 2v3h3V6zM4 13h7v7H4zm2 2v3h3v-3zm7-2h7v7h-7zm2 2v3h3v-3z" />
+											</svg>
+											Types
+										</A>
 									</Show>
+									<A href="/equipment/maintenance">
+										<svg
+											xmlns="http://www.w3.org/2000/svg"
+											viewBox="0 0 24 24"
+											fill="currentColor"
+										>
+											<path d="M12 2a10 10 0 1 0 0 20 10 10 0 0 0 0-20zm0 18a8 8 0 1 1 0-16 8 8 0 0 1 0 16z" />
+											<path d="M13 7h-2v6l5.2 3.1 1-1.7-4.2-2.5z" />
+										</svg>
+										Maintenance
+									</A>
+									<A href="/equipment/trash">
+										<svg
+											xmlns="http://www.w3.org/2000/svg"
+											viewBox="0 0 24 24"
+											fill="currentColor"
+										>
+											<path d="M15 2H9v2H4v2h16V4h-5z" />
+											<path d="M5 8v12a2 2 0 0 0 2 2h10a2 2 0 0 0 2-2V8zm2 2h10v10H7z" />
+										</svg>
+										Trash
+									</A>
 								}
 									.into_view()
 							}
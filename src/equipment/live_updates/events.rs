@@ -0,0 +1,182 @@
+use crate::{
+	auth::ssr::{AuthSession, active_user},
+	permission::{Dimension, Permissions, Subject},
+};
+
+use axum::{
+	extract::{Path, State},
+	http::StatusCode,
+	response::{
+		IntoResponse, Response,
+		sse::{Event, KeepAlive, Sse},
+	},
+};
+use sqlx::{PgConnection, PgPool, postgres::PgListener};
+use std::{convert::Infallible, sync::OnceLock, time::Duration};
+use tokio::sync::broadcast;
+
+/// The Postgres channel equipment events travel on so every server instance hears about them
+const EQUIPMENT_EVENTS_CHANNEL: &str = "equipment_events";
+
+/// How many events a slow subscriber may fall behind before it misses some
+const EQUIPMENT_EVENTS_CAPACITY: usize = 256;
+
+/// How long to wait before listening again after the database connection dropped
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EquipmentEventKind {
+	Log,
+	Notes,
+	Status,
+}
+
+impl EquipmentEventKind {
+	pub fn parse(input: &str) -> Option<Self> {
+		match input {
+			"log" => Some(EquipmentEventKind::Log),
+			"notes" => Some(EquipmentEventKind::Notes),
+			"status" => Some(EquipmentEventKind::Status),
+			_ => None,
+		}
+	}
+}
+
+impl std::fmt::Display for EquipmentEventKind {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			EquipmentEventKind::Log => write!(f, "log"),
+			EquipmentEventKind::Notes => write!(f, "notes"),
+			EquipmentEventKind::Status => write!(f, "status"),
+		}
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EquipmentEvent {
+	pub equipment: i32,
+	pub kind: EquipmentEventKind,
+}
+
+impl EquipmentEvent {
+	/// Parses the `<equipment>:<kind>` payload of a notification
+	pub fn parse(payload: &str) -> Option<Self> {
+		let (equipment, kind) = payload.split_once(':')?;
+		Some(EquipmentEvent {
+			equipment: equipment.parse().ok()?,
+			kind: EquipmentEventKind::parse(kind)?,
+		})
+	}
+}
+
+impl std::fmt::Display for EquipmentEvent {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}:{}", self.equipment, self.kind)
+	}
+}
+
+fn equipment_events() -> &'static broadcast::Sender<EquipmentEvent> {
+	static EVENTS: OnceLock<broadcast::Sender<EquipmentEvent>> = OnceLock::new();
+	EVENTS.get_or_init(|| broadcast::channel(EQUIPMENT_EVENTS_CAPACITY).0)
+}
+
+/// Tells everyone looking at an equipment item that it changed, once the surrounding transaction commits
+pub async fn publish_equipment_event(
+	connection: &mut PgConnection,
+	equipment: i32,
+	kind: EquipmentEventKind,
+) -> Result<(), sqlx::Error> {
+	sqlx::query("SELECT pg_notify($1, $2)")
+		.bind(EQUIPMENT_EVENTS_CHANNEL)
+		.bind(EquipmentEvent { equipment, kind }.to_string())
+		.execute(connection)
+		.await
+		.map(|_| ())
+}
+
+/// Listens for equipment events from the database and hands them to the open event streams of this server
+pub fn spawn_equipment_event_listener(pool: PgPool) {
+	tokio::spawn(async move {
+		loop {
+			if let Err(error) = listen_for_equipment_events(&pool).await {
+				eprintln!("Listening for equipment events failed: {error}");
+			}
+			tokio::time::sleep(RECONNECT_DELAY).await;
+		}
+	});
+}
+
+async fn listen_for_equipment_events(pool: &PgPool) -> Result<(), sqlx::Error> {
+	let mut listener = PgListener::connect_with(pool).await?;
+	listener.listen(EQUIPMENT_EVENTS_CHANNEL).await?;
+
+	loop {
+		let notification = listener.recv().await?;
+		if let Some(event) = EquipmentEvent::parse(notification.payload()) {
+			// Nobody looking at any equipment right now is not an error
+			let _ = equipment_events().send(event);
+		}
+	}
+}
+
+/// Streams the events of one equipment item to a browser as server-sent events
+pub async fn equipment_event_stream(
+	auth_session: AuthSession,
+	State(pool): State<PgPool>,
+	Path(id): Path<i32>,
+) -> Response {
+	let Some(user) = active_user(&auth_session) else {
+		return (StatusCode::UNAUTHORIZED, "User not authenticated").into_response();
+	};
+	let Permissions::All {
+		read: perm,
+		write: _,
+		create: _,
+	} = user.permission_equipment;
+
+	let subject = match Subject::load(Dimension::Equipment, id, &pool).await {
+		Ok(subject) => subject,
+		Err(error) => return (StatusCode::INTERNAL_SERVER_ERROR, error.to_string()).into_response(),
+	};
+	if !perm.has_permission_on("read", &subject) {
+		return (StatusCode::UNAUTHORIZED, "User not authenticated").into_response();
+	}
+
+	let stream = futures::stream::unfold(equipment_events().subscribe(), move |mut receiver| async move {
+		loop {
+			match receiver.recv().await {
+				Ok(event) if event.equipment == id => {
+					return Some((Ok::<_, Infallible>(Event::default().data(event.kind.to_string())), receiver));
+				},
+				Ok(_) => continue,
+				// Some events were dropped so the page may be stale, refetching everything catches it up
+				Err(broadcast::error::RecvError::Lagged(_)) => {
+					return Some((Ok(Event::default().data(EquipmentEventKind::Log.to_string())), receiver));
+				},
+				Err(broadcast::error::RecvError::Closed) => return None,
+			}
+		}
+	});
+
+	Sse::new(stream).keep_alive(KeepAlive::default()).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn equipment_event_round_trip_test() {
+		for kind in [
+			EquipmentEventKind::Log,
+			EquipmentEventKind::Notes,
+			EquipmentEventKind::Status,
+		] {
+			let event = EquipmentEvent { equipment: 42, kind };
+			assert_eq!(EquipmentEvent::parse(&event.to_string()), Some(event));
+		}
+		assert_eq!(EquipmentEvent::parse("42"), None);
+		assert_eq!(EquipmentEvent::parse("abc:log"), None);
+		assert_eq!(EquipmentEvent::parse("42:unknown"), None);
+	}
+}
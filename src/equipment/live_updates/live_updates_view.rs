@@ -0,0 +1,42 @@
+use leptos::*;
+
+/// Refetches the resources of an equipment page whenever someone else changes the item on the server
+#[component]
+pub fn LiveUpdates(id: RwSignal<String>, refetch_resources: RwSignal<usize>) -> impl IntoView {
+	#[cfg(feature = "hydrate")]
+	{
+		use wasm_bindgen::{JsCast, closure::Closure};
+		use web_sys::{EventSource, MessageEvent};
+
+		let source = store_value(None::<(EventSource, Closure<dyn FnMut(MessageEvent)>)>);
+		let close = move || {
+			source.update_value(|source| {
+				if let Some((source, _)) = source.take() {
+					source.close();
+				}
+			})
+		};
+
+		create_effect(move |_| {
+			close();
+			let id = id.get();
+			if id.is_empty() {
+				return;
+			}
+
+			let Ok(event_source) = EventSource::new(&format!("/events/equipment/{id}")) else {
+				return;
+			};
+			let on_message = Closure::<dyn FnMut(MessageEvent)>::new(move |_: MessageEvent| {
+				refetch_resources.update(|version| *version += 1);
+			});
+			event_source.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+			source.set_value(Some((event_source, on_message)));
+		});
+
+		on_cleanup(close);
+	}
+
+	#[cfg(not(feature = "hydrate"))]
+	let _ = (id, refetch_resources);
+}
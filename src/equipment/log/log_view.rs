@@ -94,6 +94,8 @@ pub fn LogItem(log: EquipmentLogData) -> impl IntoView {
 						</span>
 					}
 						.into_view()
+				} else if log.log_type == EquipmentLogType::Maintenance {
+					view! { <span class=css::log_maintenance>{log.field}</span> }.into_view()
 				} else {
 					view! {}.into_view()
 				}}
@@ -120,7 +122,11 @@ pub async fn get_log_for_equipment(
 	page: u16,
 	items_per_page: u8,
 ) -> Result<(Vec<EquipmentLogData>, i64), ServerFnError> {
-	use crate::{auth::get_user, equipment::EquipmentLogSQLData, permission::Permissions};
+	use crate::{
+		auth::get_user,
+		equipment::EquipmentLogSQLData,
+		permission::{BindFilter, Dimension, Permissions},
+	};
 
 	use sqlx::PgPool;
 
@@ -133,20 +139,21 @@ pub async fn get_log_for_equipment(
 		Err(_) => return Err(ServerFnError::Request(String::from("Invalid ID"))),
 	};
 
-	let auth_query = match user {
+	let auth_filter = match user {
 		Some(user) => {
 			let Permissions::All {
 				read: perm,
 				write: _,
 				create: _,
 			} = user.permission_equipment;
-			perm.get_query_select_without_where("equipment_log.equipment")
+			perm.get_query_filter(Dimension::Equipment, "equipment_log.equipment")
 		},
 		None => return Err(ServerFnError::Request(String::from("User not authenticated"))),
 	};
 
 	let limit = items_per_page as i64;
 	let offset = (page as i64 - 1) * items_per_page as i64;
+	let auth_query = auth_filter.and_clause(4);
 
 	let notes_sql_data = sqlx::query_as::<_, EquipmentLogSQLData>(&format!(
 		r#"SELECT
@@ -161,25 +168,42 @@ pub async fn get_log_for_equipment(
 		WHERE
 			equipment_log.equipment = $1
 			{auth_query}
-			AND equipment_log.equipment = $1
 		ORDER BY equipment_log.id DESC
 		LIMIT $2 OFFSET $3"#
 	))
 	.bind(id)
 	.bind(limit)
 	.bind(offset)
+	.bind_filter(&auth_filter)
 	.fetch_all(&pool)
 	.await
 	.map_err::<ServerFnError, _>(|error| ServerFnError::ServerError(error.to_string()))?;
 
-	let notes_data: Vec<EquipmentLogData> = notes_sql_data.into_iter().map(Into::into).collect();
+	// Custom fields are logged under their column, the log shows their label instead
+	let labels: std::collections::HashMap<String, String> =
+		sqlx::query_as::<_, (String, String)>("SELECT 'custom_' || id, label FROM equipment_fields")
+			.fetch_all(&pool)
+			.await?
+			.into_iter()
+			.collect();
+	let notes_data: Vec<EquipmentLogData> = notes_sql_data
+		.into_iter()
+		.map(Into::into)
+		.map(|mut log: EquipmentLogData| {
+			if let Some(label) = log.field.as_ref().and_then(|field| labels.get(field)) {
+				log.field = Some(label.clone());
+			}
+			log
+		})
+		.collect();
 
-	let row_count: i64 = sqlx::query_scalar(&format!(
-		"SELECT COUNT(*) FROM equipment_log WHERE equipment = $1 {auth_query} AND equipment = $1"
-	))
-	.bind(id)
-	.fetch_one(&pool)
-	.await?;
+	let auth_query = auth_filter.and_clause(2);
+	let row_count: i64 =
+		sqlx::query_scalar(&format!("SELECT COUNT(*) FROM equipment_log WHERE equipment = $1 {auth_query}"))
+			.bind(id)
+			.bind_filter(&auth_filter)
+			.fetch_one(&pool)
+			.await?;
 
 	Ok((notes_data, row_count))
 }
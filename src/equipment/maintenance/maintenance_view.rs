@@ -0,0 +1,438 @@
+use crate::{
+	app::{LoginAction, UserSignal},
+	components::{
+		button::{Button, ButtonVariant},
+		input::Input,
+		select::Select,
+	},
+	equipment::{Heading, MaintenanceDue, MaintenancePlan, MaintenanceUnit, get_equipment_types},
+	error_template::ErrorTemplate,
+	icons::EquipmentLogo,
+	login::Login,
+	permission::{Permission, Permissions},
+};
+
+use chrono::prelude::*;
+use leptos::*;
+use leptos_router::*;
+
+stylance::import_style!(css, "maintenance.module.css");
+
+/// How many days ahead the maintenance page looks for upcoming work
+pub const MAINTENANCE_LOOKAHEAD_DAYS: i64 = 30;
+
+/// Lists overdue and upcoming maintenance together with all plans and a form to add new ones
+#[component]
+pub fn Maintenance() -> impl IntoView {
+	let add_plan_action = create_server_action::<AddMaintenancePlan>();
+	let delete_plan_action = create_server_action::<DeleteMaintenancePlan>();
+
+	let login_action = use_context::<LoginAction>().expect("No login action found in context");
+	let user_signal = use_context::<UserSignal>().expect("No user signal found in context");
+
+	let versions =
+		move || (login_action.version().get(), add_plan_action.version().get(), delete_plan_action.version().get());
+	let due_data = create_resource(versions, move |_| get_maintenance_due());
+	let plans_data = create_resource(versions, move |_| get_maintenance_plans());
+	let types_data = create_resource(move || login_action.version().get(), move |_| get_equipment_types());
+
+	let can_create = move || match user_signal.get() {
+		Some(user) => {
+			let Permissions::All {
+				read: _,
+				write: _,
+				create: perm,
+			} = user.permission_equipment;
+			perm == Permission::Create(true)
+		},
+		None => false,
+	};
+
+	let error = move || {
+		[add_plan_action.value().get(), delete_plan_action.value().get()]
+			.into_iter()
+			.find_map(|value| value.and_then(Result::err))
+			.map(|error| {
+				view! {
+					<span class=css::error>
+						{error.to_string().replace("error reaching server to call server function: ", "")}
+					</span>
+				}
+			})
+	};
+
+	view! {
+		<Heading hide_new=true>
+			<EquipmentLogo />
+			" Maintenance"
+		</Heading>
+
+		<Suspense fallback=move || view! { <p>Loading maintenance...</p> }>
+			<ErrorBoundary fallback=|errors| {
+				view! { <ErrorTemplate errors=errors /> }
+			}>
+				{move || {
+					due_data
+						.get()
+						.map(|data| match data {
+							Err(error) => {
+								let error = error.to_string();
+								if error.contains("User not authenticated") {
+									view! { <Login redirect="/equipment/maintenance" /> }.into_view()
+								} else {
+									view! { <pre class="error">Server Error: {error}</pre> }.into_view()
+								}
+							}
+							Ok(due) => {
+								let now = Utc::now();
+								let (overdue, upcoming): (Vec<_>, Vec<_>) =
+									due.into_iter().partition(|item| item.is_overdue(now));
+								view! {
+									<h2>Overdue</h2>
+									<MaintenanceDueList due=overdue empty="Nothing is overdue" />
+									<h2>{format!("Due in the next {MAINTENANCE_LOOKAHEAD_DAYS} days")}</h2>
+									<MaintenanceDueList due=upcoming empty="Nothing is due soon" />
+								}
+									.into_view()
+							}
+						})
+				}}
+			</ErrorBoundary>
+		</Suspense>
+
+		<h2>Plans</h2>
+		<Transition>
+			{move || {
+				plans_data
+					.get()
+					.and_then(Result::ok)
+					.map(|plans| {
+						view! {
+							<div class=css::plans>
+								{plans
+									.into_iter()
+									.map(|plan| {
+										let id = plan.id;
+										let is_item_plan = plan.equipment.is_some();
+										let target = match (plan.equipment, plan.equipment_type.clone()) {
+											(Some(equipment), _) => {
+												view! {
+													<A href=format!("/equipment/{equipment}")>
+														{format!("Equipment #{equipment}")}
+													</A>
+												}
+													.into_view()
+											}
+											(None, Some(equipment_type)) => {
+												view! { <code>{format!("All {equipment_type}")}</code> }.into_view()
+											}
+											(None, None) => view! {}.into_view(),
+										};
+										view! {
+											<div class=css::plan>
+												<span class=css::name>{plan.name.clone()}</span>
+												<span class=css::interval>{plan.interval()}</span>
+												<span class=css::target>{target}</span>
+												<Show when=move || is_item_plan || can_create()>
+													<Button
+														variant=ButtonVariant::Text
+														on_click=Callback::new(move |_: ev::MouseEvent| {
+															delete_plan_action.dispatch(DeleteMaintenancePlan { id });
+														})
+													>
+														Remove
+													</Button>
+												</Show>
+											</div>
+										}
+									})
+									.collect_view()}
+							</div>
+						}
+					})
+			}}
+		</Transition>
+
+		<ActionForm action=add_plan_action class=css::form>
+			<Input name="name" placeholder="Task, like \"Calibrate sensor\"" required=true />
+			<Input name="interval_count" kind="number" placeholder="Every" required=true />
+			<Select name="interval_unit" required=true>
+				{MaintenanceUnit::get_fields()
+					.into_iter()
+					.map(|unit| {
+						let selected = unit == MaintenanceUnit::default().to_string();
+						view! {
+							<option value=unit.clone() selected=selected>
+								{unit.clone()}
+							</option>
+						}
+					})
+					.collect_view()}
+			</Select>
+			<Transition>
+				{move || {
+					types_data
+						.get()
+						.and_then(Result::ok)
+						.map(|types| {
+							view! {
+								<Select name="equipment_type">
+									<option value="">"One item"</option>
+									{types
+										.into_iter()
+										.map(|equipment_type| {
+											let label = format!("Every {}", equipment_type.label);
+											view! { <option value=equipment_type.name>{label}</option> }
+										})
+										.collect_view()}
+								</Select>
+							}
+						})
+				}}
+			</Transition>
+			<Input name="equipment" kind="number" placeholder="Equipment ID" />
+			<Button kind="submit" variant=ButtonVariant::Outlined>
+				Add plan
+			</Button>
+		</ActionForm>
+		<Show when=move || !can_create()>
+			<small class=css::hint>"Only people who can create equipment can add plans for a whole type"</small>
+		</Show>
+		{error}
+	}
+}
+
+#[component]
+fn MaintenanceDueList(due: Vec<MaintenanceDue>, empty: &'static str) -> impl IntoView {
+	if due.is_empty() {
+		return view! { <p class=css::hint>{empty}</p> }.into_view();
+	}
+
+	let now = Utc::now();
+	view! {
+		<div class=css::due>
+			{due
+				.into_iter()
+				.map(|item| {
+					let overdue = item.is_overdue(now);
+					view! {
+						<div class=css::due_item>
+							<A href=format!("/equipment/{}", item.equipment_id)>
+								{format!("#{} {}", item.equipment_id, item.equipment_name)}
+							</A>
+							<span class=css::name>{item.plan.name.clone()}</span>
+							<span class=css::interval>{item.plan.interval()}</span>
+							<span class=css::interval>
+								{item
+									.last_done
+									.map(|date| format!("last done {}", date.format("%d %b %Y")))
+									.unwrap_or_else(|| String::from("never done"))}
+							</span>
+							<span class=if overdue {
+								css::overdue
+							} else {
+								css::upcoming
+							}>{format!("due {}", item.due_date.format("%d %b %Y"))}</span>
+						</div>
+					}
+				})
+				.collect_view()}
+		</div>
+	}
+	.into_view()
+}
+
+/// Maintenance that is overdue or due within the lookahead for all equipment the person can read
+#[server(prefix = "/api")]
+pub async fn get_maintenance_due() -> Result<Vec<MaintenanceDue>, ServerFnError> {
+	use crate::{
+		auth::get_user,
+		equipment::maintenance_due_query,
+		permission::{BindFilter, Dimension},
+	};
+
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	let auth_filter = match user {
+		Some(user) => {
+			let Permissions::All {
+				read: perm,
+				write: _,
+				create: _,
+			} = user.permission_equipment;
+			perm.get_query_filter(Dimension::Equipment, "equipment.id")
+		},
+		None => return Err(ServerFnError::Request(String::from("User not authenticated"))),
+	};
+
+	let due = sqlx::query_as::<_, MaintenanceDue>(&maintenance_due_query(&auth_filter.and_clause(1)))
+		.bind_filter(&auth_filter)
+		.fetch_all(&pool)
+		.await?;
+
+	let horizon = Utc::now() + chrono::Duration::days(MAINTENANCE_LOOKAHEAD_DAYS);
+	Ok(due.into_iter().filter(|item| item.due_date <= horizon).collect())
+}
+
+/// Plans for whole types plus the plans of items the person can read
+#[server(prefix = "/api")]
+pub async fn get_maintenance_plans() -> Result<Vec<MaintenancePlan>, ServerFnError> {
+	use crate::{
+		auth::get_user,
+		permission::{BindFilter, Dimension},
+	};
+
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	let auth_filter = match user {
+		Some(user) => {
+			let Permissions::All {
+				read: perm,
+				write: _,
+				create: _,
+			} = user.permission_equipment;
+			perm.get_query_filter(Dimension::Equipment, "equipment_maintenance.equipment")
+		},
+		None => return Err(ServerFnError::Request(String::from("User not authenticated"))),
+	};
+
+	let mut plans = sqlx::query_as::<_, MaintenancePlan>(
+		"SELECT * FROM equipment_maintenance WHERE equipment IS NULL ORDER BY equipment_type, name",
+	)
+	.fetch_all(&pool)
+	.await?;
+
+	let auth_query = auth_filter.and_clause(1);
+	plans.extend(
+		sqlx::query_as::<_, MaintenancePlan>(&format!(
+			"SELECT * FROM equipment_maintenance WHERE equipment IS NOT NULL {auth_query} ORDER BY equipment, name"
+		))
+		.bind_filter(&auth_filter)
+		.fetch_all(&pool)
+		.await?,
+	);
+
+	Ok(plans)
+}
+
+/// Makes sure the person may change the plans of the given type or item.
+/// Type plans touch every item of the type so they need the right to create equipment.
+#[cfg(feature = "ssr")]
+pub(crate) async fn check_maintenance_plan_write(
+	pool: &sqlx::PgPool,
+	user: Option<crate::auth::User>,
+	equipment: Option<i32>,
+) -> Result<crate::auth::User, ServerFnError> {
+	use crate::{
+		equipment::check_equipment_create,
+		permission::{Dimension, Subject},
+	};
+
+	match equipment {
+		None => check_equipment_create(user),
+		Some(equipment) => {
+			let user =
+				user.ok_or_else::<ServerFnError, _>(|| ServerFnError::Request(String::from("User not authenticated")))?;
+			let Permissions::All {
+				read: _,
+				write: perm,
+				create: _,
+			} = &user.permission_equipment;
+			let subject = Subject::load(Dimension::Equipment, equipment, pool).await?;
+			if !perm.has_permission_on("write", &subject) {
+				return Err(ServerFnError::Request(String::from("User not authenticated")));
+			}
+			Ok(user)
+		},
+	}
+}
+
+#[server(prefix = "/api")]
+pub async fn add_maintenance_plan(
+	name: String,
+	interval_count: String,
+	interval_unit: String,
+	equipment_type: String,
+	equipment: String,
+) -> Result<(), ServerFnError> {
+	use crate::{auth::get_user, equipment::find_equipment_type};
+
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	let name = name.trim().to_string();
+	if name.is_empty() {
+		return Err(ServerFnError::Request(String::from("The task name can't be empty")));
+	}
+	let interval_count = match interval_count.trim().parse::<i32>() {
+		Ok(value) if value > 0 => value,
+		_ => return Err(ServerFnError::Request(String::from("The interval must be a positive number"))),
+	};
+	if !MaintenanceUnit::get_fields().contains(&interval_unit) {
+		return Err(ServerFnError::Request(String::from("Unknown interval unit")));
+	}
+
+	let (equipment, equipment_type) = match (equipment.trim(), equipment_type.trim()) {
+		("", "") => return Err(ServerFnError::Request(String::from("Pick an equipment type or an equipment ID"))),
+		("", equipment_type) => (None, Some(find_equipment_type(&pool, equipment_type).await?.name)),
+		(equipment, "") => match equipment.parse::<i32>() {
+			Ok(value) => (Some(value), None),
+			Err(_) => return Err(ServerFnError::Request(String::from("Invalid equipment ID"))),
+		},
+		_ => return Err(ServerFnError::Request(String::from("Pick either an equipment type or an equipment ID"))),
+	};
+
+	let user = check_maintenance_plan_write(&pool, user, equipment).await?;
+
+	sqlx::query(
+		r#"
+		INSERT INTO equipment_maintenance (equipment, equipment_type, name, interval_count, interval_unit, person)
+		VALUES ($1, $2, $3, $4, $5, $6)
+		"#,
+	)
+	.bind(equipment)
+	.bind(equipment_type)
+	.bind(name)
+	.bind(interval_count)
+	.bind(interval_unit)
+	.bind(user.id)
+	.execute(&pool)
+	.await?;
+
+	Ok(())
+}
+
+/// Removes a plan, the log entries of completed tasks stay but lose the link to it
+#[server(prefix = "/api")]
+pub async fn delete_maintenance_plan(id: i32) -> Result<(), ServerFnError> {
+	use crate::auth::get_user;
+
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	let equipment: Option<i32> = sqlx::query_scalar("SELECT equipment FROM equipment_maintenance WHERE id = $1")
+		.bind(id)
+		.fetch_optional(&pool)
+		.await?
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::Request(String::from("Unknown maintenance plan")))?;
+
+	check_maintenance_plan_write(&pool, user, equipment).await?;
+
+	sqlx::query("DELETE FROM equipment_maintenance WHERE id = $1").bind(id).execute(&pool).await?;
+
+	Ok(())
+}
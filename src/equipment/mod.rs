@@ -8,9 +8,15 @@ pub use equipment_view::*;
 
 pub mod equipment_detail {
 	pub mod cost_edit_view;
+	pub mod custom_field_edit_view;
+	pub mod delete_view;
+	pub mod duplicate_view;
 	pub mod equipment_detail_view;
 	pub mod equipment_form_toggle_view;
+	pub mod field_history_view;
+	pub mod fields_edit_view;
 	pub mod location_edit_view;
+	pub mod maintenance_edit_view;
 	pub mod manufacturer_edit_view;
 	pub mod name_edit_view;
 	pub mod note_edit_view;
@@ -21,9 +27,15 @@ pub mod equipment_detail {
 	pub mod warranty_expiration_date_edit_view;
 }
 pub use equipment_detail::cost_edit_view::*;
+pub use equipment_detail::custom_field_edit_view::*;
+pub use equipment_detail::delete_view::*;
+pub use equipment_detail::duplicate_view::*;
 pub use equipment_detail::equipment_detail_view::*;
 pub use equipment_detail::equipment_form_toggle_view::*;
+pub use equipment_detail::field_history_view::*;
+pub use equipment_detail::fields_edit_view::*;
 pub use equipment_detail::location_edit_view::*;
+pub use equipment_detail::maintenance_edit_view::*;
 pub use equipment_detail::manufacturer_edit_view::*;
 pub use equipment_detail::name_edit_view::*;
 pub use equipment_detail::note_edit_view::*;
@@ -38,6 +50,15 @@ pub mod notes_form {
 }
 pub use notes_form::notes_form_view::*;
 
+pub mod live_updates {
+	#[cfg(feature = "ssr")]
+	pub mod events;
+	pub mod live_updates_view;
+}
+#[cfg(feature = "ssr")]
+pub use live_updates::events::*;
+pub use live_updates::live_updates_view::*;
+
 pub mod log {
 	pub mod log_view;
 }
@@ -50,30 +71,87 @@ pub use notes::notes_view::*;
 
 pub mod equipment_add {
 	pub mod equipment_add_view;
+	pub mod template_view;
 }
 pub use equipment_add::equipment_add_view::*;
+pub use equipment_add::template_view::*;
+
+pub mod equipment_trash {
+	pub mod equipment_trash_view;
+}
+pub use equipment_trash::equipment_trash_view::*;
+
+pub mod custom_fields {
+	pub mod custom_fields_view;
+}
+pub use custom_fields::custom_fields_view::*;
+
+pub mod equipment_export {
+	pub mod equipment_export_view;
+}
+pub use equipment_export::equipment_export_view::*;
+
+pub mod equipment_import {
+	pub mod equipment_import_view;
+}
+pub use equipment_import::equipment_import_view::*;
+
+pub mod equipment_types {
+	pub mod equipment_types_view;
+}
+pub use equipment_types::equipment_types_view::*;
 
 pub mod heading {
 	pub mod heading_view;
 }
 pub use heading::heading_view::*;
 
+pub mod maintenance {
+	pub mod maintenance_view;
+}
+pub use maintenance::maintenance_view::*;
+
 pub mod row {
 	pub mod row_view;
 }
 pub use row::row_view::*;
 
+pub mod saved_views {
+	pub mod saved_views_view;
+}
+pub use saved_views::saved_views_view::*;
+
+pub mod search {
+	pub mod search_view;
+}
+pub use search::search_view::*;
+
 pub mod schema;
 pub use schema::*;
 
+pub mod schema_custom_field;
+pub use schema_custom_field::*;
+
 pub mod schema_log;
 pub use schema_log::*;
 
+pub mod schema_maintenance;
+pub use schema_maintenance::*;
+
 pub mod schema_notes;
 pub use schema_notes::*;
 
 pub mod schema_people;
 pub use schema_people::*;
 
+pub mod schema_saved_view;
+pub use schema_saved_view::*;
+
+pub mod schema_search;
+pub use schema_search::*;
+
+pub mod schema_template;
+pub use schema_template::*;
+
 pub mod thead;
 pub use thead::*;
@@ -32,6 +32,7 @@ pub fn Notes(
 	log_query_page: RwSignal<u16>,
 	log_query_ipp: RwSignal<u8>,
 	tab_query: RwSignal<String>,
+	refetch_resources: RwSignal<usize>,
 ) -> impl IntoView {
 	let params = use_params_map();
 
@@ -49,9 +50,10 @@ pub fn Notes(
 				notes_upload_action.version().get(),
 				edit_note_action.version().get(),
 				delete_note_action.version().get(),
+				refetch_resources.get(),
 			)
 		},
-		move |(id, _, _, _, _)| get_notes_for_equipment(id, notes_query_page.get(), notes_query_ipp.get()),
+		move |(id, _, _, _, _, _)| get_notes_for_equipment(id, notes_query_page.get(), notes_query_ipp.get()),
 	);
 
 	view! {
@@ -405,6 +407,7 @@ pub async fn edit_note(data: MultipartData) -> Result<(), ServerFnError> {
 	use crate::{
 		auth::get_user,
 		components::file_upload::{file_upload, remove_temp_files},
+		equipment::{EquipmentEventKind, publish_equipment_event},
 		permission::{Permission, Permissions},
 		utils::{get_equipment_base_folder, get_equipment_notes_folder},
 	};
@@ -548,7 +551,7 @@ pub async fn edit_note(data: MultipartData) -> Result<(), ServerFnError> {
 	let new_media9 = new_medias.pop();
 	let new_media10 = new_medias.pop();
 
-	sqlx::query!(
+	let equipment = sqlx::query_scalar!(
 		r#"UPDATE equipment_notes SET
 			notes = $2,
 			media1 = $3,
@@ -561,7 +564,8 @@ pub async fn edit_note(data: MultipartData) -> Result<(), ServerFnError> {
 			media8 = $10,
 			media9 = $11,
 			media10 = $12
-		WHERE id = $1"#,
+		WHERE id = $1
+		RETURNING equipment"#,
 		note_id,
 		notes,
 		new_media1,
@@ -575,10 +579,12 @@ pub async fn edit_note(data: MultipartData) -> Result<(), ServerFnError> {
 		new_media9,
 		new_media10,
 	)
-	.execute(&pool)
+	.fetch_one(&pool)
 	.await
 	.map_err::<ServerFnError, _>(|error| ServerFnError::ServerError(error.to_string()))?;
 
+	publish_equipment_event(&mut *pool.acquire().await?, equipment, EquipmentEventKind::Notes).await?;
+
 	Ok(())
 }
 
@@ -586,7 +592,9 @@ pub async fn edit_note(data: MultipartData) -> Result<(), ServerFnError> {
 pub async fn delete_note(id: i32) -> Result<(), ServerFnError> {
 	use crate::{
 		auth::get_user,
+		equipment::{EquipmentEventKind, publish_equipment_event},
 		permission::{Permission, Permissions},
+		webhooks::{WebhookEvent, queue_note_webhooks},
 	};
 
 	use sqlx::PgPool;
@@ -640,7 +648,15 @@ pub async fn delete_note(id: i32) -> Result<(), ServerFnError> {
 		}
 	}
 
-	Ok(sqlx::query!("DELETE FROM equipment_notes WHERE id = $1", id).execute(&pool).await.map(|_| ())?)
+	let mut transaction = pool.begin().await?;
+	queue_note_webhooks(&mut transaction, id, WebhookEvent::NoteDeleted).await?;
+	let equipment = sqlx::query_scalar!("DELETE FROM equipment_notes WHERE id = $1 RETURNING equipment", id)
+		.fetch_one(&mut *transaction)
+		.await?;
+	publish_equipment_event(&mut transaction, equipment, EquipmentEventKind::Notes).await?;
+	transaction.commit().await?;
+
+	Ok(())
 }
 
 #[server(prefix = "/api")]
@@ -649,7 +665,11 @@ pub async fn get_notes_for_equipment(
 	page: u16,
 	items_per_page: u8,
 ) -> Result<(Vec<EquipmentNotesData>, i32, i64), ServerFnError> {
-	use crate::{auth::get_user, equipment::EquipmentNotesSQLData, permission::Permissions};
+	use crate::{
+		auth::get_user,
+		equipment::EquipmentNotesSQLData,
+		permission::{BindFilter, Dimension, Permissions},
+	};
 
 	use sqlx::PgPool;
 
@@ -662,20 +682,21 @@ pub async fn get_notes_for_equipment(
 		Err(_) => return Err(ServerFnError::Request(String::from("Invalid ID"))),
 	};
 
-	let auth_query = match user {
+	let auth_filter = match user {
 		Some(user) => {
 			let Permissions::All {
 				read: perm,
 				write: _,
 				create: _,
 			} = user.permission_equipment;
-			perm.get_query_select_without_where("equipment_notes.equipment")
+			perm.get_query_filter(Dimension::Equipment, "equipment_notes.equipment")
 		},
 		None => return Err(ServerFnError::Request(String::from("User not authenticated"))),
 	};
 
 	let limit = items_per_page as i64;
 	let offset = (page as i64 - 1) * items_per_page as i64;
+	let auth_query = auth_filter.and_clause(4);
 
 	let notes_sql_data = sqlx::query_as::<_, EquipmentNotesSQLData>(&format!(
 		r#"SELECT
@@ -690,13 +711,13 @@ pub async fn get_notes_for_equipment(
 		WHERE
 			equipment_notes.equipment = $1
 			{auth_query}
-			AND equipment_notes.equipment = $1
 		ORDER BY equipment_notes.id DESC
 		LIMIT $2 OFFSET $3"#,
 	))
 	.bind(id)
 	.bind(limit)
 	.bind(offset)
+	.bind_filter(&auth_filter)
 	.fetch_all(&pool)
 	.await
 	.map_err::<ServerFnError, _>(|error| ServerFnError::ServerError(error.to_string()))?;
@@ -706,9 +727,11 @@ pub async fn get_notes_for_equipment(
 	let person_id: i32 =
 		sqlx::query_scalar("SELECT person FROM equipment WHERE id = $1").bind(id).fetch_one(&pool).await?;
 
+	let auth_query = auth_filter.and_clause(2);
 	let row_count: i64 =
 		sqlx::query_scalar(&format!("SELECT COUNT(*) FROM equipment_notes WHERE equipment = $1 {auth_query}"))
 			.bind(id)
+			.bind_filter(&auth_filter)
 			.fetch_one(&pool)
 			.await?;
 
@@ -1,7 +1,7 @@
 use crate::{
 	app::UserSignal,
 	components::{button::Button, file_input::FileInput, input::TextArea},
-	permission::Permissions,
+	permission::{Permissions, Subject, SubjectSignal},
 };
 
 use leptos::*;
@@ -17,6 +17,7 @@ pub fn NotesForm(
 	notes_upload_action: Action<FormData, Result<(), ServerFnError>>,
 ) -> impl IntoView {
 	let user_signal = use_context::<UserSignal>().expect("No user signal found in context");
+	let subject_signal = use_context::<SubjectSignal>();
 
 	let form_ref = create_node_ref::<html::Form>();
 
@@ -43,7 +44,7 @@ pub fn NotesForm(
 					Some(user) => {
 						let Permissions::All { read: _, write: perm, create: _ } = user.permission_equipment;
 						let id_num = id.get().parse::<i32>().unwrap_or(-1);
-						if !perm.has_permission("write", id_num, person_id) {
+						if !perm.has_permission_on("write", &Subject::resolve(subject_signal, id_num, person_id)) {
 							view! { <span /> }.into_view()
 						} else {
 							view! {
@@ -150,8 +151,11 @@ pub async fn save_notes(data: MultipartData) -> Result<(), ServerFnError> {
 	use crate::{
 		auth::get_user,
 		components::file_upload::{file_upload, remove_temp_files},
-		permission::Permissions,
+		equipment::{EquipmentEventKind, publish_equipment_event},
+		notifications::{NotificationKind, notify_equipment},
+		permission::{Dimension, Permissions, Subject},
 		utils::{get_equipment_base_folder, get_equipment_notes_folder, move_file},
+		webhooks::{WebhookEvent, queue_note_webhooks},
 	};
 
 	use sqlx::PgPool;
@@ -172,9 +176,8 @@ pub async fn save_notes(data: MultipartData) -> Result<(), ServerFnError> {
 			} = user.permission_equipment;
 			user_id = user.id;
 
-			let person: i32 =
-				sqlx::query_scalar("SELECT person FROM equipment WHERE id = $1").bind(result.id).fetch_one(&pool).await?;
-			if !perm.has_permission("write", result.id, person) {
+			let subject = Subject::load(Dimension::Equipment, result.id, &pool).await?;
+			if !perm.has_permission_on("write", &subject) {
 				remove_temp_files(result).await?;
 				return Err(ServerFnError::Request(String::from("User not authenticated")));
 			}
@@ -244,5 +247,10 @@ pub async fn save_notes(data: MultipartData) -> Result<(), ServerFnError> {
 	.await
 	.map_err::<ServerFnError, _>(|error| ServerFnError::ServerError(error.to_string()))?;
 
+	let mut connection = pool.acquire().await?;
+	queue_note_webhooks(&mut connection, note.id, WebhookEvent::NoteCreated).await?;
+	notify_equipment(&mut connection, result.id, Some(user_id), NotificationKind::Note, "added a note").await?;
+	publish_equipment_event(&mut connection, result.id, EquipmentEventKind::Notes).await?;
+
 	Ok(())
 }
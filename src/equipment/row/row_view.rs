@@ -1,6 +1,6 @@
 use crate::{
 	// components::button::{Button, ButtonVariant},
-	equipment::{/*DeleteEquipment,*/ EquipmentCell, EquipmentData},
+	equipment::{/*DeleteEquipment,*/ CustomField, EquipmentCell, EquipmentData},
 };
 
 use leptos::*;
@@ -11,6 +11,7 @@ stylance::import_style!(css, "row.module.css");
 #[component]
 pub fn Row(
 	equipment: Vec<EquipmentData>,
+	custom_fields: Vec<CustomField>,
 	// delete_equipment: Action<DeleteEquipment, Result<(), ServerFnError>>,
 	query_filter: RwSignal<Vec<String>>,
 ) -> impl IntoView {
@@ -30,10 +31,11 @@ pub fn Row(
 						query_filter.get().contains(&String::from("equipment_type"))
 					}>
 						{
+							let equipment_type = equipment.equipment_type.clone();
 							view! {
 								<td class="equipment_listing_equipment_type">
 									<A href=format!("/equipment/{}", equipment.id) class="none">
-										<EquipmentCell cell=equipment.equipment_type table_view=true />
+										<EquipmentCell cell=equipment_type table_view=true />
 									</A>
 								</td>
 							}
@@ -203,6 +205,32 @@ pub fn Row(
 							}
 						}
 					</Show>
+					{custom_fields
+						.iter()
+						.map(|custom_field| {
+							let column = custom_field.column();
+							let custom_value = equipment
+								.custom_fields
+								.iter()
+								.find(|custom_value| custom_value.field.id == custom_field.id)
+								.cloned()
+								.unwrap_or_default();
+							view! {
+								<Show when=move || query_filter.get().contains(&column)>
+									{
+										let custom_value = custom_value.clone();
+										view! {
+											<td class="equipment_listing_custom">
+												<A href=format!("/equipment/{}", equipment.id) class="none">
+													<EquipmentCell cell=custom_value table_view=true />
+												</A>
+											</td>
+										}
+									}
+								</Show>
+							}
+						})
+						.collect_view()}
 					<td class="equipment_listing_details_link">
 						<A href=format!("/equipment/{}", equipment.id)>Details</A>
 					</td>
@@ -0,0 +1,437 @@
+use crate::{
+	components::{
+		button::{Button, ButtonVariant},
+		checkbox::Checkbox,
+		input::Input,
+	},
+	equipment::{EquipmentSearch, SavedViewData, table_query},
+};
+
+use leptos::*;
+use leptos_router::*;
+
+stylance::import_style!(css, "saved_views.module.css");
+
+/// Switches between, saves and shares named views of the equipment table
+#[component]
+pub fn SavedViews(
+	query_field: RwSignal<String>,
+	query_order: RwSignal<String>,
+	query_filter: RwSignal<Vec<String>>,
+	query_ipp: RwSignal<u8>,
+	query_archive: RwSignal<bool>,
+	query_search: RwSignal<EquipmentSearch>,
+) -> impl IntoView {
+	let save_action = create_server_action::<SaveEquipmentView>();
+	let default_action = create_server_action::<SetDefaultEquipmentView>();
+	let delete_action = create_server_action::<DeleteEquipmentView>();
+
+	let views = create_resource(
+		move || (save_action.version().get(), default_action.version().get(), delete_action.version().get()),
+		move |_| get_equipment_views(),
+	);
+
+	let current = move || {
+		table_query(
+			&query_field.get(),
+			&query_order.get(),
+			&query_filter.get(),
+			query_ipp.get(),
+			query_archive.get(),
+			&query_search.get(),
+		)
+	};
+	let selected =
+		move || views.get().and_then(Result::ok).and_then(|views| views.into_iter().find(|view| view.query() == current()));
+
+	// Opening the list without any parameters shows the default view
+	let query = use_query_map();
+	let navigate = store_value(use_navigate());
+	create_effect(move |_| {
+		if !query.with(|query| query.0.is_empty()) {
+			return;
+		}
+		if let Some(view) =
+			views.get().and_then(Result::ok).and_then(|views| views.into_iter().find(|view| view.own && view.is_default))
+		{
+			navigate.with_value(|navigate| {
+				navigate(
+					&format!("/equipment?{}", view.query()),
+					NavigateOptions {
+						replace: true,
+						..Default::default()
+					},
+				)
+			});
+		}
+	});
+
+	let name = create_rw_signal(String::new());
+	let shared = create_rw_signal(false);
+	let is_default = create_rw_signal(false);
+
+	let save = Callback::new(move |_: ev::MouseEvent| {
+		save_action.dispatch(SaveEquipmentView {
+			name: name.get_untracked(),
+			field: query_field.get_untracked(),
+			order: query_order.get_untracked(),
+			filter: query_filter.get_untracked().join(","),
+			items_per_page: query_ipp.get_untracked(),
+			archive: query_archive.get_untracked(),
+			search: query_search.get_untracked(),
+			shared: shared.get_untracked(),
+			is_default: is_default.get_untracked(),
+		});
+	});
+
+	let error = move || {
+		[
+			save_action.value().get().map(|value| value.map(|_| ())),
+			default_action.value().get(),
+			delete_action.value().get(),
+		]
+		.into_iter()
+		.find_map(|value| value.and_then(Result::err))
+		.map(|error| {
+			view! {
+				<span class=css::error>
+					{error.to_string().replace("error reaching server to call server function: ", "")}
+				</span>
+			}
+		})
+	};
+
+	view! {
+		<Transition>
+			{move || {
+				views
+					.get()
+					.and_then(Result::ok)
+					.map(|views| {
+						let (own, shared_views): (Vec<SavedViewData>, Vec<SavedViewData>) = views
+							.into_iter()
+							.partition(|view| view.own);
+						let option = move |view: SavedViewData| {
+							let query = view.query();
+							let label = match (view.own, view.is_default, view.shared) {
+								(false, _, _) => format!("{} by {}", view.name, view.person_name),
+								(true, true, _) => format!("{} (default)", view.name),
+								(true, false, true) => format!("{} (shared)", view.name),
+								(true, false, false) => view.name,
+							};
+							view! {
+								<option value=query.clone() selected=move || current() == query>
+									{label}
+								</option>
+							}
+						};
+						view! {
+							<div class=css::views>
+								<label class=css::select>
+									"View: "
+									<select
+										prop:value=move || selected().map(|view| view.query()).unwrap_or_default()
+										on:change=move |event| {
+										let query = event_target_value(&event);
+										if !query.is_empty() {
+											navigate.with_value(|navigate| navigate(&format!("/equipment?{query}"), NavigateOptions::default()));
+										}
+									}>
+										<option value="" selected=move || selected().is_none()>
+											Unsaved view
+										</option>
+										{own.into_iter().map(option).collect_view()}
+										{(!shared_views.is_empty())
+											.then(|| {
+												view! {
+													<optgroup label="Shared with the team">
+														{shared_views.into_iter().map(option).collect_view()}
+													</optgroup>
+												}
+											})}
+									</select>
+								</label>
+								{move || {
+									selected()
+										.filter(|view| view.own)
+										.map(|view| {
+											let id = view.id;
+											let make_default = !view.is_default;
+											view! {
+												<Button
+													variant=ButtonVariant::Outlined
+													on_click=Callback::new(move |_: ev::MouseEvent| {
+														default_action
+															.dispatch(SetDefaultEquipmentView {
+																id,
+																is_default: make_default,
+															});
+													})
+												>
+													{if make_default { "Make default" } else { "Remove default" }}
+												</Button>
+												<Button
+													variant=ButtonVariant::Outlined
+													on_click=Callback::new(move |_: ev::MouseEvent| {
+														delete_action.dispatch(DeleteEquipmentView { id });
+													})
+												>
+													Delete view
+												</Button>
+											}
+										})
+								}}
+							</div>
+						}
+					})
+			}}
+		</Transition>
+		<div class=css::views>
+			<Input placeholder="View name" value=name />
+			<Checkbox value=shared>Share with the team</Checkbox>
+			<Checkbox value=is_default>Default</Checkbox>
+			<Button variant=ButtonVariant::Outlined on_click=save>
+				Save view
+			</Button>
+			{error}
+		</div>
+	}
+}
+
+/// Checks the state of the table before it is saved as a view
+#[cfg(feature = "ssr")]
+fn check_saved_view(
+	name: &str,
+	field: &str,
+	order: &str,
+	filter: &str,
+	items_per_page: u8,
+	search: &EquipmentSearch,
+	custom_fields: &[crate::equipment::CustomField],
+) -> Result<(), ServerFnError> {
+	use crate::equipment::{EquipmentData, SearchFilter};
+
+	if name.trim().is_empty() {
+		return Err(ServerFnError::Request(String::from("A saved view needs a name")));
+	}
+
+	let fields: Vec<String> = EquipmentData::get_fields()
+		.into_iter()
+		.map(|(id, _)| id)
+		.chain(custom_fields.iter().map(|custom_field| custom_field.column()))
+		.collect();
+	if !fields.iter().any(|id| id == field) {
+		return Err(ServerFnError::Request(format!("Unknown field \"{field}\"")));
+	}
+	if order != "asc" && order != "desc" {
+		return Err(ServerFnError::Request(format!("Unknown order \"{order}\"")));
+	}
+	if let Some(column) = filter.split(',').find(|column| !fields.iter().any(|id| id == column)) {
+		return Err(ServerFnError::Request(format!("Unknown column \"{column}\"")));
+	}
+	if items_per_page == 0 {
+		return Err(ServerFnError::Request(String::from("Items per page must be at least 1")));
+	}
+
+	SearchFilter::parse(search, custom_fields)?;
+
+	Ok(())
+}
+
+/// The user's own views followed by the ones others shared with the team
+#[server(prefix = "/api")]
+pub async fn get_equipment_views() -> Result<Vec<SavedViewData>, ServerFnError> {
+	use crate::auth::get_user;
+
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user()
+		.await?
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::Request(String::from("User not authenticated")))?;
+
+	Ok(
+		sqlx::query_as::<_, SavedViewData>(
+			r#"
+		SELECT
+			equipment_views.id,
+			equipment_views.person = $1 AS own,
+			people.preferred_name AS person_name,
+			equipment_views.name,
+			equipment_views.field,
+			equipment_views.sort_order,
+			equipment_views.filter,
+			equipment_views.items_per_page,
+			equipment_views.archive,
+			equipment_views.search,
+			equipment_views.is_default,
+			equipment_views.shared
+		FROM equipment_views
+		JOIN people ON equipment_views.person = people.id
+		WHERE equipment_views.person = $1 OR equipment_views.shared
+		ORDER BY own DESC, equipment_views.name, equipment_views.id
+		"#,
+		)
+		.bind(user.id)
+		.fetch_all(&pool)
+		.await?,
+	)
+}
+
+/// Saves the state of the table under a name, replacing the user's view of the same name
+#[allow(clippy::too_many_arguments)]
+#[server(prefix = "/api")]
+pub async fn save_equipment_view(
+	name: String,
+	field: String,
+	order: String,
+	filter: String,
+	items_per_page: u8,
+	archive: bool,
+	search: EquipmentSearch,
+	shared: bool,
+	is_default: bool,
+) -> Result<i32, ServerFnError> {
+	use crate::{auth::get_user, equipment::load_custom_fields};
+
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user()
+		.await?
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::Request(String::from("User not authenticated")))?;
+
+	let custom_fields = load_custom_fields(&pool).await?;
+	check_saved_view(&name, &field, &order, &filter, items_per_page, &search, &custom_fields)?;
+
+	let mut transaction = pool.begin().await?;
+
+	// A person has at most one default view
+	if is_default {
+		sqlx::query("UPDATE equipment_views SET is_default = FALSE WHERE person = $1")
+			.bind(user.id)
+			.execute(&mut *transaction)
+			.await?;
+	}
+
+	let id: i32 = sqlx::query_scalar(
+		r#"
+		INSERT INTO equipment_views
+			(person, name, field, sort_order, filter, items_per_page, archive, search, is_default, shared)
+		VALUES
+			($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+		ON CONFLICT (person, name) DO UPDATE SET
+			field = EXCLUDED.field,
+			sort_order = EXCLUDED.sort_order,
+			filter = EXCLUDED.filter,
+			items_per_page = EXCLUDED.items_per_page,
+			archive = EXCLUDED.archive,
+			search = EXCLUDED.search,
+			is_default = EXCLUDED.is_default,
+			shared = EXCLUDED.shared
+		RETURNING id
+		"#,
+	)
+	.bind(user.id)
+	.bind(name.trim())
+	.bind(field)
+	.bind(order)
+	.bind(filter)
+	.bind(items_per_page as i16)
+	.bind(archive)
+	.bind(search.to_query_string())
+	.bind(is_default)
+	.bind(shared)
+	.fetch_one(&mut *transaction)
+	.await?;
+
+	transaction.commit().await?;
+
+	Ok(id)
+}
+
+/// Marks one of the user's views as the one the list opens with, or stops it from being the default
+#[server(prefix = "/api")]
+pub async fn set_default_equipment_view(id: i32, is_default: bool) -> Result<(), ServerFnError> {
+	use crate::auth::get_user;
+
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user()
+		.await?
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::Request(String::from("User not authenticated")))?;
+
+	let mut transaction = pool.begin().await?;
+
+	if is_default {
+		sqlx::query("UPDATE equipment_views SET is_default = FALSE WHERE person = $1")
+			.bind(user.id)
+			.execute(&mut *transaction)
+			.await?;
+	}
+
+	let result = sqlx::query("UPDATE equipment_views SET is_default = $1 WHERE id = $2 AND person = $3")
+		.bind(is_default)
+		.bind(id)
+		.bind(user.id)
+		.execute(&mut *transaction)
+		.await?;
+	if result.rows_affected() == 0 {
+		return Err(ServerFnError::Request(String::from("Saved view not found")));
+	}
+
+	transaction.commit().await?;
+
+	Ok(())
+}
+
+/// Deletes one of the user's views, a shared view can only be deleted by the person who saved it
+#[server(prefix = "/api")]
+pub async fn delete_equipment_view(id: i32) -> Result<(), ServerFnError> {
+	use crate::auth::get_user;
+
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user()
+		.await?
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::Request(String::from("User not authenticated")))?;
+
+	let result = sqlx::query("DELETE FROM equipment_views WHERE id = $1 AND person = $2")
+		.bind(id)
+		.bind(user.id)
+		.execute(&pool)
+		.await?;
+	if result.rows_affected() == 0 {
+		return Err(ServerFnError::Request(String::from("Saved view not found")));
+	}
+
+	Ok(())
+}
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn check_saved_view_test() {
+		let search = EquipmentSearch::default();
+		assert!(check_saved_view("Mine", "name", "desc", "id,name,status", 25, &search, &[]).is_ok());
+		assert!(check_saved_view(" ", "name", "desc", "id", 25, &search, &[]).is_err());
+		assert!(check_saved_view("Mine", "name; DROP", "desc", "id", 25, &search, &[]).is_err());
+		assert!(check_saved_view("Mine", "name", "sideways", "id", 25, &search, &[]).is_err());
+		assert!(check_saved_view("Mine", "name", "asc", "id,password", 25, &search, &[]).is_err());
+		assert!(check_saved_view("Mine", "name", "asc", "id", 0, &search, &[]).is_err());
+
+		let search = EquipmentSearch {
+			cost_min: String::from("cheap"),
+			..Default::default()
+		};
+		assert!(check_saved_view("Mine", "name", "asc", "id", 25, &search, &[]).is_err());
+	}
+}
@@ -1,53 +1,87 @@
-use crate::equipment::{AvatarData, AvatarSQLData, EquipmentLogType};
+use crate::equipment::{AvatarData, AvatarSQLData, CustomFieldValue, EquipmentLogType, MaintenanceDue};
 
 use chrono::prelude::*;
 use serde::{Deserialize, Serialize};
 #[cfg(feature = "ssr")]
 use sqlx::Row;
 
+/// The icons an equipment type can be shown with
 #[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
-pub enum EquipmentType {
+pub enum EquipmentIcon {
 	#[default]
+	Equipment,
 	Flask,
 	Vessel,
 	IncubationCabinet,
 }
 
-impl EquipmentType {
+impl EquipmentIcon {
 	pub fn parse(input: String) -> Self {
-		match input.to_lowercase().as_str() {
-			"flask" => EquipmentType::Flask,
-			"vessel" => EquipmentType::Vessel,
-			"incubationcabinet" => EquipmentType::IncubationCabinet,
-			_ => Default::default(),
+		match input.as_str() {
+			"Flask" => EquipmentIcon::Flask,
+			"Vessel" => EquipmentIcon::Vessel,
+			"IncubationCabinet" => EquipmentIcon::IncubationCabinet,
+			_ => EquipmentIcon::Equipment,
 		}
 	}
+
+	pub fn get_fields() -> Vec<String> {
+		vec![
+			String::from("Equipment"),
+			String::from("Flask"),
+			String::from("Vessel"),
+			String::from("IncubationCabinet"),
+		]
+	}
 }
 
-impl std::fmt::Display for EquipmentType {
+impl std::fmt::Display for EquipmentIcon {
 	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
 		match self {
-			EquipmentType::Flask => write!(f, "Flask"),
-			EquipmentType::Vessel => write!(f, "Vessel"),
-			EquipmentType::IncubationCabinet => {
-				// Alt formatter for how the value looks in SQL
-				if f.alternate() {
-					write!(f, "IncubationCabinet")
-				} else {
-					write!(f, "Incubation Cabinet")
-				}
-			},
+			EquipmentIcon::Equipment => write!(f, "Equipment"),
+			EquipmentIcon::Flask => write!(f, "Flask"),
+			EquipmentIcon::Vessel => write!(f, "Vessel"),
+			EquipmentIcon::IncubationCabinet => write!(f, "Incubation Cabinet"),
 		}
 	}
 }
 
+/// A kind of equipment as configured in the `equipment_types` table
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct EquipmentType {
+	/// What equipment stores as its type, this never changes once the type exists
+	pub name: String,
+	pub label: String,
+	pub icon: EquipmentIcon,
+}
+
 impl EquipmentType {
-	pub fn get_fields() -> Vec<String> {
-		vec![
-			String::from("Flask"),
-			String::from("Vessel"),
-			String::from("IncubationCabinet"),
-		]
+	pub fn new(name: String, label: String, icon: String) -> Self {
+		EquipmentType {
+			name,
+			label,
+			icon: EquipmentIcon::parse(icon),
+		}
+	}
+
+	/// Finds a type by its name or its label, ignoring case and spaces
+	pub fn find<'a>(types: &'a [EquipmentType], input: &str) -> Option<&'a EquipmentType> {
+		let normalize = |value: &str| value.trim().to_lowercase().replace(' ', "");
+		let input = normalize(input);
+		types
+			.iter()
+			.find(|equipment_type| normalize(&equipment_type.name) == input || normalize(&equipment_type.label) == input)
+	}
+}
+
+impl std::fmt::Display for EquipmentType {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		// Alt formatter for how the value looks in SQL
+		if f.alternate() {
+			write!(f, "{}", self.name)
+		} else {
+			write!(f, "{}", self.label)
+		}
 	}
 }
 
@@ -64,14 +98,19 @@ pub enum EquipmentStatus {
 
 impl EquipmentStatus {
 	pub fn parse(input: String) -> Self {
-		match input.to_lowercase().as_str() {
-			"cleaned" => EquipmentStatus::Cleaned,
-			"prepared" => EquipmentStatus::Prepared,
-			"sterilized" => EquipmentStatus::Sterilized,
-			"inuse" => EquipmentStatus::InUse,
-			"dirty" => EquipmentStatus::Dirty,
-			"archived" => EquipmentStatus::Archived,
-			_ => Default::default(),
+		Self::try_parse(&input).unwrap_or_default()
+	}
+
+	/// Parses the SQL or display form of a status and rejects everything else
+	pub fn try_parse(input: &str) -> Option<Self> {
+		match input.trim().to_lowercase().replace(' ', "").as_str() {
+			"cleaned" => Some(EquipmentStatus::Cleaned),
+			"prepared" => Some(EquipmentStatus::Prepared),
+			"sterilized" => Some(EquipmentStatus::Sterilized),
+			"inuse" => Some(EquipmentStatus::InUse),
+			"dirty" => Some(EquipmentStatus::Dirty),
+			"archived" => Some(EquipmentStatus::Archived),
+			_ => None,
 		}
 	}
 }
@@ -100,24 +139,6 @@ impl EquipmentStatus {
 			String::from("Archived"),
 		]
 	}
-
-	pub fn get_next_status(current: Self, _etype: EquipmentType) -> Self {
-		match current {
-			EquipmentStatus::Cleaned => EquipmentStatus::Prepared,
-			EquipmentStatus::Prepared => {
-				// In the future we may add a Bio Reactor which won't be able to be sterilized
-				// if etype == EquipmentType::BioReactor {
-				// 	EquipmentStatus::InUse
-				// } else {
-				EquipmentStatus::Sterilized
-				// }
-			},
-			EquipmentStatus::Sterilized => EquipmentStatus::InUse,
-			EquipmentStatus::InUse => EquipmentStatus::Dirty,
-			EquipmentStatus::Dirty => EquipmentStatus::Cleaned,
-			EquipmentStatus::Archived => EquipmentStatus::Dirty,
-		}
-	}
 }
 
 impl From<EquipmentStatus> for EquipmentLogType {
@@ -135,6 +156,35 @@ impl From<EquipmentStatus> for EquipmentLogType {
 
 #[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
 pub struct Cost(i32);
+impl Cost {
+	/// Parses an amount like `1250.5` into cents without going through floats.
+	/// More than two decimals or amounts that don't fit the column are rejected instead of rounded or cut off.
+	pub fn parse_cents(value: &str) -> Result<i32, String> {
+		let invalid = || format!("Invalid cost \"{value}\"");
+		let trimmed = value.trim();
+		let (negative, amount) = match trimmed.strip_prefix('-') {
+			Some(amount) => (true, amount),
+			None => (false, trimmed),
+		};
+		let (whole, fraction) = amount.split_once('.').unwrap_or((amount, ""));
+		if (whole.is_empty() && fraction.is_empty())
+			|| fraction.len() > 2
+			|| !whole.chars().chain(fraction.chars()).all(|char| char.is_ascii_digit())
+		{
+			return Err(invalid());
+		}
+
+		let whole = if whole.is_empty() {
+			0
+		} else {
+			whole.parse::<i64>().map_err(|_| invalid())?
+		};
+		let fraction = format!("{fraction:0<2}").parse::<i64>().map_err(|_| invalid())?;
+		let cents = whole.checked_mul(100).and_then(|cents| cents.checked_add(fraction)).ok_or_else(invalid)?;
+
+		i32::try_from(if negative { -cents } else { cents }).map_err(|_| invalid())
+	}
+}
 impl std::fmt::Display for Cost {
 	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
 		write!(f, "{:.2}", self.0 as f64 / 100.0)
@@ -153,6 +203,8 @@ display_default_for_string_struct!(Notes);
 pub struct EquipmentSQLData {
 	pub id: i32,
 	pub equipment_type: String,
+	pub equipment_type_label: String,
+	pub equipment_type_icon: String,
 	pub person: AvatarSQLData,
 	pub qrcode: String,
 	pub create_date: DateTime<Utc>,
@@ -165,6 +217,8 @@ pub struct EquipmentSQLData {
 	pub warranty_expiration_date: Option<DateTime<Utc>>,
 	pub location: Option<String>,
 	pub notes: Option<String>,
+	pub deleted_at: Option<DateTime<Utc>>,
+	pub version: i32,
 }
 
 #[cfg(feature = "ssr")]
@@ -173,6 +227,8 @@ impl sqlx::FromRow<'_, sqlx::postgres::PgRow> for EquipmentSQLData {
 		Ok(EquipmentSQLData {
 			id: row.try_get("id")?,
 			equipment_type: row.try_get("equipment_type")?,
+			equipment_type_label: row.try_get("equipment_type_label")?,
+			equipment_type_icon: row.try_get("equipment_type_icon")?,
 			person: AvatarSQLData {
 				id: row.try_get("person_id")?,
 				status: row.try_get("person_status")?,
@@ -190,6 +246,8 @@ impl sqlx::FromRow<'_, sqlx::postgres::PgRow> for EquipmentSQLData {
 			warranty_expiration_date: row.try_get("warranty_expiration_date")?,
 			location: row.try_get("location")?,
 			notes: row.try_get("notes")?,
+			deleted_at: row.try_get("deleted_at")?,
+			version: row.try_get("version")?,
 		})
 	}
 }
@@ -210,6 +268,13 @@ pub struct EquipmentData {
 	pub warranty_expiration_date: Option<DateTime<Utc>>,
 	pub location: Option<String>,
 	pub notes: Option<Notes>,
+	pub deleted_at: Option<DateTime<Utc>>,
+	/// Goes up with every edit so a form can tell it was filled in from stale data
+	pub version: i32,
+	/// Every custom field of the equipment type, filled by `attach_custom_fields`
+	pub custom_fields: Vec<CustomFieldValue>,
+	/// The maintenance plans that apply to this equipment, only filled on the detail page
+	pub maintenance: Vec<MaintenanceDue>,
 }
 
 impl EquipmentData {
@@ -250,6 +315,10 @@ impl std::default::Default for EquipmentData {
 			warranty_expiration_date: None,
 			location: None,
 			notes: None,
+			deleted_at: None,
+			version: Default::default(),
+			custom_fields: Vec::new(),
+			maintenance: Vec::new(),
 		}
 	}
 }
@@ -258,7 +327,7 @@ impl From<EquipmentSQLData> for EquipmentData {
 	fn from(val: EquipmentSQLData) -> Self {
 		EquipmentData {
 			id: val.id,
-			equipment_type: EquipmentType::parse(val.equipment_type),
+			equipment_type: EquipmentType::new(val.equipment_type, val.equipment_type_label, val.equipment_type_icon),
 			person: val.person.into(),
 			qrcode: QRCode(val.qrcode),
 			create_date: val.create_date,
@@ -271,6 +340,10 @@ impl From<EquipmentSQLData> for EquipmentData {
 			warranty_expiration_date: val.warranty_expiration_date,
 			location: val.location,
 			notes: val.notes.map(Notes),
+			deleted_at: val.deleted_at,
+			version: val.version,
+			custom_fields: Vec::new(),
+			maintenance: Vec::new(),
 		}
 	}
 }
@@ -0,0 +1,208 @@
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "ssr")]
+use sqlx::Row;
+
+/// What a custom field holds, this decides how values are validated, shown and sorted
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum CustomFieldKind {
+	#[default]
+	Text,
+	Number,
+	Date,
+	Select,
+	Boolean,
+}
+
+impl CustomFieldKind {
+	pub fn parse(input: String) -> Self {
+		match input.as_str() {
+			"Number" => CustomFieldKind::Number,
+			"Date" => CustomFieldKind::Date,
+			"Select" => CustomFieldKind::Select,
+			"Boolean" => CustomFieldKind::Boolean,
+			_ => CustomFieldKind::Text,
+		}
+	}
+
+	pub fn get_fields() -> Vec<String> {
+		vec![
+			String::from("Text"),
+			String::from("Number"),
+			String::from("Date"),
+			String::from("Select"),
+			String::from("Boolean"),
+		]
+	}
+}
+
+impl std::fmt::Display for CustomFieldKind {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			CustomFieldKind::Text => write!(f, "Text"),
+			CustomFieldKind::Number => write!(f, "Number"),
+			CustomFieldKind::Date => write!(f, "Date"),
+			CustomFieldKind::Select => write!(f, "Select"),
+			CustomFieldKind::Boolean => write!(f, "Yes/No"),
+		}
+	}
+}
+
+/// A field an admin added to an equipment type
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct CustomField {
+	pub id: i32,
+	pub equipment_type: String,
+	pub label: String,
+	pub kind: CustomFieldKind,
+	pub unit: Option<String>,
+	pub options: Vec<String>,
+	pub min_value: Option<f64>,
+	pub max_value: Option<f64>,
+}
+
+#[cfg(feature = "ssr")]
+impl sqlx::FromRow<'_, sqlx::postgres::PgRow> for CustomField {
+	fn from_row(row: &sqlx::postgres::PgRow) -> Result<Self, sqlx::Error> {
+		Ok(CustomField {
+			id: row.try_get("id")?,
+			equipment_type: row.try_get("equipment_type")?,
+			label: row.try_get("label")?,
+			kind: CustomFieldKind::parse(row.try_get("kind")?),
+			unit: row.try_get("unit")?,
+			options: row.try_get("options")?,
+			min_value: row.try_get("min_value")?,
+			max_value: row.try_get("max_value")?,
+		})
+	}
+}
+
+impl CustomField {
+	/// The key of the field in the column filter, sorting and search of the equipment list
+	pub fn column(&self) -> String {
+		format!("custom_{}", self.id)
+	}
+
+	/// The stored value for the current `equipment` row as SQL of the right type, NULL when it isn't set
+	#[cfg(feature = "ssr")]
+	pub fn sql_value(&self) -> String {
+		let cast = match self.kind {
+			CustomFieldKind::Number => "::DOUBLE PRECISION",
+			CustomFieldKind::Date => "::DATE",
+			_ => "",
+		};
+		format!(
+			"(SELECT value{cast} FROM equipment_field_values WHERE equipment_field_values.equipment = equipment.id AND equipment_field_values.field = {})",
+			self.id
+		)
+	}
+
+	/// Turns what someone typed into the form that is stored, an empty input clears the value
+	pub fn validate(&self, input: &str) -> Result<Option<String>, String> {
+		let input = input.trim();
+		if input.is_empty() {
+			return Ok(None);
+		}
+
+		match self.kind {
+			CustomFieldKind::Text => Ok(Some(input.to_string())),
+			CustomFieldKind::Number => {
+				let number = input
+					.parse::<f64>()
+					.ok()
+					.filter(|number| number.is_finite())
+					.ok_or_else(|| format!("{} must be a number", self.label))?;
+				if let Some(min) = self.min_value.filter(|min| number < *min) {
+					return Err(format!("{} must be at least {min}", self.label));
+				}
+				if let Some(max) = self.max_value.filter(|max| number > *max) {
+					return Err(format!("{} must be at most {max}", self.label));
+				}
+				Ok(Some(number.to_string()))
+			},
+			CustomFieldKind::Date => chrono::NaiveDate::parse_from_str(input, "%Y-%m-%d")
+				.map(|date| Some(date.format("%Y-%m-%d").to_string()))
+				.map_err(|_| format!("{} must be a date like 2024-12-31", self.label)),
+			CustomFieldKind::Select => self
+				.options
+				.iter()
+				.find(|option| option.eq_ignore_ascii_case(input))
+				.map(|option| Some(option.clone()))
+				.ok_or_else(|| format!("{} must be one of {}", self.label, self.options.join(", "))),
+			CustomFieldKind::Boolean => match input.to_lowercase().as_str() {
+				"true" | "yes" | "1" => Ok(Some(String::from("true"))),
+				"false" | "no" | "0" => Ok(Some(String::from("false"))),
+				_ => Err(format!("{} must be yes or no", self.label)),
+			},
+		}
+	}
+
+	/// How a stored value reads on the page
+	pub fn format(&self, value: &str) -> String {
+		match (self.kind, &self.unit) {
+			(CustomFieldKind::Number, Some(unit)) => format!("{value} {unit}"),
+			(CustomFieldKind::Boolean, _) if value == "true" => String::from("Yes"),
+			(CustomFieldKind::Boolean, _) => String::from("No"),
+			_ => value.to_string(),
+		}
+	}
+}
+
+/// A custom field of an equipment's type with the value that equipment has for it
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct CustomFieldValue {
+	pub field: CustomField,
+	pub value: Option<String>,
+}
+
+impl std::fmt::Display for CustomFieldValue {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match &self.value {
+			Some(value) => write!(f, "{}", self.field.format(value)),
+			None => Ok(()),
+		}
+	}
+}
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+	use super::*;
+
+	fn field(kind: CustomFieldKind) -> CustomField {
+		CustomField {
+			id: 1,
+			equipment_type: String::from("Flask"),
+			label: String::from("Volume"),
+			kind,
+			unit: Some(String::from("ml")),
+			options: vec![String::from("Glass"), String::from("Plastic")],
+			min_value: Some(0.0),
+			max_value: Some(1000.0),
+		}
+	}
+
+	#[test]
+	fn custom_field_validate_normalizes_test() {
+		assert_eq!(field(CustomFieldKind::Number).validate(" 250.50 "), Ok(Some(String::from("250.5"))));
+		assert_eq!(field(CustomFieldKind::Number).validate(""), Ok(None));
+		assert_eq!(field(CustomFieldKind::Date).validate("2024-3-1"), Ok(Some(String::from("2024-03-01"))));
+		assert_eq!(field(CustomFieldKind::Select).validate("glass"), Ok(Some(String::from("Glass"))));
+		assert_eq!(field(CustomFieldKind::Boolean).validate("Yes"), Ok(Some(String::from("true"))));
+		assert_eq!(field(CustomFieldKind::Text).validate(" big one "), Ok(Some(String::from("big one"))));
+	}
+
+	#[test]
+	fn custom_field_validate_rejects_test() {
+		assert_eq!(field(CustomFieldKind::Number).validate("1001"), Err(String::from("Volume must be at most 1000")));
+		assert!(field(CustomFieldKind::Number).validate("-1").is_err());
+		assert!(field(CustomFieldKind::Number).validate("NaN").is_err());
+		assert!(field(CustomFieldKind::Date).validate("yesterday").is_err());
+		assert!(field(CustomFieldKind::Select).validate("Steel").is_err());
+		assert!(field(CustomFieldKind::Boolean).validate("maybe").is_err());
+	}
+
+	#[test]
+	fn custom_field_format_test() {
+		assert_eq!(field(CustomFieldKind::Number).format("250"), "250 ml");
+		assert_eq!(field(CustomFieldKind::Boolean).format("false"), "No");
+	}
+}
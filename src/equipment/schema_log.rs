@@ -15,6 +15,8 @@ pub enum EquipmentLogType {
 	Using,
 	Dirty,
 	Archive,
+	Create,
+	Maintenance,
 }
 
 impl EquipmentLogType {
@@ -27,6 +29,8 @@ impl EquipmentLogType {
 			"using" => EquipmentLogType::Using,
 			"dirty" => EquipmentLogType::Dirty,
 			"archive" => EquipmentLogType::Archive,
+			"create" => EquipmentLogType::Create,
+			"maintenance" => EquipmentLogType::Maintenance,
 			_ => Default::default(),
 		}
 	}
@@ -42,6 +46,8 @@ impl std::fmt::Display for EquipmentLogType {
 			EquipmentLogType::Using => write!(f, "Using"),
 			EquipmentLogType::Dirty => write!(f, "Dirty"),
 			EquipmentLogType::Archive => write!(f, "Archive"),
+			EquipmentLogType::Create => write!(f, "Create"),
+			EquipmentLogType::Maintenance => write!(f, "Maintenance"),
 		}
 	}
 }
@@ -56,6 +62,20 @@ impl EquipmentLogType {
 			String::from("Using"),
 			String::from("Dirty"),
 			String::from("Archive"),
+			String::from("Create"),
+			String::from("Maintenance"),
+		]
+	}
+
+	/// The log types status changes are recorded under
+	pub fn status_types() -> Vec<Self> {
+		vec![
+			EquipmentLogType::Cleaning,
+			EquipmentLogType::Preparation,
+			EquipmentLogType::Sterilization,
+			EquipmentLogType::Using,
+			EquipmentLogType::Dirty,
+			EquipmentLogType::Archive,
 		]
 	}
 }
@@ -71,6 +91,7 @@ pub struct EquipmentLogSQLData {
 	pub field: Option<String>,
 	pub old_value: Option<String>,
 	pub new_value: Option<String>,
+	pub reverts: Option<i32>,
 	pub media1: Option<String>,
 	pub media2: Option<String>,
 	pub media3: Option<String>,
@@ -101,6 +122,7 @@ impl sqlx::FromRow<'_, sqlx::postgres::PgRow> for EquipmentLogSQLData {
 			field: row.try_get("field")?,
 			old_value: row.try_get("old_value")?,
 			new_value: row.try_get("new_value")?,
+			reverts: row.try_get("reverts")?,
 			media1: row.try_get("media1")?,
 			media2: row.try_get("media2")?,
 			media3: row.try_get("media3")?,
@@ -126,6 +148,7 @@ pub struct EquipmentLogData {
 	pub field: Option<String>,
 	pub old_value: Option<String>,
 	pub new_value: Option<String>,
+	pub reverts: Option<i32>,
 	pub media1: Option<String>,
 	pub media2: Option<String>,
 	pub media3: Option<String>,
@@ -150,6 +173,7 @@ impl EquipmentLogData {
 			(String::from("field"), String::from("Field")),
 			(String::from("old_value"), String::from("Old Value")),
 			(String::from("new_value"), String::from("New Value")),
+			(String::from("reverts"), String::from("Reverts")),
 			(String::from("media1"), String::from("Media 1")),
 			(String::from("media2"), String::from("Media 2")),
 			(String::from("media3"), String::from("Media 3")),
@@ -176,6 +200,7 @@ impl std::default::Default for EquipmentLogData {
 			field: None,
 			old_value: None,
 			new_value: None,
+			reverts: None,
 			media1: None,
 			media2: None,
 			media3: None,
@@ -202,6 +227,7 @@ impl From<EquipmentLogSQLData> for EquipmentLogData {
 			field: val.field,
 			old_value: val.old_value,
 			new_value: val.new_value,
+			reverts: val.reverts,
 			media1: val.media1,
 			media2: val.media2,
 			media3: val.media3,
@@ -0,0 +1,177 @@
+use chrono::prelude::*;
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "ssr")]
+use sqlx::Row;
+
+/// The unit of the interval a maintenance plan repeats in
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum MaintenanceUnit {
+	Days,
+	Weeks,
+	#[default]
+	Months,
+	Years,
+}
+
+impl MaintenanceUnit {
+	pub fn parse(input: String) -> Self {
+		match input.to_lowercase().as_str() {
+			"days" => MaintenanceUnit::Days,
+			"weeks" => MaintenanceUnit::Weeks,
+			"years" => MaintenanceUnit::Years,
+			_ => MaintenanceUnit::Months,
+		}
+	}
+
+	pub fn get_fields() -> Vec<String> {
+		vec![
+			String::from("days"),
+			String::from("weeks"),
+			String::from("months"),
+			String::from("years"),
+		]
+	}
+}
+
+impl std::fmt::Display for MaintenanceUnit {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			MaintenanceUnit::Days => write!(f, "days"),
+			MaintenanceUnit::Weeks => write!(f, "weeks"),
+			MaintenanceUnit::Months => write!(f, "months"),
+			MaintenanceUnit::Years => write!(f, "years"),
+		}
+	}
+}
+
+/// Recurring work for one item or for every item of a type
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct MaintenancePlan {
+	pub id: i32,
+	pub equipment: Option<i32>,
+	pub equipment_type: Option<String>,
+	pub name: String,
+	pub interval_count: i32,
+	pub interval_unit: MaintenanceUnit,
+}
+
+#[cfg(feature = "ssr")]
+impl sqlx::FromRow<'_, sqlx::postgres::PgRow> for MaintenancePlan {
+	fn from_row(row: &sqlx::postgres::PgRow) -> Result<Self, sqlx::Error> {
+		Ok(MaintenancePlan {
+			id: row.try_get("id")?,
+			equipment: row.try_get("equipment")?,
+			equipment_type: row.try_get("equipment_type")?,
+			name: row.try_get("name")?,
+			interval_count: row.try_get("interval_count")?,
+			interval_unit: MaintenanceUnit::parse(row.try_get("interval_unit")?),
+		})
+	}
+}
+
+impl MaintenancePlan {
+	/// The interval as people say it, like "every 6 months" or "every year"
+	pub fn interval(&self) -> String {
+		if self.interval_count == 1 {
+			format!("every {}", self.interval_unit.to_string().trim_end_matches('s'))
+		} else {
+			format!("every {} {}", self.interval_count, self.interval_unit)
+		}
+	}
+}
+
+/// When a maintenance plan is next due for one piece of equipment
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MaintenanceDue {
+	pub plan: MaintenancePlan,
+	pub equipment_id: i32,
+	pub equipment_name: String,
+	pub last_done: Option<DateTime<Utc>>,
+	pub due_date: DateTime<Utc>,
+}
+
+#[cfg(feature = "ssr")]
+impl sqlx::FromRow<'_, sqlx::postgres::PgRow> for MaintenanceDue {
+	fn from_row(row: &sqlx::postgres::PgRow) -> Result<Self, sqlx::Error> {
+		Ok(MaintenanceDue {
+			plan: MaintenancePlan::from_row(row)?,
+			equipment_id: row.try_get("equipment_id")?,
+			equipment_name: row.try_get("equipment_name")?,
+			last_done: row.try_get("last_done")?,
+			due_date: row.try_get("due_date")?,
+		})
+	}
+}
+
+impl MaintenanceDue {
+	pub fn is_overdue(&self, now: DateTime<Utc>) -> bool {
+		self.due_date <= now
+	}
+}
+
+/// Every plan that applies to the equipment matched by `where_clause` with the date it is next due.
+/// Trashed and archived equipment is left out since nobody services it.
+#[cfg(feature = "ssr")]
+pub fn maintenance_due_query(where_clause: &str) -> String {
+	format!(
+		r#"
+		SELECT
+			equipment_maintenance.*,
+			equipment.id AS equipment_id,
+			equipment.name AS equipment_name,
+			last.done AS last_done,
+			COALESCE(last.done, GREATEST(equipment_maintenance.create_date, equipment.create_date))
+				+ (equipment_maintenance.interval_count || ' ' || equipment_maintenance.interval_unit)::INTERVAL AS due_date
+		FROM
+			equipment_maintenance
+			JOIN equipment ON equipment.id = equipment_maintenance.equipment
+				OR equipment.equipment_type = equipment_maintenance.equipment_type
+			LEFT JOIN LATERAL (
+				SELECT MAX(equipment_log.create_date) AS done
+				FROM equipment_log
+				WHERE equipment_log.maintenance = equipment_maintenance.id AND equipment_log.equipment = equipment.id
+			) last ON TRUE
+		WHERE
+			equipment.deleted_at IS NULL
+			AND equipment.status IS DISTINCT FROM 'Archived'
+			{where_clause}
+		ORDER BY due_date, equipment.id
+		"#
+	)
+}
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn maintenance_plan_interval_test() {
+		let mut plan = MaintenancePlan {
+			interval_count: 6,
+			interval_unit: MaintenanceUnit::Months,
+			..Default::default()
+		};
+		assert_eq!(plan.interval(), "every 6 months");
+
+		plan.interval_count = 1;
+		plan.interval_unit = MaintenanceUnit::Years;
+		assert_eq!(plan.interval(), "every year");
+	}
+
+	#[test]
+	fn maintenance_due_query_person_scope_test() {
+		use crate::permission::{Dimension, Permission, Scope};
+
+		// Both tables have a person column so the owner check has to name the equipment table
+		let filter = Permission::Read(vec![Scope::Person(3)]).get_query_filter(Dimension::Equipment, "equipment.id");
+		let query = maintenance_due_query(&filter.and_clause(1));
+		assert!(query.contains(" AND (equipment.id IN (SELECT id FROM equipment WHERE person = ANY($1)))"));
+
+		let filter = Permission::Read(vec![Scope::Person(3)])
+			.get_query_filter(Dimension::Equipment, "equipment_maintenance.equipment");
+		assert_eq!(
+			filter.and_clause(1),
+			" AND (equipment_maintenance.equipment IN (SELECT id FROM equipment WHERE person = ANY($1)))"
+		);
+	}
+}
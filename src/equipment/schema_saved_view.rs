@@ -0,0 +1,51 @@
+use crate::equipment::EquipmentSearch;
+
+use serde::{Deserialize, Serialize};
+
+/// A named set of columns, sorting, page size, archive toggle and search of the equipment table
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ssr", derive(sqlx::FromRow))]
+pub struct SavedViewData {
+	pub id: i32,
+	/// Whether the view belongs to the user who loaded it
+	pub own: bool,
+	pub person_name: String,
+	pub name: String,
+	pub field: String,
+	pub sort_order: String,
+	pub filter: String,
+	pub items_per_page: i16,
+	pub archive: bool,
+	/// The search and column filters as percent-encoded URL query pairs
+	pub search: String,
+	pub is_default: bool,
+	pub shared: bool,
+}
+
+impl SavedViewData {
+	/// The query string of the equipment list this view opens
+	pub fn query(&self) -> String {
+		list_query(&self.field, &self.sort_order, &self.filter, self.items_per_page as u8, self.archive, &self.search)
+	}
+}
+
+/// The query string of the equipment list from the state of the table
+pub fn table_query(
+	field: &str,
+	order: &str,
+	filter: &[String],
+	items_per_page: u8,
+	archive: bool,
+	search: &EquipmentSearch,
+) -> String {
+	list_query(field, order, &filter.join(","), items_per_page, archive, &search.to_query_string())
+}
+
+fn list_query(field: &str, order: &str, filter: &str, items_per_page: u8, archive: bool, search: &str) -> String {
+	let query = format!("field={field}&order={order}&filter={filter}&items_per_page={items_per_page}&archive={archive}");
+	if search.is_empty() {
+		query
+	} else {
+		format!("{query}&{search}")
+	}
+}
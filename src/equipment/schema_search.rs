@@ -0,0 +1,413 @@
+use leptos_router::ParamsMap;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// The search and column filters of the equipment list as they appear in the URL
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EquipmentSearch {
+	#[serde(rename = "q")]
+	pub query: String,
+	/// Comma separated statuses
+	pub status: String,
+	/// Comma separated equipment types
+	pub equipment_type: String,
+	pub purchase_from: String,
+	pub purchase_to: String,
+	pub warranty_from: String,
+	pub warranty_to: String,
+	pub cost_min: String,
+	pub cost_max: String,
+	/// Filters on custom fields keyed by their column, number and date fields take `_from` and `_to` ranges
+	#[serde(flatten)]
+	pub custom: BTreeMap<String, String>,
+}
+
+impl EquipmentSearch {
+	const KEYS: [&'static str; 9] = [
+		"q",
+		"status",
+		"equipment_type",
+		"purchase_from",
+		"purchase_to",
+		"warranty_from",
+		"warranty_to",
+		"cost_min",
+		"cost_max",
+	];
+
+	pub fn from_query(query: &ParamsMap) -> Self {
+		let get = |key: &str| query.get(key).cloned().unwrap_or_default();
+		EquipmentSearch {
+			query: get("q"),
+			status: get("status"),
+			equipment_type: get("equipment_type"),
+			purchase_from: get("purchase_from"),
+			purchase_to: get("purchase_to"),
+			warranty_from: get("warranty_from"),
+			warranty_to: get("warranty_to"),
+			cost_min: get("cost_min"),
+			cost_max: get("cost_max"),
+			custom: query
+				.0
+				.iter()
+				.filter(|(key, value)| key.starts_with("custom_") && !value.is_empty())
+				.map(|(key, value)| (key.clone(), value.clone()))
+				.collect(),
+		}
+	}
+
+	/// The filters that are set as URL query pairs
+	pub fn to_query(&self) -> Vec<(String, String)> {
+		Self::KEYS
+			.iter()
+			.zip([
+				&self.query,
+				&self.status,
+				&self.equipment_type,
+				&self.purchase_from,
+				&self.purchase_to,
+				&self.warranty_from,
+				&self.warranty_to,
+				&self.cost_min,
+				&self.cost_max,
+			])
+			.map(|(key, value)| (key.to_string(), value))
+			.chain(self.custom.iter().filter(|(key, _)| key.starts_with("custom_")).map(|(key, value)| (key.clone(), value)))
+			.filter(|(_, value)| !value.is_empty())
+			.map(|(key, value)| (key, value.clone()))
+			.collect()
+	}
+
+	/// The filters that are set as percent-encoded URL query pairs
+	pub fn to_query_string(&self) -> String {
+		use percent_encoding::{NON_ALPHANUMERIC, utf8_percent_encode};
+
+		self
+			.to_query()
+			.into_iter()
+			.map(|(key, value)| format!("{key}={}", utf8_percent_encode(&value, NON_ALPHANUMERIC)))
+			.collect::<Vec<_>>()
+			.join("&")
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.to_query().is_empty()
+	}
+}
+
+/// The text columns the free-text search looks through.
+/// This has to match the `equipment_search` index in the database for the index to be used.
+#[cfg(feature = "ssr")]
+const SEARCH_DOCUMENT: &str = "(coalesce(equipment.name, '') || ' ' || coalesce(equipment.location, '') || ' ' || coalesce(equipment.manufacturer, '') || ' ' || coalesce(equipment.vendor, '') || ' ' || coalesce(equipment.notes, ''))";
+
+/// How many words of a free-text search are used
+#[cfg(feature = "ssr")]
+const SEARCH_WORDS: usize = 8;
+
+/// A `%word%` pattern for ILIKE that matches `%` and `_` literally
+#[cfg(feature = "ssr")]
+fn like_pattern(word: &str) -> String {
+	format!("%{}%", word.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_"))
+}
+
+#[cfg(feature = "ssr")]
+#[derive(Clone, Debug, PartialEq)]
+pub enum SearchValue {
+	Text(String),
+	TextList(Vec<String>),
+	Date(chrono::NaiveDate),
+	Cents(i32),
+	Number(f64),
+}
+
+/// An `EquipmentSearch` turned into SQL conditions with the values to bind for them
+#[cfg(feature = "ssr")]
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct SearchFilter {
+	conditions: Vec<String>,
+	values: Vec<SearchValue>,
+}
+
+#[cfg(feature = "ssr")]
+impl SearchFilter {
+	pub fn parse(
+		search: &EquipmentSearch,
+		custom_fields: &[crate::equipment::CustomField],
+	) -> Result<Self, leptos::ServerFnError> {
+		use crate::equipment::{CustomFieldKind, EquipmentStatus};
+
+		use chrono::{Days, NaiveDate};
+		use leptos::ServerFnError;
+
+		let mut filter = SearchFilter::default();
+
+		let words = search.query.split_whitespace().take(SEARCH_WORDS).map(like_pattern);
+		for word in words {
+			filter.push(format!("{SEARCH_DOCUMENT} ILIKE {{}}"), SearchValue::Text(word));
+		}
+
+		let list = |value: &str| value.split(',').filter(|item| !item.is_empty()).map(String::from).collect::<Vec<_>>();
+
+		let statuses = list(&search.status)
+			.into_iter()
+			.map(|status| {
+				EquipmentStatus::try_parse(&status)
+					.map(|status| format!("{status:?}"))
+					.ok_or_else(|| ServerFnError::Request(format!("Unknown status \"{status}\"")))
+			})
+			.collect::<Result<Vec<_>, ServerFnError>>()?;
+		if !statuses.is_empty() {
+			filter.push(String::from("equipment.status = ANY({})"), SearchValue::TextList(statuses));
+		}
+
+		// Types live in the database so one that doesn't exist simply matches nothing
+		let types = list(&search.equipment_type);
+		if !types.is_empty() {
+			filter.push(String::from("equipment.equipment_type = ANY({})"), SearchValue::TextList(types));
+		}
+
+		let date = |value: &str, label: &str| -> Result<Option<NaiveDate>, ServerFnError> {
+			if value.is_empty() {
+				return Ok(None);
+			}
+			NaiveDate::parse_from_str(value, "%Y-%m-%d")
+				.map(Some)
+				.map_err(|_| ServerFnError::Request(format!("Invalid {label} \"{value}\"")))
+		};
+		for (column, from, to, label) in [
+			("purchase_date", &search.purchase_from, &search.purchase_to, "purchase date"),
+			("warranty_expiration_date", &search.warranty_from, &search.warranty_to, "warranty date"),
+		] {
+			if let Some(from) = date(from, label)? {
+				filter.push(format!("equipment.{column} >= {{}}"), SearchValue::Date(from));
+			}
+			// The end of a range includes the whole day
+			if let Some(to) = date(to, label)? {
+				filter.push(format!("equipment.{column} < {{}}"), SearchValue::Date(to + Days::new(1)));
+			}
+		}
+
+		let cents = |value: &str| -> Result<Option<i32>, ServerFnError> {
+			if value.is_empty() {
+				return Ok(None);
+			}
+			crate::equipment::Cost::parse_cents(value).map(Some).map_err(ServerFnError::Request)
+		};
+		if let Some(min) = cents(&search.cost_min)? {
+			filter.push(String::from("equipment.cost_in_cent >= {}"), SearchValue::Cents(min));
+		}
+		if let Some(max) = cents(&search.cost_max)? {
+			filter.push(String::from("equipment.cost_in_cent <= {}"), SearchValue::Cents(max));
+		}
+
+		for (key, value) in search.custom.iter().filter(|(key, value)| key.starts_with("custom_") && !value.is_empty()) {
+			let (column, bound) = match (key.strip_suffix("_from"), key.strip_suffix("_to")) {
+				(Some(column), _) => (column, Some(">=")),
+				(_, Some(column)) => (column, Some("<=")),
+				_ => (key.as_str(), None),
+			};
+			let custom_field = custom_fields
+				.iter()
+				.find(|custom_field| custom_field.column() == column)
+				.ok_or_else::<ServerFnError, _>(|| ServerFnError::Request(format!("Unknown custom field \"{key}\"")))?;
+			let sql_value = custom_field.sql_value();
+
+			match (custom_field.kind, bound) {
+				(CustomFieldKind::Number, Some(bound)) => {
+					let number = value.trim().parse::<f64>().map_err::<ServerFnError, _>(|_| {
+						ServerFnError::Request(format!("Invalid {} \"{value}\"", custom_field.label))
+					})?;
+					filter.push(format!("{sql_value} {bound} {{}}"), SearchValue::Number(number));
+				},
+				(CustomFieldKind::Date, Some(bound)) => {
+					if let Some(date) = date(value, &custom_field.label)? {
+						filter.push(format!("{sql_value} {bound} {{}}"), SearchValue::Date(date));
+					}
+				},
+				(CustomFieldKind::Text, None) => {
+					filter.push(format!("{sql_value} ILIKE {{}}"), SearchValue::Text(like_pattern(value)));
+				},
+				(CustomFieldKind::Select | CustomFieldKind::Boolean, None) => {
+					let values = list(value)
+						.iter()
+						.filter_map(|value| custom_field.validate(value).transpose())
+						.collect::<Result<Vec<_>, String>>()
+						.map_err::<ServerFnError, _>(ServerFnError::Request)?;
+					filter.push(format!("{sql_value} = ANY({{}})"), SearchValue::TextList(values));
+				},
+				_ => return Err(ServerFnError::Request(format!("Unknown custom field \"{key}\""))),
+			}
+		}
+
+		Ok(filter)
+	}
+
+	fn push(&mut self, condition: String, value: SearchValue) {
+		self.conditions.push(condition);
+		self.values.push(value);
+	}
+
+	/// How many parameters the filter binds
+	pub fn len(&self) -> usize {
+		self.values.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.values.is_empty()
+	}
+
+	/// The filter as ` AND ...` clauses with their parameters numbered from `first_param`
+	pub fn and_clause(&self, first_param: usize) -> String {
+		self
+			.conditions
+			.iter()
+			.enumerate()
+			.map(|(index, condition)| format!(" AND {}", condition.replace("{}", &format!("${}", first_param + index))))
+			.collect()
+	}
+
+	pub fn values(&self) -> &[SearchValue] {
+		&self.values
+	}
+}
+
+/// Binds the values of a `SearchFilter` in the order its clauses were numbered
+#[cfg(feature = "ssr")]
+pub trait BindSearch {
+	fn bind_search(self, filter: &SearchFilter) -> Self;
+}
+
+#[cfg(feature = "ssr")]
+macro_rules! bind_search_values {
+	($query:expr, $filter:expr) => {
+		$filter.values().iter().cloned().fold($query, |query, value| match value {
+			SearchValue::Text(text) => query.bind(text),
+			SearchValue::TextList(list) => query.bind(list),
+			SearchValue::Date(date) => query.bind(date),
+			SearchValue::Cents(cents) => query.bind(cents),
+			SearchValue::Number(number) => query.bind(number),
+		})
+	};
+}
+
+#[cfg(feature = "ssr")]
+impl BindSearch for sqlx::query::Query<'_, sqlx::Postgres, sqlx::postgres::PgArguments> {
+	fn bind_search(self, filter: &SearchFilter) -> Self {
+		bind_search_values!(self, filter)
+	}
+}
+
+#[cfg(feature = "ssr")]
+impl<O> BindSearch for sqlx::query::QueryAs<'_, sqlx::Postgres, O, sqlx::postgres::PgArguments> {
+	fn bind_search(self, filter: &SearchFilter) -> Self {
+		bind_search_values!(self, filter)
+	}
+}
+
+#[cfg(feature = "ssr")]
+impl<O> BindSearch for sqlx::query::QueryScalar<'_, sqlx::Postgres, O, sqlx::postgres::PgArguments> {
+	fn bind_search(self, filter: &SearchFilter) -> Self {
+		bind_search_values!(self, filter)
+	}
+}
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn search_filter_numbers_parameters_test() {
+		let search = EquipmentSearch {
+			query: String::from("big 50%"),
+			status: String::from("InUse,dirty"),
+			cost_max: String::from("12.5"),
+			..Default::default()
+		};
+		let filter = SearchFilter::parse(&search, &[]).unwrap();
+
+		assert_eq!(filter.len(), 4);
+		assert_eq!(
+			filter.and_clause(3),
+			format!(
+				" AND {SEARCH_DOCUMENT} ILIKE $3 AND {SEARCH_DOCUMENT} ILIKE $4 AND equipment.status = ANY($5) AND equipment.cost_in_cent <= $6"
+			)
+		);
+		assert_eq!(
+			filter.values(),
+			&[
+				SearchValue::Text(String::from("%big%")),
+				SearchValue::Text(String::from("%50\\%%")),
+				SearchValue::TextList(vec![String::from("InUse"), String::from("Dirty")]),
+				SearchValue::Cents(1250),
+			]
+		);
+
+		let search = EquipmentSearch {
+			cost_min: String::from("30000000"),
+			..Default::default()
+		};
+		assert!(SearchFilter::parse(&search, &[]).is_err());
+	}
+
+	#[test]
+	fn search_filter_custom_fields_test() {
+		use crate::equipment::{CustomField, CustomFieldKind};
+
+		let custom_fields = vec![
+			CustomField {
+				id: 1,
+				label: String::from("Volume"),
+				kind: CustomFieldKind::Number,
+				..Default::default()
+			},
+			CustomField {
+				id: 2,
+				label: String::from("Autoclavable"),
+				kind: CustomFieldKind::Boolean,
+				..Default::default()
+			},
+		];
+		let search = EquipmentSearch {
+			custom: BTreeMap::from([
+				(String::from("custom_1_from"), String::from("100")),
+				(String::from("custom_2"), String::from("yes")),
+			]),
+			..Default::default()
+		};
+		let filter = SearchFilter::parse(&search, &custom_fields).unwrap();
+
+		assert_eq!(
+			filter.and_clause(1),
+			format!(" AND {} >= $1 AND {} = ANY($2)", custom_fields[0].sql_value(), custom_fields[1].sql_value())
+		);
+		assert_eq!(
+			filter.values(),
+			&[
+				SearchValue::Number(100.0),
+				SearchValue::TextList(vec![String::from("true")])
+			]
+		);
+
+		let search = EquipmentSearch {
+			custom: BTreeMap::from([(String::from("custom_3"), String::from("big"))]),
+			..Default::default()
+		};
+		assert!(SearchFilter::parse(&search, &custom_fields).is_err());
+	}
+
+	#[test]
+	fn search_filter_rejects_unknown_values_test() {
+		let search = EquipmentSearch {
+			status: String::from("Bucket"),
+			..Default::default()
+		};
+		assert!(SearchFilter::parse(&search, &[]).is_err());
+
+		let search = EquipmentSearch {
+			purchase_to: String::from("yesterday"),
+			..Default::default()
+		};
+		assert!(SearchFilter::parse(&search, &[]).is_err());
+	}
+}
@@ -0,0 +1,46 @@
+use crate::equipment::EquipmentType;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ssr", derive(sqlx::FromRow))]
+pub struct EquipmentTemplateSQLData {
+	pub id: i32,
+	pub name: String,
+	pub equipment_type: String,
+	pub equipment_type_label: String,
+	pub equipment_type_icon: String,
+	pub manufacturer: Option<String>,
+	pub vendor: Option<String>,
+	pub cost_in_cent: Option<i32>,
+	pub warranty_days: Option<i32>,
+	pub location: Option<String>,
+}
+
+/// The shared fields a batch of identical equipment is created from
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct EquipmentTemplateData {
+	pub id: i32,
+	pub name: String,
+	pub equipment_type: EquipmentType,
+	pub manufacturer: Option<String>,
+	pub vendor: Option<String>,
+	pub cost_in_cent: Option<i32>,
+	pub warranty_days: Option<i32>,
+	pub location: Option<String>,
+}
+
+impl From<EquipmentTemplateSQLData> for EquipmentTemplateData {
+	fn from(val: EquipmentTemplateSQLData) -> Self {
+		EquipmentTemplateData {
+			id: val.id,
+			name: val.name,
+			equipment_type: EquipmentType::new(val.equipment_type, val.equipment_type_label, val.equipment_type_icon),
+			manufacturer: val.manufacturer,
+			vendor: val.vendor,
+			cost_in_cent: val.cost_in_cent,
+			warranty_days: val.warranty_days,
+			location: val.location,
+		}
+	}
+}
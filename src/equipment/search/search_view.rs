@@ -0,0 +1,207 @@
+use crate::{
+	components::{
+		button::Button,
+		input::Input,
+		select::{MultiSelect, MultiSelectOption},
+	},
+	equipment::{CustomField, CustomFieldKind, EquipmentSearch, EquipmentStatus, get_custom_fields, get_equipment_types},
+};
+
+use leptos::*;
+use leptos_router::*;
+
+stylance::import_style!(css, "search.module.css");
+
+/// The search box and column filters above the equipment table
+#[component]
+pub fn EquipmentSearchForm(
+	query_field: RwSignal<String>,
+	query_order: RwSignal<String>,
+	query_filter: RwSignal<Vec<String>>,
+	query_ipp: RwSignal<u8>,
+	query_archive: RwSignal<bool>,
+	query_search: RwSignal<EquipmentSearch>,
+) -> impl IntoView {
+	let search = query_search.get_untracked();
+	let custom_search = store_value(search.custom.clone());
+
+	let status = create_rw_signal(split_list(&search.status));
+	let equipment_type = create_rw_signal(split_list(&search.equipment_type));
+
+	let status_options = EquipmentStatus::get_fields()
+		.into_iter()
+		.map(|status| MultiSelectOption::new(EquipmentStatus::parse(status.clone()).to_string(), status))
+		.collect::<Vec<_>>();
+
+	let types = create_resource(|| (), move |_| get_equipment_types());
+	let type_options = create_rw_signal(Vec::new());
+	create_effect(move |_| {
+		if let Some(Ok(types)) = types.get() {
+			type_options.set(
+				types
+					.into_iter()
+					.map(|equipment_type| MultiSelectOption::new(equipment_type.label, equipment_type.name))
+					.collect(),
+			);
+		}
+	});
+
+	let custom_fields = create_resource(|| (), move |_| get_custom_fields());
+
+	let clear_href = move || {
+		format!(
+			"/equipment?field={}&order={}&filter={}&items_per_page={}&archive={}",
+			query_field.get(),
+			query_order.get(),
+			query_filter.get().join(","),
+			query_ipp.get(),
+			query_archive.get(),
+		)
+	};
+
+	view! {
+		<form action="/equipment" method="get" class=css::search>
+			<input type="hidden" name="field" value=query_field.get() />
+			<input type="hidden" name="order" value=query_order.get() />
+			<input type="hidden" name="filter" value=query_filter.get().join(",") />
+			<input type="hidden" name="items_per_page" value=query_ipp.get() />
+			<input type="hidden" name="archive" value=query_archive.get().to_string() />
+			<input type="hidden" name="status" value=move || status.get().join(",") />
+			<input type="hidden" name="equipment_type" value=move || equipment_type.get().join(",") />
+
+			<label class=css::query>
+				<span>Search</span>
+				<Input name="q" placeholder="Name, location, manufacturer, vendor or notes" value=create_rw_signal(search.query) />
+			</label>
+			<label>
+				<span>Status</span>
+				<MultiSelect value=status options=create_rw_signal(status_options) />
+			</label>
+			<label>
+				<span>Type</span>
+				<MultiSelect value=equipment_type options=type_options />
+			</label>
+			<label>
+				<span>Purchased from</span>
+				<Input name="purchase_from" kind="date" value=create_rw_signal(search.purchase_from) />
+			</label>
+			<label>
+				<span>Purchased to</span>
+				<Input name="purchase_to" kind="date" value=create_rw_signal(search.purchase_to) />
+			</label>
+			<label>
+				<span>Warranty from</span>
+				<Input name="warranty_from" kind="date" value=create_rw_signal(search.warranty_from) />
+			</label>
+			<label>
+				<span>Warranty to</span>
+				<Input name="warranty_to" kind="date" value=create_rw_signal(search.warranty_to) />
+			</label>
+			<label>
+				<span>Cost from</span>
+				<Input name="cost_min" placeholder="0.00" value=create_rw_signal(search.cost_min) />
+			</label>
+			<label>
+				<span>Cost to</span>
+				<Input name="cost_max" placeholder="0.00" value=create_rw_signal(search.cost_max) />
+			</label>
+
+			<Transition>
+				{move || {
+					custom_fields
+						.get()
+						.and_then(Result::ok)
+						.map(|custom_fields| {
+							custom_fields
+								.into_iter()
+								.map(|custom_field| view! { <CustomFieldSearch custom_field custom_search=custom_search.get_value() /> })
+								.collect_view()
+						})
+				}}
+			</Transition>
+
+			<div class=css::btns>
+				<Show when=move || !query_search.get().is_empty()>
+					<A href=clear_href>Clear</A>
+				</Show>
+				<Button kind="submit">Search</Button>
+			</div>
+		</form>
+	}
+}
+
+/// The filter inputs of one custom field, number and date fields get a range
+#[component]
+fn CustomFieldSearch(
+	custom_field: CustomField,
+	custom_search: std::collections::BTreeMap<String, String>,
+) -> impl IntoView {
+	let column = custom_field.column();
+	let get = |key: &str| custom_search.get(key).cloned().unwrap_or_default();
+
+	match custom_field.kind {
+		CustomFieldKind::Text => view! {
+			<label>
+				<span>{custom_field.label}</span>
+				<Input name=column.clone() value=create_rw_signal(get(&column)) />
+			</label>
+		}
+		.into_view(),
+		CustomFieldKind::Number | CustomFieldKind::Date => {
+			let kind = if custom_field.kind == CustomFieldKind::Date {
+				"date"
+			} else {
+				"text"
+			};
+			let from = format!("{column}_from");
+			let to = format!("{column}_to");
+			view! {
+				<label>
+					<span>{format!("{} from", custom_field.label)}</span>
+					<Input name=from.clone() kind value=create_rw_signal(get(&from)) />
+				</label>
+				<label>
+					<span>{format!("{} to", custom_field.label)}</span>
+					<Input name=to.clone() kind value=create_rw_signal(get(&to)) />
+				</label>
+			}
+			.into_view()
+		},
+		CustomFieldKind::Select | CustomFieldKind::Boolean => {
+			let options = if custom_field.kind == CustomFieldKind::Boolean {
+				vec![
+					MultiSelectOption::new("Yes", String::from("true")),
+					MultiSelectOption::new("No", String::from("false")),
+				]
+			} else {
+				custom_field.options.iter().map(|option| MultiSelectOption::new(option.clone(), option.clone())).collect()
+			};
+			let value = create_rw_signal(split_list(&get(&column)));
+			view! {
+				<label>
+					<span>{custom_field.label}</span>
+					<MultiSelect value options=create_rw_signal(options) />
+					<input type="hidden" name=column value=move || value.get().join(",") />
+				</label>
+			}
+			.into_view()
+		},
+	}
+}
+
+fn split_list(value: &str) -> Vec<String> {
+	value.split(',').filter(|item| !item.is_empty()).map(String::from).collect()
+}
+
+/// Carries the current search along with forms that change other parts of the list
+#[component]
+pub fn SearchHiddenFields(query_search: RwSignal<EquipmentSearch>) -> impl IntoView {
+	move || {
+		query_search
+			.get()
+			.to_query()
+			.into_iter()
+			.map(|(key, value)| view! { <input type="hidden" name=key value=value /> })
+			.collect_view()
+	}
+}
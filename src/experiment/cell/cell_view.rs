@@ -0,0 +1,23 @@
+use crate::{components::multiline::MultiLine, equipment::EquipmentCellView, experiment::Protocol};
+
+use leptos::*;
+
+impl EquipmentCellView for Option<Protocol> {
+	fn view(self, table_view: bool) -> impl IntoView {
+		match self {
+			Some(value) => {
+				let mut text = value.to_string();
+				if table_view && text.chars().count() > 100 {
+					text = text.chars().take(100).chain(std::iter::once('…')).collect::<String>()
+				}
+				view! {
+					<div>
+						<MultiLine text />
+					</div>
+				}
+				.into_view()
+			},
+			None => view! { <div /> }.into_view(),
+		}
+	}
+}
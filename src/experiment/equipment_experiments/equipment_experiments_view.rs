@@ -0,0 +1,126 @@
+use crate::{equipment::EquipmentCell, error_template::ErrorTemplate, experiment::ExperimentSummaryData};
+
+use leptos::*;
+use leptos_router::*;
+
+stylance::import_style!(css, "equipment_experiments.module.css");
+
+/// Lists the experiments a piece of equipment was used in
+#[component]
+pub fn EquipmentExperiments(id: i32) -> impl IntoView {
+	let experiments_data = create_resource(|| (), move |_| get_experiments_for_equipment(id.to_string()));
+
+	view! {
+		<Suspense fallback=move || view! { <p>Loading experiments...</p> }>
+			<ErrorBoundary fallback=|errors| {
+				view! { <ErrorTemplate errors=errors /> }
+			}>
+				{move || {
+					if experiments_data.get().is_some() {
+						match experiments_data.get().unwrap() {
+							Err(error) => {
+								view! { <pre class="error">Experiments Server Error: {error.to_string()}</pre> }
+									.into_view()
+							}
+							Ok(experiments) if experiments.is_empty() => {
+								view! { <span class=css::empty>"Not used in any experiment yet"</span> }.into_view()
+							}
+							Ok(experiments) => {
+								view! {
+									<ul class=css::experiments>
+										{experiments
+											.into_iter()
+											.map(|experiment| {
+												view! {
+													<li>
+														<A href=format!(
+															"/experiment/{}",
+															experiment.id,
+														)>{experiment.name}</A>
+														<EquipmentCell cell=experiment.start_date />
+														" - "
+														{match experiment.end_date {
+															Some(end_date) => {
+																view! { <EquipmentCell cell=end_date /> }.into_view()
+															}
+															None => view! { <span>"ongoing"</span> }.into_view(),
+														}}
+													</li>
+												}
+											})
+											.collect_view()}
+									</ul>
+								}
+									.into_view()
+							}
+						}
+					} else {
+						view! {}.into_view()
+					}
+				}}
+			</ErrorBoundary>
+		</Suspense>
+	}
+}
+
+#[server(prefix = "/api")]
+pub async fn get_experiments_for_equipment(id: String) -> Result<Vec<ExperimentSummaryData>, ServerFnError> {
+	use crate::{
+		auth::get_user,
+		experiment::ExperimentSummarySQLData,
+		permission::{BindFilter, Dimension, Permissions, Subject},
+	};
+
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	let id = match id.parse::<i32>() {
+		Ok(value) => value,
+		Err(_) => return Err(ServerFnError::Request(String::from("Invalid ID"))),
+	};
+
+	let auth_filter = match user {
+		Some(user) => {
+			let Permissions::All {
+				read: perm,
+				write: _,
+				create: _,
+			} = user.permission_equipment;
+			let subject = Subject::load(Dimension::Equipment, id, &pool).await?;
+			if !perm.has_permission_on("read", &subject) {
+				return Err(ServerFnError::Request(String::from("User not authenticated")));
+			}
+
+			let Permissions::All {
+				read: perm,
+				write: _,
+				create: _,
+			} = user.permission_experiment;
+			perm.get_query_filter(Dimension::Experiment, "experiment.id")
+		},
+		None => return Err(ServerFnError::Request(String::from("User not authenticated"))),
+	};
+
+	let auth_query = auth_filter.and_clause(2);
+	let experiments_sql_data = sqlx::query_as::<_, ExperimentSummarySQLData>(&format!(
+		r#"
+		SELECT experiment.id, experiment.name, experiment.start_date, experiment.end_date
+		FROM
+			experiment_equipment
+			JOIN experiment ON experiment_equipment.experiment = experiment.id
+		WHERE
+			experiment_equipment.equipment = $1
+			AND experiment.id IN (SELECT experiment.id FROM experiment WHERE experiment.id IS NOT NULL {auth_query})
+		ORDER BY experiment.start_date DESC"#
+	))
+	.bind(id)
+	.bind_filter(&auth_filter)
+	.fetch_all(&pool)
+	.await
+	.map_err::<ServerFnError, _>(|error| ServerFnError::ServerError(error.to_string()))?;
+
+	Ok(experiments_sql_data.into_iter().map(Into::into).collect())
+}
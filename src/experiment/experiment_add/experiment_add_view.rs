@@ -0,0 +1,227 @@
+use crate::{
+	app::UserSignal,
+	components::{
+		button::Button,
+		datepicker::DatePicker,
+		input::{Input, TextArea},
+		timezone_offset::Timezone,
+	},
+	experiment::Heading,
+	icons::ExperimentLogo,
+	login::Login,
+	permission::{Permission, Permissions},
+};
+
+use chrono::prelude::*;
+use leptos::*;
+use leptos_router::*;
+
+stylance::import_style!(css, "../../equipment/equipment_add/equipment_add.module.css");
+
+#[component]
+pub fn ExperimentAdd() -> impl IntoView {
+	let add_experiment_action = create_server_action::<AddExperiment>();
+	let user_signal = use_context::<UserSignal>().expect("No user signal found in context");
+
+	let loading = create_rw_signal(false);
+
+	let navigate = use_navigate();
+	create_effect(move |_| {
+		if let Some(Ok(id)) = add_experiment_action.value().get() {
+			navigate(&format!("/experiment/{id}"), NavigateOptions::default());
+		}
+	});
+
+	create_effect(move |_| {
+		if !add_experiment_action.pending().get() {
+			loading.set(false);
+		}
+	});
+
+	view! {
+		<Heading hide_new=true>
+			<ExperimentLogo />
+			" Add new Experiment"
+		</Heading>
+
+		<Suspense fallback=move || {
+			view! { <Login redirect="/experiment/add" /> }
+		}>
+			{move || {
+				match user_signal.get() {
+					None => view! { <Login redirect="/experiment/add" /> }.into_view(),
+					Some(user) => {
+						let Permissions::All { read: _, write: _, create: perm } = user.permission_experiment;
+						if perm != Permission::Create(true) {
+							view! { <span>"You don't have permission to create new Experiments"</span> }.into_view()
+						} else {
+							view! {
+								<ActionForm
+									action=add_experiment_action
+									class=css::form
+									on:submit=move |_| loading.set(true)
+								>
+									<Timezone />
+									<label class=css::label>
+										<span class=css::text>Name:</span>
+										<span class=css::input>
+											<Input name="name" placeholder="Name" required=true />
+										</span>
+									</label>
+
+									<label class=css::label>
+										<span class=css::text>Start Date:</span>
+										<span class=css::input>
+											<DatePicker
+												attr:name="start_date"
+												attr:placeholder="Start Date"
+												value=create_rw_signal(Some(Local::now().date_naive()))
+											/>
+										</span>
+									</label>
+
+									<label class=css::label>
+										<span class=css::text>End Date:</span>
+										<span class=css::input>
+											<DatePicker attr:name="end_date" attr:placeholder="End Date" />
+										</span>
+									</label>
+
+									<label class=css::label>
+										<span class=css::text>Protocol:</span>
+										<span class=css::input>
+											<TextArea name="protocol" placeholder="Protocol" />
+										</span>
+									</label>
+
+									<label class=css::label>
+										<span class=css::text>Notes:</span>
+										<span class=css::input>
+											<TextArea name="notes" placeholder="Notes" />
+										</span>
+									</label>
+
+									<div class=css::btn_row>
+										{move || {
+											if let Some(responds) = add_experiment_action.value().get() {
+												match responds {
+													Ok(_) => view! {}.into_view(),
+													Err(error) => {
+														view! {
+															<span class=css::error>
+																{error
+																	.to_string()
+																	.replace(
+																		"error reaching server to call server function: ",
+																		"",
+																	)}
+															</span>
+														}
+															.into_view()
+													}
+												}
+											} else {
+												view! {}.into_view()
+											}
+										}} <Button kind="submit" loading=loading>
+											Add
+										</Button>
+									</div>
+								</ActionForm>
+							}
+								.into_view()
+						}
+					}
+				}
+			}}
+		</Suspense>
+	}
+}
+
+#[server(prefix = "/api")]
+pub async fn add_experiment(
+	timezone_offset: i32,
+	name: String,
+	start_date: String,
+	end_date: String,
+	protocol: String,
+	notes: String,
+) -> Result<i32, ServerFnError> {
+	use crate::{
+		auth::get_user,
+		permission::{Permission, Permissions},
+	};
+
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	let user_id;
+	match user {
+		Some(user) => {
+			let Permissions::All {
+				read: _,
+				write: _,
+				create: perm,
+			} = user.permission_experiment;
+			user_id = user.id;
+			if perm != Permission::Create(true) {
+				return Err(ServerFnError::Request(String::from("User not authenticated")));
+			}
+		},
+		None => return Err(ServerFnError::Request(String::from("User not authenticated"))),
+	};
+
+	let hours = timezone_offset / 60;
+	let minutes = timezone_offset % 60;
+	let offset_str = format!("{:+03}:{:02}", hours, minutes.abs());
+	let start_date_with_tz = format!("{}T00:00:00{}", start_date, offset_str);
+	let end_date_with_tz = format!("{}T00:00:00{}", end_date, offset_str);
+
+	let start_date: DateTime<Utc> = match DateTime::parse_from_str(&start_date_with_tz, "%Y-%m-%dT%H:%M:%S%z") {
+		Ok(date) => date,
+		Err(error) => return Err(ServerFnError::Request(format!("Invalid start_date date: {}", error))),
+	}
+	.with_timezone(&Utc);
+
+	let end_date: Option<DateTime<Utc>> = if end_date.is_empty() {
+		None
+	} else {
+		Some(
+			match DateTime::parse_from_str(&end_date_with_tz, "%Y-%m-%dT%H:%M:%S%z") {
+				Ok(date) => date,
+				Err(error) => return Err(ServerFnError::Request(format!("Invalid end_date date: {}", error))),
+			}
+			.with_timezone(&Utc),
+		)
+	};
+
+	if end_date.is_some_and(|end_date| end_date < start_date) {
+		return Err(ServerFnError::Request(String::from("The end date can't be before the start date")));
+	}
+
+	let protocol = if protocol.is_empty() { None } else { Some(protocol) };
+	let notes = if notes.is_empty() { None } else { Some(notes) };
+
+	let id: i32 = sqlx::query_scalar(
+		"INSERT INTO experiment\
+		(person, create_date, name, protocol, start_date, end_date, notes)\
+		VALUES\
+		($1, $2, $3, $4, $5, $6, $7)\
+		RETURNING id",
+	)
+	.bind(user_id)
+	.bind(Utc::now())
+	.bind(name)
+	.bind(protocol)
+	.bind(start_date)
+	.bind(end_date)
+	.bind(notes)
+	.fetch_one(&pool)
+	.await
+	.map_err::<ServerFnError, _>(|error| ServerFnError::ServerError(error.to_string()))?;
+
+	Ok(id)
+}
@@ -0,0 +1,308 @@
+use crate::{
+	app::UserSignal,
+	components::{
+		button::{Button, ButtonVariant},
+		input::Input,
+	},
+	equipment::EquipmentCell,
+	error_template::ErrorTemplate,
+	experiment::{ExperimentCultureData, ExperimentData},
+	permission::{Permissions, Subject, SubjectSignal},
+};
+
+use leptos::*;
+use leptos_router::*;
+
+stylance::import_style!(
+	#[allow(dead_code)]
+	css,
+	"experiment_detail.module.css"
+);
+
+#[component]
+pub fn CultureLinks(experiment: ExperimentData, user_signal: UserSignal) -> impl IntoView {
+	let link_action = create_server_action::<LinkExperimentCulture>();
+	let unlink_action = create_server_action::<UnlinkExperimentCulture>();
+
+	let experiment_id = experiment.id;
+	let culture_data = create_resource(
+		move || (link_action.version().get(), unlink_action.version().get()),
+		move |_| get_experiment_cultures(experiment_id.to_string()),
+	);
+
+	let subject_signal = use_context::<SubjectSignal>();
+	let can_write = move || match user_signal.get() {
+		Some(user) => {
+			let Permissions::All {
+				read: _,
+				write: perm,
+				create: _,
+			} = user.permission_experiment;
+			perm.has_permission_on("write", &Subject::resolve(subject_signal, experiment.id, experiment.person.id))
+		},
+		None => false,
+	};
+
+	view! {
+		<Suspense fallback=move || view! { <p>Loading cultures...</p> }>
+			<ErrorBoundary fallback=|errors| {
+				view! { <ErrorTemplate errors=errors /> }
+			}>
+				{move || {
+					if culture_data.get().is_some() {
+						match culture_data.get().unwrap() {
+							Err(error) => {
+								view! { <pre class="error">Culture Server Error: {error.to_string()}</pre> }.into_view()
+							}
+							Ok(cultures) => {
+								view! {
+									<div class=css::links>
+										{if cultures.is_empty() {
+											view! { <span class=css::empty>"No cultures linked yet"</span> }.into_view()
+										} else {
+											cultures
+												.into_iter()
+												.map(|culture| {
+													view! {
+														<CultureLinkItem
+															experiment_id
+															culture
+															unlink_action
+															can_write
+														/>
+													}
+												})
+												.collect_view()
+										}}
+									</div>
+								}
+									.into_view()
+							}
+						}
+					} else {
+						view! {}.into_view()
+					}
+				}}
+			</ErrorBoundary>
+		</Suspense>
+
+		<Show when=can_write>
+			<ActionForm action=link_action class=css::link_form>
+				<input type="hidden" name="id" value=experiment_id />
+				<Input name="culture" kind="number" placeholder="Culture ID" required=true />
+				<Button kind="submit" variant=ButtonVariant::Outlined>
+					Link culture
+				</Button>
+				{move || {
+					if let Some(Err(error)) = link_action.value().get() {
+						view! {
+							<span>
+								{error.to_string().replace("error reaching server to call server function: ", "")}
+							</span>
+						}
+							.into_view()
+					} else {
+						view! {}.into_view()
+					}
+				}}
+			</ActionForm>
+		</Show>
+	}
+}
+
+#[component]
+fn CultureLinkItem(
+	experiment_id: i32,
+	culture: ExperimentCultureData,
+	unlink_action: Action<UnlinkExperimentCulture, Result<(), ServerFnError>>,
+	can_write: impl Fn() -> bool + 'static,
+) -> impl IntoView {
+	view! {
+		<div class=css::link_item>
+			<div class=css::link_heading>
+				<A href=format!("/culture/{}", culture.id)>{culture.name}</A>
+				<EquipmentCell cell=culture.contamination_status />
+				<Show when=can_write>
+					<ActionForm action=unlink_action class=css::unlink>
+						<input type="hidden" name="id" value=experiment_id />
+						<input type="hidden" name="culture" value=culture.id />
+						<Button kind="submit" variant=ButtonVariant::Text>
+							Unlink
+						</Button>
+					</ActionForm>
+				</Show>
+			</div>
+		</div>
+	}
+}
+
+#[server(prefix = "/api")]
+pub async fn get_experiment_cultures(id: String) -> Result<Vec<ExperimentCultureData>, ServerFnError> {
+	use crate::{
+		auth::get_user,
+		experiment::ExperimentCultureSQLData,
+		permission::{BindFilter, Dimension, Permissions, Subject},
+	};
+
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	let id = match id.parse::<i32>() {
+		Ok(value) => value,
+		Err(_) => return Err(ServerFnError::Request(String::from("Invalid ID"))),
+	};
+
+	let auth_filter = match user {
+		Some(user) => {
+			let Permissions::All {
+				read: perm,
+				write: _,
+				create: _,
+			} = user.permission_experiment;
+			let subject = Subject::load(Dimension::Experiment, id, &pool).await?;
+			if !perm.has_permission_on("read", &subject) {
+				return Err(ServerFnError::Request(String::from("User not authenticated")));
+			}
+
+			let Permissions::All {
+				read: perm,
+				write: _,
+				create: _,
+			} = user.permission_culture;
+			perm.get_query_filter(Dimension::Culture, "culture.id")
+		},
+		None => return Err(ServerFnError::Request(String::from("User not authenticated"))),
+	};
+
+	let auth_query = auth_filter.and_clause(2);
+	let culture_sql_data = sqlx::query_as::<_, ExperimentCultureSQLData>(&format!(
+		r#"
+		SELECT culture.id, culture.name, culture.contamination_status
+		FROM
+			experiment_culture
+			JOIN culture ON experiment_culture.culture = culture.id
+		WHERE
+			experiment_culture.experiment = $1
+			AND culture.id IN (SELECT culture.id FROM culture WHERE culture.id IS NOT NULL {auth_query})
+		ORDER BY culture.id"#
+	))
+	.bind(id)
+	.bind_filter(&auth_filter)
+	.fetch_all(&pool)
+	.await
+	.map_err::<ServerFnError, _>(|error| ServerFnError::ServerError(error.to_string()))?;
+
+	Ok(culture_sql_data.into_iter().map(Into::into).collect())
+}
+
+#[server(prefix = "/api")]
+pub async fn link_experiment_culture(id: String, culture: String) -> Result<(), ServerFnError> {
+	use crate::{
+		auth::get_user,
+		permission::{Dimension, Permissions, Subject},
+	};
+
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	let id = match id.parse::<i32>() {
+		Ok(value) => value,
+		Err(_) => return Err(ServerFnError::Request(String::from("Invalid ID"))),
+	};
+
+	let culture = match culture.parse::<i32>() {
+		Ok(value) => value,
+		Err(_) => return Err(ServerFnError::Request(String::from("Invalid culture ID"))),
+	};
+
+	match user {
+		Some(user) => {
+			let Permissions::All {
+				read: _,
+				write: perm,
+				create: _,
+			} = user.permission_experiment;
+			let subject = Subject::load(Dimension::Experiment, id, &pool).await?;
+			if !perm.has_permission_on("write", &subject) {
+				return Err(ServerFnError::Request(String::from("User not authenticated")));
+			}
+
+			let Permissions::All {
+				read: perm,
+				write: _,
+				create: _,
+			} = user.permission_culture;
+			match Subject::load(Dimension::Culture, culture, &pool).await {
+				Ok(subject) if perm.has_permission_on("read", &subject) => {},
+				Ok(_) | Err(sqlx::Error::RowNotFound) => {
+					return Err(ServerFnError::Request(String::from("Culture not found")));
+				},
+				Err(error) => return Err(error.into()),
+			}
+		},
+		None => return Err(ServerFnError::Request(String::from("User not authenticated"))),
+	};
+
+	sqlx::query("INSERT INTO experiment_culture (experiment, culture) VALUES ($1, $2) ON CONFLICT DO NOTHING")
+		.bind(id)
+		.bind(culture)
+		.execute(&pool)
+		.await
+		.map(|_| ())?;
+
+	Ok(())
+}
+
+#[server(prefix = "/api")]
+pub async fn unlink_experiment_culture(id: String, culture: String) -> Result<(), ServerFnError> {
+	use crate::{
+		auth::get_user,
+		permission::{Dimension, Permissions, Subject},
+	};
+
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	let id = match id.parse::<i32>() {
+		Ok(value) => value,
+		Err(_) => return Err(ServerFnError::Request(String::from("Invalid ID"))),
+	};
+
+	let culture = match culture.parse::<i32>() {
+		Ok(value) => value,
+		Err(_) => return Err(ServerFnError::Request(String::from("Invalid culture ID"))),
+	};
+
+	match user {
+		Some(user) => {
+			let Permissions::All {
+				read: _,
+				write: perm,
+				create: _,
+			} = user.permission_experiment;
+			let subject = Subject::load(Dimension::Experiment, id, &pool).await?;
+			if !perm.has_permission_on("write", &subject) {
+				return Err(ServerFnError::Request(String::from("User not authenticated")));
+			}
+		},
+		None => return Err(ServerFnError::Request(String::from("User not authenticated"))),
+	};
+
+	sqlx::query("DELETE FROM experiment_culture WHERE experiment = $1 AND culture = $2")
+		.bind(id)
+		.bind(culture)
+		.execute(&pool)
+		.await
+		.map(|_| ())?;
+
+	Ok(())
+}
@@ -0,0 +1,387 @@
+use crate::{
+	app::UserSignal,
+	components::{
+		button::{Button, ButtonVariant},
+		input::Input,
+	},
+	equipment::{EquipmentCell, LogItem},
+	error_template::ErrorTemplate,
+	experiment::{ExperimentData, ExperimentEquipmentData},
+	permission::{Permissions, Subject, SubjectSignal},
+};
+
+use leptos::*;
+use leptos_router::*;
+
+stylance::import_style!(
+	#[allow(dead_code)]
+	css,
+	"experiment_detail.module.css"
+);
+
+#[component]
+pub fn EquipmentLinks(experiment: ExperimentData, user_signal: UserSignal) -> impl IntoView {
+	let link_action = create_server_action::<LinkExperimentEquipment>();
+	let unlink_action = create_server_action::<UnlinkExperimentEquipment>();
+
+	let experiment_id = experiment.id;
+	let equipment_data = create_resource(
+		move || (link_action.version().get(), unlink_action.version().get()),
+		move |_| get_experiment_equipment(experiment_id.to_string()),
+	);
+
+	let subject_signal = use_context::<SubjectSignal>();
+	let can_write = move || match user_signal.get() {
+		Some(user) => {
+			let Permissions::All {
+				read: _,
+				write: perm,
+				create: _,
+			} = user.permission_experiment;
+			perm.has_permission_on("write", &Subject::resolve(subject_signal, experiment.id, experiment.person.id))
+		},
+		None => false,
+	};
+
+	view! {
+		<Suspense fallback=move || view! { <p>Loading equipment...</p> }>
+			<ErrorBoundary fallback=|errors| {
+				view! { <ErrorTemplate errors=errors /> }
+			}>
+				{move || {
+					if equipment_data.get().is_some() {
+						match equipment_data.get().unwrap() {
+							Err(error) => {
+								view! { <pre class="error">Equipment Server Error: {error.to_string()}</pre> }.into_view()
+							}
+							Ok(equipment) => {
+								view! {
+									<div class=css::links>
+										{if equipment.is_empty() {
+											view! { <span class=css::empty>"No equipment linked yet"</span> }.into_view()
+										} else {
+											equipment
+												.into_iter()
+												.map(|equipment| {
+													view! {
+														<EquipmentLinkItem
+															experiment_id
+															equipment
+															unlink_action
+															can_write
+														/>
+													}
+												})
+												.collect_view()
+										}}
+									</div>
+								}
+									.into_view()
+							}
+						}
+					} else {
+						view! {}.into_view()
+					}
+				}}
+			</ErrorBoundary>
+		</Suspense>
+
+		<Show when=can_write>
+			<ActionForm action=link_action class=css::link_form>
+				<input type="hidden" name="id" value=experiment_id />
+				<Input name="equipment" kind="number" placeholder="Equipment ID" required=true />
+				<Button kind="submit" variant=ButtonVariant::Outlined>
+					Link equipment
+				</Button>
+				{move || {
+					if let Some(Err(error)) = link_action.value().get() {
+						view! {
+							<span>
+								{error.to_string().replace("error reaching server to call server function: ", "")}
+							</span>
+						}
+							.into_view()
+					} else {
+						view! {}.into_view()
+					}
+				}}
+			</ActionForm>
+		</Show>
+	}
+}
+
+#[component]
+fn EquipmentLinkItem(
+	experiment_id: i32,
+	equipment: ExperimentEquipmentData,
+	unlink_action: Action<UnlinkExperimentEquipment, Result<(), ServerFnError>>,
+	can_write: impl Fn() -> bool + 'static,
+) -> impl IntoView {
+	view! {
+		<div class=css::link_item>
+			<div class=css::link_heading>
+				<A href=format!("/equipment/{}", equipment.id)>
+					<EquipmentCell cell=equipment.equipment_type />
+					" "
+					{equipment.name}
+				</A>
+				<EquipmentCell cell=equipment.status />
+				<Show when=can_write>
+					<ActionForm action=unlink_action class=css::unlink>
+						<input type="hidden" name="id" value=experiment_id />
+						<input type="hidden" name="equipment" value=equipment.id />
+						<Button kind="submit" variant=ButtonVariant::Text>
+							Unlink
+						</Button>
+					</ActionForm>
+				</Show>
+			</div>
+			<div class=css::history>
+				{if equipment.history.is_empty() {
+					view! { <span class=css::empty>"No status changes during this experiment"</span> }.into_view()
+				} else {
+					equipment
+						.history
+						.into_iter()
+						.map(|log| {
+							view! { <LogItem log=log /> }
+						})
+						.collect_view()
+				}}
+			</div>
+		</div>
+	}
+}
+
+#[server(prefix = "/api")]
+pub async fn get_experiment_equipment(id: String) -> Result<Vec<ExperimentEquipmentData>, ServerFnError> {
+	use crate::{
+		auth::get_user,
+		equipment::{EquipmentLogData, EquipmentLogSQLData, EquipmentLogType},
+		experiment::ExperimentEquipmentSQLData,
+		permission::{BindFilter, Dimension, Permissions, Subject},
+	};
+
+	use chrono::prelude::*;
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	let id = match id.parse::<i32>() {
+		Ok(value) => value,
+		Err(_) => return Err(ServerFnError::Request(String::from("Invalid ID"))),
+	};
+
+	let auth_filter = match user {
+		Some(user) => {
+			let Permissions::All {
+				read: perm,
+				write: _,
+				create: _,
+			} = user.permission_experiment;
+			let subject = Subject::load(Dimension::Experiment, id, &pool).await?;
+			if !perm.has_permission_on("read", &subject) {
+				return Err(ServerFnError::Request(String::from("User not authenticated")));
+			}
+
+			let Permissions::All {
+				read: perm,
+				write: _,
+				create: _,
+			} = user.permission_equipment;
+			perm.get_query_filter(Dimension::Equipment, "equipment.id")
+		},
+		None => return Err(ServerFnError::Request(String::from("User not authenticated"))),
+	};
+
+	let (start_date, end_date): (DateTime<Utc>, Option<DateTime<Utc>>) =
+		sqlx::query_as("SELECT start_date, end_date FROM experiment WHERE id = $1").bind(id).fetch_one(&pool).await?;
+
+	let auth_query = auth_filter.and_clause(2);
+	let equipment_sql_data = sqlx::query_as::<_, ExperimentEquipmentSQLData>(&format!(
+		r#"
+		SELECT
+			equipment.id,
+			equipment.name,
+			equipment.equipment_type,
+			equipment_types.label AS equipment_type_label,
+			equipment_types.icon AS equipment_type_icon,
+			equipment.status
+		FROM
+			experiment_equipment
+			JOIN equipment ON experiment_equipment.equipment = equipment.id
+			JOIN equipment_types ON equipment.equipment_type = equipment_types.name
+		WHERE
+			experiment_equipment.experiment = $1
+			AND equipment.deleted_at IS NULL
+			AND equipment.id IN (SELECT equipment.id FROM equipment WHERE equipment.id IS NOT NULL {auth_query})
+		ORDER BY equipment.id"#
+	))
+	.bind(id)
+	.bind_filter(&auth_filter)
+	.fetch_all(&pool)
+	.await
+	.map_err::<ServerFnError, _>(|error| ServerFnError::ServerError(error.to_string()))?;
+
+	let mut equipment_data: Vec<ExperimentEquipmentData> = equipment_sql_data.into_iter().map(Into::into).collect();
+	let equipment_ids = equipment_data.iter().map(|equipment| equipment.id).collect::<Vec<i32>>();
+
+	// Only status changes count as history, they are logged under the status they moved to.
+	// End dates are stored as the start of the day so the whole last day still belongs to the run
+	let status_log_types = EquipmentLogType::status_types()
+		.iter()
+		.map(|log_type| log_type.to_string().to_lowercase())
+		.collect::<Vec<String>>();
+	let history_sql_data = sqlx::query_as::<_, EquipmentLogSQLData>(
+		r#"
+		SELECT
+			equipment_log.*,
+			people.id AS person_id,
+			people.status AS person_status,
+			people.preferred_name AS person_preferred_name,
+			people.picture AS person_picture
+		FROM
+			equipment_log
+			JOIN people ON equipment_log.person = people.id
+		WHERE
+			equipment_log.equipment = ANY($1)
+			AND LOWER(equipment_log.log_type) = ANY($4)
+			AND equipment_log.create_date >= $2
+			AND ($3::TIMESTAMPTZ IS NULL OR equipment_log.create_date < $3 + INTERVAL '1 day')
+		ORDER BY equipment_log.create_date ASC, equipment_log.id ASC"#,
+	)
+	.bind(&equipment_ids)
+	.bind(start_date)
+	.bind(end_date)
+	.bind(status_log_types)
+	.fetch_all(&pool)
+	.await
+	.map_err::<ServerFnError, _>(|error| ServerFnError::ServerError(error.to_string()))?;
+
+	for log in history_sql_data.into_iter().map(EquipmentLogData::from) {
+		if let Some(equipment) = equipment_data.iter_mut().find(|equipment| equipment.id == log.equipment) {
+			equipment.history.push(log);
+		}
+	}
+
+	Ok(equipment_data)
+}
+
+#[server(prefix = "/api")]
+pub async fn link_experiment_equipment(id: String, equipment: String) -> Result<(), ServerFnError> {
+	use crate::{
+		auth::get_user,
+		permission::{Dimension, Permissions, Subject},
+	};
+
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	let id = match id.parse::<i32>() {
+		Ok(value) => value,
+		Err(_) => return Err(ServerFnError::Request(String::from("Invalid ID"))),
+	};
+
+	let equipment = match equipment.parse::<i32>() {
+		Ok(value) => value,
+		Err(_) => return Err(ServerFnError::Request(String::from("Invalid equipment ID"))),
+	};
+
+	match user {
+		Some(user) => {
+			let Permissions::All {
+				read: _,
+				write: perm,
+				create: _,
+			} = user.permission_experiment;
+			let subject = Subject::load(Dimension::Experiment, id, &pool).await?;
+			if !perm.has_permission_on("write", &subject) {
+				return Err(ServerFnError::Request(String::from("User not authenticated")));
+			}
+
+			let Permissions::All {
+				read: perm,
+				write: _,
+				create: _,
+			} = user.permission_equipment;
+			match Subject::load(Dimension::Equipment, equipment, &pool).await {
+				Ok(subject) if perm.has_permission_on("read", &subject) => {},
+				Ok(_) | Err(sqlx::Error::RowNotFound) => {
+					return Err(ServerFnError::Request(String::from("Equipment not found")));
+				},
+				Err(error) => return Err(error.into()),
+			}
+		},
+		None => return Err(ServerFnError::Request(String::from("User not authenticated"))),
+	};
+
+	let deleted: bool = sqlx::query_scalar("SELECT deleted_at IS NOT NULL FROM equipment WHERE id = $1")
+		.bind(equipment)
+		.fetch_one(&pool)
+		.await?;
+	if deleted {
+		return Err(ServerFnError::Request(String::from("Equipment not found")));
+	}
+
+	sqlx::query("INSERT INTO experiment_equipment (experiment, equipment) VALUES ($1, $2) ON CONFLICT DO NOTHING")
+		.bind(id)
+		.bind(equipment)
+		.execute(&pool)
+		.await
+		.map(|_| ())?;
+
+	Ok(())
+}
+
+#[server(prefix = "/api")]
+pub async fn unlink_experiment_equipment(id: String, equipment: String) -> Result<(), ServerFnError> {
+	use crate::{
+		auth::get_user,
+		permission::{Dimension, Permissions, Subject},
+	};
+
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	let id = match id.parse::<i32>() {
+		Ok(value) => value,
+		Err(_) => return Err(ServerFnError::Request(String::from("Invalid ID"))),
+	};
+
+	let equipment = match equipment.parse::<i32>() {
+		Ok(value) => value,
+		Err(_) => return Err(ServerFnError::Request(String::from("Invalid equipment ID"))),
+	};
+
+	match user {
+		Some(user) => {
+			let Permissions::All {
+				read: _,
+				write: perm,
+				create: _,
+			} = user.permission_experiment;
+			let subject = Subject::load(Dimension::Experiment, id, &pool).await?;
+			if !perm.has_permission_on("write", &subject) {
+				return Err(ServerFnError::Request(String::from("User not authenticated")));
+			}
+		},
+		None => return Err(ServerFnError::Request(String::from("User not authenticated"))),
+	};
+
+	sqlx::query("DELETE FROM experiment_equipment WHERE experiment = $1 AND equipment = $2")
+		.bind(id)
+		.bind(equipment)
+		.execute(&pool)
+		.await
+		.map(|_| ())?;
+
+	Ok(())
+}
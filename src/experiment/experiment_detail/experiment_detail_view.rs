@@ -0,0 +1,209 @@
+use crate::{
+	app::{LoginAction, UserSignal},
+	equipment::EquipmentCell,
+	error_template::ErrorTemplate,
+	experiment::{
+		CultureLinks, EndDateEdit, EquipmentLinks, ExperimentData, ExperimentNoteEdit, Heading, NameEdit, ProtocolEdit,
+		StartDateEdit,
+	},
+	icons::ExperimentLogo,
+	login::Login,
+	permission::{Dimension, provide_subject_signal},
+};
+
+use leptos::*;
+use leptos_router::*;
+
+stylance::import_style!(
+	#[allow(dead_code)]
+	css,
+	"../../equipment/equipment_detail/equipment_details.module.css"
+);
+
+#[component]
+pub fn ExperimentDetail() -> impl IntoView {
+	let params = use_params_map();
+
+	let refetch_resources = create_rw_signal(0);
+
+	let login_action = use_context::<LoginAction>().expect("No login action found in context");
+	let user_signal = use_context::<UserSignal>().expect("No user signal found in context");
+	provide_subject_signal(
+		Dimension::Experiment,
+		move || params.with(|p| p.get("id").cloned().unwrap_or_default()),
+		refetch_resources,
+	);
+
+	let experiment_data = create_resource(
+		move || {
+			(login_action.version().get(), params.with(|p| p.get("id").cloned().unwrap_or_default()), refetch_resources.get())
+		},
+		move |(_, id, _)| get_experiment_data_by_id(id),
+	);
+
+	view! {
+		<Suspense fallback=move || view! { <p>Loading experiment...</p> }>
+			<ErrorBoundary fallback=|errors| {
+				view! { <ErrorTemplate errors=errors /> }
+			}>
+				{move || {
+					if experiment_data.get().is_some() {
+						match experiment_data.get().unwrap() {
+							Err(error) => {
+								let error = error.to_string();
+								if error.contains("User not authenticated") {
+									view! {
+										<Login redirect=format!(
+											"/experiment/{}",
+											params.with(|p| p.get("id").cloned().unwrap_or_default()),
+										) />
+									}
+								} else {
+									view! { <pre class="error">Server Error: {error}</pre> }.into_view()
+								}
+							}
+							Ok(experiment) => {
+								let title = experiment.name.clone();
+								view! {
+									<div class=css::details>
+										<Heading>
+											<ExperimentLogo />
+											" "
+											{title}
+										</Heading>
+
+										<dl class=css::list>
+											<dt>ID</dt>
+											<dd>
+												<EquipmentCell cell=experiment.id />
+											</dd>
+
+											<dt>Name</dt>
+											<dd class=css::edit>
+												<NameEdit experiment=experiment.clone() user_signal refetch_resources />
+											</dd>
+
+											<dt>Owner</dt>
+											<dd>
+												<EquipmentCell cell=experiment.person.clone() />
+											</dd>
+
+											<dt>Create Date</dt>
+											<dd>
+												<EquipmentCell cell=experiment.create_date />
+											</dd>
+
+											<dt>Start Date</dt>
+											<dd class=css::edit>
+												<StartDateEdit
+													experiment=experiment.clone()
+													user_signal
+													refetch_resources
+												/>
+											</dd>
+
+											<dt>End Date</dt>
+											<dd class=css::edit>
+												<EndDateEdit
+													experiment=experiment.clone()
+													user_signal
+													refetch_resources
+												/>
+											</dd>
+
+											<dt>Protocol</dt>
+											<dd class=css::edit>
+												<ProtocolEdit
+													experiment=experiment.clone()
+													user_signal
+													refetch_resources
+												/>
+											</dd>
+
+											<dt>Notes</dt>
+											<dd class=css::edit>
+												<ExperimentNoteEdit
+													experiment=experiment.clone()
+													user_signal
+													refetch_resources
+												/>
+											</dd>
+
+											<dt>Cultures</dt>
+											<dd>
+												<CultureLinks experiment=experiment.clone() user_signal />
+											</dd>
+
+											<dt>Equipment</dt>
+											<dd>
+												<EquipmentLinks experiment=experiment.clone() user_signal />
+											</dd>
+										</dl>
+									</div>
+								}
+									.into_view()
+							}
+						}
+					} else {
+						view! { <div>Nothing found</div> }.into_view()
+					}
+				}}
+			</ErrorBoundary>
+		</Suspense>
+	}
+}
+
+#[server(prefix = "/api")]
+pub async fn get_experiment_data_by_id(id: String) -> Result<ExperimentData, ServerFnError> {
+	use crate::{
+		auth::get_user,
+		experiment::ExperimentSQLData,
+		permission::{Dimension, Permissions, Subject},
+	};
+
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	let id = match id.parse::<i32>() {
+		Ok(value) => value,
+		Err(_) => return Err(ServerFnError::Request(String::from("Invalid ID"))),
+	};
+
+	match user {
+		Some(user) => {
+			let Permissions::All {
+				read: perm,
+				write: _,
+				create: _,
+			} = user.permission_experiment;
+			let subject = Subject::load(Dimension::Experiment, id, &pool).await?;
+			if !perm.has_permission_on("read", &subject) {
+				return Err(ServerFnError::Request(String::from("User not authenticated")));
+			}
+		},
+		None => return Err(ServerFnError::Request(String::from("User not authenticated"))),
+	};
+
+	let experiment_sql_data = sqlx::query_as::<_, ExperimentSQLData>(
+		r#"
+		SELECT
+			experiment.*,
+			people.id AS person_id,
+			people.status AS person_status,
+			people.preferred_name AS person_preferred_name,
+			people.picture AS person_picture
+		FROM
+			experiment
+			JOIN people ON experiment.person = people.id
+		WHERE experiment.id = $1"#,
+	)
+	.bind(id)
+	.fetch_one(&pool)
+	.await
+	.map_err::<ServerFnError, _>(|error| ServerFnError::ServerError(error.to_string()))?;
+
+	Ok(experiment_sql_data.into())
+}
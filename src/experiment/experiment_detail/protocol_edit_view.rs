@@ -0,0 +1,114 @@
+use crate::{
+	app::UserSignal,
+	components::{button::Button, input::TextArea},
+	experiment::{ExperimentData, ExperimentFormToggle},
+};
+
+use leptos::*;
+use leptos_router::*;
+
+stylance::import_style!(css, "../../equipment/equipment_detail/equipment_details_edits.module.css");
+stylance::import_style!(notes_css, "../../equipment/equipment_detail/equipment_details_notes_edits.module.css");
+
+#[component]
+pub fn ProtocolEdit(
+	experiment: ExperimentData,
+	user_signal: UserSignal,
+	refetch_resources: RwSignal<usize>,
+) -> impl IntoView {
+	let protocol_action = create_server_action::<EditExperimentProtocol>();
+
+	view! {
+		<ExperimentFormToggle
+			user_id=experiment.person.id
+			id=experiment.id
+			user_signal=user_signal
+			item=experiment.protocol.clone()
+		>
+			{
+				let protocol_clone = experiment.protocol.clone();
+				view! {
+					<ActionForm action=protocol_action class=css::edit_form>
+						<input type="hidden" name="id" value=experiment.id />
+						<TextArea
+							class=notes_css::notes.to_string()
+							name="protocol"
+							value=create_rw_signal(protocol_clone.unwrap_or_default().to_string())
+						/>
+						<div class=css::btns>
+							{move || {
+								if let Some(responds) = protocol_action.value().get() {
+									match responds {
+										Ok(_) => {
+											protocol_action.value().set(None);
+											refetch_resources.update(|version| *version += 1);
+											view! {}.into_view()
+										}
+										Err(error) => {
+											view! {
+												<span>
+													{error
+														.to_string()
+														.replace("error reaching server to call server function: ", "")}
+												</span>
+											}
+												.into_view()
+										}
+									}
+								} else {
+									view! {}.into_view()
+								}
+							}} <Button kind="submit">Save</Button>
+						</div>
+					</ActionForm>
+				}
+			}
+		</ExperimentFormToggle>
+	}
+}
+
+#[server(prefix = "/api")]
+pub async fn edit_experiment_protocol(id: String, protocol: String) -> Result<(), ServerFnError> {
+	use crate::{
+		auth::get_user,
+		permission::{Dimension, Permissions, Subject},
+	};
+
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	let id = match id.parse::<i32>() {
+		Ok(value) => value,
+		Err(_) => return Err(ServerFnError::Request(String::from("Invalid ID"))),
+	};
+
+	match user {
+		Some(user) => {
+			let Permissions::All {
+				read: _,
+				write: perm,
+				create: _,
+			} = user.permission_experiment;
+
+			let subject = Subject::load(Dimension::Experiment, id, &pool).await?;
+			if !perm.has_permission_on("write", &subject) {
+				return Err(ServerFnError::Request(String::from("User not authenticated")));
+			}
+		},
+		None => return Err(ServerFnError::Request(String::from("User not authenticated"))),
+	};
+
+	let protocol = if protocol.is_empty() { None } else { Some(protocol) };
+
+	sqlx::query("UPDATE experiment SET protocol = $1 WHERE id = $2")
+		.bind(protocol)
+		.bind(id)
+		.execute(&pool)
+		.await
+		.map(|_| ())?;
+
+	Ok(())
+}
@@ -0,0 +1,130 @@
+use crate::{
+	app::UserSignal,
+	components::{button::Button, datepicker::DatePicker, timezone_offset::Timezone},
+	experiment::{ExperimentData, ExperimentFormToggle},
+};
+
+use leptos::*;
+use leptos_router::*;
+
+stylance::import_style!(css, "../../equipment/equipment_detail/equipment_details_edits.module.css");
+
+#[component]
+pub fn StartDateEdit(
+	experiment: ExperimentData,
+	user_signal: UserSignal,
+	refetch_resources: RwSignal<usize>,
+) -> impl IntoView {
+	let start_date_action = create_server_action::<EditExperimentStartDate>();
+
+	view! {
+		<ExperimentFormToggle
+			user_id=experiment.person.id
+			id=experiment.id
+			user_signal=user_signal
+			item=experiment.start_date
+		>
+			{
+				let start_date_clone = experiment.start_date;
+				view! {
+					<ActionForm action=start_date_action class=css::edit_form>
+						<input type="hidden" name="id" value=experiment.id />
+						<Timezone />
+						<DatePicker attr:name="start_date" value=create_rw_signal(Some(start_date_clone.date_naive())) />
+						<div class=css::btns>
+							{move || {
+								if let Some(responds) = start_date_action.value().get() {
+									match responds {
+										Ok(_) => {
+											start_date_action.value().set(None);
+											refetch_resources.update(|version| *version += 1);
+											view! {}.into_view()
+										}
+										Err(error) => {
+											view! {
+												<span>
+													{error
+														.to_string()
+														.replace("error reaching server to call server function: ", "")}
+												</span>
+											}
+												.into_view()
+										}
+									}
+								} else {
+									view! {}.into_view()
+								}
+							}} <Button kind="submit">Save</Button>
+						</div>
+					</ActionForm>
+				}
+			}
+		</ExperimentFormToggle>
+	}
+}
+
+#[server(prefix = "/api")]
+pub async fn edit_experiment_start_date(
+	id: String,
+	start_date: String,
+	timezone_offset: i32,
+) -> Result<(), ServerFnError> {
+	use crate::{
+		auth::get_user,
+		permission::{Dimension, Permissions, Subject},
+	};
+
+	use chrono::prelude::*;
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	let id = match id.parse::<i32>() {
+		Ok(value) => value,
+		Err(_) => return Err(ServerFnError::Request(String::from("Invalid ID"))),
+	};
+
+	match user {
+		Some(user) => {
+			let Permissions::All {
+				read: _,
+				write: perm,
+				create: _,
+			} = user.permission_experiment;
+
+			let subject = Subject::load(Dimension::Experiment, id, &pool).await?;
+			if !perm.has_permission_on("write", &subject) {
+				return Err(ServerFnError::Request(String::from("User not authenticated")));
+			}
+		},
+		None => return Err(ServerFnError::Request(String::from("User not authenticated"))),
+	};
+
+	let hours = timezone_offset / 60;
+	let minutes = timezone_offset % 60;
+	let offset_str = format!("{:+03}:{:02}", hours, minutes.abs());
+	let start_date_with_tz = format!("{}T00:00:00{}", start_date, offset_str);
+
+	let start_date: DateTime<Utc> = match DateTime::parse_from_str(&start_date_with_tz, "%Y-%m-%dT%H:%M:%S%z") {
+		Ok(date) => date,
+		Err(error) => return Err(ServerFnError::Request(format!("Invalid date: {}", error))),
+	}
+	.with_timezone(&Utc);
+
+	let end_date: Option<DateTime<Utc>> =
+		sqlx::query_scalar("SELECT end_date FROM experiment WHERE id = $1").bind(id).fetch_one(&pool).await?;
+	if end_date.is_some_and(|end_date| end_date < start_date) {
+		return Err(ServerFnError::Request(String::from("The start date can't be after the end date")));
+	}
+
+	sqlx::query("UPDATE experiment SET start_date = $1 WHERE id = $2")
+		.bind(start_date)
+		.bind(id)
+		.execute(&pool)
+		.await
+		.map(|_| ())?;
+
+	Ok(())
+}
@@ -0,0 +1,291 @@
+use crate::{
+	app::LoginAction,
+	components::{
+		button::{Button, ButtonVariant},
+		pagination::Pagination,
+		select::{MultiSelect, MultiSelectOption},
+	},
+	equipment::THead,
+	error_template::ErrorTemplate,
+	experiment::{ExperimentData, Heading, Row},
+	icons::ExperimentLogo,
+	login::Login,
+};
+
+use leptos::*;
+use leptos_router::*;
+
+stylance::import_style!(
+	#[allow(dead_code)]
+	css,
+	"../equipment/equipment.module.css"
+);
+
+#[component]
+pub fn Experiment() -> impl IntoView {
+	let query = use_query_map();
+
+	let query_field = create_rw_signal(String::from("id"));
+	let query_order = create_rw_signal(String::from("asc"));
+	let query_filter = create_rw_signal(vec![
+		String::from("id"),
+		String::from("name"),
+		String::from("person"),
+		String::from("start_date"),
+		String::from("end_date"),
+	]);
+	let query_page = create_rw_signal::<u16>(1);
+	let query_ipp = create_rw_signal::<u8>(25);
+
+	create_effect(move |_| {
+		let (field, order, filter, page, ipp) = query.with(|p| {
+			let field = p.get("field").cloned().unwrap_or(String::from("id"));
+			let order = p.get("order").cloned().unwrap_or(String::from("asc"));
+			let filter = p
+				.get("filter")
+				.cloned()
+				.unwrap_or(String::from("id,name,person,start_date,end_date"))
+				.split(",")
+				.map(String::from)
+				.collect::<Vec<String>>();
+			let page = p.get("page").cloned().unwrap_or(String::from("1")).parse::<u16>().unwrap_or(1);
+			let ipp = p.get("items_per_page").cloned().unwrap_or(String::from("25")).parse::<u8>().unwrap_or(25);
+
+			(field, order, filter, page, ipp)
+		});
+
+		query_field.set(field);
+		query_order.set(order);
+		query_filter.set(filter);
+		query_page.set(if page > 0 { page } else { 1 });
+		query_ipp.set(if ipp > 0 { ipp } else { 1 });
+	});
+
+	let login_action = use_context::<LoginAction>().expect("No login action found in context");
+
+	let experiment_data = create_resource(
+		move || {
+			let (field, order, page, ipp) = query.with(|p| {
+				let field = p.get("field").cloned().unwrap_or(String::from("id"));
+				let order = p.get("order").cloned().unwrap_or(String::from("asc"));
+				let page = p.get("page").cloned().unwrap_or(String::from("1")).parse::<u16>().unwrap_or(1);
+				let ipp = p.get("items_per_page").cloned().unwrap_or(String::from("25")).parse::<u8>().unwrap_or(25);
+
+				(field, order, page, ipp)
+			});
+			(login_action.version().get(), field, order, page, ipp)
+		},
+		move |(_, field, order, page, ipp)| get_experiment_data(field, order, page, ipp),
+	);
+
+	view! {
+		<Heading>
+			<ExperimentLogo />
+			" Experiment"
+		</Heading>
+		<Suspense fallback=move || view! { <p>Loading experiments...</p> }>
+			<ErrorBoundary fallback=|errors| {
+				view! { <ErrorTemplate errors /> }
+			}>
+				{move || {
+					view! {
+						{experiment_data
+							.get()
+							.map(move |data| match data {
+								Err(error) => {
+									if error.to_string().contains("User not authenticated") {
+										view! { <Login redirect="/experiment" /> }
+									} else {
+										view! { <pre class="error">Server Error: {error.to_string()}</pre> }.into_view()
+									}
+								}
+								Ok((experiment, row_count)) => {
+									let hidden_fields = vec![
+										(String::from("field"), query_field.get()),
+										(String::from("order"), query_order.get()),
+										(String::from("filter"), query_filter.get().join(",")),
+									];
+									view! {
+										<Pagination
+											action=String::from("/experiment")
+											page_key="page"
+											ipp_key="items_per_page"
+											query_page
+											query_ipp
+											row_count
+											hidden_fields=hidden_fields.clone()
+										/>
+										<div class=css::filter>
+											"Columns: "
+											<MultiSelect
+												value=query_filter
+												options=create_rw_signal(
+													ExperimentData::get_fields()
+														.into_iter()
+														.map(|(id, name)| MultiSelectOption::new(name, id))
+														.collect::<Vec<MultiSelectOption<String>>>(),
+												)
+											/> <div class=css::btns>
+												<Button
+													variant=ButtonVariant::Outlined
+													on_click=move |_| {
+														query_filter
+															.set(
+																ExperimentData::get_fields()
+																	.into_iter()
+																	.map(|(id, _)| id)
+																	.collect::<Vec<String>>(),
+															);
+													}
+												>
+													All
+												</Button>
+											</div>
+										</div>
+										<div class=css::table_wrapper>
+											<table class=css::table>
+												<thead>
+													<tr>
+														<THead
+															action="/experiment"
+															items=ExperimentData::get_fields()
+															query_field
+															query_order
+															query_filter
+														>
+															<input type="hidden" name="page" value=query_page.get() />
+															<input
+																type="hidden"
+																name="items_per_page"
+																value=query_ipp.get()
+															/>
+															<input
+																type="hidden"
+																name="filter"
+																value=query_filter.get().join(",")
+															/>
+														</THead>
+													</tr>
+												</thead>
+												<tbody>
+													{if experiment.is_empty() {
+														view! {
+															<tr>
+																<td colspan=ExperimentData::get_fields()
+																	.len()>"No experiments found."</td>
+															</tr>
+														}
+															.into_view()
+													} else {
+														view! { <Row experiment query_filter /> }.into_view()
+													}}
+												</tbody>
+											</table>
+										</div>
+										<Pagination
+											action=String::from("/experiment")
+											page_key="page"
+											ipp_key="items_per_page"
+											query_page
+											query_ipp
+											row_count
+											hidden_fields
+										/>
+									}
+										.into_view()
+								}
+							})
+							.unwrap_or_default()}
+					}
+				}}
+			</ErrorBoundary>
+		</Suspense>
+	}
+}
+
+#[server(prefix = "/api")]
+pub async fn get_experiment_data(
+	field: String,
+	order: String,
+	page: u16,
+	items_per_page: u8,
+) -> Result<(Vec<ExperimentData>, i64), ServerFnError> {
+	use crate::{
+		auth::get_user,
+		experiment::ExperimentSQLData,
+		permission::{BindFilter, Dimension, Permissions},
+	};
+
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	let auth_filter = match user {
+		Some(user) => {
+			let Permissions::All {
+				read: perm,
+				write: _,
+				create: _,
+			} = user.permission_experiment;
+			perm.get_query_filter(Dimension::Experiment, "experiment.id")
+		},
+		None => return Err(ServerFnError::Request(String::from("User not authenticated"))),
+	};
+
+	let order_sanitized = match order.to_lowercase().as_str() {
+		"asc" => "ASC",
+		"desc" => "DESC",
+		_ => "ASC",
+	};
+
+	let field_sanitized = match field.to_lowercase().as_str() {
+		f @ "id" | f @ "create_date" | f @ "name" | f @ "protocol" | f @ "start_date" | f @ "end_date" | f @ "notes" => {
+			format!("experiment.{f}")
+		},
+		"person" => String::from("person_preferred_name"),
+		_ => String::from("experiment.id"),
+	};
+
+	let limit = items_per_page as i64;
+	let offset = (page as i64 - 1) * items_per_page as i64;
+	let auth_query = auth_filter.and_clause(3);
+
+	let query = format!(
+		r#"
+			SELECT
+				experiment.*,
+				people.id AS person_id,
+				people.status AS person_status,
+				people.preferred_name AS person_preferred_name,
+				people.picture AS person_picture
+			FROM
+				experiment
+				JOIN people ON experiment.person = people.id
+			WHERE
+				experiment.id IS NOT NULL
+				{auth_query}
+			ORDER BY {field_sanitized} {order_sanitized}
+			LIMIT $1 OFFSET $2
+			"#
+	);
+	let experiment_sql_data = sqlx::query_as::<_, ExperimentSQLData>(&query)
+		.bind(limit)
+		.bind(offset)
+		.bind_filter(&auth_filter)
+		.fetch_all(&pool)
+		.await
+		.map_err::<ServerFnError, _>(|error| ServerFnError::ServerError(error.to_string()))?;
+
+	let experiment_data: Vec<ExperimentData> = experiment_sql_data.into_iter().map(Into::into).collect();
+
+	let auth_query = auth_filter.and_clause(1);
+	let row_count: i64 =
+		sqlx::query_scalar(&format!("SELECT COUNT(*) FROM experiment WHERE id IS NOT NULL {auth_query}"))
+			.bind_filter(&auth_filter)
+			.fetch_one(&pool)
+			.await?;
+
+	Ok((experiment_data, row_count))
+}
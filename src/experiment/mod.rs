@@ -0,0 +1,50 @@
+pub mod cell {
+	pub mod cell_view;
+}
+
+pub mod experiment_view;
+pub use experiment_view::*;
+
+pub mod experiment_detail {
+	pub mod culture_links_view;
+	pub mod end_date_edit_view;
+	pub mod equipment_links_view;
+	pub mod experiment_detail_view;
+	pub mod experiment_form_toggle_view;
+	pub mod name_edit_view;
+	pub mod note_edit_view;
+	pub mod protocol_edit_view;
+	pub mod start_date_edit_view;
+}
+pub use experiment_detail::culture_links_view::*;
+pub use experiment_detail::end_date_edit_view::*;
+pub use experiment_detail::equipment_links_view::*;
+pub use experiment_detail::experiment_detail_view::*;
+pub use experiment_detail::experiment_form_toggle_view::*;
+pub use experiment_detail::name_edit_view::*;
+pub use experiment_detail::note_edit_view::*;
+pub use experiment_detail::protocol_edit_view::*;
+pub use experiment_detail::start_date_edit_view::*;
+
+pub mod equipment_experiments {
+	pub mod equipment_experiments_view;
+}
+pub use equipment_experiments::equipment_experiments_view::*;
+
+pub mod experiment_add {
+	pub mod experiment_add_view;
+}
+pub use experiment_add::experiment_add_view::*;
+
+pub mod heading {
+	pub mod heading_view;
+}
+pub use heading::heading_view::*;
+
+pub mod row {
+	pub mod row_view;
+}
+pub use row::row_view::*;
+
+pub mod schema;
+pub use schema::*;
@@ -0,0 +1,124 @@
+use crate::{equipment::EquipmentCell, experiment::ExperimentData};
+
+use leptos::*;
+use leptos_router::*;
+
+stylance::import_style!(css, "row.module.css");
+
+#[component]
+pub fn Row(experiment: Vec<ExperimentData>, query_filter: RwSignal<Vec<String>>) -> impl IntoView {
+	experiment
+		.into_iter()
+		.map(move |experiment| {
+			view! {
+				<tr>
+					<Show when=move || { query_filter.get().contains(&String::from("id")) }>
+						<td class="experiment_listing_id">
+							<A href=format!("/experiment/{}", experiment.id) class="none">
+								<EquipmentCell cell=experiment.id table_view=true />
+							</A>
+						</td>
+					</Show>
+					<Show when=move || {
+						query_filter.get().contains(&String::from("person"))
+					}>
+						{
+							let experiment_person = experiment.person.clone();
+							view! {
+								<td class="experiment_listing_person">
+									<A href=format!("/experiment/{}", experiment.id) class="none">
+										<EquipmentCell cell=experiment_person table_view=true />
+									</A>
+								</td>
+							}
+						}
+					</Show>
+					<Show when=move || {
+						query_filter.get().contains(&String::from("create_date"))
+					}>
+						{
+							view! {
+								<td class="experiment_listing_create_date">
+									<A href=format!("/experiment/{}", experiment.id) class="none">
+										<EquipmentCell cell=experiment.create_date table_view=true />
+									</A>
+								</td>
+							}
+						}
+					</Show>
+					<Show when=move || {
+						query_filter.get().contains(&String::from("name"))
+					}>
+						{
+							let experiment_name = experiment.name.clone();
+							view! {
+								<td class="experiment_listing_name">
+									<A href=format!("/experiment/{}", experiment.id) class="none">
+										<EquipmentCell cell=experiment_name table_view=true />
+									</A>
+								</td>
+							}
+						}
+					</Show>
+					<Show when=move || {
+						query_filter.get().contains(&String::from("protocol"))
+					}>
+						{
+							let experiment_protocol = experiment.protocol.clone();
+							view! {
+								<td class="experiment_listing_protocol">
+									<A href=format!("/experiment/{}", experiment.id) class="none">
+										<EquipmentCell cell=experiment_protocol table_view=true />
+									</A>
+								</td>
+							}
+						}
+					</Show>
+					<Show when=move || {
+						query_filter.get().contains(&String::from("start_date"))
+					}>
+						{
+							view! {
+								<td class="experiment_listing_start_date">
+									<A href=format!("/experiment/{}", experiment.id) class="none">
+										<EquipmentCell cell=experiment.start_date table_view=true />
+									</A>
+								</td>
+							}
+						}
+					</Show>
+					<Show when=move || {
+						query_filter.get().contains(&String::from("end_date"))
+					}>
+						{
+							view! {
+								<td class="experiment_listing_end_date">
+									<A href=format!("/experiment/{}", experiment.id) class="none">
+										<EquipmentCell cell=experiment.end_date table_view=true />
+									</A>
+								</td>
+							}
+						}
+					</Show>
+					<Show when=move || {
+						query_filter.get().contains(&String::from("notes"))
+					}>
+						{
+							let experiment_notes = experiment.notes.clone();
+							view! {
+								<td class="experiment_listing_notes">
+									<A href=format!("/experiment/{}", experiment.id) class="none">
+										<EquipmentCell cell=experiment_notes table_view=true />
+									</A>
+								</td>
+							}
+						}
+					</Show>
+					<td class="experiment_listing_details_link">
+						<A href=format!("/experiment/{}", experiment.id)>Details</A>
+					</td>
+				</tr>
+			}
+		})
+		.collect_view()
+}
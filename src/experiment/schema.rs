@@ -0,0 +1,190 @@
+use crate::{
+	culture::ContaminationStatus,
+	equipment::{AvatarData, AvatarSQLData, EquipmentLogData, EquipmentStatus, EquipmentType, Notes},
+};
+
+use chrono::prelude::*;
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "ssr")]
+use sqlx::{FromRow, Row};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Protocol(String);
+display_default_for_string_struct!(Protocol);
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExperimentSQLData {
+	pub id: i32,
+	pub person: AvatarSQLData,
+	pub create_date: DateTime<Utc>,
+	pub name: String,
+	pub protocol: Option<String>,
+	pub start_date: DateTime<Utc>,
+	pub end_date: Option<DateTime<Utc>>,
+	pub notes: Option<String>,
+}
+
+#[cfg(feature = "ssr")]
+impl sqlx::FromRow<'_, sqlx::postgres::PgRow> for ExperimentSQLData {
+	fn from_row(row: &sqlx::postgres::PgRow) -> Result<Self, sqlx::Error> {
+		Ok(ExperimentSQLData {
+			id: row.try_get("id")?,
+			person: AvatarSQLData {
+				id: row.try_get("person_id")?,
+				status: row.try_get("person_status")?,
+				preferred_name: row.try_get("person_preferred_name")?,
+				picture: row.try_get("person_picture")?,
+			},
+			create_date: row.try_get("create_date")?,
+			name: row.try_get("name")?,
+			protocol: row.try_get("protocol")?,
+			start_date: row.try_get("start_date")?,
+			end_date: row.try_get("end_date")?,
+			notes: row.try_get("notes")?,
+		})
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExperimentData {
+	pub id: i32,
+	pub person: AvatarData,
+	pub create_date: DateTime<Utc>,
+	pub name: String,
+	pub protocol: Option<Protocol>,
+	pub start_date: DateTime<Utc>,
+	pub end_date: Option<DateTime<Utc>>,
+	pub notes: Option<Notes>,
+}
+
+impl ExperimentData {
+	pub fn get_fields() -> Vec<(String, String)> {
+		vec![
+			(String::from("id"), String::from("ID")),
+			(String::from("person"), String::from("Owner")),
+			(String::from("create_date"), String::from("Create")),
+			(String::from("name"), String::from("Name")),
+			(String::from("protocol"), String::from("Protocol")),
+			(String::from("start_date"), String::from("Start")),
+			(String::from("end_date"), String::from("End")),
+			(String::from("notes"), String::from("Notes")),
+		]
+	}
+}
+
+impl std::default::Default for ExperimentData {
+	fn default() -> Self {
+		ExperimentData {
+			id: Default::default(),
+			person: Default::default(),
+			create_date: Utc::now(),
+			name: Default::default(),
+			protocol: None,
+			start_date: Utc::now(),
+			end_date: None,
+			notes: None,
+		}
+	}
+}
+
+impl From<ExperimentSQLData> for ExperimentData {
+	fn from(val: ExperimentSQLData) -> Self {
+		ExperimentData {
+			id: val.id,
+			person: val.person.into(),
+			create_date: val.create_date,
+			name: val.name,
+			protocol: val.protocol.map(Into::into),
+			start_date: val.start_date,
+			end_date: val.end_date,
+			notes: val.notes.map(Into::into),
+		}
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ssr", derive(FromRow))]
+pub struct ExperimentEquipmentSQLData {
+	pub id: i32,
+	pub name: String,
+	pub equipment_type: String,
+	pub equipment_type_label: String,
+	pub equipment_type_icon: String,
+	pub status: String,
+}
+
+/// A piece of equipment used in an experiment together with its status changes during the run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExperimentEquipmentData {
+	pub id: i32,
+	pub name: String,
+	pub equipment_type: EquipmentType,
+	pub status: EquipmentStatus,
+	pub history: Vec<EquipmentLogData>,
+}
+
+impl From<ExperimentEquipmentSQLData> for ExperimentEquipmentData {
+	fn from(val: ExperimentEquipmentSQLData) -> Self {
+		ExperimentEquipmentData {
+			id: val.id,
+			name: val.name,
+			equipment_type: EquipmentType::new(val.equipment_type, val.equipment_type_label, val.equipment_type_icon),
+			status: EquipmentStatus::parse(val.status),
+			history: Vec::new(),
+		}
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ssr", derive(FromRow))]
+pub struct ExperimentCultureSQLData {
+	pub id: i32,
+	pub name: String,
+	pub contamination_status: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExperimentCultureData {
+	pub id: i32,
+	pub name: String,
+	pub contamination_status: ContaminationStatus,
+}
+
+impl From<ExperimentCultureSQLData> for ExperimentCultureData {
+	fn from(val: ExperimentCultureSQLData) -> Self {
+		ExperimentCultureData {
+			id: val.id,
+			name: val.name,
+			contamination_status: ContaminationStatus::parse(val.contamination_status),
+		}
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ssr", derive(FromRow))]
+pub struct ExperimentSummarySQLData {
+	pub id: i32,
+	pub name: String,
+	pub start_date: DateTime<Utc>,
+	pub end_date: Option<DateTime<Utc>>,
+}
+
+/// Short form of an experiment used when listing experiments on other pages
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExperimentSummaryData {
+	pub id: i32,
+	pub name: String,
+	pub start_date: DateTime<Utc>,
+	pub end_date: Option<DateTime<Utc>>,
+}
+
+impl From<ExperimentSummarySQLData> for ExperimentSummaryData {
+	fn from(val: ExperimentSummarySQLData) -> Self {
+		ExperimentSummaryData {
+			id: val.id,
+			name: val.name,
+			start_date: val.start_date,
+			end_date: val.end_date,
+		}
+	}
+}
@@ -1,7 +1,7 @@
 use crate::{
 	app::{LoginAction, LogoutAction},
+	equipment::{EquipmentType, EquipmentTypeIcon},
 	error_template::ErrorTemplate,
-	icons::{Flask, IncubationCabinet, Vessel},
 	login::Login,
 };
 
@@ -12,13 +12,13 @@ use sqlx::FromRow;
 
 stylance::import_style!(css, "home.module.css");
 
-#[derive(Debug, Copy, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 #[cfg_attr(feature = "ssr", derive(FromRow))]
 pub struct EquipmentStats {
 	all: i64,
-	type_flask: i64,
-	type_vessel: i64,
-	type_incubation_cabinet: i64,
+	/// Every configured type with its count, filled by a second query
+	#[cfg_attr(feature = "ssr", sqlx(skip))]
+	types: Vec<(EquipmentType, i64)>,
 	status_cleaned: i64,
 	status_prepared: i64,
 	status_sterilized: i64,
@@ -63,29 +63,21 @@ pub fn Home() -> impl IntoView {
 												<code class=css::code>{stats.all}</code>
 											</dd>
 
-											<dt>
-												<Flask />
-												Flasks:
-											</dt>
-											<dd>
-												<code class=css::code>{stats.type_flask}</code>
-											</dd>
-
-											<dt>
-												<Vessel />
-												Vessel:
-											</dt>
-											<dd>
-												<code class=css::code>{stats.type_vessel}</code>
-											</dd>
-
-											<dt>
-												<IncubationCabinet />
-												Incubation Cabinet:
-											</dt>
-											<dd>
-												<code class=css::code>{stats.type_incubation_cabinet}</code>
-											</dd>
+											{stats
+												.types
+												.into_iter()
+												.map(|(equipment_type, count)| {
+													view! {
+														<dt>
+															<EquipmentTypeIcon icon=equipment_type.icon />
+															{format!("{}:", equipment_type.label)}
+														</dt>
+														<dd>
+															<code class=css::code>{count}</code>
+														</dd>
+													}
+												})
+												.collect_view()}
 
 											<dt>Cleaned:</dt>
 											<dd>
@@ -130,7 +122,10 @@ pub fn Home() -> impl IntoView {
 
 #[server(prefix = "/api")]
 pub async fn get_equipment_stats() -> Result<EquipmentStats, ServerFnError> {
-	use crate::{auth::get_user, permission::Permissions};
+	use crate::{
+		auth::get_user,
+		permission::{BindFilter, Dimension, Permissions},
+	};
 
 	use sqlx::PgPool;
 
@@ -138,36 +133,55 @@ pub async fn get_equipment_stats() -> Result<EquipmentStats, ServerFnError> {
 		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
 	let user = get_user().await?;
 
-	let auth_query = match user {
+	let auth_filter = match user {
 		Some(user) => {
 			let Permissions::All {
 				read: perm,
 				write: _,
 				create: _,
 			} = user.permission_equipment;
-			perm.get_query_select_without_where("equipment.id")
+			perm.get_query_filter(Dimension::Equipment, "equipment.id")
 		},
 		None => return Err(ServerFnError::Request(String::from("User not authenticated"))),
 	};
 
-	Ok(
-		sqlx::query_as::<_, EquipmentStats>(&format!(
-			r#"
+	let auth_query = auth_filter.and_clause(1);
+	let mut stats = sqlx::query_as::<_, EquipmentStats>(&format!(
+		r#"
 			SELECT
 				COUNT(*) AS "all",
-				COUNT(*) FILTER (WHERE equipment_type = 'Flask') AS "type_flask",
-				COUNT(*) FILTER (WHERE equipment_type = 'Vessel') AS "type_vessel",
-				COUNT(*) FILTER (WHERE equipment_type = 'IncubationCabinet') AS "type_incubation_cabinet",
 				COUNT(*) FILTER (WHERE status = 'Cleaned') AS "status_cleaned",
 				COUNT(*) FILTER (WHERE status = 'Prepared') AS "status_prepared",
 				COUNT(*) FILTER (WHERE status = 'Sterilized') AS "status_sterilized",
 				COUNT(*) FILTER (WHERE status = 'InUse') AS "status_in_use",
 				COUNT(*) FILTER (WHERE status = 'Dirty') AS "status_dirty",
 				COUNT(*) FILTER (WHERE status = 'Archived') AS "status_archived"
-			FROM equipment WHERE id IS NOT NULL {auth_query}
+			FROM equipment WHERE deleted_at IS NULL {auth_query}
+		"#
+	))
+	.bind_filter(&auth_filter)
+	.fetch_one(&pool)
+	.await?;
+
+	let types: Vec<(String, String, String, i64)> = sqlx::query_as(&format!(
+		r#"
+			SELECT
+				equipment_types.name,
+				equipment_types.label,
+				equipment_types.icon,
+				COUNT(equipment.id)
+			FROM equipment_types
+			LEFT JOIN equipment
+				ON equipment.equipment_type = equipment_types.name AND equipment.deleted_at IS NULL {auth_query}
+			GROUP BY equipment_types.name
+			ORDER BY equipment_types.label
 		"#
-		))
-		.fetch_one(&pool)
-		.await?,
-	)
+	))
+	.bind_filter(&auth_filter)
+	.fetch_all(&pool)
+	.await?;
+	stats.types =
+		types.into_iter().map(|(name, label, icon, count)| (EquipmentType::new(name, label, icon), count)).collect();
+
+	Ok(stats)
 }
@@ -0,0 +1,233 @@
+use crate::{
+	app::LoginAction,
+	components::button::{Button, ButtonVariant},
+	error_template::ErrorTemplate,
+	icons::PeopleLogo,
+	jobs::{JobData, JobRunStatus, ReminderSettingsForm},
+	login::Login,
+	people::Heading,
+};
+
+use chrono::prelude::*;
+use leptos::*;
+
+stylance::import_style!(css, "jobs.module.css");
+
+/// How many past runs are listed per job
+pub const JOB_RUNS_SHOWN: i64 = 10;
+
+/// Lists the background jobs with their next and past runs for people admins
+#[component]
+pub fn Jobs() -> impl IntoView {
+	let run_job_action = create_server_action::<RunJobNow>();
+
+	let login_action = use_context::<LoginAction>().expect("No login action found in context");
+
+	let jobs_data =
+		create_resource(move || (login_action.version().get(), run_job_action.version().get()), move |_| get_jobs());
+
+	view! {
+		<Heading hide_new=true>
+			<PeopleLogo />
+			" Jobs"
+		</Heading>
+
+		<Suspense fallback=move || view! { <p>Loading jobs...</p> }>
+			<ErrorBoundary fallback=|errors| {
+				view! { <ErrorTemplate errors=errors /> }
+			}>
+				{move || {
+					jobs_data
+						.get()
+						.map(|data| match data {
+							Err(error) => {
+								let error = error.to_string();
+								if error.contains("User not authenticated") {
+									view! { <Login redirect="/jobs" /> }.into_view()
+								} else {
+									view! { <pre class="error">Server Error: {error}</pre> }.into_view()
+								}
+							}
+							Ok(jobs) => {
+								let now = Utc::now();
+								view! {
+									<div class=css::jobs>
+										{jobs
+											.into_iter()
+											.map(|job| {
+												let name = job.name.clone();
+												let state = if job.is_running(now) {
+													format!("running on {}", job.locked_by.clone().unwrap_or_default())
+												} else if job.attempts > 0 {
+													format!(
+														"retry {} due {}",
+														job.attempts + 1,
+														job.next_run.format("%d %b %Y %H:%M"),
+													)
+												} else {
+													format!("next run {}", job.next_run.format("%d %b %Y %H:%M"))
+												};
+												view! {
+													<div class=css::job>
+														<div class=css::job_heading>
+															<code class=css::name>{job.name.clone()}</code>
+															<span class=css::muted>{job.interval()}</span>
+															<span>{state}</span>
+															<Button
+																variant=ButtonVariant::Text
+																on_click=Callback::new(move |_: ev::MouseEvent| {
+																	run_job_action.dispatch(RunJobNow { name: name.clone() });
+																})
+															>
+																Run now
+															</Button>
+														</div>
+														<p class=css::muted>{job.description}</p>
+														<table class=css::runs>
+															{job
+																.runs
+																.into_iter()
+																.map(|run| {
+																	let status_class = match run.status {
+																		JobRunStatus::Running => css::running,
+																		JobRunStatus::Succeeded => css::succeeded,
+																		JobRunStatus::Failed => css::failed,
+																	};
+																	let took = run
+																		.finished_at
+																		.map(|finished_at| {
+																			format!(
+																				"{} ms",
+																				(finished_at - run.started_at).num_milliseconds(),
+																			)
+																		})
+																		.unwrap_or_default();
+																	view! {
+																		<tr>
+																			<td>
+																				{run.started_at.format("%d %b %Y %H:%M:%S").to_string()}
+																			</td>
+																			<td class=status_class>{run.status.to_string()}</td>
+																			<td>{format!("attempt {}", run.attempt)}</td>
+																			<td>{took}</td>
+																			<td class=css::error>{run.error.unwrap_or_default()}</td>
+																		</tr>
+																	}
+																})
+																.collect_view()}
+														</table>
+													</div>
+												}
+											})
+											.collect_view()}
+									</div>
+								}
+									.into_view()
+							}
+						})
+				}}
+			</ErrorBoundary>
+		</Suspense>
+		<ReminderSettingsForm />
+		{move || {
+			if let Some(Err(error)) = run_job_action.value().get() {
+				view! {
+					<span class=css::error>
+						{error.to_string().replace("error reaching server to call server function: ", "")}
+					</span>
+				}
+					.into_view()
+			} else {
+				view! {}.into_view()
+			}
+		}}
+	}
+}
+
+/// Jobs and webhooks are system wide so only people who can create people get to manage them
+#[cfg(feature = "ssr")]
+pub(crate) fn check_jobs_admin(user: Option<crate::auth::User>) -> Result<crate::auth::User, ServerFnError> {
+	use crate::permission::{Permission, Permissions};
+
+	match user {
+		Some(user) => {
+			let Permissions::All {
+				read: _,
+				write: _,
+				create: perm,
+			} = &user.permission_people;
+			if *perm != Permission::Create(true) {
+				return Err(ServerFnError::Request(String::from("User not authenticated")));
+			}
+			Ok(user)
+		},
+		None => Err(ServerFnError::Request(String::from("User not authenticated"))),
+	}
+}
+
+#[server(prefix = "/api")]
+pub async fn get_jobs() -> Result<Vec<JobData>, ServerFnError> {
+	use crate::{
+		auth::get_user,
+		jobs::{JobRunData, jobs},
+	};
+
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	check_jobs_admin(user)?;
+
+	let mut jobs_data = sqlx::query_as::<_, JobData>("SELECT * FROM jobs ORDER BY name").fetch_all(&pool).await?;
+
+	let runs = sqlx::query_as::<_, JobRunData>(
+		r#"
+		SELECT runs.*
+		FROM
+			jobs
+			JOIN LATERAL (
+				SELECT * FROM job_runs WHERE job_runs.job = jobs.name ORDER BY started_at DESC LIMIT $1
+			) runs ON TRUE
+		ORDER BY runs.started_at DESC
+		"#,
+	)
+	.bind(JOB_RUNS_SHOWN)
+	.fetch_all(&pool)
+	.await?;
+
+	// Jobs that were removed from the code keep their row but are not described anymore
+	let registered = jobs();
+	for job in jobs_data.iter_mut() {
+		job.description = registered
+			.iter()
+			.find(|registered| registered.name == job.name)
+			.map(|registered| registered.description.to_string())
+			.unwrap_or_else(|| String::from("No longer registered"));
+		job.runs = runs.iter().filter(|run| run.job == job.name).cloned().collect();
+	}
+
+	Ok(jobs_data)
+}
+
+/// Makes a job due right away, the runner picks it up on its next poll
+#[server(prefix = "/api")]
+pub async fn run_job_now(name: String) -> Result<(), ServerFnError> {
+	use crate::auth::get_user;
+
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	check_jobs_admin(user)?;
+
+	let result = sqlx::query("UPDATE jobs SET next_run = NOW() WHERE name = $1").bind(&name).execute(&pool).await?;
+	if result.rows_affected() == 0 {
+		return Err(ServerFnError::Request(format!("Unknown job \"{name}\"")));
+	}
+
+	Ok(())
+}
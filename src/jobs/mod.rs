@@ -0,0 +1,18 @@
+pub mod jobs_view;
+pub use jobs_view::*;
+
+pub mod reminder_settings_view;
+pub use reminder_settings_view::*;
+
+#[cfg(feature = "ssr")]
+pub mod reminders;
+#[cfg(feature = "ssr")]
+pub use reminders::*;
+
+#[cfg(feature = "ssr")]
+pub mod runner;
+#[cfg(feature = "ssr")]
+pub use runner::*;
+
+pub mod schema;
+pub use schema::*;
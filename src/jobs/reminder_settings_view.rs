@@ -0,0 +1,152 @@
+use crate::{
+	components::{
+		button::{Button, ButtonVariant},
+		input::Input,
+	},
+	jobs::ReminderSettings,
+};
+
+use leptos::*;
+use leptos_router::*;
+
+stylance::import_style!(css, "reminder_settings.module.css");
+
+/// Lets people admins pick when reminder emails go out and who else receives them
+#[component]
+pub fn ReminderSettingsForm() -> impl IntoView {
+	let save_action = create_server_action::<SaveReminderSettings>();
+	let settings_data = create_resource(move || save_action.version().get(), move |_| get_reminder_settings());
+
+	view! {
+		<h2>Reminders</h2>
+		<Transition>
+			{move || {
+				settings_data
+					.get()
+					.and_then(Result::ok)
+					.map(|settings| {
+						let reminder_days = settings
+							.reminder_days
+							.iter()
+							.map(ToString::to_string)
+							.collect::<Vec<_>>()
+							.join(", ");
+						view! {
+							<ActionForm action=save_action class=css::form>
+								<label>
+									"Days before"
+									<Input name="reminder_days" value=create_rw_signal(reminder_days) />
+								</label>
+								<label>
+									"Also send to"
+									<Input
+										name="recipients"
+										placeholder="Emails, comma separated"
+										value=create_rw_signal(settings.recipients.join(", "))
+									/>
+								</label>
+								<Button kind="submit" variant=ButtonVariant::Outlined>
+									Save
+								</Button>
+							</ActionForm>
+						}
+					})
+			}}
+		</Transition>
+		{move || {
+			if let Some(Err(error)) = save_action.value().get() {
+				view! {
+					<span class=css::error>
+						{error.to_string().replace("error reaching server to call server function: ", "")}
+					</span>
+				}
+					.into_view()
+			} else {
+				view! {}.into_view()
+			}
+		}}
+	}
+}
+
+/// Reads a comma separated list of days, ignoring duplicates
+pub fn parse_reminder_days(input: &str) -> Result<Vec<i32>, String> {
+	let mut days = input
+		.split(',')
+		.map(str::trim)
+		.filter(|day| !day.is_empty())
+		.map(|day| match day.parse::<i32>() {
+			Ok(value) if (0..=365).contains(&value) => Ok(value),
+			_ => Err(format!("\"{day}\" is not a number of days between 0 and 365")),
+		})
+		.collect::<Result<Vec<i32>, String>>()?;
+	days.sort_unstable_by(|a, b| b.cmp(a));
+	days.dedup();
+	Ok(days)
+}
+
+#[server(prefix = "/api")]
+pub async fn get_reminder_settings() -> Result<ReminderSettings, ServerFnError> {
+	use crate::{
+		auth::get_user,
+		jobs::{check_jobs_admin, load_reminder_settings},
+	};
+
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	check_jobs_admin(user)?;
+
+	Ok(load_reminder_settings(&pool).await?)
+}
+
+#[server(prefix = "/api")]
+pub async fn save_reminder_settings(reminder_days: String, recipients: String) -> Result<(), ServerFnError> {
+	use crate::{auth::get_user, jobs::check_jobs_admin};
+
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	check_jobs_admin(user)?;
+
+	let reminder_days = parse_reminder_days(&reminder_days).map_err::<ServerFnError, _>(ServerFnError::Request)?;
+	let recipients: Vec<String> = recipients
+		.split(',')
+		.map(|recipient| recipient.trim().to_string())
+		.filter(|recipient| !recipient.is_empty())
+		.collect();
+	if let Some(recipient) = recipients.iter().find(|recipient| !crate::email::is_valid_address(recipient)) {
+		return Err(ServerFnError::Request(format!("\"{recipient}\" is not an email address")));
+	}
+
+	sqlx::query(
+		r#"
+		INSERT INTO reminder_settings (id, reminder_days, recipients) VALUES (1, $1, $2)
+		ON CONFLICT (id) DO UPDATE SET reminder_days = EXCLUDED.reminder_days, recipients = EXCLUDED.recipients
+		"#,
+	)
+	.bind(reminder_days)
+	.bind(recipients)
+	.execute(&pool)
+	.await?;
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn parse_reminder_days_test() {
+		assert_eq!(parse_reminder_days("7, 60,30, 7"), Ok(vec![60, 30, 7]));
+		assert_eq!(parse_reminder_days(""), Ok(vec![]));
+		assert!(parse_reminder_days("soon").is_err());
+		assert!(parse_reminder_days("-1").is_err());
+	}
+}
@@ -0,0 +1,263 @@
+use crate::{
+	email::{EmailConfig, app_url, send_email},
+	equipment::{MaintenanceDue, maintenance_due_query},
+	jobs::ReminderSettings,
+	notifications::{NotificationKind, notify_equipment},
+};
+
+use chrono::prelude::*;
+use sqlx::PgPool;
+
+/// The reminder that applies when something is due in `days_left` days.
+/// Only the closest threshold counts so a late first run doesn't send the 60 and 30 day reminders at once.
+pub fn reminder_threshold(days_left: i64, reminder_days: &[i32]) -> Option<i32> {
+	if days_left < 0 {
+		return None;
+	}
+	reminder_days.iter().copied().filter(|days| *days as i64 >= days_left).min()
+}
+
+fn in_days(days_left: i64) -> String {
+	match days_left {
+		0 => String::from("today"),
+		1 => String::from("tomorrow"),
+		days => format!("in {days} days"),
+	}
+}
+
+/// Subject and body of the email about an expiring warranty
+pub fn warranty_reminder(id: i32, name: &str, expires: NaiveDate, days_left: i64, url: &str) -> (String, String) {
+	(
+		format!("Warranty of \"{name}\" expires {}", in_days(days_left)),
+		format!(
+			"The warranty of \"{name}\" (#{id}) expires {} on {}.\n\nOpen the equipment: {url}/equipment/{id}\n",
+			in_days(days_left),
+			expires.format("%d %b %Y"),
+		),
+	)
+}
+
+/// Subject and body of the email about upcoming maintenance
+pub fn maintenance_reminder(due: &MaintenanceDue, days_left: i64, url: &str) -> (String, String) {
+	let id = due.equipment_id;
+	(
+		format!("\"{}\" for \"{}\" is due {}", due.plan.name, due.equipment_name, in_days(days_left)),
+		format!(
+			"\"{}\" ({}) for \"{}\" (#{id}) is due {} on {}.\n\nRecord it once it is done: {url}/equipment/{id}\n",
+			due.plan.name,
+			due.plan.interval(),
+			due.equipment_name,
+			in_days(days_left),
+			due.due_date.format("%d %b %Y"),
+		),
+	)
+}
+
+pub async fn load_reminder_settings(pool: &PgPool) -> Result<ReminderSettings, sqlx::Error> {
+	let settings: Option<(Vec<i32>, Vec<String>)> =
+		sqlx::query_as("SELECT reminder_days, recipients FROM reminder_settings WHERE id = 1").fetch_optional(pool).await?;
+
+	Ok(
+		settings
+			.map(|(reminder_days, recipients)| ReminderSettings {
+				reminder_days,
+				recipients,
+			})
+			.unwrap_or_default(),
+	)
+}
+
+/// One email that may be due to go out
+struct Reminder {
+	equipment: i32,
+	kind: &'static str,
+	reference: i32,
+	due_date: NaiveDate,
+	days: i32,
+	owner: String,
+	subject: String,
+	body: String,
+}
+
+/// Tells the owner and watchers in the app and emails the owner and the configured recipients about warranties and
+/// maintenance coming up. Each threshold is sent once per due date and recipient, which `sent_reminders` keeps track of.
+/// Due dates are compared in the server's local timezone which is the day people see in the app.
+pub async fn send_reminders(pool: PgPool) -> Result<(), String> {
+	let config = EmailConfig::from_env();
+
+	let settings = load_reminder_settings(&pool).await.map_err(|error| error.to_string())?;
+	let Some(max_days) = settings.reminder_days.iter().copied().max() else {
+		return Ok(());
+	};
+	let today = Local::now().date_naive();
+	let url = app_url();
+	let mut reminders = Vec::new();
+
+	let warranties: Vec<(i32, String, DateTime<Utc>, String)> = sqlx::query_as(
+		r#"
+		SELECT equipment.id, equipment.name, equipment.warranty_expiration_date, people.email
+		FROM
+			equipment
+			JOIN people ON equipment.person = people.id
+		WHERE
+			equipment.deleted_at IS NULL
+			AND equipment.status IS DISTINCT FROM 'Archived'
+			AND equipment.warranty_expiration_date >= CURRENT_DATE - 1
+			AND equipment.warranty_expiration_date < CURRENT_DATE + $1 + 2
+		"#,
+	)
+	.bind(max_days)
+	.fetch_all(&pool)
+	.await
+	.map_err(|error| error.to_string())?;
+
+	// The query leaves a day on either side for timezones, the exact local day is checked here
+	for (id, name, expires, owner) in warranties {
+		let due_date = expires.with_timezone(&Local).date_naive();
+		let days_left = (due_date - today).num_days();
+		if let Some(days) = reminder_threshold(days_left, &settings.reminder_days) {
+			let (subject, body) = warranty_reminder(id, &name, due_date, days_left, &url);
+			reminders.push(Reminder {
+				equipment: id,
+				kind: "warranty",
+				reference: 0,
+				due_date,
+				days,
+				owner,
+				subject,
+				body,
+			});
+		}
+	}
+
+	let due = sqlx::query_as::<_, MaintenanceDue>(&maintenance_due_query(""))
+		.fetch_all(&pool)
+		.await
+		.map_err(|error| error.to_string())?;
+	let ids: Vec<i32> = due.iter().map(|item| item.equipment_id).collect();
+	let owners: Vec<(i32, String)> = sqlx::query_as(
+		"SELECT equipment.id, people.email FROM equipment JOIN people ON equipment.person = people.id WHERE equipment.id = ANY($1)",
+	)
+	.bind(&ids)
+	.fetch_all(&pool)
+	.await
+	.map_err(|error| error.to_string())?;
+
+	for item in due {
+		let due_date = item.due_date.with_timezone(&Local).date_naive();
+		let days_left = (due_date - today).num_days();
+		let owner = owners.iter().find(|(id, _)| *id == item.equipment_id).map(|(_, email)| email.clone());
+		if let (Some(days), Some(owner)) = (reminder_threshold(days_left, &settings.reminder_days), owner) {
+			let (subject, body) = maintenance_reminder(&item, days_left, &url);
+			reminders.push(Reminder {
+				equipment: item.equipment_id,
+				kind: "maintenance",
+				reference: item.plan.id,
+				due_date,
+				days,
+				owner,
+				subject,
+				body,
+			});
+		}
+	}
+
+	let mut errors = Vec::new();
+	for reminder in reminders {
+		let sent: Vec<String> = sqlx::query_scalar(
+			r#"
+			SELECT recipient FROM sent_reminders
+			WHERE equipment = $1 AND kind = $2 AND reference = $3 AND due_date = $4 AND days = $5
+			"#,
+		)
+		.bind(reminder.equipment)
+		.bind(reminder.kind)
+		.bind(reminder.reference)
+		.bind(reminder.due_date)
+		.bind(reminder.days)
+		.fetch_all(&pool)
+		.await
+		.map_err(|error| error.to_string())?;
+
+		// The in-app notification goes first so it shows up even when no email can be sent.
+		// Without a mail server reminders only show up in the app.
+		let mut recipients = vec![String::new()];
+		if config.is_some() {
+			recipients.push(reminder.owner.clone());
+			recipients.extend(settings.recipients.iter().filter(|recipient| **recipient != reminder.owner).cloned());
+		}
+
+		for recipient in recipients.into_iter().filter(|recipient| !sent.contains(recipient)) {
+			match &config {
+				Some(config) if !recipient.is_empty() => {
+					// One email per recipient so a refused address doesn't keep everyone else from getting theirs
+					if let Err(error) =
+						send_email(config, std::slice::from_ref(&recipient), &reminder.subject, &reminder.body).await
+					{
+						errors.push(format!("#{} {} to {recipient}: {error}", reminder.equipment, reminder.kind));
+						continue;
+					}
+				},
+				_ => {
+					notify_equipment(
+						&mut *pool.acquire().await.map_err(|error| error.to_string())?,
+						reminder.equipment,
+						None,
+						NotificationKind::parse(reminder.kind),
+						&reminder.subject,
+					)
+					.await
+					.map_err(|error| error.to_string())?;
+				},
+			}
+
+			sqlx::query(
+				"INSERT INTO sent_reminders (equipment, kind, reference, due_date, days, recipient) VALUES ($1, $2, $3, $4, $5, $6)",
+			)
+			.bind(reminder.equipment)
+			.bind(reminder.kind)
+			.bind(reminder.reference)
+			.bind(reminder.due_date)
+			.bind(reminder.days)
+			.bind(&recipient)
+			.execute(&pool)
+			.await
+			.map_err(|error| error.to_string())?;
+		}
+	}
+
+	// Reminders that went out stay recorded so a retry only sends the ones that failed
+	if errors.is_empty() {
+		Ok(())
+	} else {
+		Err(errors.join("\n"))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn reminder_threshold_test() {
+		let days = [60, 30, 7];
+		assert_eq!(reminder_threshold(61, &days), None);
+		assert_eq!(reminder_threshold(60, &days), Some(60));
+		assert_eq!(reminder_threshold(45, &days), Some(60));
+		assert_eq!(reminder_threshold(30, &days), Some(30));
+		assert_eq!(reminder_threshold(3, &days), Some(7));
+		assert_eq!(reminder_threshold(0, &days), Some(7));
+		assert_eq!(reminder_threshold(-1, &days), None);
+		assert_eq!(reminder_threshold(3, &[]), None);
+	}
+
+	#[test]
+	fn warranty_reminder_test() {
+		let expires = NaiveDate::from_ymd_opt(2024, 6, 30).unwrap();
+		let (subject, body) = warranty_reminder(12, "Spore Spa", expires, 7, "http://codon.test");
+
+		assert_eq!(subject, "Warranty of \"Spore Spa\" expires in 7 days");
+		assert!(body.contains("on 30 Jun 2024"));
+		assert!(body.contains("http://codon.test/equipment/12"));
+	}
+}
@@ -0,0 +1,317 @@
+use std::{future::Future, pin::Pin, sync::OnceLock};
+
+use chrono::Duration;
+use sqlx::PgPool;
+
+/// How often the runner looks for jobs that are due
+const POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// How long a claimed job stays locked, a crashed instance frees its jobs once this runs out
+const LOCK_SECONDS: i32 = 15 * 60;
+
+/// How often a running job extends its lock so a long run isn't picked up by another instance
+const LOCK_RENEW_INTERVAL: std::time::Duration = std::time::Duration::from_secs(LOCK_SECONDS as u64 / 3);
+
+/// How long finished runs stay in the history
+const JOB_RUN_RETENTION_DAYS: i32 = 14;
+
+/// How a run is closed when the instance running it went away
+const STALE_RUN: &str =
+	"status = 'failed', error = 'The instance running the job stopped before it finished', finished_at = NOW()";
+
+/// After this many failed attempts in a row a job waits for its next regular run
+pub const MAX_ATTEMPTS: i32 = 5;
+
+pub type JobFuture = Pin<Box<dyn Future<Output = Result<(), String>> + Send>>;
+
+/// A task the server runs on a schedule
+pub struct Job {
+	pub name: &'static str,
+	pub description: &'static str,
+	pub interval: Duration,
+	pub run: fn(PgPool) -> JobFuture,
+}
+
+/// Every job the server knows about, new jobs only need to be added here
+pub fn jobs() -> Vec<Job> {
+	vec![
+		Job {
+			name: "temp_upload_cleanup",
+			description: "Removes uploads older than a day that were never attached to anything",
+			interval: Duration::hours(6),
+			run: |_| Box::pin(cleanup_temp_uploads()),
+		},
+		Job {
+			name: "job_run_cleanup",
+			description: "Removes old job runs and marks runs of crashed instances as failed",
+			interval: Duration::hours(24),
+			run: |pool| Box::pin(cleanup_job_runs(pool)),
+		},
+		Job {
+			name: "equipment_trash_purge",
+			description: "Purges equipment that has been in the trash for longer than the restore window",
+			interval: Duration::hours(24),
+			run: |pool| Box::pin(crate::equipment::purge_expired_equipment(pool)),
+		},
+		Job {
+			name: "expiry_reminders",
+			description: "Sends reminders about expiring warranties and upcoming maintenance",
+			interval: Duration::hours(1),
+			run: |pool| Box::pin(crate::jobs::send_reminders(pool)),
+		},
+		Job {
+			name: "webhook_delivery",
+			description: "Sends queued webhook deliveries and retries the ones that failed",
+			interval: Duration::minutes(1),
+			run: |pool| Box::pin(crate::webhooks::deliver_webhooks(pool)),
+		},
+	]
+}
+
+/// Identifies this server process in the job tables
+pub fn instance_id() -> &'static str {
+	static INSTANCE: OnceLock<String> = OnceLock::new();
+	INSTANCE.get_or_init(|| uuid::Uuid::new_v4().to_string())
+}
+
+/// How long to wait before retrying a job that failed `attempt` times in a row
+pub fn retry_backoff(attempt: i32) -> Duration {
+	let minutes = 2_i64.saturating_pow(attempt.clamp(1, 31) as u32 - 1);
+	Duration::minutes(minutes.min(60))
+}
+
+/// Registers all jobs and polls for due ones in the background for as long as the server runs
+pub fn spawn_job_runner(pool: PgPool) {
+	tokio::spawn(async move {
+		if let Err(error) = register_jobs(&pool).await {
+			eprintln!("Registering jobs failed: {error}");
+			return;
+		}
+
+		loop {
+			// Every job gets its own task so a long run doesn't hold up the others,
+			// the lock it holds while running keeps the next poll from starting it twice
+			for job in jobs() {
+				let pool = pool.clone();
+				tokio::spawn(async move {
+					if let Err(error) = run_job_if_due(&pool, &job).await {
+						eprintln!("Running job {} failed: {error}", job.name);
+					}
+				});
+			}
+			tokio::time::sleep(POLL_INTERVAL).await;
+		}
+	});
+}
+
+async fn register_jobs(pool: &PgPool) -> Result<(), sqlx::Error> {
+	for job in jobs() {
+		sqlx::query(
+			r#"
+			INSERT INTO jobs (name, interval_seconds) VALUES ($1, $2)
+			ON CONFLICT (name) DO UPDATE SET interval_seconds = EXCLUDED.interval_seconds
+			"#,
+		)
+		.bind(job.name)
+		.bind(job.interval.num_seconds() as i32)
+		.execute(pool)
+		.await?;
+	}
+
+	Ok(())
+}
+
+/// Claims the job if it is due and no other instance holds it, then runs it and records the outcome
+async fn run_job_if_due(pool: &PgPool, job: &Job) -> Result<(), sqlx::Error> {
+	let instance = instance_id();
+
+	// The claim is a single UPDATE so two instances can never both win it
+	let attempt: Option<i32> = sqlx::query_scalar(
+		r#"
+		UPDATE jobs SET
+			attempts = attempts + 1,
+			locked_until = NOW() + $2 * INTERVAL '1 second',
+			locked_by = $3
+		WHERE
+			name = $1
+			AND next_run <= NOW()
+			AND (locked_until IS NULL OR locked_until < NOW())
+		RETURNING attempts
+		"#,
+	)
+	.bind(job.name)
+	.bind(LOCK_SECONDS)
+	.bind(instance)
+	.fetch_optional(pool)
+	.await?;
+
+	let Some(attempt) = attempt else {
+		return Ok(());
+	};
+
+	// Holding the claim means any run still marked as running belongs to an instance that stopped
+	sqlx::query(&format!("UPDATE job_runs SET {STALE_RUN} WHERE job = $1 AND status = 'running'"))
+		.bind(job.name)
+		.execute(pool)
+		.await?;
+
+	let run_id: i32 = sqlx::query_scalar(
+		"INSERT INTO job_runs (job, status, attempt, instance) VALUES ($1, 'running', $2, $3) RETURNING id",
+	)
+	.bind(job.name)
+	.bind(attempt)
+	.bind(instance)
+	.fetch_one(pool)
+	.await?;
+
+	let renewal = tokio::spawn(renew_lock(pool.clone(), job.name));
+	// Running on its own task turns a panic into an error instead of taking the runner down
+	let result = match tokio::spawn((job.run)(pool.clone())).await {
+		Ok(result) => result,
+		Err(error) => Err(format!("The job panicked: {error}")),
+	};
+	renewal.abort();
+
+	match result {
+		Ok(()) => {
+			sqlx::query("UPDATE job_runs SET status = 'succeeded', finished_at = NOW() WHERE id = $1")
+				.bind(run_id)
+				.execute(pool)
+				.await?;
+			sqlx::query(
+				r#"
+				UPDATE jobs SET
+					next_run = NOW() + interval_seconds * INTERVAL '1 second',
+					attempts = 0,
+					locked_until = NULL,
+					locked_by = NULL
+				WHERE name = $1
+				"#,
+			)
+			.bind(job.name)
+			.execute(pool)
+			.await?;
+		},
+		Err(error) => {
+			sqlx::query("UPDATE job_runs SET status = 'failed', error = $2, finished_at = NOW() WHERE id = $1")
+				.bind(run_id)
+				.bind(error)
+				.execute(pool)
+				.await?;
+
+			let (next_run, attempts) = if attempt < MAX_ATTEMPTS {
+				(retry_backoff(attempt), attempt)
+			} else {
+				(job.interval, 0)
+			};
+			sqlx::query(
+				r#"
+				UPDATE jobs SET
+					next_run = NOW() + $2 * INTERVAL '1 second',
+					attempts = $3,
+					locked_until = NULL,
+					locked_by = NULL
+				WHERE name = $1
+				"#,
+			)
+			.bind(job.name)
+			.bind(next_run.num_seconds() as i32)
+			.bind(attempts)
+			.execute(pool)
+			.await?;
+		},
+	}
+
+	Ok(())
+}
+
+/// Extends the lock of a job for as long as this instance runs it
+async fn renew_lock(pool: PgPool, name: &'static str) {
+	loop {
+		tokio::time::sleep(LOCK_RENEW_INTERVAL).await;
+		if let Err(error) =
+			sqlx::query("UPDATE jobs SET locked_until = NOW() + $2 * INTERVAL '1 second' WHERE name = $1 AND locked_by = $3")
+				.bind(name)
+				.bind(LOCK_SECONDS)
+				.bind(instance_id())
+				.execute(&pool)
+				.await
+		{
+			eprintln!("Renewing the lock of job {name} failed: {error}");
+		}
+	}
+}
+
+/// Deletes runs past the retention and fails runs whose job lock ran out while they were still marked as running
+async fn cleanup_job_runs(pool: PgPool) -> Result<(), String> {
+	sqlx::query(&format!(
+		r#"
+		UPDATE job_runs SET {STALE_RUN}
+		FROM jobs
+		WHERE
+			job_runs.job = jobs.name
+			AND job_runs.status = 'running'
+			AND (jobs.locked_until IS NULL OR jobs.locked_until < NOW())
+		"#
+	))
+	.execute(&pool)
+	.await
+	.map_err(|error| error.to_string())?;
+
+	sqlx::query("DELETE FROM job_runs WHERE status <> 'running' AND started_at < NOW() - $1 * INTERVAL '1 day'")
+		.bind(JOB_RUN_RETENTION_DAYS)
+		.execute(&pool)
+		.await
+		.map_err(|error| error.to_string())?;
+
+	Ok(())
+}
+
+/// Deletes files in upload temp folders that are older than a day.
+/// Uploads land there until the record they belong to exists so anything left behind was abandoned.
+async fn cleanup_temp_uploads() -> Result<(), String> {
+	let max_age = std::time::Duration::from_secs(24 * 60 * 60);
+	let mut folders = vec![(std::path::PathBuf::from(format!("{}public/upload_media/", env!("UPLOAD_ROOT"))), false)];
+
+	while let Some((folder, is_temp)) = folders.pop() {
+		let mut entries = match tokio::fs::read_dir(&folder).await {
+			Ok(entries) => entries,
+			Err(error) if error.kind() == std::io::ErrorKind::NotFound => continue,
+			Err(error) => return Err(format!("Reading folder {folder:?} failed: {error}")),
+		};
+
+		while let Some(entry) = entries.next_entry().await.map_err(|error| error.to_string())? {
+			let path = entry.path();
+			let metadata = entry.metadata().await.map_err(|error| error.to_string())?;
+			if metadata.is_dir() {
+				folders.push((path, is_temp || entry.file_name() == "temp"));
+			} else if is_temp && metadata.modified().ok().and_then(|modified| modified.elapsed().ok()) > Some(max_age) {
+				tokio::fs::remove_file(&path).await.map_err(|error| format!("Removing file {path:?} failed: {error}"))?;
+			}
+		}
+	}
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn retry_backoff_test() {
+		assert_eq!(retry_backoff(1), Duration::minutes(1));
+		assert_eq!(retry_backoff(2), Duration::minutes(2));
+		assert_eq!(retry_backoff(4), Duration::minutes(8));
+		assert_eq!(retry_backoff(10), Duration::minutes(60));
+		assert_eq!(retry_backoff(i32::MAX), Duration::minutes(60));
+	}
+
+	#[test]
+	fn jobs_have_unique_names_test() {
+		let mut names: Vec<&str> = jobs().iter().map(|job| job.name).collect();
+		names.sort();
+		names.dedup();
+		assert_eq!(names.len(), jobs().len());
+	}
+}
@@ -0,0 +1,161 @@
+use chrono::prelude::*;
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "ssr")]
+use sqlx::Row;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum JobRunStatus {
+	#[default]
+	Running,
+	Succeeded,
+	Failed,
+}
+
+impl JobRunStatus {
+	pub fn parse(input: String) -> Self {
+		match input.to_lowercase().as_str() {
+			"succeeded" => JobRunStatus::Succeeded,
+			"failed" => JobRunStatus::Failed,
+			_ => JobRunStatus::Running,
+		}
+	}
+}
+
+impl std::fmt::Display for JobRunStatus {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			JobRunStatus::Running => write!(f, "running"),
+			JobRunStatus::Succeeded => write!(f, "succeeded"),
+			JobRunStatus::Failed => write!(f, "failed"),
+		}
+	}
+}
+
+/// One attempt at running a job
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JobRunData {
+	pub id: i32,
+	pub job: String,
+	pub status: JobRunStatus,
+	pub attempt: i32,
+	pub instance: String,
+	pub error: Option<String>,
+	pub started_at: DateTime<Utc>,
+	pub finished_at: Option<DateTime<Utc>>,
+}
+
+#[cfg(feature = "ssr")]
+impl sqlx::FromRow<'_, sqlx::postgres::PgRow> for JobRunData {
+	fn from_row(row: &sqlx::postgres::PgRow) -> Result<Self, sqlx::Error> {
+		Ok(JobRunData {
+			id: row.try_get("id")?,
+			job: row.try_get("job")?,
+			status: JobRunStatus::parse(row.try_get("status")?),
+			attempt: row.try_get("attempt")?,
+			instance: row.try_get("instance")?,
+			error: row.try_get("error")?,
+			started_at: row.try_get("started_at")?,
+			finished_at: row.try_get("finished_at")?,
+		})
+	}
+}
+
+/// A registered job with when it runs next and its most recent runs
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JobData {
+	pub name: String,
+	pub description: String,
+	pub interval_seconds: i32,
+	pub next_run: DateTime<Utc>,
+	pub attempts: i32,
+	pub locked_until: Option<DateTime<Utc>>,
+	pub locked_by: Option<String>,
+	pub runs: Vec<JobRunData>,
+}
+
+#[cfg(feature = "ssr")]
+impl sqlx::FromRow<'_, sqlx::postgres::PgRow> for JobData {
+	fn from_row(row: &sqlx::postgres::PgRow) -> Result<Self, sqlx::Error> {
+		Ok(JobData {
+			name: row.try_get("name")?,
+			description: String::new(),
+			interval_seconds: row.try_get("interval_seconds")?,
+			next_run: row.try_get("next_run")?,
+			attempts: row.try_get("attempts")?,
+			locked_until: row.try_get("locked_until")?,
+			locked_by: row.try_get("locked_by")?,
+			runs: Vec::new(),
+		})
+	}
+}
+
+impl JobData {
+	/// The interval in the largest unit it divides into, like "every 6 hours"
+	pub fn interval(&self) -> String {
+		let seconds = self.interval_seconds;
+		let (count, unit) = if seconds % 86_400 == 0 {
+			(seconds / 86_400, "day")
+		} else if seconds % 3_600 == 0 {
+			(seconds / 3_600, "hour")
+		} else if seconds % 60 == 0 {
+			(seconds / 60, "minute")
+		} else {
+			(seconds, "second")
+		};
+
+		if count == 1 {
+			format!("every {unit}")
+		} else {
+			format!("every {count} {unit}s")
+		}
+	}
+
+	pub fn is_running(&self, now: DateTime<Utc>) -> bool {
+		self.locked_until.is_some_and(|locked_until| locked_until > now)
+	}
+}
+
+/// When reminders go out and who gets them besides the person the equipment belongs to
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReminderSettings {
+	pub reminder_days: Vec<i32>,
+	pub recipients: Vec<String>,
+}
+
+impl std::default::Default for ReminderSettings {
+	fn default() -> Self {
+		ReminderSettings {
+			reminder_days: vec![60, 30, 7],
+			recipients: Vec::new(),
+		}
+	}
+}
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn job_interval_test() {
+		let mut job = JobData {
+			name: String::from("test"),
+			description: String::new(),
+			interval_seconds: 3_600,
+			next_run: Utc::now(),
+			attempts: 0,
+			locked_until: None,
+			locked_by: None,
+			runs: Vec::new(),
+		};
+		assert_eq!(job.interval(), "every hour");
+
+		job.interval_seconds = 6 * 3_600;
+		assert_eq!(job.interval(), "every 6 hours");
+
+		job.interval_seconds = 86_400;
+		assert_eq!(job.interval(), "every day");
+
+		job.interval_seconds = 90;
+		assert_eq!(job.interval(), "every 90 seconds");
+	}
+}
@@ -21,20 +21,32 @@ pub mod components {
 	pub mod switch;
 	pub mod timezone_offset;
 }
+pub mod culture;
 pub mod db;
 pub mod ds;
 pub mod equipment;
 pub mod error_template;
+pub mod experiment;
 pub mod footer;
 pub mod header;
 pub mod home;
 pub mod icons;
+pub mod jobs;
 pub mod login;
 pub mod nav;
+pub mod notifications;
+pub mod people;
 pub mod permission;
 pub mod profile;
 pub mod qrcode;
 pub mod utils;
+pub mod webhooks;
+
+#[cfg(feature = "ssr")]
+pub mod audit;
+
+#[cfg(feature = "ssr")]
+pub mod email;
 
 #[cfg(feature = "ssr")]
 pub mod fileserv;
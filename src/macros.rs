@@ -13,5 +13,11 @@ macro_rules! display_default_for_string_struct {
 				$struct_name(Default::default())
 			}
 		}
+
+		impl From<String> for $struct_name {
+			fn from(val: String) -> Self {
+				$struct_name(val)
+			}
+		}
 	};
 }
@@ -21,24 +21,36 @@ pub mod components {
 	pub mod switch;
 	pub mod timezone_offset;
 }
+pub mod culture;
 pub mod db;
 pub mod ds;
 pub mod equipment;
 pub mod error_template;
+pub mod experiment;
 pub mod footer;
 pub mod header;
 pub mod home;
 pub mod icons;
+pub mod jobs;
 pub mod login;
 pub mod nav;
+pub mod notifications;
+pub mod people;
 pub mod permission;
 pub mod profile;
 pub mod qrcode;
 pub mod utils;
+pub mod webhooks;
 
 #[cfg(feature = "ssr")]
 use dotenvy::dotenv;
 
+#[cfg(feature = "ssr")]
+pub mod audit;
+
+#[cfg(feature = "ssr")]
+pub mod email;
+
 #[cfg(feature = "ssr")]
 pub mod fileserv;
 
@@ -46,6 +58,10 @@ pub mod fileserv;
 use crate::{
 	app::App,
 	auth::{User, ssr::AuthSession},
+	equipment::{
+		equipment_export::equipment_export_view::ssr::export_equipment,
+		live_updates::events::{equipment_event_stream, spawn_equipment_event_listener},
+	},
 	fileserv::file_and_error_handler,
 };
 
@@ -148,6 +164,9 @@ async fn main() {
 	let addr = leptos_options.site_addr;
 	let routes = generate_route_list(App);
 
+	crate::jobs::spawn_job_runner(get_db().clone());
+	spawn_equipment_event_listener(get_db().clone());
+
 	let app_state = AppState {
 		leptos_options,
 		routes: routes.clone(),
@@ -157,6 +176,8 @@ async fn main() {
 	// build our application with a route
 	let app = Router::new()
 		.route("/api/*fn_name", get(server_fn_handler).post(server_fn_handler))
+		.route("/export/equipment", get(export_equipment))
+		.route("/events/equipment/:id", get(equipment_event_stream))
 		.leptos_routes_with_handler(routes, get(leptos_routes_handler))
 		.fallback(file_and_error_handler)
 		.layer(AuthSessionLayer::<User, i32, SessionPgPool, PgPool>::new(Some(get_db().clone())).with_config(auth_config))
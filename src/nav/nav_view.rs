@@ -4,6 +4,7 @@ use crate::{
 		avatar::Avatar,
 		dropdown::{Dropdown, DropdownPlacement, DropdownTrigger},
 	},
+	notifications::NotificationBell,
 };
 
 use leptos::*;
@@ -25,6 +26,15 @@ pub fn Nav() -> impl IntoView {
 				<li>
 					<A href="/equipment">Equipment</A>
 				</li>
+				<li>
+					<A href="/culture">Culture</A>
+				</li>
+				<li>
+					<A href="/experiment">Experiment</A>
+				</li>
+				<li>
+					<A href="/people">People</A>
+				</li>
 				<li class=css::person>
 					<Suspense fallback=move || {
 						view! { <A href="/login">"Login"</A> }
@@ -34,6 +44,7 @@ pub fn Nav() -> impl IntoView {
 								None => view! { <A href="/login">"Login"</A> }.into_view(),
 								Some(user) => {
 									view! {
+										<NotificationBell />
 										<Dropdown placement=DropdownPlacement::BottomEnd on_select=move |_| {}>
 											<DropdownTrigger slot>
 												<Avatar data=user.into() />
@@ -0,0 +1,16 @@
+pub mod notification_bell_view;
+pub use notification_bell_view::*;
+
+pub mod notifications_view;
+pub use notifications_view::*;
+
+#[cfg(feature = "ssr")]
+pub mod notify;
+#[cfg(feature = "ssr")]
+pub use notify::*;
+
+pub mod schema;
+pub use schema::*;
+
+pub mod watch_view;
+pub use watch_view::*;
@@ -0,0 +1,38 @@
+use crate::{app::NotificationsRefetch, notifications::get_unread_notification_count};
+
+use leptos::*;
+use leptos_router::*;
+
+stylance::import_style!(css, "notification_bell.module.css");
+
+/// Links to the notifications page and shows how many are unread
+#[component]
+pub fn NotificationBell() -> impl IntoView {
+	let location = use_location();
+	let notifications_refetch =
+		use_context::<NotificationsRefetch>().expect("No notifications refetch signal found in context");
+
+	let unread_data = create_resource(
+		move || (location.pathname.get(), notifications_refetch.get()),
+		move |_| get_unread_notification_count(),
+	);
+
+	view! {
+		<A href="/notifications" class=css::bell>
+			<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 24 24" fill="currentColor" aria-label="Notifications">
+				<path d="M12 22a2.5 2.5 0 0 0 2.45-2h-4.9A2.5 2.5 0 0 0 12 22zm7-6V11a7 7 0 0 0-5.5-6.84V3a1.5 1.5 0 0 0-3 0v1.16A7 7 0 0 0 5 11v5l-2 2v1h18v-1l-2-2zm-2 1H7v-6a5 5 0 0 1 10 0v6z" />
+			</svg>
+			<Transition>
+				{move || {
+					unread_data
+						.get()
+						.and_then(Result::ok)
+						.filter(|count| *count > 0)
+						.map(|count| {
+							view! { <span class=css::count>{if count > 99 { String::from("99+") } else { count.to_string() }}</span> }
+						})
+				}}
+			</Transition>
+		</A>
+	}
+}
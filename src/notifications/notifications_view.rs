@@ -0,0 +1,306 @@
+use crate::{
+	app::{LoginAction, NotificationsRefetch},
+	components::{
+		avatar::Avatar,
+		button::{Button, ButtonVariant},
+	},
+	error_template::ErrorTemplate,
+	login::Login,
+	notifications::{NotificationData, SetWatching},
+};
+
+use leptos::*;
+use leptos_router::*;
+
+stylance::import_style!(css, "notifications.module.css");
+
+/// How many notifications the notifications page lists
+pub const NOTIFICATIONS_SHOWN: i64 = 100;
+
+/// The notifications of the logged in person together with the equipment they watch
+#[component]
+pub fn Notifications() -> impl IntoView {
+	let mark_read_action = create_server_action::<MarkNotificationsRead>();
+	let set_watching_action = create_server_action::<SetWatching>();
+
+	let login_action = use_context::<LoginAction>().expect("No login action found in context");
+	let notifications_refetch =
+		use_context::<NotificationsRefetch>().expect("No notifications refetch signal found in context");
+
+	// The bell counts unread notifications so it has to hear about them being read
+	create_effect(move |_| {
+		if mark_read_action.version().get() > 0 {
+			notifications_refetch.update(|version| *version += 1);
+		}
+	});
+
+	let notifications_data = create_resource(
+		move || (login_action.version().get(), mark_read_action.version().get()),
+		move |_| get_notifications(),
+	);
+	let watching_data = create_resource(
+		move || (login_action.version().get(), set_watching_action.version().get()),
+		move |_| get_watched_equipment(),
+	);
+
+	view! {
+		<h1>Notifications</h1>
+		<Suspense fallback=move || view! { <p>Loading notifications...</p> }>
+			<ErrorBoundary fallback=|errors| {
+				view! { <ErrorTemplate errors=errors /> }
+			}>
+				{move || {
+					notifications_data
+						.get()
+						.map(|data| match data {
+							Err(error) => {
+								let error = error.to_string();
+								if error.contains("User not authenticated") {
+									view! { <Login redirect="/notifications" /> }.into_view()
+								} else {
+									view! { <pre class="error">Server Error: {error}</pre> }.into_view()
+								}
+							}
+							Ok(notifications) if notifications.is_empty() => {
+								view! { <p class=css::muted>Nothing happened to your equipment yet</p> }.into_view()
+							}
+							Ok(notifications) => {
+								let has_unread = notifications.iter().any(NotificationData::is_unread);
+								view! {
+									<Show when=move || has_unread>
+										<Button
+											variant=ButtonVariant::Text
+											on_click=Callback::new(move |_: ev::MouseEvent| {
+												mark_read_action.dispatch(MarkNotificationsRead { id: None });
+											})
+										>
+											Mark all as read
+										</Button>
+									</Show>
+									<ul class=css::notifications>
+										{notifications
+											.into_iter()
+											.map(|notification| {
+												view! { <NotificationItem notification mark_read_action /> }
+											})
+											.collect_view()}
+									</ul>
+								}
+									.into_view()
+							}
+						})
+				}}
+			</ErrorBoundary>
+		</Suspense>
+
+		<h2>Watching</h2>
+		<Transition>
+			{move || {
+				watching_data
+					.get()
+					.and_then(Result::ok)
+					.map(|watching| {
+						if watching.is_empty() {
+							return view! {
+								<p class=css::muted>
+									"Watch equipment from its page to hear about it even when it isn't yours"
+								</p>
+							}
+								.into_view();
+						}
+						view! {
+							<ul class=css::watching>
+								{watching
+									.into_iter()
+									.map(|(id, name)| {
+										view! {
+											<li>
+												<A href=format!("/equipment/{id}")>{format!("#{id} {name}")}</A>
+												<Button
+													variant=ButtonVariant::Text
+													on_click=Callback::new(move |_: ev::MouseEvent| {
+														set_watching_action
+															.dispatch(SetWatching {
+																id,
+																watching: false,
+															});
+													})
+												>
+													Unwatch
+												</Button>
+											</li>
+										}
+									})
+									.collect_view()}
+							</ul>
+						}
+							.into_view()
+					})
+			}}
+		</Transition>
+	}
+}
+
+#[component]
+fn NotificationItem(
+	notification: NotificationData,
+	mark_read_action: Action<MarkNotificationsRead, Result<(), ServerFnError>>,
+) -> impl IntoView {
+	let id = notification.id;
+	let unread = notification.is_unread();
+	let equipment = match (notification.equipment, notification.equipment_name) {
+		(Some(equipment), Some(name)) => {
+			view! { <A href=format!("/equipment/{equipment}")>{format!("#{equipment} {name}:")}</A> }.into_view()
+		},
+		_ => view! {}.into_view(),
+	};
+	let message = match &notification.actor {
+		Some(actor) => format!("{} {}", actor.preferred_name, notification.message),
+		None => notification.message.clone(),
+	};
+
+	view! {
+		<li class=if unread { format!("{} {}", css::notification, css::unread) } else { css::notification.to_string() }>
+			{notification.actor.map(|actor| view! { <Avatar data=actor tiny=true /> })}
+			<span class=css::message>{equipment} " " {message}</span>
+			<span class=css::muted>{notification.create_date.format("%d %b %Y %H:%M").to_string()}</span>
+			<Show when=move || unread>
+				<Button
+					variant=ButtonVariant::Text
+					on_click=Callback::new(move |_: ev::MouseEvent| {
+						mark_read_action.dispatch(MarkNotificationsRead { id: Some(id) });
+					})
+				>
+					Mark read
+				</Button>
+			</Show>
+		</li>
+	}
+}
+
+#[server(prefix = "/api")]
+pub async fn get_notifications() -> Result<Vec<NotificationData>, ServerFnError> {
+	use crate::auth::get_user;
+
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	let user = user.ok_or_else::<ServerFnError, _>(|| ServerFnError::Request(String::from("User not authenticated")))?;
+
+	Ok(
+		sqlx::query_as::<_, NotificationData>(
+			r#"
+			SELECT
+				notifications.*,
+				equipment.name AS equipment_name,
+				people.id AS actor_id,
+				people.status AS actor_status,
+				people.preferred_name AS actor_preferred_name,
+				people.picture AS actor_picture
+			FROM
+				notifications
+				LEFT JOIN equipment ON notifications.equipment = equipment.id
+				LEFT JOIN people ON notifications.actor = people.id
+			WHERE notifications.person = $1
+			ORDER BY notifications.id DESC
+			LIMIT $2
+			"#,
+		)
+		.bind(user.id)
+		.bind(NOTIFICATIONS_SHOWN)
+		.fetch_all(&pool)
+		.await?,
+	)
+}
+
+#[server(prefix = "/api")]
+pub async fn get_unread_notification_count() -> Result<i64, ServerFnError> {
+	use crate::auth::get_user;
+
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	let user = user.ok_or_else::<ServerFnError, _>(|| ServerFnError::Request(String::from("User not authenticated")))?;
+
+	Ok(
+		sqlx::query_scalar("SELECT COUNT(*) FROM notifications WHERE person = $1 AND read_at IS NULL")
+			.bind(user.id)
+			.fetch_one(&pool)
+			.await?,
+	)
+}
+
+/// Marks one notification as read or all of them when no id is given
+#[server(prefix = "/api")]
+pub async fn mark_notifications_read(id: Option<i32>) -> Result<(), ServerFnError> {
+	use crate::auth::get_user;
+
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	let user = user.ok_or_else::<ServerFnError, _>(|| ServerFnError::Request(String::from("User not authenticated")))?;
+
+	sqlx::query(
+		r#"
+		UPDATE notifications SET read_at = NOW()
+		WHERE person = $1 AND read_at IS NULL AND ($2::INT IS NULL OR id = $2)
+		"#,
+	)
+	.bind(user.id)
+	.bind(id)
+	.execute(&pool)
+	.await?;
+
+	Ok(())
+}
+
+/// The id and name of every equipment item the logged in person watches
+#[server(prefix = "/api")]
+pub async fn get_watched_equipment() -> Result<Vec<(i32, String)>, ServerFnError> {
+	use crate::{
+		auth::get_user,
+		permission::{BindFilter, Dimension, Permissions},
+	};
+
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	let user = user.ok_or_else::<ServerFnError, _>(|| ServerFnError::Request(String::from("User not authenticated")))?;
+	let Permissions::All {
+		read: perm,
+		write: _,
+		create: _,
+	} = user.permission_equipment;
+	// Watches outlive a loss of read access, the equipment just stops showing up
+	let auth_filter = perm.get_query_filter(Dimension::Equipment, "equipment.id");
+	let auth_query = auth_filter.and_clause(2);
+
+	Ok(
+		sqlx::query_as(&format!(
+			r#"
+			SELECT equipment.id, equipment.name
+			FROM
+				equipment_watchers
+				JOIN equipment ON equipment_watchers.equipment = equipment.id
+			WHERE equipment_watchers.person = $1 {auth_query}
+			ORDER BY equipment.name
+			"#
+		))
+		.bind(user.id)
+		.bind_filter(&auth_filter)
+		.fetch_all(&pool)
+		.await?,
+	)
+}
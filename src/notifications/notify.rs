@@ -0,0 +1,61 @@
+use crate::{
+	auth::User,
+	equipment::PeopleStatus,
+	notifications::NotificationKind,
+	permission::{Dimension, Permissions, Subject},
+};
+
+use sqlx::PgConnection;
+
+/// Notifies the owner and watchers of an equipment item, leaving out whoever caused it.
+/// Watchers who can't read the equipment anymore are skipped so nothing leaks after losing access.
+pub async fn notify_equipment(
+	connection: &mut PgConnection,
+	equipment: i32,
+	actor: Option<i32>,
+	kind: NotificationKind,
+	message: &str,
+) -> Result<(), sqlx::Error> {
+	let recipients: Vec<i32> = sqlx::query_scalar(
+		r#"
+		SELECT recipient
+		FROM (
+			SELECT person AS recipient FROM equipment WHERE id = $1
+			UNION
+			SELECT person FROM equipment_watchers WHERE equipment = $1
+		) recipients
+		WHERE $2::INT IS NULL OR recipient <> $2
+		"#,
+	)
+	.bind(equipment)
+	.bind(actor)
+	.fetch_all(&mut *connection)
+	.await?;
+
+	let subject = Subject::load(Dimension::Equipment, equipment, &mut *connection).await?;
+
+	for recipient in recipients {
+		let Some(user) = User::get_from_id_on(recipient, connection).await else {
+			continue;
+		};
+		let Permissions::All {
+			read: perm,
+			write: _,
+			create: _,
+		} = user.permission_equipment;
+		if user.status == PeopleStatus::Left || !perm.has_permission_on("read", &subject) {
+			continue;
+		}
+
+		sqlx::query("INSERT INTO notifications (person, equipment, actor, kind, message) VALUES ($1, $2, $3, $4, $5)")
+			.bind(recipient)
+			.bind(equipment)
+			.bind(actor)
+			.bind(kind.to_string())
+			.bind(message)
+			.execute(&mut *connection)
+			.await?;
+	}
+
+	Ok(())
+}
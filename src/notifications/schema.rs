@@ -0,0 +1,106 @@
+use crate::equipment::AvatarData;
+#[cfg(feature = "ssr")]
+use crate::equipment::AvatarSQLData;
+
+use chrono::prelude::*;
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "ssr")]
+use sqlx::Row;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum NotificationKind {
+	#[default]
+	Status,
+	Note,
+	Warranty,
+	Maintenance,
+}
+
+impl NotificationKind {
+	pub fn parse(input: &str) -> Self {
+		match input.to_lowercase().as_str() {
+			"note" => NotificationKind::Note,
+			"warranty" => NotificationKind::Warranty,
+			"maintenance" => NotificationKind::Maintenance,
+			_ => NotificationKind::Status,
+		}
+	}
+}
+
+impl std::fmt::Display for NotificationKind {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			NotificationKind::Status => write!(f, "status"),
+			NotificationKind::Note => write!(f, "note"),
+			NotificationKind::Warranty => write!(f, "warranty"),
+			NotificationKind::Maintenance => write!(f, "maintenance"),
+		}
+	}
+}
+
+/// Something that happened to equipment a person owns or watches
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NotificationData {
+	pub id: i32,
+	pub kind: NotificationKind,
+	pub equipment: Option<i32>,
+	pub equipment_name: Option<String>,
+	/// Who caused it, `None` for reminders
+	pub actor: Option<AvatarData>,
+	pub message: String,
+	pub read_at: Option<DateTime<Utc>>,
+	pub create_date: DateTime<Utc>,
+}
+
+#[cfg(feature = "ssr")]
+impl sqlx::FromRow<'_, sqlx::postgres::PgRow> for NotificationData {
+	fn from_row(row: &sqlx::postgres::PgRow) -> Result<Self, sqlx::Error> {
+		let actor_id: Option<i32> = row.try_get("actor_id")?;
+		let actor = match actor_id {
+			Some(id) => Some(
+				AvatarSQLData {
+					id,
+					status: row.try_get("actor_status")?,
+					preferred_name: row.try_get("actor_preferred_name")?,
+					picture: row.try_get("actor_picture")?,
+				}
+				.into(),
+			),
+			None => None,
+		};
+
+		Ok(NotificationData {
+			id: row.try_get("id")?,
+			kind: NotificationKind::parse(row.try_get("kind")?),
+			equipment: row.try_get("equipment")?,
+			equipment_name: row.try_get("equipment_name")?,
+			actor,
+			message: row.try_get("message")?,
+			read_at: row.try_get("read_at")?,
+			create_date: row.try_get("create_date")?,
+		})
+	}
+}
+
+impl NotificationData {
+	pub fn is_unread(&self) -> bool {
+		self.read_at.is_none()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn notification_kind_round_trip_test() {
+		for kind in [
+			NotificationKind::Status,
+			NotificationKind::Note,
+			NotificationKind::Warranty,
+			NotificationKind::Maintenance,
+		] {
+			assert_eq!(NotificationKind::parse(&kind.to_string()), kind);
+		}
+	}
+}
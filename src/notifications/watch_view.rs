@@ -0,0 +1,108 @@
+use crate::components::button::{Button, ButtonVariant};
+
+use leptos::*;
+
+/// Lets a person watch an equipment item to be notified about it like its owner
+#[component]
+pub fn WatchToggle(id: i32) -> impl IntoView {
+	let set_watching_action = create_server_action::<SetWatching>();
+	let watching_data = create_resource(move || set_watching_action.version().get(), move |_| get_watching(id));
+
+	view! {
+		<Transition>
+			{move || {
+				watching_data
+					.get()
+					.and_then(Result::ok)
+					.map(|watching| {
+						view! {
+							<Button
+								variant=ButtonVariant::Outlined
+								on_click=Callback::new(move |_: ev::MouseEvent| {
+									set_watching_action
+										.dispatch(SetWatching {
+											id,
+											watching: !watching,
+										});
+								})
+							>
+								{if watching { "Unwatch" } else { "Watch" }}
+							</Button>
+						}
+					})
+			}}
+		</Transition>
+	}
+}
+
+#[server(prefix = "/api")]
+pub async fn get_watching(id: i32) -> Result<bool, ServerFnError> {
+	use crate::auth::get_user;
+
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	let user = user.ok_or_else::<ServerFnError, _>(|| ServerFnError::Request(String::from("User not authenticated")))?;
+
+	Ok(
+		sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM equipment_watchers WHERE equipment = $1 AND person = $2)")
+			.bind(id)
+			.bind(user.id)
+			.fetch_one(&pool)
+			.await?,
+	)
+}
+
+#[server(prefix = "/api")]
+pub async fn set_watching(id: i32, watching: bool) -> Result<(), ServerFnError> {
+	use crate::{
+		auth::get_user,
+		permission::{Dimension, Permissions, Subject},
+	};
+
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	let user_id;
+	match user {
+		Some(user) => {
+			let Permissions::All {
+				read: perm,
+				write: _,
+				create: _,
+			} = user.permission_equipment;
+			user_id = user.id;
+
+			// Unwatching is always allowed so people can let go of equipment they can't see anymore
+			if watching {
+				let subject = Subject::load(Dimension::Equipment, id, &pool).await?;
+				if !perm.has_permission_on("read", &subject) {
+					return Err(ServerFnError::Request(String::from("User not authenticated")));
+				}
+			}
+		},
+		None => return Err(ServerFnError::Request(String::from("User not authenticated"))),
+	};
+
+	if watching {
+		sqlx::query("INSERT INTO equipment_watchers (equipment, person) VALUES ($1, $2) ON CONFLICT DO NOTHING")
+			.bind(id)
+			.bind(user_id)
+			.execute(&pool)
+			.await?;
+	} else {
+		sqlx::query("DELETE FROM equipment_watchers WHERE equipment = $1 AND person = $2")
+			.bind(id)
+			.bind(user_id)
+			.execute(&pool)
+			.await?;
+	}
+
+	Ok(())
+}
@@ -0,0 +1,21 @@
+use crate::equipment::{EquipmentCellView, PeopleStatus};
+
+use leptos::*;
+
+stylance::import_style!(css, "cell.module.css");
+
+impl EquipmentCellView for PeopleStatus {
+	fn view(self, _: bool) -> impl IntoView {
+		match self {
+			PeopleStatus::Active => {
+				view! { <div class=css::active>"Active"</div> }
+			},
+			PeopleStatus::OnLeave => {
+				view! { <div class=css::on_leave>"On" "\u{00A0}" "Leave"</div> }
+			},
+			PeopleStatus::Left => {
+				view! { <div class=css::left>"Left"</div> }
+			},
+		}
+	}
+}
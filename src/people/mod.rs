@@ -0,0 +1,53 @@
+pub mod cell {
+	pub mod cell_view;
+}
+
+pub mod people_view;
+pub use people_view::*;
+
+pub mod people_detail {
+	pub mod deactivate_view;
+	pub mod field_edit_view;
+	pub mod hire_date_edit_view;
+	pub mod people_detail_view;
+	pub mod people_form_toggle_view;
+	pub mod permissions_edit_view;
+	pub mod roles_edit_view;
+	pub mod status_edit_view;
+}
+pub use people_detail::deactivate_view::*;
+pub use people_detail::field_edit_view::*;
+pub use people_detail::hire_date_edit_view::*;
+pub use people_detail::people_detail_view::*;
+pub use people_detail::people_form_toggle_view::*;
+pub use people_detail::permissions_edit_view::*;
+pub use people_detail::roles_edit_view::*;
+pub use people_detail::status_edit_view::*;
+
+pub mod people_add {
+	pub mod people_add_view;
+}
+pub use people_add::people_add_view::*;
+
+pub mod heading {
+	pub mod heading_view;
+}
+pub use heading::heading_view::*;
+
+pub mod row {
+	pub mod row_view;
+}
+pub use row::row_view::*;
+
+pub mod roles {
+	pub mod roles_view;
+}
+pub use roles::roles_view::*;
+
+pub mod role_detail {
+	pub mod role_detail_view;
+}
+pub use role_detail::role_detail_view::*;
+
+pub mod schema;
+pub use schema::*;
@@ -0,0 +1,272 @@
+use crate::{
+	app::UserSignal,
+	components::{button::Button, input::Input},
+	icons::PeopleLogo,
+	login::Login,
+	people::Heading,
+	permission::{Permission, Permissions},
+};
+
+use leptos::*;
+use leptos_router::*;
+
+stylance::import_style!(css, "../../equipment/equipment_add/equipment_add.module.css");
+
+#[component]
+pub fn PeopleAdd() -> impl IntoView {
+	let add_people_action = create_server_action::<AddPeople>();
+	let user_signal = use_context::<UserSignal>().expect("No user signal found in context");
+
+	let loading = create_rw_signal(false);
+
+	let navigate = use_navigate();
+	create_effect(move |_| {
+		if let Some(Ok(id)) = add_people_action.value().get() {
+			navigate(&format!("/people/{id}"), NavigateOptions::default());
+		}
+	});
+
+	create_effect(move |_| {
+		if !add_people_action.pending().get() {
+			loading.set(false);
+		}
+	});
+
+	view! {
+		<Heading hide_new=true>
+			<PeopleLogo />
+			" Add new Person"
+		</Heading>
+
+		<Suspense fallback=move || {
+			view! { <Login redirect="/people/add" /> }
+		}>
+			{move || {
+				match user_signal.get() {
+					None => view! { <Login redirect="/people/add" /> }.into_view(),
+					Some(user) => {
+						let Permissions::All { read: _, write: _, create: perm } = user.permission_people;
+						if perm != Permission::Create(true) {
+							view! { <span>"You don't have permission to add new People"</span> }.into_view()
+						} else {
+							view! {
+								<ActionForm
+									action=add_people_action
+									class=css::form
+									on:submit=move |_| loading.set(true)
+								>
+									<label class=css::label>
+										<span class=css::text>Username:</span>
+										<span class=css::input>
+											<Input name="username" placeholder="Username" required=true />
+										</span>
+									</label>
+
+									<label class=css::label>
+										<span class=css::text>Password:</span>
+										<span class=css::input>
+											<Input name="password" kind="password" placeholder="Password" required=true />
+										</span>
+									</label>
+
+									<label class=css::label>
+										<span class=css::text>Confirm Password:</span>
+										<span class=css::input>
+											<Input
+												name="password_confirmation"
+												kind="password"
+												placeholder="Confirm Password"
+												required=true
+											/>
+										</span>
+									</label>
+
+									<label class=css::label>
+										<span class=css::text>Preferred Name:</span>
+										<span class=css::input>
+											<Input name="preferred_name" placeholder="Preferred Name" required=true />
+										</span>
+									</label>
+
+									<label class=css::label>
+										<span class=css::text>Email:</span>
+										<span class=css::input>
+											<Input name="email" kind="email" placeholder="Email" required=true />
+										</span>
+									</label>
+
+									<label class=css::label>
+										<span class=css::text>First Name:</span>
+										<span class=css::input>
+											<Input name="first_name" placeholder="First Name" />
+										</span>
+									</label>
+
+									<label class=css::label>
+										<span class=css::text>Last Name:</span>
+										<span class=css::input>
+											<Input name="last_name" placeholder="Last Name" />
+										</span>
+									</label>
+
+									<label class=css::label>
+										<span class=css::text>Employee ID:</span>
+										<span class=css::input>
+											<Input name="employee_id" placeholder="Employee ID" />
+										</span>
+									</label>
+
+									<label class=css::label>
+										<span class=css::text>Department:</span>
+										<span class=css::input>
+											<Input name="department" placeholder="Department" />
+										</span>
+									</label>
+
+									<label class=css::label>
+										<span class=css::text>Role:</span>
+										<span class=css::input>
+											<Input name="role" placeholder="Role" />
+										</span>
+									</label>
+
+									<div class=css::btn_row>
+										{move || {
+											if let Some(responds) = add_people_action.value().get() {
+												match responds {
+													Ok(_) => view! {}.into_view(),
+													Err(error) => {
+														view! {
+															<span class=css::error>
+																{error
+																	.to_string()
+																	.replace(
+																		"error reaching server to call server function: ",
+																		"",
+																	)}
+															</span>
+														}
+															.into_view()
+													}
+												}
+											} else {
+												view! {}.into_view()
+											}
+										}} <Button kind="submit" loading=loading>
+											Add
+										</Button>
+									</div>
+								</ActionForm>
+							}
+								.into_view()
+						}
+					}
+				}
+			}}
+		</Suspense>
+	}
+}
+
+#[allow(clippy::too_many_arguments)]
+#[server(prefix = "/api")]
+pub async fn add_people(
+	username: String,
+	password: String,
+	password_confirmation: String,
+	preferred_name: String,
+	email: String,
+	first_name: String,
+	last_name: String,
+	employee_id: String,
+	department: String,
+	role: String,
+) -> Result<i32, ServerFnError> {
+	use crate::{
+		auth::{get_user, ssr::*},
+		equipment::PeopleStatus,
+		permission::{Permission, Permissions},
+	};
+
+	use server_fn::error::NoCustomError;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	match user {
+		Some(user) => {
+			let Permissions::All {
+				read: _,
+				write: _,
+				create: perm,
+			} = user.permission_people;
+			if perm != Permission::Create(true) {
+				return Err(ServerFnError::Request(String::from("User not authenticated")));
+			}
+		},
+		None => return Err(ServerFnError::Request(String::from("User not authenticated"))),
+	};
+
+	let username = username.trim().to_string();
+	let preferred_name = preferred_name.trim().to_string();
+	let email = email.trim().to_string();
+
+	if username.is_empty() || preferred_name.is_empty() || email.is_empty() {
+		return Err(ServerFnError::Request(String::from("Username, preferred name and email are required")));
+	}
+
+	if !crate::email::is_valid_address(&email) {
+		return Err(ServerFnError::Request(String::from("The email is not a valid email address")));
+	}
+
+	if password.trim().is_empty() {
+		return Err(ServerFnError::Request(String::from("The password can't be empty")));
+	}
+
+	if password != password_confirmation {
+		return Err(ServerFnError::Request(String::from("Passwords did not match")));
+	}
+
+	let salt = SaltString::generate(&mut OsRng);
+
+	let password_hashed = Argon2::default()
+		.hash_password(password.as_bytes(), &salt)
+		.map_err(|error| ServerFnError::<NoCustomError>::ServerError(format!("Hashing error: {}", error)))?
+		.to_string();
+
+	let optional = |value: String| {
+		let value = value.trim().to_string();
+		if value.is_empty() { None } else { Some(value) }
+	};
+
+	// New people start without access until someone grants them permissions
+	let id: i32 = sqlx::query_scalar(
+		"INSERT INTO people\
+		(username, password, status, preferred_name, email, first_name, last_name, employee_id, department, role, \
+		permission_equipment, permission_people, permission_culture, permission_experiment)\
+		VALUES\
+		($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $11, $11, $11)\
+		RETURNING id",
+	)
+	.bind(username)
+	.bind(password_hashed)
+	.bind(format!("{:#?}", PeopleStatus::Active))
+	.bind(preferred_name)
+	.bind(email)
+	.bind(optional(first_name))
+	.bind(optional(last_name))
+	.bind(optional(employee_id))
+	.bind(optional(department))
+	.bind(optional(role))
+	.bind(Permissions::none().to_string())
+	.fetch_one(&pool)
+	.await
+	.map_err::<ServerFnError, _>(|error| match error.as_database_error().and_then(|error| error.code()) {
+		Some(code) if code == "23505" => {
+			ServerFnError::Request(String::from("The username, email or employee ID is already in use"))
+		},
+		_ => ServerFnError::ServerError(error.to_string()),
+	})?;
+
+	Ok(id)
+}
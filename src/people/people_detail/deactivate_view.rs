@@ -0,0 +1,113 @@
+use crate::{
+	app::UserSignal,
+	components::button::{Button, ButtonVariant},
+	equipment::{PeopleData, PeopleStatus},
+	permission::{Permissions, Subject, SubjectSignal},
+};
+
+use leptos::*;
+use leptos_router::*;
+
+stylance::import_style!(css, "../../equipment/equipment_detail/equipment_details_edits.module.css");
+
+/// Marks a person as having left which also stops them from logging in
+#[component]
+pub fn Deactivate(people: PeopleData, user_signal: UserSignal, refetch_resources: RwSignal<usize>) -> impl IntoView {
+	let deactivate_action = create_server_action::<DeactivatePeople>();
+	let subject_signal = use_context::<SubjectSignal>();
+
+	let can_deactivate = move || match user_signal.get() {
+		Some(user) => {
+			let Permissions::All {
+				read: _,
+				write: perm,
+				create: _,
+			} = user.permission_people;
+			user.id != people.id
+				&& people.status != PeopleStatus::Left
+				&& perm.has_permission_on("write", &Subject::resolve(subject_signal, people.id, people.id))
+		},
+		None => false,
+	};
+
+	view! {
+		<Show when=can_deactivate>
+			<ActionForm action=deactivate_action class=css::edit_form>
+				<input type="hidden" name="id" value=people.id />
+				<div class=css::btns>
+					{move || {
+						if let Some(responds) = deactivate_action.value().get() {
+							match responds {
+								Ok(_) => {
+									deactivate_action.value().set(None);
+									refetch_resources.update(|version| *version += 1);
+									view! {}.into_view()
+								}
+								Err(error) => {
+									view! {
+										<span>
+											{error.to_string().replace("error reaching server to call server function: ", "")}
+										</span>
+									}
+										.into_view()
+								}
+							}
+						} else {
+							view! {}.into_view()
+						}
+					}} <Button kind="submit" variant=ButtonVariant::Outlined>
+						Deactivate
+					</Button>
+				</div>
+			</ActionForm>
+		</Show>
+	}
+}
+
+#[server(prefix = "/api")]
+pub async fn deactivate_people(id: String) -> Result<(), ServerFnError> {
+	use crate::{
+		auth::get_user,
+		permission::{Dimension, Permissions, Subject},
+	};
+
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	let id = match id.parse::<i32>() {
+		Ok(value) => value,
+		Err(_) => return Err(ServerFnError::Request(String::from("Invalid ID"))),
+	};
+
+	match user {
+		Some(user) => {
+			let Permissions::All {
+				read: _,
+				write: perm,
+				create: _,
+			} = user.permission_people;
+
+			let subject = Subject::load(Dimension::People, id, &pool).await?;
+			if !perm.has_permission_on("write", &subject) {
+				return Err(ServerFnError::Request(String::from("User not authenticated")));
+			}
+
+			if user.id == id {
+				return Err(ServerFnError::Request(String::from("You can't deactivate yourself")));
+			}
+		},
+		None => return Err(ServerFnError::Request(String::from("User not authenticated"))),
+	};
+
+	sqlx::query("UPDATE people SET status = $1 WHERE id = $2")
+		.bind(format!("{:#?}", PeopleStatus::Left))
+		.bind(id)
+		.execute(&pool)
+		.await
+		.map(|_| ())?;
+
+	Ok(())
+}
@@ -0,0 +1,149 @@
+use crate::{
+	app::UserSignal,
+	components::{
+		button::Button,
+		input::{Input, TextArea},
+	},
+	equipment::PeopleData,
+	people::PeopleFormToggle,
+};
+
+use leptos::*;
+use leptos_router::*;
+
+stylance::import_style!(css, "../../equipment/equipment_detail/equipment_details_edits.module.css");
+
+#[component]
+pub fn PeopleFieldEdit(
+	people: PeopleData,
+	field: &'static str,
+	value: Option<String>,
+	user_signal: UserSignal,
+	refetch_resources: RwSignal<usize>,
+	#[prop(optional)] multiline: bool,
+) -> impl IntoView {
+	let field_action = create_server_action::<EditPeopleField>();
+
+	view! {
+		<PeopleFormToggle user_id=people.id id=people.id user_signal=user_signal item=value.clone()>
+			{
+				let value_clone = value.clone().unwrap_or_default();
+				view! {
+					<ActionForm action=field_action class=css::edit_form>
+						<input type="hidden" name="id" value=people.id />
+						<input type="hidden" name="field" value=field />
+						{if multiline {
+							view! { <TextArea name="value" value=create_rw_signal(value_clone) /> }.into_view()
+						} else {
+							view! { <Input name="value" value=create_rw_signal(value_clone) /> }.into_view()
+						}}
+						<div class=css::btns>
+							{move || {
+								if let Some(responds) = field_action.value().get() {
+									match responds {
+										Ok(_) => {
+											field_action.value().set(None);
+											refetch_resources.update(|version| *version += 1);
+											view! {}.into_view()
+										}
+										Err(error) => {
+											view! {
+												<span>
+													{error
+														.to_string()
+														.replace("error reaching server to call server function: ", "")}
+												</span>
+											}
+												.into_view()
+										}
+									}
+								} else {
+									view! {}.into_view()
+								}
+							}} <Button kind="submit">Save</Button>
+						</div>
+					</ActionForm>
+				}
+			}
+		</PeopleFormToggle>
+	}
+}
+
+#[server(prefix = "/api")]
+pub async fn edit_people_field(id: String, field: String, value: String) -> Result<(), ServerFnError> {
+	use crate::{
+		auth::get_user,
+		permission::{Dimension, Permissions, Subject},
+	};
+
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	let id = match id.parse::<i32>() {
+		Ok(value) => value,
+		Err(_) => return Err(ServerFnError::Request(String::from("Invalid ID"))),
+	};
+
+	match user {
+		Some(user) => {
+			let Permissions::All {
+				read: _,
+				write: perm,
+				create: _,
+			} = user.permission_people;
+
+			let subject = Subject::load(Dimension::People, id, &pool).await?;
+			if !perm.has_permission_on("write", &subject) {
+				return Err(ServerFnError::Request(String::from("User not authenticated")));
+			}
+		},
+		None => return Err(ServerFnError::Request(String::from("User not authenticated"))),
+	};
+
+	let (field_sanitized, required) = match field.to_lowercase().as_str() {
+		"preferred_name" => ("preferred_name", true),
+		"email" => ("email", true),
+		"employee_id" => ("employee_id", false),
+		"first_name" => ("first_name", false),
+		"last_name" => ("last_name", false),
+		"phone_number" => ("phone_number", false),
+		"department" => ("department", false),
+		"role" => ("role", false),
+		"emergency_contact" => ("emergency_contact", false),
+		"certifications" => ("certifications", false),
+		"specializations" => ("specializations", false),
+		"bio" => ("bio", false),
+		_ => return Err(ServerFnError::Request(String::from("Invalid field"))),
+	};
+
+	let value = value.trim();
+	let value = if value.is_empty() {
+		if required {
+			return Err(ServerFnError::Request(format!("The {} can't be empty", field_sanitized.replace('_', " "))));
+		}
+		None
+	} else {
+		Some(value)
+	};
+
+	if field_sanitized == "email" && !value.is_some_and(crate::email::is_valid_address) {
+		return Err(ServerFnError::Request(String::from("The email is not a valid email address")));
+	}
+
+	sqlx::query(&format!("UPDATE people SET {field_sanitized} = $1 WHERE id = $2"))
+		.bind(value)
+		.bind(id)
+		.execute(&pool)
+		.await
+		.map_err::<ServerFnError, _>(|error| match error.as_database_error().and_then(|error| error.code()) {
+			Some(code) if code == "23505" => {
+				ServerFnError::Request(format!("This {} is already in use", field_sanitized.replace('_', " ")))
+			},
+			_ => ServerFnError::ServerError(error.to_string()),
+		})?;
+
+	Ok(())
+}
@@ -0,0 +1,126 @@
+use crate::{
+	app::UserSignal,
+	components::{button::Button, datepicker::DatePicker, timezone_offset::Timezone},
+	equipment::PeopleData,
+	people::PeopleFormToggle,
+};
+
+use leptos::*;
+use leptos_router::*;
+
+stylance::import_style!(css, "../../equipment/equipment_detail/equipment_details_edits.module.css");
+
+#[component]
+pub fn HireDateEdit(people: PeopleData, user_signal: UserSignal, refetch_resources: RwSignal<usize>) -> impl IntoView {
+	let hire_date_action = create_server_action::<EditPeopleHireDate>();
+
+	view! {
+		<PeopleFormToggle
+			user_id=people.id
+			id=people.id
+			user_signal=user_signal
+			item=people.hire_date
+		>
+			{
+				let hire_date_clone = people.hire_date;
+				view! {
+					<ActionForm action=hire_date_action class=css::edit_form>
+						<input type="hidden" name="id" value=people.id />
+						<Timezone />
+						<DatePicker
+							attr:name="hire_date"
+							value=create_rw_signal(hire_date_clone.map(|date| date.date_naive()))
+						/>
+						<div class=css::btns>
+							{move || {
+								if let Some(responds) = hire_date_action.value().get() {
+									match responds {
+										Ok(_) => {
+											hire_date_action.value().set(None);
+											refetch_resources.update(|version| *version += 1);
+											view! {}.into_view()
+										}
+										Err(error) => {
+											view! {
+												<span>
+													{error
+														.to_string()
+														.replace("error reaching server to call server function: ", "")}
+												</span>
+											}
+												.into_view()
+										}
+									}
+								} else {
+									view! {}.into_view()
+								}
+							}} <Button kind="submit">Save</Button>
+						</div>
+					</ActionForm>
+				}
+			}
+		</PeopleFormToggle>
+	}
+}
+
+#[server(prefix = "/api")]
+pub async fn edit_people_hire_date(id: String, hire_date: String, timezone_offset: i32) -> Result<(), ServerFnError> {
+	use crate::{
+		auth::get_user,
+		permission::{Dimension, Permissions, Subject},
+	};
+
+	use chrono::prelude::*;
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	let id = match id.parse::<i32>() {
+		Ok(value) => value,
+		Err(_) => return Err(ServerFnError::Request(String::from("Invalid ID"))),
+	};
+
+	match user {
+		Some(user) => {
+			let Permissions::All {
+				read: _,
+				write: perm,
+				create: _,
+			} = user.permission_people;
+
+			let subject = Subject::load(Dimension::People, id, &pool).await?;
+			if !perm.has_permission_on("write", &subject) {
+				return Err(ServerFnError::Request(String::from("User not authenticated")));
+			}
+		},
+		None => return Err(ServerFnError::Request(String::from("User not authenticated"))),
+	};
+
+	let hours = timezone_offset / 60;
+	let minutes = timezone_offset % 60;
+	let offset_str = format!("{:+03}:{:02}", hours, minutes.abs());
+	let hire_date_with_tz = format!("{}T00:00:00{}", hire_date, offset_str);
+
+	let hire_date: Option<DateTime<Utc>> = if hire_date.is_empty() {
+		None
+	} else {
+		Some(
+			match DateTime::parse_from_str(&hire_date_with_tz, "%Y-%m-%dT%H:%M:%S%z") {
+				Ok(date) => date,
+				Err(error) => return Err(ServerFnError::Request(format!("Invalid date: {}", error))),
+			}
+			.with_timezone(&Utc),
+		)
+	};
+
+	sqlx::query("UPDATE people SET hire_date = $1 WHERE id = $2")
+		.bind(hire_date)
+		.bind(id)
+		.execute(&pool)
+		.await
+		.map(|_| ())?;
+
+	Ok(())
+}
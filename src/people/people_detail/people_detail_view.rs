@@ -0,0 +1,326 @@
+use crate::{
+	app::{LoginAction, UserSignal},
+	components::avatar::Avatar,
+	equipment::{AvatarData, EquipmentCell, PeopleData},
+	error_template::ErrorTemplate,
+	icons::PeopleLogo,
+	login::Login,
+	people::{
+		Deactivate, Heading, HireDateEdit, PeopleFieldEdit, PeopleRolesEdit, PeopleStatusEdit, PermissionTarget,
+		PermissionsEdit,
+	},
+	permission::{Dimension, provide_subject_signal},
+};
+
+use leptos::*;
+use leptos_router::*;
+
+stylance::import_style!(
+	#[allow(dead_code)]
+	css,
+	"../../equipment/equipment_detail/equipment_details.module.css"
+);
+
+#[component]
+pub fn PeopleDetail() -> impl IntoView {
+	let params = use_params_map();
+
+	let refetch_resources = create_rw_signal(0);
+
+	let login_action = use_context::<LoginAction>().expect("No login action found in context");
+	let user_signal = use_context::<UserSignal>().expect("No user signal found in context");
+	provide_subject_signal(
+		Dimension::People,
+		move || params.with(|p| p.get("id").cloned().unwrap_or_default()),
+		refetch_resources,
+	);
+
+	let people_data = create_resource(
+		move || {
+			(login_action.version().get(), params.with(|p| p.get("id").cloned().unwrap_or_default()), refetch_resources.get())
+		},
+		move |(_, id, _)| get_people_data_by_id(id),
+	);
+
+	view! {
+		<Suspense fallback=move || view! { <p>Loading person...</p> }>
+			<ErrorBoundary fallback=|errors| {
+				view! { <ErrorTemplate errors=errors /> }
+			}>
+				{move || {
+					if people_data.get().is_some() {
+						match people_data.get().unwrap() {
+							Err(error) => {
+								let error = error.to_string();
+								if error.contains("User not authenticated") {
+									view! {
+										<Login redirect=format!(
+											"/people/{}",
+											params.with(|p| p.get("id").cloned().unwrap_or_default()),
+										) />
+									}
+								} else {
+									view! { <pre class="error">Server Error: {error}</pre> }.into_view()
+								}
+							}
+							Ok(people) => {
+								let title = people.preferred_name.clone();
+								view! {
+									<div class=css::details>
+										<Heading>
+											<PeopleLogo />
+											" "
+											{title}
+										</Heading>
+
+										<Avatar data=AvatarData {
+											id: people.id,
+											status: people.status,
+											preferred_name: people.preferred_name.clone(),
+											picture: people.picture.clone(),
+										} />
+
+										<dl class=css::list>
+											<dt>ID</dt>
+											<dd>
+												<EquipmentCell cell=people.id />
+											</dd>
+
+											<dt>Status</dt>
+											<dd class=css::edit>
+												<PeopleStatusEdit people=people.clone() user_signal refetch_resources />
+												<Deactivate people=people.clone() user_signal refetch_resources />
+											</dd>
+
+											<dt>Employee ID</dt>
+											<dd class=css::edit>
+												<PeopleFieldEdit
+													people=people.clone()
+													field="employee_id"
+													value=people.employee_id.clone()
+													user_signal
+													refetch_resources
+												/>
+											</dd>
+
+											<dt>Preferred Name</dt>
+											<dd class=css::edit>
+												<PeopleFieldEdit
+													people=people.clone()
+													field="preferred_name"
+													value=Some(people.preferred_name.clone())
+													user_signal
+													refetch_resources
+												/>
+											</dd>
+
+											<dt>First Name</dt>
+											<dd class=css::edit>
+												<PeopleFieldEdit
+													people=people.clone()
+													field="first_name"
+													value=people.first_name.clone()
+													user_signal
+													refetch_resources
+												/>
+											</dd>
+
+											<dt>Last Name</dt>
+											<dd class=css::edit>
+												<PeopleFieldEdit
+													people=people.clone()
+													field="last_name"
+													value=people.last_name.clone()
+													user_signal
+													refetch_resources
+												/>
+											</dd>
+
+											<dt>Email</dt>
+											<dd class=css::edit>
+												<PeopleFieldEdit
+													people=people.clone()
+													field="email"
+													value=Some(people.email.clone())
+													user_signal
+													refetch_resources
+												/>
+											</dd>
+
+											<dt>Phone Number</dt>
+											<dd class=css::edit>
+												<PeopleFieldEdit
+													people=people.clone()
+													field="phone_number"
+													value=people.phone_number.clone()
+													user_signal
+													refetch_resources
+												/>
+											</dd>
+
+											<dt>Department</dt>
+											<dd class=css::edit>
+												<PeopleFieldEdit
+													people=people.clone()
+													field="department"
+													value=people.department.clone()
+													user_signal
+													refetch_resources
+												/>
+											</dd>
+
+											<dt>Role</dt>
+											<dd class=css::edit>
+												<PeopleFieldEdit
+													people=people.clone()
+													field="role"
+													value=people.role.clone()
+													user_signal
+													refetch_resources
+												/>
+											</dd>
+
+											<dt>Hire Date</dt>
+											<dd class=css::edit>
+												<HireDateEdit people=people.clone() user_signal refetch_resources />
+											</dd>
+
+											<dt>Emergency Contact</dt>
+											<dd class=css::edit>
+												<PeopleFieldEdit
+													people=people.clone()
+													field="emergency_contact"
+													value=people.emergency_contact.clone()
+													user_signal
+													refetch_resources
+												/>
+											</dd>
+
+											<dt>Certifications</dt>
+											<dd class=css::edit>
+												<PeopleFieldEdit
+													people=people.clone()
+													field="certifications"
+													value=people.certifications.clone()
+													user_signal
+													refetch_resources
+												/>
+											</dd>
+
+											<dt>Specializations</dt>
+											<dd class=css::edit>
+												<PeopleFieldEdit
+													people=people.clone()
+													field="specializations"
+													value=people.specializations.clone()
+													user_signal
+													refetch_resources
+												/>
+											</dd>
+
+											<dt>Bio</dt>
+											<dd class=css::edit>
+												<PeopleFieldEdit
+													people=people.clone()
+													field="bio"
+													value=people.bio.clone()
+													user_signal
+													refetch_resources
+													multiline=true
+												/>
+											</dd>
+
+											<dt>Create Date</dt>
+											<dd>
+												<EquipmentCell cell=people.create_date />
+											</dd>
+
+											<dt>Access Roles</dt>
+											<dd>
+												<PeopleRolesEdit people=people.clone() user_signal />
+											</dd>
+
+											<dt>Permissions</dt>
+											<dd>
+												<PermissionsEdit target=PermissionTarget::Person id=people.id user_signal />
+											</dd>
+										</dl>
+									</div>
+								}
+									.into_view()
+							}
+						}
+					} else {
+						view! { <div>Nothing found</div> }.into_view()
+					}
+				}}
+			</ErrorBoundary>
+		</Suspense>
+	}
+}
+
+#[server(prefix = "/api")]
+pub async fn get_people_data_by_id(id: String) -> Result<PeopleData, ServerFnError> {
+	use crate::{
+		auth::get_user,
+		equipment::PeopleSQLData,
+		permission::{Dimension, Permissions, Subject},
+	};
+
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	let id = match id.parse::<i32>() {
+		Ok(value) => value,
+		Err(_) => return Err(ServerFnError::Request(String::from("Invalid ID"))),
+	};
+
+	match user {
+		Some(user) => {
+			let Permissions::All {
+				read: perm,
+				write: _,
+				create: _,
+			} = user.permission_people;
+			let subject = Subject::load(Dimension::People, id, &pool).await?;
+			if !perm.has_permission_on("read", &subject) {
+				return Err(ServerFnError::Request(String::from("User not authenticated")));
+			}
+		},
+		None => return Err(ServerFnError::Request(String::from("User not authenticated"))),
+	};
+
+	let people_sql_data = sqlx::query_as::<_, PeopleSQLData>(
+		r#"
+		SELECT
+			id,
+			employee_id,
+			status,
+			first_name,
+			last_name,
+			preferred_name,
+			email,
+			phone_number,
+			department,
+			role,
+			hire_date,
+			emergency_contact,
+			certifications,
+			specializations,
+			picture,
+			bio,
+			create_date
+		FROM
+			people
+		WHERE id = $1"#,
+	)
+	.bind(id)
+	.fetch_one(&pool)
+	.await
+	.map_err::<ServerFnError, _>(|error| ServerFnError::ServerError(error.to_string()))?;
+
+	Ok(people_sql_data.into())
+}
@@ -0,0 +1,551 @@
+use crate::{
+	app::UserSignal,
+	components::{
+		button::{Button, ButtonVariant},
+		input::Input,
+		select::Select,
+	},
+	error_template::ErrorTemplate,
+	permission::{Permission, Permissions, Scope, Subject, SubjectSignal},
+};
+
+use leptos::*;
+use leptos_router::*;
+
+stylance::import_style!(css, "permissions_edit.module.css");
+
+/// What a set of stored permissions belongs to
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PermissionTarget {
+	Person,
+	Role,
+}
+
+impl PermissionTarget {
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			PermissionTarget::Person => "person",
+			PermissionTarget::Role => "role",
+		}
+	}
+}
+
+/// Lists the permissions of a person or role per dimension and lets admins add or remove scopes
+#[component]
+pub fn PermissionsEdit(target: PermissionTarget, id: i32, user_signal: UserSignal) -> impl IntoView {
+	let edit_action = create_server_action::<EditPermission>();
+	let add_scope_action = create_server_action::<AddPermissionScope>();
+	let subject_signal = use_context::<SubjectSignal>();
+
+	let permissions_data = create_resource(
+		move || (edit_action.version().get(), add_scope_action.version().get()),
+		move |_| get_permissions(target.as_str().to_string(), id.to_string()),
+	);
+
+	let can_edit = Signal::derive(move || match user_signal.get() {
+		Some(user) => {
+			let Permissions::All {
+				read: _,
+				write: perm,
+				create,
+			} = user.permission_people;
+			// Changing a role you hold would be the same as changing your own permissions
+			create == Permission::Create(true)
+				&& match target {
+					PermissionTarget::Person => {
+						user.id != id && perm.has_permission_on("write", &Subject::resolve(subject_signal, id, id))
+					},
+					PermissionTarget::Role => !user.roles.contains(&id),
+				}
+		},
+		None => false,
+	});
+
+	view! {
+		<Suspense fallback=move || view! { <p>Loading permissions...</p> }>
+			<ErrorBoundary fallback=|errors| {
+				view! { <ErrorTemplate errors=errors /> }
+			}>
+				{move || {
+					if permissions_data.get().is_some() {
+						match permissions_data.get().unwrap() {
+							Err(error) => {
+								view! { <pre class="error">Permissions Server Error: {error.to_string()}</pre> }
+									.into_view()
+							}
+							Ok(permissions) => {
+								view! {
+									<div class=css::permissions>
+										{permissions
+											.into_iter()
+											.map(|(dimension, raw)| {
+												view! {
+													<PermissionDimension
+														target
+														id
+														dimension
+														raw
+														can_edit
+														edit_action
+														add_scope_action
+													/>
+												}
+											})
+											.collect_view()}
+									</div>
+								}
+									.into_view()
+							}
+						}
+					} else {
+						view! {}.into_view()
+					}
+				}}
+			</ErrorBoundary>
+		</Suspense>
+		{move || {
+			let error = match (edit_action.value().get(), add_scope_action.value().get()) {
+				(Some(Err(error)), _) | (_, Some(Err(error))) => error.to_string(),
+				_ => return view! {}.into_view(),
+			};
+			view! { <span class=css::error>{error.replace("error reaching server to call server function: ", "")}</span> }
+				.into_view()
+		}}
+	}
+}
+
+#[component]
+fn PermissionDimension(
+	target: PermissionTarget,
+	id: i32,
+	dimension: String,
+	raw: String,
+	can_edit: Signal<bool>,
+	edit_action: Action<EditPermission, Result<(), ServerFnError>>,
+	add_scope_action: Action<AddPermissionScope, Result<(), ServerFnError>>,
+) -> impl IntoView {
+	let draft = create_rw_signal(raw.clone());
+	let draft_error = move || Permission::parse(draft.get()).err();
+	let draft_invalid = create_rw_signal(false);
+	create_effect(move |_| draft_invalid.set(draft_error().is_some()));
+
+	let dimension = store_value(dimension);
+	let save = move |permissions: Permissions| {
+		edit_action.dispatch(EditPermission {
+			target: target.as_str().to_string(),
+			id: id.to_string(),
+			dimension: dimension.get_value(),
+			permission: permissions.to_string(),
+		});
+	};
+
+	view! {
+		<section class=css::dimension>
+			<h3 class=css::dimension_heading>{dimension.get_value()}</h3>
+			{match Permission::parse(raw.clone()) {
+				Err(error) => {
+					view! {
+						<span class=css::error>"Stored permission is invalid, access is disabled until it's fixed"</span>
+						<PermissionErrorView raw=raw.clone() column=error.column message=error.to_string() />
+					}
+						.into_view()
+				}
+				Ok(permissions) => {
+					let (read, write, create) = permissions.to_scopes();
+					view! {
+						<ScopeList
+							label="Read"
+							scopes=read.clone()
+							locked=write.clone()
+							can_edit
+							on_remove={
+								let write = write.clone();
+								move |scope| {
+									let read = read.iter().filter(|item| **item != scope).cloned().collect();
+									save(Permissions::from_scopes(read, write.clone(), create));
+								}
+							}
+						/>
+						<ScopeList
+							label="Write"
+							scopes=write.clone()
+							locked=Vec::new()
+							can_edit
+							on_remove={
+								let (read, write) = (permissions.to_scopes().0, write.clone());
+								move |scope| {
+									let write = write.iter().filter(|item| **item != scope).cloned().collect();
+									save(Permissions::from_scopes(read.clone(), write, create));
+								}
+							}
+						/>
+						<div class=css::scopes>
+							<span class=css::label>"Create"</span>
+							<span>{if create { "Yes" } else { "No" }}</span>
+							<Show when=move || can_edit.get()>
+								<Button
+									variant=ButtonVariant::Text
+									on_click={
+										let (read, write) = (permissions.to_scopes().0, permissions.to_scopes().1);
+										Callback::new(move |_| {
+											save(Permissions::from_scopes(read.clone(), write.clone(), !create))
+										})
+									}
+								>
+									{if create { "Revoke" } else { "Grant" }}
+								</Button>
+							</Show>
+						</div>
+					}
+						.into_view()
+				}
+			}}
+			<Show when=move || can_edit.get()>
+				<ActionForm action=add_scope_action class=css::form>
+					<input type="hidden" name="target" value=target.as_str() />
+					<input type="hidden" name="id" value=id />
+					<input type="hidden" name="dimension" value=dimension.get_value() />
+					<Select name="action">
+						<option value="read">"Read"</option>
+						<option value="write">"Write"</option>
+					</Select>
+					<Select name="kind">
+						<option value="equipment">"Item id"</option>
+						<option value="person">"Owner id"</option>
+						<option value="type">"Equipment type"</option>
+						<option value="location">"Location"</option>
+						<option value="department">"Owner department"</option>
+						<option value="any">"Everything"</option>
+					</Select>
+					<Input name="value" placeholder="ID or value" />
+					<Button kind="submit" variant=ButtonVariant::Outlined>
+						Add scope
+					</Button>
+				</ActionForm>
+				<ActionForm action=edit_action class=css::form>
+					<input type="hidden" name="target" value=target.as_str() />
+					<input type="hidden" name="id" value=id />
+					<input type="hidden" name="dimension" value=dimension.get_value() />
+					<span class=css::raw on:input=move |event| draft.set(event_target_value(&event))>
+						<Input name="permission" value=draft />
+					</span>
+					<Button kind="submit" variant=ButtonVariant::Outlined disabled=draft_invalid>
+						Save
+					</Button>
+				</ActionForm>
+				{move || {
+					draft_error()
+						.map(|error| {
+							view! {
+								<PermissionErrorView raw=draft.get() column=error.column message=error.to_string() />
+							}
+						})
+				}}
+			</Show>
+		</section>
+	}
+}
+
+#[component]
+fn ScopeList(
+	label: &'static str,
+	scopes: Vec<Scope>,
+	/// Scopes that can't be removed here because they are implied by another action
+	locked: Vec<Scope>,
+	can_edit: Signal<bool>,
+	on_remove: impl Fn(Scope) + Clone + 'static,
+) -> impl IntoView {
+	let removable = scopes.len() > 1;
+
+	view! {
+		<div class=css::scopes>
+			<span class=css::label>{label}</span>
+			{scopes
+				.into_iter()
+				.map(|scope| {
+					let on_remove = on_remove.clone();
+					let is_locked = locked.contains(&scope);
+					view! {
+						<span class=css::scope>
+							{match &scope {
+								Scope::Any => String::from("everything"),
+								Scope::Equipment(id) => format!("item {id}"),
+								Scope::Person(id) => format!("owner {id}"),
+								Scope::EquipmentType(value) => format!("type {value}"),
+								Scope::Location(value) => format!("location {value}"),
+								Scope::Department(value) => format!("department {value}"),
+							}}
+							<Show when=move || can_edit.get() && removable && !is_locked>
+								<button
+									class=css::remove
+									type="button"
+									title="Remove scope"
+									on:click={
+										let (on_remove, scope) = (on_remove.clone(), scope.clone());
+										move |_| on_remove(scope.clone())
+									}
+								>
+									"×"
+								</button>
+							</Show>
+						</span>
+					}
+				})
+				.collect_view()}
+		</div>
+	}
+}
+
+/// Shows the raw permission string with a marker under the column the parser stopped at
+#[component]
+fn PermissionErrorView(raw: String, column: usize, message: String) -> impl IntoView {
+	view! {
+		<pre class=css::parse_error>{raw}"\n"{" ".repeat(column.saturating_sub(1))}"^ "{message}</pre>
+	}
+}
+
+#[server(prefix = "/api")]
+pub async fn get_permissions(target: String, id: String) -> Result<Vec<(String, String)>, ServerFnError> {
+	use crate::{
+		auth::get_user,
+		permission::{Dimension, Permissions, Subject},
+	};
+
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	let id = match id.parse::<i32>() {
+		Ok(value) => value,
+		Err(_) => return Err(ServerFnError::Request(String::from("Invalid ID"))),
+	};
+
+	let table = get_permission_table(&target)?;
+
+	match user {
+		Some(user) => {
+			let Permissions::All {
+				read: perm,
+				write: _,
+				create: _,
+			} = user.permission_people;
+			// Roles are shared configuration so anyone logged in may see what they grant
+			if table == "people" && !perm.has_permission_on("read", &Subject::load(Dimension::People, id, &pool).await?) {
+				return Err(ServerFnError::Request(String::from("User not authenticated")));
+			}
+		},
+		None => return Err(ServerFnError::Request(String::from("User not authenticated"))),
+	};
+
+	let (equipment, people, culture, experiment): (String, String, String, String) = sqlx::query_as(&format!(
+		"SELECT permission_equipment, permission_people, permission_culture, permission_experiment FROM {table} WHERE id = $1"
+	))
+	.bind(id)
+	.fetch_one(&pool)
+	.await
+	.map_err::<ServerFnError, _>(|error| ServerFnError::ServerError(error.to_string()))?;
+
+	Ok(vec![
+		(String::from("Equipment"), equipment),
+		(String::from("People"), people),
+		(String::from("Culture"), culture),
+		(String::from("Experiment"), experiment),
+	])
+}
+
+/// Maps a permission target coming from the client onto the table its permissions are stored in
+#[cfg(feature = "ssr")]
+fn get_permission_table(target: &str) -> Result<&'static str, ServerFnError> {
+	match target {
+		"person" => Ok("people"),
+		"role" => Ok("roles"),
+		_ => Err(ServerFnError::Request(String::from("Invalid permission target"))),
+	}
+}
+
+/// Maps a dimension name coming from the client onto its permission column
+#[cfg(feature = "ssr")]
+fn get_permission_column(dimension: &str) -> Result<&'static str, ServerFnError> {
+	match dimension.to_lowercase().as_str() {
+		"equipment" => Ok("permission_equipment"),
+		"people" => Ok("permission_people"),
+		"culture" => Ok("permission_culture"),
+		"experiment" => Ok("permission_experiment"),
+		_ => Err(ServerFnError::Request(String::from("Invalid permission dimension"))),
+	}
+}
+
+/// Only people who can create people may change permissions and never their own,
+/// neither directly nor through a role they hold
+#[cfg(feature = "ssr")]
+pub async fn check_permission_admin(
+	user: Option<crate::auth::User>,
+	table: &str,
+	id: i32,
+	pool: &sqlx::PgPool,
+) -> Result<crate::auth::User, ServerFnError> {
+	use crate::permission::{Dimension, Subject};
+
+	match user {
+		Some(user) => {
+			let Permissions::All {
+				read: _,
+				write: perm,
+				create,
+			} = &user.permission_people;
+			if *create != Permission::Create(true) {
+				return Err(ServerFnError::Request(String::from("User not authenticated")));
+			}
+			if table == "people" && !perm.has_permission_on("write", &Subject::load(Dimension::People, id, pool).await?) {
+				return Err(ServerFnError::Request(String::from("User not authenticated")));
+			}
+			if table == "people" && user.id == id {
+				return Err(ServerFnError::Request(String::from("You can't change your own permissions")));
+			}
+			if table == "roles" && user.roles.contains(&id) {
+				return Err(ServerFnError::Request(String::from("You can't change a role you hold")));
+			}
+			Ok(user)
+		},
+		None => Err(ServerFnError::Request(String::from("User not authenticated"))),
+	}
+}
+
+/// Nobody may hand out more than they have themselves,
+/// so permissions stored in `column` have to be a subset of the user's own for that dimension
+#[cfg(feature = "ssr")]
+pub fn check_permission_grant(
+	user: &crate::auth::User,
+	column: &str,
+	permissions: &Permissions,
+) -> Result<(), ServerFnError> {
+	let own = match column {
+		"permission_equipment" => &user.permission_equipment,
+		"permission_people" => &user.permission_people,
+		"permission_culture" => &user.permission_culture,
+		"permission_experiment" => &user.permission_experiment,
+		_ => return Err(ServerFnError::Request(String::from("Invalid permission dimension"))),
+	};
+
+	if permissions.is_subset_of(own) {
+		Ok(())
+	} else {
+		Err(ServerFnError::Request(String::from("You can't grant more than your own permissions")))
+	}
+}
+
+#[server(prefix = "/api")]
+pub async fn edit_permission(
+	target: String,
+	id: String,
+	dimension: String,
+	permission: String,
+) -> Result<(), ServerFnError> {
+	use crate::auth::get_user;
+
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	let id = match id.parse::<i32>() {
+		Ok(value) => value,
+		Err(_) => return Err(ServerFnError::Request(String::from("Invalid ID"))),
+	};
+
+	let table = get_permission_table(&target)?;
+	let user = check_permission_admin(user, table, id, &pool).await?;
+	let column = get_permission_column(&dimension)?;
+
+	let permissions =
+		Permission::parse(permission).map_err::<ServerFnError, _>(|error| ServerFnError::Request(error.to_string()))?;
+	check_permission_grant(&user, column, &permissions)?;
+
+	sqlx::query(&format!("UPDATE {table} SET {column} = $1 WHERE id = $2"))
+		.bind(permissions.to_string())
+		.bind(id)
+		.execute(&pool)
+		.await
+		.map(|_| ())?;
+
+	Ok(())
+}
+
+#[server(prefix = "/api")]
+pub async fn add_permission_scope(
+	target: String,
+	id: String,
+	dimension: String,
+	action: String,
+	kind: String,
+	value: String,
+) -> Result<(), ServerFnError> {
+	use crate::auth::get_user;
+
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	let id = match id.parse::<i32>() {
+		Ok(value) => value,
+		Err(_) => return Err(ServerFnError::Request(String::from("Invalid ID"))),
+	};
+
+	let table = get_permission_table(&target)?;
+	let user = check_permission_admin(user, table, id, &pool).await?;
+	let column = get_permission_column(&dimension)?;
+
+	let value = value.trim().to_string();
+	let scope = match kind.as_str() {
+		"any" => Scope::Any,
+		"equipment" | "person" => {
+			let scope_id = match value.parse::<i32>() {
+				Ok(value) => value,
+				Err(_) => return Err(ServerFnError::Request(String::from("Invalid scope ID"))),
+			};
+			if kind == "equipment" {
+				Scope::Equipment(scope_id)
+			} else {
+				Scope::Person(scope_id)
+			}
+		},
+		"type" | "location" | "department" if value.is_empty() => {
+			return Err(ServerFnError::Request(String::from("The scope value can't be empty")));
+		},
+		"type" => Scope::EquipmentType(value),
+		"location" => Scope::Location(value),
+		"department" => Scope::Department(value),
+		_ => return Err(ServerFnError::Request(String::from("Invalid scope"))),
+	};
+
+	let current: String =
+		sqlx::query_scalar(&format!("SELECT {column} FROM {table} WHERE id = $1")).bind(id).fetch_one(&pool).await?;
+	let (mut read, mut write, create) = Permission::parse(current)
+		.map_err::<ServerFnError, _>(|error| ServerFnError::Request(format!("Fix the stored permission first: {error}")))?
+		.to_scopes();
+
+	let scopes = match action.as_str() {
+		"read" => &mut read,
+		"write" => &mut write,
+		_ => return Err(ServerFnError::Request(String::from("Invalid action"))),
+	};
+	if !scopes.contains(&scope) {
+		scopes.push(scope);
+	}
+	let permissions = Permissions::from_scopes(read, write, create);
+	check_permission_grant(&user, column, &permissions)?;
+
+	sqlx::query(&format!("UPDATE {table} SET {column} = $1 WHERE id = $2"))
+		.bind(permissions.to_string())
+		.bind(id)
+		.execute(&pool)
+		.await
+		.map(|_| ())?;
+
+	Ok(())
+}
@@ -0,0 +1,265 @@
+use crate::{
+	app::UserSignal,
+	components::{
+		button::{Button, ButtonVariant},
+		select::Select,
+	},
+	equipment::PeopleData,
+	error_template::ErrorTemplate,
+	people::{RoleData, get_roles},
+	permission::{Permission, Permissions, Subject, SubjectSignal},
+};
+
+use leptos::*;
+use leptos_router::*;
+
+stylance::import_style!(
+	#[allow(dead_code)]
+	css,
+	"permissions_edit.module.css"
+);
+
+/// Shows the roles a person holds and lets people admins hand out or take away roles
+#[component]
+pub fn PeopleRolesEdit(people: PeopleData, user_signal: UserSignal) -> impl IntoView {
+	let assign_action = create_server_action::<AssignPeopleRole>();
+	let unassign_action = create_server_action::<UnassignPeopleRole>();
+	let subject_signal = use_context::<SubjectSignal>();
+
+	let people_id = people.id;
+	let roles_data = create_resource(
+		move || (assign_action.version().get(), unassign_action.version().get()),
+		move |_| async move { Ok::<_, ServerFnError>((get_people_roles(people_id.to_string()).await?, get_roles().await?)) },
+	);
+
+	// Handing yourself a role would be the same as changing your own permissions
+	let can_edit = Signal::derive(move || match user_signal.get() {
+		Some(user) => {
+			let Permissions::All {
+				read: _,
+				write: perm,
+				create,
+			} = user.permission_people;
+			user.id != people_id
+				&& create == Permission::Create(true)
+				&& perm.has_permission_on("write", &Subject::resolve(subject_signal, people_id, people_id))
+		},
+		None => false,
+	});
+
+	view! {
+		<Suspense fallback=move || view! { <p>Loading roles...</p> }>
+			<ErrorBoundary fallback=|errors| {
+				view! { <ErrorTemplate errors=errors /> }
+			}>
+				{move || {
+					if roles_data.get().is_some() {
+						match roles_data.get().unwrap() {
+							Err(error) => {
+								view! { <pre class="error">Roles Server Error: {error.to_string()}</pre> }.into_view()
+							}
+							Ok((held, all)) => {
+								let available = all
+									.into_iter()
+									.filter(|role| !held.iter().any(|item| item.id == role.id))
+									.collect::<Vec<RoleData>>();
+								let has_available = !available.is_empty();
+								let available = store_value(available);
+								view! {
+									<div class=css::scopes>
+										{if held.is_empty() {
+											view! { <span class=css::label>"No roles"</span> }.into_view()
+										} else {
+											held.into_iter()
+												.map(|role| {
+													view! {
+														<span class=css::scope>
+															<A href=format!("/people/roles/{}", role.id)>{role.name}</A>
+															<Show when=move || can_edit.get()>
+																<ActionForm action=unassign_action>
+																	<input type="hidden" name="id" value=people_id />
+																	<input type="hidden" name="role" value=role.id />
+																	<button class=css::remove type="submit" title="Remove role">
+																		"×"
+																	</button>
+																</ActionForm>
+															</Show>
+														</span>
+													}
+												})
+												.collect_view()
+										}}
+									</div>
+									<Show when=move || can_edit.get() && has_available>
+										<ActionForm action=assign_action class=css::form>
+											<input type="hidden" name="id" value=people_id />
+											<Select name="role">
+												{available
+													.get_value()
+													.into_iter()
+													.map(|role| {
+														view! { <option value=role.id>{role.name}</option> }
+													})
+													.collect_view()}
+											</Select>
+											<Button kind="submit" variant=ButtonVariant::Outlined>
+												Add role
+											</Button>
+										</ActionForm>
+									</Show>
+								}
+									.into_view()
+							}
+						}
+					} else {
+						view! {}.into_view()
+					}
+				}}
+			</ErrorBoundary>
+		</Suspense>
+		{move || {
+			let error = match (assign_action.value().get(), unassign_action.value().get()) {
+				(Some(Err(error)), _) | (_, Some(Err(error))) => error.to_string(),
+				_ => return view! {}.into_view(),
+			};
+			view! { <span class=css::error>{error.replace("error reaching server to call server function: ", "")}</span> }
+				.into_view()
+		}}
+	}
+}
+
+#[server(prefix = "/api")]
+pub async fn get_people_roles(id: String) -> Result<Vec<RoleData>, ServerFnError> {
+	use crate::{
+		auth::get_user,
+		permission::{Dimension, Permissions, Subject},
+	};
+
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	let id = match id.parse::<i32>() {
+		Ok(value) => value,
+		Err(_) => return Err(ServerFnError::Request(String::from("Invalid ID"))),
+	};
+
+	match user {
+		Some(user) => {
+			let Permissions::All {
+				read: perm,
+				write: _,
+				create: _,
+			} = user.permission_people;
+			let subject = Subject::load(Dimension::People, id, &pool).await?;
+			if !perm.has_permission_on("read", &subject) {
+				return Err(ServerFnError::Request(String::from("User not authenticated")));
+			}
+		},
+		None => return Err(ServerFnError::Request(String::from("User not authenticated"))),
+	};
+
+	Ok(
+		sqlx::query_as::<_, RoleData>(
+			r#"
+		SELECT
+			roles.id,
+			roles.name,
+			(SELECT COUNT(*) FROM people_roles AS members WHERE members.role = roles.id) AS members,
+			roles.create_date
+		FROM
+			roles
+			JOIN people_roles ON people_roles.role = roles.id
+		WHERE people_roles.person = $1
+		ORDER BY roles.name"#,
+		)
+		.bind(id)
+		.fetch_all(&pool)
+		.await?,
+	)
+}
+
+#[server(prefix = "/api")]
+pub async fn assign_people_role(id: String, role: String) -> Result<(), ServerFnError> {
+	use crate::{
+		auth::get_user,
+		people::{check_permission_admin, check_permission_grant},
+		permission::Permission,
+	};
+
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	let (id, role) = match (id.parse::<i32>(), role.parse::<i32>()) {
+		(Ok(id), Ok(role)) => (id, role),
+		_ => return Err(ServerFnError::Request(String::from("Invalid ID"))),
+	};
+
+	let user = check_permission_admin(user, "people", id, &pool).await?;
+
+	// Holding a role grants all of its permissions so each of them has to be grantable
+	let permissions: Option<(String, String, String, String)> = sqlx::query_as(
+		"SELECT permission_equipment, permission_people, permission_culture, permission_experiment FROM roles WHERE id = $1",
+	)
+	.bind(role)
+	.fetch_optional(&pool)
+	.await?;
+	let Some((equipment, people, culture, experiment)) = permissions else {
+		return Err(ServerFnError::Request(String::from("This role doesn't exist")));
+	};
+	for (column, permission) in [
+		("permission_equipment", equipment),
+		("permission_people", people),
+		("permission_culture", culture),
+		("permission_experiment", experiment),
+	] {
+		let permissions = Permission::parse(permission).map_err::<ServerFnError, _>(|error| {
+			ServerFnError::Request(format!("Fix the role's permissions first: {error}"))
+		})?;
+		check_permission_grant(&user, column, &permissions)?;
+	}
+
+	sqlx::query("INSERT INTO people_roles (person, role) VALUES ($1, $2) ON CONFLICT DO NOTHING")
+		.bind(id)
+		.bind(role)
+		.execute(&pool)
+		.await
+		.map_err::<ServerFnError, _>(|error| match error.as_database_error().and_then(|error| error.code()) {
+			Some(code) if code == "23503" => ServerFnError::Request(String::from("This role doesn't exist")),
+			_ => ServerFnError::ServerError(error.to_string()),
+		})?;
+
+	Ok(())
+}
+
+#[server(prefix = "/api")]
+pub async fn unassign_people_role(id: String, role: String) -> Result<(), ServerFnError> {
+	use crate::{auth::get_user, people::check_permission_admin};
+
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	let (id, role) = match (id.parse::<i32>(), role.parse::<i32>()) {
+		(Ok(id), Ok(role)) => (id, role),
+		_ => return Err(ServerFnError::Request(String::from("Invalid ID"))),
+	};
+
+	check_permission_admin(user, "people", id, &pool).await?;
+
+	sqlx::query("DELETE FROM people_roles WHERE person = $1 AND role = $2")
+		.bind(id)
+		.bind(role)
+		.execute(&pool)
+		.await
+		.map(|_| ())?;
+
+	Ok(())
+}
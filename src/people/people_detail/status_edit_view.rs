@@ -0,0 +1,120 @@
+use crate::{
+	app::UserSignal,
+	components::{button::Button, select::Select},
+	equipment::{PeopleData, PeopleStatus},
+	people::PeopleFormToggle,
+};
+
+use leptos::*;
+use leptos_router::*;
+
+stylance::import_style!(css, "../../equipment/equipment_detail/equipment_details_edits.module.css");
+
+#[component]
+pub fn PeopleStatusEdit(
+	people: PeopleData,
+	user_signal: UserSignal,
+	refetch_resources: RwSignal<usize>,
+) -> impl IntoView {
+	let status_action = create_server_action::<EditPeopleStatus>();
+
+	view! {
+		<PeopleFormToggle user_id=people.id id=people.id user_signal=user_signal item=people.status>
+			{
+				view! {
+					<ActionForm action=status_action class=css::edit_form>
+						<input type="hidden" name="id" value=people.id />
+						<Select name="status">
+							{PeopleStatus::get_fields()
+								.into_iter()
+								.map(|field| {
+									let status = PeopleStatus::parse(field);
+									view! {
+										<option value=format!("{:#?}", status) selected=people.status == status>
+											{format!("{status}")}
+										</option>
+									}
+								})
+								.collect_view()}
+						</Select>
+						<div class=css::btns>
+							{move || {
+								if let Some(responds) = status_action.value().get() {
+									match responds {
+										Ok(_) => {
+											status_action.value().set(None);
+											refetch_resources.update(|version| *version += 1);
+											view! {}.into_view()
+										}
+										Err(error) => {
+											view! {
+												<span>
+													{error
+														.to_string()
+														.replace("error reaching server to call server function: ", "")}
+												</span>
+											}
+												.into_view()
+										}
+									}
+								} else {
+									view! {}.into_view()
+								}
+							}} <Button kind="submit">Save</Button>
+						</div>
+					</ActionForm>
+				}
+			}
+		</PeopleFormToggle>
+	}
+}
+
+#[server(prefix = "/api")]
+pub async fn edit_people_status(id: String, status: String) -> Result<(), ServerFnError> {
+	use crate::{
+		auth::get_user,
+		permission::{Dimension, Permissions, Subject},
+	};
+
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	let id = match id.parse::<i32>() {
+		Ok(value) => value,
+		Err(_) => return Err(ServerFnError::Request(String::from("Invalid ID"))),
+	};
+
+	let status = PeopleStatus::parse(status);
+
+	match user {
+		Some(user) => {
+			let Permissions::All {
+				read: _,
+				write: perm,
+				create: _,
+			} = user.permission_people;
+
+			let subject = Subject::load(Dimension::People, id, &pool).await?;
+			if !perm.has_permission_on("write", &subject) {
+				return Err(ServerFnError::Request(String::from("User not authenticated")));
+			}
+
+			if user.id == id && status == PeopleStatus::Left {
+				return Err(ServerFnError::Request(String::from("You can't deactivate yourself")));
+			}
+		},
+		None => return Err(ServerFnError::Request(String::from("User not authenticated"))),
+	};
+
+	sqlx::query("UPDATE people SET status = $1 WHERE id = $2")
+		.bind(format!("{status:#?}"))
+		.bind(id)
+		.execute(&pool)
+		.await
+		.map(|_| ())?;
+
+	Ok(())
+}
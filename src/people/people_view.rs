@@ -0,0 +1,312 @@
+use crate::{
+	app::LoginAction,
+	components::{
+		button::{Button, ButtonVariant},
+		pagination::Pagination,
+		select::{MultiSelect, MultiSelectOption},
+	},
+	equipment::{PeopleData, THead},
+	error_template::ErrorTemplate,
+	icons::PeopleLogo,
+	login::Login,
+	people::{Heading, Row},
+};
+
+use leptos::*;
+use leptos_router::*;
+
+stylance::import_style!(
+	#[allow(dead_code)]
+	css,
+	"../equipment/equipment.module.css"
+);
+
+#[component]
+pub fn People() -> impl IntoView {
+	let query = use_query_map();
+
+	let query_field = create_rw_signal(String::from("id"));
+	let query_order = create_rw_signal(String::from("asc"));
+	let query_filter = create_rw_signal(vec![
+		String::from("id"),
+		String::from("status"),
+		String::from("preferred_name"),
+		String::from("email"),
+		String::from("department"),
+		String::from("role"),
+	]);
+	let query_page = create_rw_signal::<u16>(1);
+	let query_ipp = create_rw_signal::<u8>(25);
+
+	create_effect(move |_| {
+		let (field, order, filter, page, ipp) = query.with(|p| {
+			let field = p.get("field").cloned().unwrap_or(String::from("id"));
+			let order = p.get("order").cloned().unwrap_or(String::from("asc"));
+			let filter = p
+				.get("filter")
+				.cloned()
+				.unwrap_or(String::from("id,status,preferred_name,email,department,role"))
+				.split(",")
+				.map(String::from)
+				.collect::<Vec<String>>();
+			let page = p.get("page").cloned().unwrap_or(String::from("1")).parse::<u16>().unwrap_or(1);
+			let ipp = p.get("items_per_page").cloned().unwrap_or(String::from("25")).parse::<u8>().unwrap_or(25);
+
+			(field, order, filter, page, ipp)
+		});
+
+		query_field.set(field);
+		query_order.set(order);
+		query_filter.set(filter);
+		query_page.set(if page > 0 { page } else { 1 });
+		query_ipp.set(if ipp > 0 { ipp } else { 1 });
+	});
+
+	let login_action = use_context::<LoginAction>().expect("No login action found in context");
+
+	let people_data = create_resource(
+		move || {
+			let (field, order, page, ipp) = query.with(|p| {
+				let field = p.get("field").cloned().unwrap_or(String::from("id"));
+				let order = p.get("order").cloned().unwrap_or(String::from("asc"));
+				let page = p.get("page").cloned().unwrap_or(String::from("1")).parse::<u16>().unwrap_or(1);
+				let ipp = p.get("items_per_page").cloned().unwrap_or(String::from("25")).parse::<u8>().unwrap_or(25);
+
+				(field, order, page, ipp)
+			});
+			(login_action.version().get(), field, order, page, ipp)
+		},
+		move |(_, field, order, page, ipp)| get_people_data(field, order, page, ipp),
+	);
+
+	view! {
+		<Heading>
+			<PeopleLogo />
+			" People"
+		</Heading>
+		<Suspense fallback=move || view! { <p>Loading people...</p> }>
+			<ErrorBoundary fallback=|errors| {
+				view! { <ErrorTemplate errors /> }
+			}>
+				{move || {
+					view! {
+						{people_data
+							.get()
+							.map(move |data| match data {
+								Err(error) => {
+									if error.to_string().contains("User not authenticated") {
+										view! { <Login redirect="/people" /> }
+									} else {
+										view! { <pre class="error">Server Error: {error.to_string()}</pre> }.into_view()
+									}
+								}
+								Ok((people, row_count)) => {
+									let hidden_fields = vec![
+										(String::from("field"), query_field.get()),
+										(String::from("order"), query_order.get()),
+										(String::from("filter"), query_filter.get().join(",")),
+									];
+									view! {
+										<Pagination
+											action=String::from("/people")
+											page_key="page"
+											ipp_key="items_per_page"
+											query_page
+											query_ipp
+											row_count
+											hidden_fields=hidden_fields.clone()
+										/>
+										<div class=css::filter>
+											"Columns: "
+											<MultiSelect
+												value=query_filter
+												options=create_rw_signal(
+													PeopleData::get_fields()
+														.into_iter()
+														.map(|(id, name)| MultiSelectOption::new(name, id))
+														.collect::<Vec<MultiSelectOption<String>>>(),
+												)
+											/> <div class=css::btns>
+												<Button
+													variant=ButtonVariant::Outlined
+													on_click=move |_| {
+														query_filter
+															.set(
+																PeopleData::get_fields()
+																	.into_iter()
+																	.map(|(id, _)| id)
+																	.collect::<Vec<String>>(),
+															);
+													}
+												>
+													All
+												</Button>
+											</div>
+										</div>
+										<div class=css::table_wrapper>
+											<table class=css::table>
+												<thead>
+													<tr>
+														<THead
+															action="/people"
+															items=PeopleData::get_fields()
+															query_field
+															query_order
+															query_filter
+														>
+															<input type="hidden" name="page" value=query_page.get() />
+															<input
+																type="hidden"
+																name="items_per_page"
+																value=query_ipp.get()
+															/>
+															<input
+																type="hidden"
+																name="filter"
+																value=query_filter.get().join(",")
+															/>
+														</THead>
+													</tr>
+												</thead>
+												<tbody>
+													{if people.is_empty() {
+														view! {
+															<tr>
+																<td colspan=PeopleData::get_fields()
+																	.len()>"No people found."</td>
+															</tr>
+														}
+															.into_view()
+													} else {
+														view! { <Row people query_filter /> }.into_view()
+													}}
+												</tbody>
+											</table>
+										</div>
+										<Pagination
+											action=String::from("/people")
+											page_key="page"
+											ipp_key="items_per_page"
+											query_page
+											query_ipp
+											row_count
+											hidden_fields
+										/>
+									}
+										.into_view()
+								}
+							})
+							.unwrap_or_default()}
+					}
+				}}
+			</ErrorBoundary>
+		</Suspense>
+	}
+}
+
+#[server(prefix = "/api")]
+pub async fn get_people_data(
+	field: String,
+	order: String,
+	page: u16,
+	items_per_page: u8,
+) -> Result<(Vec<PeopleData>, i64), ServerFnError> {
+	use crate::{
+		auth::get_user,
+		equipment::PeopleSQLData,
+		permission::{BindFilter, Dimension, Permissions},
+	};
+
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	let auth_filter = match user {
+		Some(user) => {
+			let Permissions::All {
+				read: perm,
+				write: _,
+				create: _,
+			} = user.permission_people;
+			perm.get_query_filter(Dimension::People, "people.id")
+		},
+		None => return Err(ServerFnError::Request(String::from("User not authenticated"))),
+	};
+
+	let order_sanitized = match order.to_lowercase().as_str() {
+		"asc" => "ASC",
+		"desc" => "DESC",
+		_ => "ASC",
+	};
+
+	let field_sanitized = match field.to_lowercase().as_str() {
+		f @ "id"
+		| f @ "employee_id"
+		| f @ "status"
+		| f @ "first_name"
+		| f @ "last_name"
+		| f @ "preferred_name"
+		| f @ "email"
+		| f @ "phone_number"
+		| f @ "department"
+		| f @ "role"
+		| f @ "hire_date"
+		| f @ "emergency_contact"
+		| f @ "certifications"
+		| f @ "specializations"
+		| f @ "picture"
+		| f @ "bio"
+		| f @ "create_date" => format!("people.{f}"),
+		_ => String::from("people.id"),
+	};
+
+	let limit = items_per_page as i64;
+	let offset = (page as i64 - 1) * items_per_page as i64;
+
+	let auth_query = auth_filter.where_clause(3);
+	let query = format!(
+		r#"
+			SELECT
+				people.id,
+				people.employee_id,
+				people.status,
+				people.first_name,
+				people.last_name,
+				people.preferred_name,
+				people.email,
+				people.phone_number,
+				people.department,
+				people.role,
+				people.hire_date,
+				people.emergency_contact,
+				people.certifications,
+				people.specializations,
+				people.picture,
+				people.bio,
+				people.create_date
+			FROM
+				people
+			{auth_query}
+			ORDER BY {field_sanitized} {order_sanitized}
+			LIMIT $1 OFFSET $2
+			"#
+	);
+	let people_sql_data = sqlx::query_as::<_, PeopleSQLData>(&query)
+		.bind(limit)
+		.bind(offset)
+		.bind_filter(&auth_filter)
+		.fetch_all(&pool)
+		.await
+		.map_err::<ServerFnError, _>(|error| ServerFnError::ServerError(error.to_string()))?;
+
+	let people_data: Vec<PeopleData> = people_sql_data.into_iter().map(Into::into).collect();
+
+	let row_count: i64 = sqlx::query_scalar(&format!("SELECT COUNT(*) FROM people{}", auth_filter.where_clause(1)))
+		.bind_filter(&auth_filter)
+		.fetch_one(&pool)
+		.await?;
+
+	Ok((people_data, row_count))
+}
@@ -0,0 +1,302 @@
+use crate::{
+	app::{LoginAction, UserSignal},
+	components::{
+		avatar::Avatar,
+		button::{Button, ButtonVariant},
+	},
+	equipment::{AvatarData, EquipmentCell},
+	error_template::ErrorTemplate,
+	icons::PeopleLogo,
+	login::Login,
+	people::{Heading, PermissionTarget, PermissionsEdit, RoleData},
+	permission::{Permission, Permissions},
+};
+
+use leptos::*;
+use leptos_router::*;
+
+stylance::import_style!(
+	#[allow(dead_code)]
+	css,
+	"../../equipment/equipment_detail/equipment_details.module.css"
+);
+stylance::import_style!(
+	#[allow(dead_code)]
+	roles_css,
+	"../roles/roles.module.css"
+);
+
+#[component]
+pub fn RoleDetail() -> impl IntoView {
+	let params = use_params_map();
+	let delete_action = create_server_action::<DeleteRole>();
+
+	let login_action = use_context::<LoginAction>().expect("No login action found in context");
+	let user_signal = use_context::<UserSignal>().expect("No user signal found in context");
+
+	let role_data = create_resource(
+		move || (login_action.version().get(), params.with(|p| p.get("id").cloned().unwrap_or_default())),
+		move |(_, id)| get_role_by_id(id),
+	);
+
+	let navigate = use_navigate();
+	create_effect(move |_| {
+		if let Some(Ok(_)) = delete_action.value().get() {
+			navigate("/people/roles", NavigateOptions::default());
+		}
+	});
+
+	view! {
+		<Suspense fallback=move || view! { <p>Loading role...</p> }>
+			<ErrorBoundary fallback=|errors| {
+				view! { <ErrorTemplate errors=errors /> }
+			}>
+				{move || {
+					if role_data.get().is_some() {
+						match role_data.get().unwrap() {
+							Err(error) => {
+								let error = error.to_string();
+								if error.contains("User not authenticated") {
+									view! {
+										<Login redirect=format!(
+											"/people/roles/{}",
+											params.with(|p| p.get("id").cloned().unwrap_or_default()),
+										) />
+									}
+								} else {
+									view! { <pre class="error">Server Error: {error}</pre> }.into_view()
+								}
+							}
+							Ok(role) => {
+								let role_id = role.id;
+								let can_delete = move || match user_signal.get() {
+									Some(user) => {
+										let Permissions::All { read: _, write: _, create: perm } = user
+											.permission_people;
+										perm == Permission::Create(true) && !user.roles.contains(&role_id)
+									}
+									None => false,
+								};
+								view! {
+									<div class=css::details>
+										<Heading hide_new=true>
+											<PeopleLogo />
+											" "
+											{role.name.clone()}
+										</Heading>
+
+										<dl class=css::list>
+											<dt>ID</dt>
+											<dd>
+												<EquipmentCell cell=role.id />
+											</dd>
+
+											<dt>Members</dt>
+											<dd>
+												<RoleMembers id=role.id />
+											</dd>
+
+											<dt>Create Date</dt>
+											<dd>
+												<EquipmentCell cell=role.create_date />
+											</dd>
+
+											<dt>Permissions</dt>
+											<dd>
+												<PermissionsEdit target=PermissionTarget::Role id=role.id user_signal />
+											</dd>
+										</dl>
+
+										<Show when=can_delete>
+											<ActionForm action=delete_action class=roles_css::form>
+												<input type="hidden" name="id" value=role_id />
+												<Button kind="submit" variant=ButtonVariant::Outlined>
+													Delete role
+												</Button>
+												{move || {
+													if let Some(Err(error)) = delete_action.value().get() {
+														view! {
+															<span class=roles_css::error>
+																{error
+																	.to_string()
+																	.replace(
+																		"error reaching server to call server function: ",
+																		"",
+																	)}
+															</span>
+														}
+															.into_view()
+													} else {
+														view! {}.into_view()
+													}
+												}}
+											</ActionForm>
+										</Show>
+									</div>
+								}
+									.into_view()
+							}
+						}
+					} else {
+						view! { <div>Nothing found</div> }.into_view()
+					}
+				}}
+			</ErrorBoundary>
+		</Suspense>
+	}
+}
+
+#[component]
+fn RoleMembers(id: i32) -> impl IntoView {
+	let members_data = create_resource(move || id, move |id| get_role_members(id.to_string()));
+
+	view! {
+		<Suspense fallback=move || view! { <p>Loading members...</p> }>
+			{move || {
+				members_data
+					.get()
+					.map(|members| match members {
+						Err(error) => {
+							view! { <pre class="error">Members Server Error: {error.to_string()}</pre> }.into_view()
+						}
+						Ok(members) if members.is_empty() => {
+							view! { <span class=roles_css::empty>"Nobody holds this role yet"</span> }.into_view()
+						}
+						Ok(members) => {
+							view! {
+								<div class=roles_css::roles>
+									{members
+										.into_iter()
+										.map(|member| {
+											view! {
+												<A href=format!("/people/{}", member.id)>
+													<Avatar data=member tiny=true />
+												</A>
+											}
+										})
+										.collect_view()}
+								</div>
+							}
+								.into_view()
+						}
+					})
+			}}
+		</Suspense>
+	}
+}
+
+#[server(prefix = "/api")]
+pub async fn get_role_by_id(id: String) -> Result<RoleData, ServerFnError> {
+	use crate::auth::get_user;
+
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	let id = match id.parse::<i32>() {
+		Ok(value) => value,
+		Err(_) => return Err(ServerFnError::Request(String::from("Invalid ID"))),
+	};
+
+	if user.is_none() {
+		return Err(ServerFnError::Request(String::from("User not authenticated")));
+	}
+
+	sqlx::query_as::<_, RoleData>(
+		r#"
+		SELECT
+			roles.id,
+			roles.name,
+			COUNT(people_roles.person) AS members,
+			roles.create_date
+		FROM
+			roles
+			LEFT JOIN people_roles ON people_roles.role = roles.id
+		WHERE roles.id = $1
+		GROUP BY roles.id"#,
+	)
+	.bind(id)
+	.fetch_one(&pool)
+	.await
+	.map_err::<ServerFnError, _>(|error| ServerFnError::ServerError(error.to_string()))
+}
+
+#[server(prefix = "/api")]
+pub async fn get_role_members(id: String) -> Result<Vec<AvatarData>, ServerFnError> {
+	use crate::{
+		auth::get_user,
+		equipment::AvatarSQLData,
+		permission::{BindFilter, Dimension},
+	};
+
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	let id = match id.parse::<i32>() {
+		Ok(value) => value,
+		Err(_) => return Err(ServerFnError::Request(String::from("Invalid ID"))),
+	};
+
+	let auth_filter = match user {
+		Some(user) => {
+			let Permissions::All {
+				read: perm,
+				write: _,
+				create: _,
+			} = user.permission_people;
+			perm.get_query_filter(Dimension::People, "id")
+		},
+		None => return Err(ServerFnError::Request(String::from("User not authenticated"))),
+	};
+
+	let auth_query = auth_filter.and_clause(2);
+
+	// Only list the members the current user is allowed to see
+	let members = sqlx::query_as::<_, AvatarSQLData>(&format!(
+		r#"
+		SELECT
+			people.id,
+			people.status,
+			people.preferred_name,
+			people.picture
+		FROM
+			people
+			JOIN people_roles ON people_roles.person = people.id
+		WHERE people_roles.role = $1
+		AND people.id IN (SELECT id FROM (SELECT id, id AS person FROM people) AS scoped WHERE id IS NOT NULL {auth_query})
+		ORDER BY people.preferred_name"#
+	))
+	.bind(id)
+	.bind_filter(&auth_filter)
+	.fetch_all(&pool)
+	.await?;
+
+	Ok(members.into_iter().map(AvatarData::from).collect())
+}
+
+#[server(prefix = "/api")]
+pub async fn delete_role(id: String) -> Result<(), ServerFnError> {
+	use crate::{auth::get_user, people::check_permission_admin};
+
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	let id = match id.parse::<i32>() {
+		Ok(value) => value,
+		Err(_) => return Err(ServerFnError::Request(String::from("Invalid ID"))),
+	};
+
+	check_permission_admin(user, "roles", id, &pool).await?;
+
+	sqlx::query("DELETE FROM roles WHERE id = $1").bind(id).execute(&pool).await.map(|_| ())?;
+
+	Ok(())
+}
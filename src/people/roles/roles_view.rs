@@ -0,0 +1,200 @@
+use crate::{
+	app::{LoginAction, UserSignal},
+	components::{
+		button::{Button, ButtonVariant},
+		input::Input,
+	},
+	equipment::EquipmentCell,
+	error_template::ErrorTemplate,
+	icons::PeopleLogo,
+	login::Login,
+	people::{Heading, RoleData},
+	permission::{Permission, Permissions},
+};
+
+use leptos::*;
+use leptos_router::*;
+
+stylance::import_style!(css, "roles.module.css");
+
+/// Lists all roles and lets people admins create new ones
+#[component]
+pub fn Roles() -> impl IntoView {
+	let add_role_action = create_server_action::<AddRole>();
+
+	let login_action = use_context::<LoginAction>().expect("No login action found in context");
+	let user_signal = use_context::<UserSignal>().expect("No user signal found in context");
+
+	let roles_data =
+		create_resource(move || (login_action.version().get(), add_role_action.version().get()), move |_| get_roles());
+
+	let can_create = move || match user_signal.get() {
+		Some(user) => {
+			let Permissions::All {
+				read: _,
+				write: _,
+				create: perm,
+			} = user.permission_people;
+			perm == Permission::Create(true)
+		},
+		None => false,
+	};
+
+	view! {
+		<Heading hide_new=true>
+			<PeopleLogo />
+			" Roles"
+		</Heading>
+
+		<Suspense fallback=move || view! { <p>Loading roles...</p> }>
+			<ErrorBoundary fallback=|errors| {
+				view! { <ErrorTemplate errors=errors /> }
+			}>
+				{move || {
+					if roles_data.get().is_some() {
+						match roles_data.get().unwrap() {
+							Err(error) => {
+								let error = error.to_string();
+								if error.contains("User not authenticated") {
+									view! { <Login redirect="/people/roles" /> }.into_view()
+								} else {
+									view! { <pre class="error">Server Error: {error}</pre> }.into_view()
+								}
+							}
+							Ok(roles) => {
+								view! {
+									<div class=css::roles>
+										{if roles.is_empty() {
+											view! { <span class=css::empty>"No roles yet"</span> }.into_view()
+										} else {
+											roles
+												.into_iter()
+												.map(|role| {
+													view! {
+														<div class=css::role>
+															<A href=format!("/people/roles/{}", role.id)>{role.name}</A>
+															<span class=css::members>
+																{format!(
+																	"{} {}",
+																	role.members,
+																	if role.members == 1 { "member" } else { "members" },
+																)}
+															</span>
+															<EquipmentCell cell=role.create_date />
+														</div>
+													}
+												})
+												.collect_view()
+										}}
+									</div>
+								}
+									.into_view()
+							}
+						}
+					} else {
+						view! {}.into_view()
+					}
+				}}
+			</ErrorBoundary>
+		</Suspense>
+
+		<Show when=can_create>
+			<ActionForm action=add_role_action class=css::form>
+				<Input name="name" placeholder="Role name" required=true />
+				<Button kind="submit" variant=ButtonVariant::Outlined>
+					Add role
+				</Button>
+				{move || {
+					if let Some(Err(error)) = add_role_action.value().get() {
+						view! {
+							<span class=css::error>
+								{error.to_string().replace("error reaching server to call server function: ", "")}
+							</span>
+						}
+							.into_view()
+					} else {
+						view! {}.into_view()
+					}
+				}}
+			</ActionForm>
+		</Show>
+	}
+}
+
+#[server(prefix = "/api")]
+pub async fn get_roles() -> Result<Vec<RoleData>, ServerFnError> {
+	use crate::auth::get_user;
+
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	if user.is_none() {
+		return Err(ServerFnError::Request(String::from("User not authenticated")));
+	}
+
+	Ok(
+		sqlx::query_as::<_, RoleData>(
+			r#"
+		SELECT
+			roles.id,
+			roles.name,
+			COUNT(people_roles.person) AS members,
+			roles.create_date
+		FROM
+			roles
+			LEFT JOIN people_roles ON people_roles.role = roles.id
+		GROUP BY roles.id
+		ORDER BY roles.name"#,
+		)
+		.fetch_all(&pool)
+		.await?,
+	)
+}
+
+#[server(prefix = "/api")]
+pub async fn add_role(name: String) -> Result<i32, ServerFnError> {
+	use crate::auth::get_user;
+
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	match user {
+		Some(user) => {
+			let Permissions::All {
+				read: _,
+				write: _,
+				create: perm,
+			} = user.permission_people;
+			if perm != Permission::Create(true) {
+				return Err(ServerFnError::Request(String::from("User not authenticated")));
+			}
+		},
+		None => return Err(ServerFnError::Request(String::from("User not authenticated"))),
+	};
+
+	let name = name.trim().to_string();
+	if name.is_empty() {
+		return Err(ServerFnError::Request(String::from("The role name can't be empty")));
+	}
+
+	// New roles grant nothing until their permissions are edited
+	let id: i32 = sqlx::query_scalar(
+		"INSERT INTO roles (name, permission_equipment, permission_people, permission_culture, permission_experiment) VALUES ($1, $2, $2, $2, $2) RETURNING id",
+	)
+	.bind(name)
+	.bind(Permissions::none().to_string())
+	.fetch_one(&pool)
+	.await
+	.map_err::<ServerFnError, _>(|error| match error.as_database_error().and_then(|error| error.code()) {
+		Some(code) if code == "23505" => ServerFnError::Request(String::from("A role with this name already exists")),
+		_ => ServerFnError::ServerError(error.to_string()),
+	})?;
+
+	Ok(id)
+}
@@ -0,0 +1,256 @@
+use crate::equipment::{EquipmentCell, PeopleData};
+
+use leptos::*;
+use leptos_router::*;
+
+stylance::import_style!(css, "row.module.css");
+
+#[component]
+pub fn Row(people: Vec<PeopleData>, query_filter: RwSignal<Vec<String>>) -> impl IntoView {
+	people
+		.into_iter()
+		.map(move |people| {
+			view! {
+				<tr>
+					<Show when=move || {
+						query_filter.get().contains(&String::from("id"))
+					}>
+						{
+							view! {
+								<td class="people_listing_id">
+									<A href=format!("/people/{}", people.id) class="none">
+										<EquipmentCell cell=people.id table_view=true />
+									</A>
+								</td>
+							}
+						}
+					</Show>
+					<Show when=move || {
+						query_filter.get().contains(&String::from("employee_id"))
+					}>
+						{
+							let people_employee_id = people.employee_id.clone();
+							view! {
+								<td class="people_listing_employee_id">
+									<A href=format!("/people/{}", people.id) class="none">
+										<EquipmentCell cell=people_employee_id table_view=true />
+									</A>
+								</td>
+							}
+						}
+					</Show>
+					<Show when=move || {
+						query_filter.get().contains(&String::from("status"))
+					}>
+						{
+							view! {
+								<td class="people_listing_status">
+									<A href=format!("/people/{}", people.id) class="none">
+										<EquipmentCell cell=people.status table_view=true />
+									</A>
+								</td>
+							}
+						}
+					</Show>
+					<Show when=move || {
+						query_filter.get().contains(&String::from("first_name"))
+					}>
+						{
+							let people_first_name = people.first_name.clone();
+							view! {
+								<td class="people_listing_first_name">
+									<A href=format!("/people/{}", people.id) class="none">
+										<EquipmentCell cell=people_first_name table_view=true />
+									</A>
+								</td>
+							}
+						}
+					</Show>
+					<Show when=move || {
+						query_filter.get().contains(&String::from("last_name"))
+					}>
+						{
+							let people_last_name = people.last_name.clone();
+							view! {
+								<td class="people_listing_last_name">
+									<A href=format!("/people/{}", people.id) class="none">
+										<EquipmentCell cell=people_last_name table_view=true />
+									</A>
+								</td>
+							}
+						}
+					</Show>
+					<Show when=move || {
+						query_filter.get().contains(&String::from("preferred_name"))
+					}>
+						{
+							let people_preferred_name = people.preferred_name.clone();
+							view! {
+								<td class="people_listing_preferred_name">
+									<A href=format!("/people/{}", people.id) class="none">
+										<EquipmentCell cell=people_preferred_name table_view=true />
+									</A>
+								</td>
+							}
+						}
+					</Show>
+					<Show when=move || {
+						query_filter.get().contains(&String::from("email"))
+					}>
+						{
+							let people_email = people.email.clone();
+							view! {
+								<td class="people_listing_email">
+									<A href=format!("/people/{}", people.id) class="none">
+										<EquipmentCell cell=people_email table_view=true />
+									</A>
+								</td>
+							}
+						}
+					</Show>
+					<Show when=move || {
+						query_filter.get().contains(&String::from("phone_number"))
+					}>
+						{
+							let people_phone_number = people.phone_number.clone();
+							view! {
+								<td class="people_listing_phone_number">
+									<A href=format!("/people/{}", people.id) class="none">
+										<EquipmentCell cell=people_phone_number table_view=true />
+									</A>
+								</td>
+							}
+						}
+					</Show>
+					<Show when=move || {
+						query_filter.get().contains(&String::from("department"))
+					}>
+						{
+							let people_department = people.department.clone();
+							view! {
+								<td class="people_listing_department">
+									<A href=format!("/people/{}", people.id) class="none">
+										<EquipmentCell cell=people_department table_view=true />
+									</A>
+								</td>
+							}
+						}
+					</Show>
+					<Show when=move || {
+						query_filter.get().contains(&String::from("role"))
+					}>
+						{
+							let people_role = people.role.clone();
+							view! {
+								<td class="people_listing_role">
+									<A href=format!("/people/{}", people.id) class="none">
+										<EquipmentCell cell=people_role table_view=true />
+									</A>
+								</td>
+							}
+						}
+					</Show>
+					<Show when=move || {
+						query_filter.get().contains(&String::from("hire_date"))
+					}>
+						{
+							view! {
+								<td class="people_listing_hire_date">
+									<A href=format!("/people/{}", people.id) class="none">
+										<EquipmentCell cell=people.hire_date table_view=true />
+									</A>
+								</td>
+							}
+						}
+					</Show>
+					<Show when=move || {
+						query_filter.get().contains(&String::from("emergency_contact"))
+					}>
+						{
+							let people_emergency_contact = people.emergency_contact.clone();
+							view! {
+								<td class="people_listing_emergency_contact">
+									<A href=format!("/people/{}", people.id) class="none">
+										<EquipmentCell cell=people_emergency_contact table_view=true />
+									</A>
+								</td>
+							}
+						}
+					</Show>
+					<Show when=move || {
+						query_filter.get().contains(&String::from("certifications"))
+					}>
+						{
+							let people_certifications = people.certifications.clone();
+							view! {
+								<td class="people_listing_certifications">
+									<A href=format!("/people/{}", people.id) class="none">
+										<EquipmentCell cell=people_certifications table_view=true />
+									</A>
+								</td>
+							}
+						}
+					</Show>
+					<Show when=move || {
+						query_filter.get().contains(&String::from("specializations"))
+					}>
+						{
+							let people_specializations = people.specializations.clone();
+							view! {
+								<td class="people_listing_specializations">
+									<A href=format!("/people/{}", people.id) class="none">
+										<EquipmentCell cell=people_specializations table_view=true />
+									</A>
+								</td>
+							}
+						}
+					</Show>
+					<Show when=move || {
+						query_filter.get().contains(&String::from("picture"))
+					}>
+						{
+							let people_picture = people.picture.clone();
+							view! {
+								<td class="people_listing_picture">
+									<A href=format!("/people/{}", people.id) class="none">
+										<EquipmentCell cell=people_picture table_view=true />
+									</A>
+								</td>
+							}
+						}
+					</Show>
+					<Show when=move || {
+						query_filter.get().contains(&String::from("bio"))
+					}>
+						{
+							let people_bio = people.bio.clone();
+							view! {
+								<td class="people_listing_bio">
+									<A href=format!("/people/{}", people.id) class="none">
+										<EquipmentCell cell=people_bio table_view=true />
+									</A>
+								</td>
+							}
+						}
+					</Show>
+					<Show when=move || {
+						query_filter.get().contains(&String::from("create_date"))
+					}>
+						{
+							view! {
+								<td class="people_listing_create_date">
+									<A href=format!("/people/{}", people.id) class="none">
+										<EquipmentCell cell=people.create_date table_view=true />
+									</A>
+								</td>
+							}
+						}
+					</Show>
+					<td class="people_listing_details_link">
+						<A href=format!("/people/{}", people.id)>Details</A>
+					</td>
+				</tr>
+			}
+		})
+		.collect_view()
+}
@@ -0,0 +1,12 @@
+use chrono::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// A named set of permissions that can be handed to many people at once
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ssr", derive(sqlx::FromRow))]
+pub struct RoleData {
+	pub id: i32,
+	pub name: String,
+	pub members: i64,
+	pub create_date: DateTime<Utc>,
+}
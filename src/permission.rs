@@ -1,14 +1,242 @@
+use leptos::*;
 use serde::{Deserialize, Serialize};
-#[cfg(feature = "ssr")]
-use std::fmt::Write;
+use thiserror::Error;
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Scope {
 	Equipment(i32),
 	Person(i32),
+	/// Equipment of the given type
+	EquipmentType(String),
+	/// Records stored at the given location
+	Location(String),
+	/// Records owned by someone in the given department
+	Department(String),
 	Any,
 }
 
+/// The kind of record a set of permissions applies to, attribute scopes are resolved against its table
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Dimension {
+	Equipment,
+	Culture,
+	Experiment,
+	People,
+}
+
+impl Dimension {
+	pub fn parse(input: &str) -> Option<Self> {
+		match input.to_lowercase().as_str() {
+			"equipment" => Some(Dimension::Equipment),
+			"culture" => Some(Dimension::Culture),
+			"experiment" => Some(Dimension::Experiment),
+			"people" => Some(Dimension::People),
+			_ => None,
+		}
+	}
+
+	pub fn table(&self) -> &'static str {
+		match self {
+			Dimension::Equipment => "equipment",
+			Dimension::Culture => "culture",
+			Dimension::Experiment => "experiment",
+			Dimension::People => "people",
+		}
+	}
+}
+
+/// The record a permission is checked against.
+/// Attributes that are unknown never match an attribute scope.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ssr", derive(sqlx::FromRow))]
+pub struct Subject {
+	pub id: i32,
+	pub person: i32,
+	pub equipment_type: Option<String>,
+	pub location: Option<String>,
+	pub department: Option<String>,
+}
+
+/// The subject of the detail page currently shown so nested edit toggles can check attribute scopes
+pub type SubjectSignal = RwSignal<Option<Subject>>;
+
+impl Subject {
+	pub fn new(id: i32, person: i32) -> Self {
+		Subject {
+			id,
+			person,
+			..Default::default()
+		}
+	}
+
+	/// Picks the subject provided by the surrounding detail page if it's about the same record
+	pub fn resolve(subject_signal: Option<SubjectSignal>, id: i32, person: i32) -> Self {
+		match subject_signal.and_then(|signal| signal.get()) {
+			Some(subject) if subject.id == id => subject,
+			_ => Subject::new(id, person),
+		}
+	}
+
+	#[cfg(feature = "ssr")]
+	pub async fn load<'a, E: sqlx::PgExecutor<'a>>(
+		dimension: Dimension,
+		id: i32,
+		executor: E,
+	) -> Result<Self, sqlx::Error> {
+		let query = match dimension {
+			Dimension::Equipment => {
+				"SELECT equipment.id, equipment.person, equipment.equipment_type, equipment.location, people.department FROM equipment JOIN people ON people.id = equipment.person WHERE equipment.id = $1"
+			},
+			Dimension::Culture => {
+				"SELECT culture.id, culture.person, NULL AS equipment_type, culture.location, people.department FROM culture JOIN people ON people.id = culture.person WHERE culture.id = $1"
+			},
+			Dimension::Experiment => {
+				"SELECT experiment.id, experiment.person, NULL AS equipment_type, NULL AS location, people.department FROM experiment JOIN people ON people.id = experiment.person WHERE experiment.id = $1"
+			},
+			Dimension::People => {
+				"SELECT id, id AS person, NULL AS equipment_type, NULL AS location, department FROM people WHERE id = $1"
+			},
+		};
+
+		sqlx::query_as::<_, Subject>(query).bind(id).fetch_one(executor).await
+	}
+}
+
+/// The values of a `QueryFilter` in the order they have to be bound
+#[cfg(feature = "ssr")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum QueryValues {
+	Ids(Vec<i32>),
+	Text(Vec<String>),
+}
+
+#[cfg(feature = "ssr")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum FilterClause {
+	Ids(Vec<i32>),
+	Persons(Vec<i32>),
+	EquipmentTypes(Vec<String>),
+	Locations(Vec<String>),
+	Departments(Vec<String>),
+}
+
+/// A permission filter for SQL queries that keeps its scope values in bind parameters.
+/// The generated SQL only depends on which kinds of scopes are present so query plans can be reused.
+#[cfg(feature = "ssr")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct QueryFilter {
+	dimension: Dimension,
+	field: String,
+	/// `None` when nothing needs to be filtered
+	clauses: Option<Vec<FilterClause>>,
+}
+
+#[cfg(feature = "ssr")]
+impl QueryFilter {
+	pub fn unrestricted(dimension: Dimension, field: &str) -> Self {
+		QueryFilter {
+			dimension,
+			field: field.to_string(),
+			clauses: None,
+		}
+	}
+
+	fn conditions(&self, first_param: usize) -> Option<String> {
+		let clauses = self.clauses.as_ref()?;
+		if clauses.is_empty() {
+			return Some(String::from("FALSE"));
+		}
+
+		let field = &self.field;
+		let table = self.dimension.table();
+		let conditions = clauses
+			.iter()
+			.enumerate()
+			.map(|(index, clause)| {
+				let param = first_param + index;
+				match clause {
+					FilterClause::Ids(_) => format!("{field} = ANY(${param})"),
+					// Going through the subject's own table keeps `person` from resolving to another joined table
+					FilterClause::Persons(_) if self.dimension == Dimension::People => format!("{field} = ANY(${param})"),
+					FilterClause::Persons(_) => format!("{field} IN (SELECT id FROM {table} WHERE person = ANY(${param}))"),
+					FilterClause::EquipmentTypes(_) => {
+						format!("{field} IN (SELECT id FROM equipment WHERE equipment_type = ANY(${param}))")
+					},
+					FilterClause::Locations(_) => format!("{field} IN (SELECT id FROM {table} WHERE location = ANY(${param}))"),
+					FilterClause::Departments(_) if self.dimension == Dimension::People => {
+						format!("{field} IN (SELECT id FROM people WHERE department = ANY(${param}))")
+					},
+					FilterClause::Departments(_) => format!(
+						"{field} IN (SELECT {table}.id FROM {table} JOIN people ON people.id = {table}.person WHERE people.department = ANY(${param}))"
+					),
+				}
+			})
+			.collect::<Vec<String>>();
+
+		Some(format!("({})", conditions.join(" OR ")))
+	}
+
+	/// The filter as a ` WHERE ...` clause with its parameters numbered from `first_param`
+	pub fn where_clause(&self, first_param: usize) -> String {
+		self.conditions(first_param).map(|conditions| format!(" WHERE {conditions}")).unwrap_or_default()
+	}
+
+	/// The filter as an ` AND ...` clause with its parameters numbered from `first_param`
+	pub fn and_clause(&self, first_param: usize) -> String {
+		self.conditions(first_param).map(|conditions| format!(" AND {conditions}")).unwrap_or_default()
+	}
+
+	pub fn values(&self) -> Vec<QueryValues> {
+		self
+			.clauses
+			.iter()
+			.flatten()
+			.map(|clause| match clause {
+				FilterClause::Ids(ids) | FilterClause::Persons(ids) => QueryValues::Ids(ids.clone()),
+				FilterClause::EquipmentTypes(values) | FilterClause::Locations(values) | FilterClause::Departments(values) => {
+					QueryValues::Text(values.clone())
+				},
+			})
+			.collect()
+	}
+}
+
+/// Binds the values of a `QueryFilter` after all other parameters of a query
+#[cfg(feature = "ssr")]
+pub trait BindFilter {
+	fn bind_filter(self, filter: &QueryFilter) -> Self;
+}
+
+#[cfg(feature = "ssr")]
+impl BindFilter for sqlx::query::Query<'_, sqlx::Postgres, sqlx::postgres::PgArguments> {
+	fn bind_filter(self, filter: &QueryFilter) -> Self {
+		filter.values().into_iter().fold(self, |query, values| match values {
+			QueryValues::Ids(ids) => query.bind(ids),
+			QueryValues::Text(values) => query.bind(values),
+		})
+	}
+}
+
+#[cfg(feature = "ssr")]
+impl<O> BindFilter for sqlx::query::QueryAs<'_, sqlx::Postgres, O, sqlx::postgres::PgArguments> {
+	fn bind_filter(self, filter: &QueryFilter) -> Self {
+		filter.values().into_iter().fold(self, |query, values| match values {
+			QueryValues::Ids(ids) => query.bind(ids),
+			QueryValues::Text(values) => query.bind(values),
+		})
+	}
+}
+
+#[cfg(feature = "ssr")]
+impl<O> BindFilter for sqlx::query::QueryScalar<'_, sqlx::Postgres, O, sqlx::postgres::PgArguments> {
+	fn bind_filter(self, filter: &QueryFilter) -> Self {
+		filter.values().into_iter().fold(self, |query, values| match values {
+			QueryValues::Ids(ids) => query.bind(ids),
+			QueryValues::Text(values) => query.bind(values),
+		})
+	}
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Permission {
 	ReadAny,
@@ -27,164 +255,495 @@ pub enum Permissions {
 	},
 }
 
-impl Permission {
-	#[cfg(feature = "ssr")]
-	pub fn parse(perm: String) -> Result<Permissions, &'static str> {
-		let perms: String = perm.chars().filter(|&c| c != ' ' && c != ')').map(|c| c.to_ascii_uppercase()).collect();
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Error)]
+pub enum PermissionErrorReason {
+	#[error("expected {0}")]
+	Expected(&'static str),
+	#[error("unrecognized action")]
+	UnrecognizedAction,
+	#[error("unrecognized scope")]
+	UnrecognizedScope,
+	#[error("could not parse id")]
+	InvalidId,
+	#[error("unterminated value")]
+	UnterminatedValue,
+	#[error("missing {0} section")]
+	MissingSection(&'static str),
+}
+
+/// A parse error together with the 1-based column in the permission string it was found at
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Error)]
+#[error("Invalid permission string ({reason} at column {column})")]
+pub struct PermissionError {
+	pub column: usize,
+	pub reason: PermissionErrorReason,
+}
+
+/// Recursive descent parser for permission strings.
+/// Keywords are case-insensitive and whitespace between tokens is ignored.
+///
+/// ```text
+/// permissions = section { "|" section } ;
+/// section     = ( "READ" | "WRITE" ) "(" scopes ")" | "CREATE" "(" ( "TRUE" | "FALSE" ) ")" ;
+/// scopes      = "*" | scope { "," scope } ;
+/// scope       = ( "EQUIPMENT" | "PERSON" ) "[" integer "]"
+///             | ( "TYPE" | "LOCATION" | "DEPARTMENT" ) "[" value "]" ;
+/// value       = '"' { character | '\\"' | '\\\\' } '"' ;
+/// ```
+struct PermissionParser {
+	chars: Vec<char>,
+	position: usize,
+}
+
+impl PermissionParser {
+	fn new(input: &str) -> Self {
+		PermissionParser {
+			chars: input.chars().collect(),
+			position: 0,
+		}
+	}
+
+	fn error(&self, position: usize, reason: PermissionErrorReason) -> PermissionError {
+		PermissionError {
+			column: position + 1,
+			reason,
+		}
+	}
+
+	fn skip_whitespace(&mut self) {
+		while self.chars.get(self.position).is_some_and(|c| c.is_whitespace()) {
+			self.position += 1;
+		}
+	}
 
+	fn peek(&mut self) -> Option<char> {
+		self.skip_whitespace();
+		self.chars.get(self.position).copied()
+	}
+
+	fn expect(&mut self, expected: char, label: &'static str) -> Result<(), PermissionError> {
+		if self.peek() == Some(expected) {
+			self.position += 1;
+			Ok(())
+		} else {
+			Err(self.error(self.position, PermissionErrorReason::Expected(label)))
+		}
+	}
+
+	/// Returns the next keyword in upper case together with the position it started at
+	fn keyword(&mut self) -> (String, usize) {
+		self.skip_whitespace();
+		let start = self.position;
+		while self.chars.get(self.position).is_some_and(|c| c.is_ascii_alphabetic()) {
+			self.position += 1;
+		}
+		(self.chars[start..self.position].iter().collect::<String>().to_ascii_uppercase(), start)
+	}
+
+	fn id(&mut self) -> Result<i32, PermissionError> {
+		self.skip_whitespace();
+		let start = self.position;
+		if self.chars.get(self.position) == Some(&'-') {
+			self.position += 1;
+		}
+		while self.chars.get(self.position).is_some_and(|c| c.is_ascii_digit()) {
+			self.position += 1;
+		}
+		self.chars[start..self.position]
+			.iter()
+			.collect::<String>()
+			.parse::<i32>()
+			.map_err(|_| self.error(start, PermissionErrorReason::InvalidId))
+	}
+
+	/// A double quoted value where quotes and backslashes are escaped with a backslash
+	fn value(&mut self) -> Result<String, PermissionError> {
+		self.expect('"', "a quoted value")?;
+		let start = self.position - 1;
+		let mut value = String::new();
+		loop {
+			match self.chars.get(self.position) {
+				None => return Err(self.error(start, PermissionErrorReason::UnterminatedValue)),
+				Some('"') => break,
+				Some('\\') => {
+					self.position += 1;
+					match self.chars.get(self.position) {
+						Some(c @ ('"' | '\\')) => value.push(*c),
+						_ => return Err(self.error(self.position, PermissionErrorReason::Expected("'\\\"' or '\\\\'"))),
+					}
+				},
+				Some(c) => value.push(*c),
+			}
+			self.position += 1;
+		}
+		self.position += 1;
+		Ok(value)
+	}
+
+	fn scope(&mut self) -> Result<Scope, PermissionError> {
+		let (kind, start) = self.keyword();
+		let scope = match kind.as_str() {
+			"EQUIPMENT" | "PERSON" => {
+				self.expect('[', "'['")?;
+				let id = self.id()?;
+				if kind == "EQUIPMENT" {
+					Scope::Equipment(id)
+				} else {
+					Scope::Person(id)
+				}
+			},
+			"TYPE" | "LOCATION" | "DEPARTMENT" => {
+				self.expect('[', "'['")?;
+				let value = self.value()?;
+				match kind.as_str() {
+					"TYPE" => Scope::EquipmentType(value),
+					"LOCATION" => Scope::Location(value),
+					_ => Scope::Department(value),
+				}
+			},
+			"" => return Err(self.error(start, PermissionErrorReason::Expected("a scope"))),
+			_ => return Err(self.error(start, PermissionErrorReason::UnrecognizedScope)),
+		};
+		self.expect(']', "']'")?;
+		Ok(scope)
+	}
+
+	fn scopes(&mut self) -> Result<Vec<Scope>, PermissionError> {
+		if self.peek() == Some('*') {
+			self.position += 1;
+			return Ok(vec![Scope::Any]);
+		}
+
+		let mut scopes = vec![self.scope()?];
+		while self.peek() == Some(',') {
+			self.position += 1;
+			scopes.push(self.scope()?);
+		}
+		Ok(scopes)
+	}
+
+	fn create(&mut self) -> Result<bool, PermissionError> {
+		let (value, start) = self.keyword();
+		match value.as_str() {
+			"TRUE" => Ok(true),
+			"FALSE" => Ok(false),
+			_ => Err(self.error(start, PermissionErrorReason::Expected("true or false"))),
+		}
+	}
+
+	fn permissions(&mut self) -> Result<Permissions, PermissionError> {
 		let mut read_scopes = Vec::new();
 		let mut write_scopes = Vec::new();
 		let mut create_scope = None;
 
-		for perm in perms.split("|") {
-			match perm {
-				"READ(*" => read_scopes.push(Scope::Any),
-				"WRITE(*" => write_scopes.push(Scope::Any),
-				"CREATE(TRUE" => create_scope = Some(true),
-				"CREATE(FALSE" => create_scope = Some(false),
-				cleaned_perm => {
-					let (action, scope) = cleaned_perm.split_once('(').ok_or("Invalid permission string (No scope found)")?;
-
-					let scope = scope.split(",").collect::<Vec<&str>>();
-
-					for scope_str in &scope {
-						let open_paren = scope_str.find('[').ok_or("Invalid permission string (Missing id)")?;
-						let close_paren = scope_str.find(']').ok_or("Invalid permission string (Missing id)")?;
-						let id_str = &scope_str[open_paren + 1..close_paren];
-
-						let id = match id_str.parse::<i32>() {
-							Ok(id) => id,
-							Err(_) => return Err("Invalid permission string (Could not parse id)"),
-						};
-
-						match &scope_str[..open_paren] {
-							"EQUIPMENT" => match action {
-								"READ" => {
-									read_scopes.push(Scope::Equipment(id));
-								},
-								"WRITE" => {
-									write_scopes.push(Scope::Equipment(id));
-								},
-								_ => return Err("Invalid permission string (Unrecognized action)"),
-							},
-							"PERSON" => match action {
-								"READ" => {
-									read_scopes.push(Scope::Person(id));
-								},
-								"WRITE" => {
-									write_scopes.push(Scope::Person(id));
-								},
-								_ => return Err("Invalid permission string (Unrecognized action)"),
-							},
-							_ => return Err("Invalid permission string (Unrecognized scope)"),
-						}
+		loop {
+			let (action, start) = self.keyword();
+			match action.as_str() {
+				"READ" | "WRITE" => {
+					self.expect('(', "'('")?;
+					let scopes = self.scopes()?;
+					self.expect(')', "')'")?;
+					if action == "READ" {
+						read_scopes.extend(scopes);
+					} else {
+						write_scopes.extend(scopes);
 					}
 				},
+				"CREATE" => {
+					self.expect('(', "'('")?;
+					create_scope = Some(self.create()?);
+					self.expect(')', "')'")?;
+				},
+				"" => return Err(self.error(start, PermissionErrorReason::Expected("READ, WRITE or CREATE"))),
+				_ => return Err(self.error(start, PermissionErrorReason::UnrecognizedAction)),
+			}
+
+			match self.peek() {
+				None => break,
+				Some('|') => self.position += 1,
+				Some(_) => return Err(self.error(self.position, PermissionErrorReason::Expected("'|'"))),
 			}
 		}
 
-		if read_scopes.is_empty() || write_scopes.is_empty() || create_scope.is_none() {
-			Err("Invalid permission string (No action/scope found)")
+		let end = self.chars.len();
+		if read_scopes.is_empty() {
+			return Err(self.error(end, PermissionErrorReason::MissingSection("READ")));
+		}
+		if write_scopes.is_empty() {
+			return Err(self.error(end, PermissionErrorReason::MissingSection("WRITE")));
+		}
+		match create_scope {
+			Some(create) => Ok(Permissions::from_scopes(read_scopes, write_scopes, create)),
+			None => Err(self.error(end, PermissionErrorReason::MissingSection("CREATE"))),
+		}
+	}
+}
+
+impl Permissions {
+	/// Builds the permissions from raw scopes making sure everything writable is also readable
+	pub fn from_scopes(mut read_scopes: Vec<Scope>, write_scopes: Vec<Scope>, create: bool) -> Self {
+		let (read, write) = if write_scopes.contains(&Scope::Any) {
+			// If we can write any, we must be able to read any
+			(Permission::ReadAny, Permission::WriteAny)
+		} else if read_scopes.contains(&Scope::Any) {
+			// If we can read all then our write can be a subset of ids
+			(Permission::ReadAny, Permission::Write(write_scopes))
 		} else {
-			let (read, write) = if write_scopes.contains(&Scope::Any) {
-				// If we can write any, we must be able to read any
-				(Permission::ReadAny, Permission::WriteAny)
-			} else if read_scopes.contains(&Scope::Any) {
-				// If we can read all then our write can be a subset of ids
-				(Permission::ReadAny, Permission::Write(write_scopes))
-			} else {
-				// If we have a list of ids in write let's make sure each id is also readable
-				for id in &write_scopes {
-					if !read_scopes.contains(id) {
-						read_scopes.push(*id);
-					}
+			// If we have a list of ids in write let's make sure each id is also readable
+			for scope in &write_scopes {
+				if !read_scopes.contains(scope) {
+					read_scopes.push(scope.clone());
 				}
-				(Permission::Read(read_scopes), Permission::Write(write_scopes))
-			};
+			}
+			(Permission::Read(read_scopes), Permission::Write(write_scopes))
+		};
 
-			Ok(Permissions::All {
-				read,
-				write,
-				create: Permission::Create(create_scope.unwrap()),
-			})
+		Permissions::All {
+			read,
+			write,
+			create: Permission::Create(create),
 		}
 	}
 
-	#[cfg(feature = "ssr")]
-	pub fn get_query_select(&self, field: &str) -> String {
-		let mut query = String::new();
+	/// Access to nothing, used for guests and for permission strings that can't be parsed
+	pub fn none() -> Self {
+		Permissions::All {
+			read: Permission::Read(vec![Scope::Equipment(-1)]),
+			write: Permission::Write(vec![Scope::Equipment(-1)]),
+			create: Permission::Create(false),
+		}
+	}
+
+	/// Combines two sets of permissions so everything either of them allows is allowed
+	pub fn union(&self, other: &Permissions) -> Self {
+		let (mut read, mut write, create) = self.to_scopes();
+		let (other_read, other_write, other_create) = other.to_scopes();
+
+		for scope in other_read {
+			if !read.contains(&scope) {
+				read.push(scope);
+			}
+		}
+		for scope in other_write {
+			if !write.contains(&scope) {
+				write.push(scope);
+			}
+		}
+
+		Permissions::from_scopes(read, write, create || other_create)
+	}
+
+	/// Whether everything these permissions allow is also allowed by `other`.
+	/// Scopes with negative ids can't match any record, like the ones `none` uses, so they never count.
+	pub fn is_subset_of(&self, other: &Permissions) -> bool {
+		let (read, write, create) = self.to_scopes();
+		let (other_read, other_write, other_create) = other.to_scopes();
+		let covered = |scopes: &[Scope], other_scopes: &[Scope]| {
+			other_scopes.contains(&Scope::Any)
+				|| scopes.iter().all(|scope| {
+					matches!(scope, Scope::Equipment(id) | Scope::Person(id) if *id < 0) || other_scopes.contains(scope)
+				})
+		};
+
+		covered(&read, &other_read) && covered(&write, &other_write) && (!create || other_create)
+	}
+
+	/// Splits the permissions back into the scopes of each action and the create flag
+	pub fn to_scopes(&self) -> (Vec<Scope>, Vec<Scope>, bool) {
+		let Permissions::All { read, write, create } = self;
+		(read.scopes(), write.scopes(), *create == Permission::Create(true))
+	}
+}
+
+impl std::fmt::Display for Scope {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		let quote = |value: &str| format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""));
 		match self {
-			Permission::ReadAny | Permission::WriteAny | Permission::Write(_) | Permission::Create(_) => {},
-			Permission::Read(scope) => {
-				let mut equipment_ids = String::new();
-				let mut person_ids = String::new();
-
-				for item in scope.iter() {
-					match item {
-						Scope::Equipment(id) => {
-							if !equipment_ids.is_empty() {
-								equipment_ids.push(',');
-							}
-							write!(&mut equipment_ids, "{id}").unwrap();
-						},
-						Scope::Person(id) => {
-							if !person_ids.is_empty() {
-								person_ids.push(',');
-							}
-							write!(&mut person_ids, "{id}").unwrap();
-						},
-						Scope::Any => {},
-					}
-				}
+			Scope::Equipment(id) => write!(f, "equipment[{id}]"),
+			Scope::Person(id) => write!(f, "person[{id}]"),
+			Scope::EquipmentType(value) => write!(f, "type[{}]", quote(value)),
+			Scope::Location(value) => write!(f, "location[{}]", quote(value)),
+			Scope::Department(value) => write!(f, "department[{}]", quote(value)),
+			Scope::Any => write!(f, "*"),
+		}
+	}
+}
 
-				let mut first_clause = true;
-				if !equipment_ids.is_empty() || !person_ids.is_empty() {
-					write!(&mut query, " WHERE ").unwrap();
-				}
-				if !equipment_ids.is_empty() {
-					write!(&mut query, "{field} IN ({equipment_ids})").unwrap();
-					first_clause = false;
-				}
-				if !person_ids.is_empty() {
-					if !first_clause {
-						write!(&mut query, " OR ").unwrap();
-					}
-					write!(&mut query, "person IN ({person_ids})").unwrap();
-				}
-			},
+impl std::fmt::Display for Permission {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		let join = |scopes: &[Scope]| scopes.iter().map(Scope::to_string).collect::<Vec<String>>().join(",");
+		match self {
+			Permission::ReadAny => write!(f, "READ(*)"),
+			Permission::Read(scopes) => write!(f, "READ({})", join(scopes)),
+			Permission::WriteAny => write!(f, "WRITE(*)"),
+			Permission::Write(scopes) => write!(f, "WRITE({})", join(scopes)),
+			Permission::Create(create) => write!(f, "CREATE({create})"),
 		}
+	}
+}
 
-		query
+impl std::fmt::Display for Permissions {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Permissions::All { read, write, create } => write!(f, "{read}|{write}|{create}"),
+		}
 	}
+}
 
-	#[cfg(feature = "ssr")]
-	pub fn get_query_select_without_where(&self, field: &str) -> String {
-		self.get_query_select(field).replace("WHERE", "AND")
+impl Permission {
+	pub fn parse(perm: String) -> Result<Permissions, PermissionError> {
+		PermissionParser::new(&perm).permissions()
+	}
+
+	fn scopes(&self) -> Vec<Scope> {
+		match self {
+			Permission::ReadAny | Permission::WriteAny => vec![Scope::Any],
+			Permission::Read(scopes) | Permission::Write(scopes) => scopes.clone(),
+			Permission::Create(_) => Vec::new(),
+		}
 	}
 
-	fn check_scope(&self, scope: &[Scope], equipment_id: i32, person: i32) -> bool {
-		for item in scope {
+	/// Builds a filter that limits `field` to the records this read permission covers.
+	/// The scope values are never part of the SQL, they are bound as array parameters instead.
+	#[cfg(feature = "ssr")]
+	pub fn get_query_filter(&self, dimension: Dimension, field: &str) -> QueryFilter {
+		let Permission::Read(scope) = self else {
+			return QueryFilter::unrestricted(dimension, field);
+		};
+		if scope.contains(&Scope::Any) {
+			return QueryFilter::unrestricted(dimension, field);
+		}
+
+		let mut equipment_ids = Vec::new();
+		let mut person_ids = Vec::new();
+		let mut equipment_types = Vec::new();
+		let mut locations = Vec::new();
+		let mut departments = Vec::new();
+
+		for item in scope.iter() {
 			match item {
-				Scope::Equipment(scope_id) if *scope_id == equipment_id => return true,
-				Scope::Person(scope_id) if *scope_id == person => return true,
-				Scope::Any => return true,
-				_ => {},
+				Scope::Equipment(id) => equipment_ids.push(*id),
+				Scope::Person(id) => person_ids.push(*id),
+				Scope::EquipmentType(value) => equipment_types.push(value.clone()),
+				Scope::Location(value) => locations.push(value.clone()),
+				Scope::Department(value) => departments.push(value.clone()),
+				Scope::Any => {},
 			}
 		}
-		false
+
+		let mut clauses = Vec::new();
+		if !equipment_ids.is_empty() {
+			clauses.push(FilterClause::Ids(equipment_ids));
+		}
+		if !person_ids.is_empty() {
+			clauses.push(FilterClause::Persons(person_ids));
+		}
+		if !equipment_types.is_empty() && dimension == Dimension::Equipment {
+			clauses.push(FilterClause::EquipmentTypes(equipment_types));
+		}
+		if !locations.is_empty() && matches!(dimension, Dimension::Equipment | Dimension::Culture) {
+			clauses.push(FilterClause::Locations(locations));
+		}
+		if !departments.is_empty() {
+			clauses.push(FilterClause::Departments(departments));
+		}
+
+		// Attribute scopes that don't exist on this dimension leave no clauses which denies everything
+		QueryFilter {
+			dimension,
+			field: field.to_string(),
+			clauses: Some(clauses),
+		}
+	}
+
+	fn check_scope(&self, scope: &[Scope], subject: &Subject) -> bool {
+		let matches = |value: &String, attribute: &Option<String>| attribute.as_ref() == Some(value);
+		scope.iter().any(|item| match item {
+			Scope::Equipment(scope_id) => *scope_id == subject.id,
+			Scope::Person(scope_id) => *scope_id == subject.person,
+			Scope::EquipmentType(value) => matches(value, &subject.equipment_type),
+			Scope::Location(value) => matches(value, &subject.location),
+			Scope::Department(value) => matches(value, &subject.department),
+			Scope::Any => true,
+		})
 	}
 
 	pub fn has_permission(&self, action: &str, equipment_id: i32, person: i32) -> bool {
+		self.has_permission_on(action, &Subject::new(equipment_id, person))
+	}
+
+	/// Like `has_permission` but also honours attribute scopes for the attributes the subject carries
+	pub fn has_permission_on(&self, action: &str, subject: &Subject) -> bool {
 		match (action, self) {
 			("read", Permission::ReadAny) | ("write", Permission::WriteAny) => true,
-			("read", Permission::Read(scope)) | ("write", Permission::Write(scope)) => {
-				self.check_scope(scope, equipment_id, person)
-			},
+			("read", Permission::Read(scope)) | ("write", Permission::Write(scope)) => self.check_scope(scope, subject),
 			_ => false,
 		}
 	}
 }
 
+/// Provide a `SubjectSignal` for a detail page that is kept in sync with the record it shows
+pub fn provide_subject_signal<T: Clone + PartialEq + 'static>(
+	dimension: Dimension,
+	id: impl Fn() -> String + 'static,
+	refetch_resources: RwSignal<T>,
+) -> SubjectSignal {
+	let subject_signal: SubjectSignal = create_rw_signal(None);
+	let subject = create_resource(
+		move || (id(), refetch_resources.get()),
+		move |(id, _)| get_permission_subject(dimension.table().to_string(), id),
+	);
+	create_effect(move |_| {
+		subject_signal.set(subject.get().and_then(Result::ok));
+	});
+	provide_context::<SubjectSignal>(subject_signal);
+
+	subject_signal
+}
+
+#[server(prefix = "/api")]
+pub async fn get_permission_subject(dimension: String, id: String) -> Result<Subject, ServerFnError> {
+	use crate::auth::get_user;
+
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	let dimension = match Dimension::parse(&dimension) {
+		Some(value) => value,
+		None => return Err(ServerFnError::Request(String::from("Invalid dimension"))),
+	};
+
+	let id = match id.parse::<i32>() {
+		Ok(value) => value,
+		Err(_) => return Err(ServerFnError::Request(String::from("Invalid ID"))),
+	};
+
+	match user {
+		Some(user) => {
+			let Permissions::All {
+				read: perm,
+				write: _,
+				create: _,
+			} = match dimension {
+				Dimension::Equipment => user.permission_equipment,
+				Dimension::Culture => user.permission_culture,
+				Dimension::Experiment => user.permission_experiment,
+				Dimension::People => user.permission_people,
+			};
+			let subject = Subject::load(dimension, id, &pool).await?;
+			if !perm.has_permission_on("read", &subject) {
+				return Err(ServerFnError::Request(String::from("User not authenticated")));
+			}
+
+			Ok(subject)
+		},
+		None => Err(ServerFnError::Request(String::from("User not authenticated"))),
+	}
+}
+
 #[cfg(all(test, feature = "ssr"))]
 mod tests {
 	use super::*;
@@ -336,173 +895,232 @@ mod tests {
 
 		assert_eq!(
 			Permission::parse(String::from("READ||WRITE(equipment[1])|CREATE(false)")),
-			Err("Invalid permission string (No scope found)")
+			Err(PermissionError {
+				column: 5,
+				reason: PermissionErrorReason::Expected("'('"),
+			})
 		);
 		assert_eq!(
 			Permission::parse(String::from("READ(equipment[1])||WRITE")),
-			Err("Invalid permission string (No scope found)")
+			Err(PermissionError {
+				column: 20,
+				reason: PermissionErrorReason::Expected("READ, WRITE or CREATE"),
+			})
 		);
 		assert_eq!(
 			Permission::parse(String::from("READ(equipment[1])|WRITE(equipment[1])|CREATE")),
-			Err("Invalid permission string (No scope found)")
+			Err(PermissionError {
+				column: 46,
+				reason: PermissionErrorReason::Expected("'('"),
+			})
 		);
 		assert_eq!(
 			Permission::parse(String::from("READ(equipment[1])")),
-			Err("Invalid permission string (No action/scope found)")
+			Err(PermissionError {
+				column: 19,
+				reason: PermissionErrorReason::MissingSection("WRITE"),
+			})
 		);
 		assert_eq!(
 			Permission::parse(String::from("WRITE(equipment[1])")),
-			Err("Invalid permission string (No action/scope found)")
+			Err(PermissionError {
+				column: 20,
+				reason: PermissionErrorReason::MissingSection("READ"),
+			})
 		);
 		assert_eq!(
 			Permission::parse(String::from("CREATE(false)")),
-			Err("Invalid permission string (No action/scope found)")
+			Err(PermissionError {
+				column: 14,
+				reason: PermissionErrorReason::MissingSection("READ"),
+			})
 		);
 		assert_eq!(
 			Permission::parse(String::from("READ(|WRITE(equipment[1])|CREATE(true)")),
-			Err("Invalid permission string (Missing id)")
+			Err(PermissionError {
+				column: 6,
+				reason: PermissionErrorReason::Expected("a scope"),
+			})
 		);
 		assert_eq!(
 			Permission::parse(String::from("READ()|WRITE(equipment[1])|CREATE(true)")),
-			Err("Invalid permission string (Missing id)")
+			Err(PermissionError {
+				column: 6,
+				reason: PermissionErrorReason::Expected("a scope"),
+			})
 		);
 		assert_eq!(
 			Permission::parse(String::from("READ(equipment)|WRITE(equipment[1])|CREATE(true)")),
-			Err("Invalid permission string (Missing id)")
+			Err(PermissionError {
+				column: 15,
+				reason: PermissionErrorReason::Expected("'['"),
+			})
 		);
 		assert_eq!(
 			Permission::parse(String::from("READ(equipment[)|WRITE(equipment[1])|CREATE(true)")),
-			Err("Invalid permission string (Missing id)")
+			Err(PermissionError {
+				column: 16,
+				reason: PermissionErrorReason::InvalidId,
+			})
 		);
 		assert_eq!(
 			Permission::parse(String::from("READ(equipment[5])|WRITE(equipment[1],*)|CREATE(true)")),
-			Err("Invalid permission string (Missing id)")
+			Err(PermissionError {
+				column: 39,
+				reason: PermissionErrorReason::Expected("a scope"),
+			})
 		);
 		assert_eq!(
 			Permission::parse(String::from("READ(equipment[1])|WRITE(equipment[1])|CREATE(foo)")),
-			Err("Invalid permission string (Missing id)")
+			Err(PermissionError {
+				column: 47,
+				reason: PermissionErrorReason::Expected("true or false"),
+			})
 		);
 		assert_eq!(
 			Permission::parse(String::from("READ(equipment[])|WRITE(equipment[1])|CREATE(true)")),
-			Err("Invalid permission string (Could not parse id)")
+			Err(PermissionError {
+				column: 16,
+				reason: PermissionErrorReason::InvalidId,
+			})
 		);
 		assert_eq!(
 			Permission::parse(String::from(
 				"READ(equipment[1],equipment[2],equipment[x],equipment[4])|WRITE(equipment[1])|CREATE(true)"
 			)),
-			Err("Invalid permission string (Could not parse id)")
+			Err(PermissionError {
+				column: 42,
+				reason: PermissionErrorReason::InvalidId,
+			})
 		);
 		assert_eq!(
 			Permission::parse(String::from("FOO(equipment[1],equipment[2],equipment[3])|WRITE(equipment[1])|CREATE(true)")),
-			Err("Invalid permission string (Unrecognized action)")
+			Err(PermissionError {
+				column: 1,
+				reason: PermissionErrorReason::UnrecognizedAction,
+			})
 		);
 		assert_eq!(
 			Permission::parse(String::from("READ(equipment[1],x[2],equipment[3])|WRITE(equipment[1])|CREATE(true)")),
-			Err("Invalid permission string (Unrecognized scope)")
+			Err(PermissionError {
+				column: 19,
+				reason: PermissionErrorReason::UnrecognizedScope,
+			})
 		);
 	}
 
 	#[test]
-	fn get_query_select_test() {
-		assert_eq!(
-			Permission::Read(vec![Scope::Equipment(1), Scope::Equipment(2), Scope::Equipment(3)]).get_query_select("id"),
-			String::from(" WHERE id IN (1,2,3)")
-		);
+	fn permission_display_round_trip_test() {
+		for perm in [
+			"READ(*)|WRITE(*)|CREATE(true)",
+			"READ(*)|WRITE(equipment[1],equipment[5],person[7])|CREATE(false)",
+			"READ(equipment[1],person[2],equipment[3])|WRITE(equipment[3])|CREATE(false)",
+			"READ(equipment[-1])|WRITE(equipment[-1])|CREATE(false)",
+		] {
+			let parsed = Permission::parse(String::from(perm)).unwrap();
+			assert_eq!(parsed.to_string(), perm);
+			assert_eq!(Permission::parse(parsed.to_string()), Ok(parsed));
+		}
+
 		assert_eq!(
-			Permission::Read(vec![Scope::Equipment(1), Scope::Equipment(2), Scope::Equipment(3)])
-				.get_query_select("equipment"),
-			String::from(" WHERE equipment IN (1,2,3)")
+			Permission::parse(String::from(" write( Equipment[2] ) | read(person[1]) | create(TRUE) ")).unwrap().to_string(),
+			String::from("READ(person[1],equipment[2])|WRITE(equipment[2])|CREATE(true)")
 		);
 		assert_eq!(
-			Permission::Read(vec![Scope::Person(1), Scope::Person(2), Scope::Person(3)]).get_query_select("id"),
-			String::from(" WHERE person IN (1,2,3)")
+			Permission::parse(String::from("READ(equipment[1])|WRITE(equipment[1]|CREATE(true)")).unwrap_err().to_string(),
+			String::from("Invalid permission string (expected ')' at column 38)")
 		);
+	}
 
-		assert_eq!(
-			Permission::Read(vec![Scope::Equipment(1), Scope::Equipment(2), Scope::Equipment(3)]).get_query_select("foo"),
-			String::from(" WHERE foo IN (1,2,3)")
-		);
+	#[test]
+	fn get_query_filter_test() {
+		let filter = Permission::Read(vec![Scope::Equipment(1), Scope::Equipment(2), Scope::Equipment(3)])
+			.get_query_filter(Dimension::Equipment, "id");
+		assert_eq!(filter.where_clause(1), String::from(" WHERE (id = ANY($1))"));
+		assert_eq!(filter.and_clause(3), String::from(" AND (id = ANY($3))"));
+		assert_eq!(filter.values(), vec![QueryValues::Ids(vec![1, 2, 3])]);
 
+		let filter = Permission::Read(vec![Scope::Person(1), Scope::Person(2), Scope::Person(3)])
+			.get_query_filter(Dimension::Equipment, "equipment");
 		assert_eq!(
-			Permission::Read(vec![
-				Scope::Equipment(1),
-				Scope::Equipment(2),
-				Scope::Person(666),
-				Scope::Person(42)
-			])
-			.get_query_select("id"),
-			String::from(" WHERE id IN (1,2) OR person IN (666,42)")
+			filter.where_clause(1),
+			String::from(" WHERE (equipment IN (SELECT id FROM equipment WHERE person = ANY($1)))")
 		);
+		assert_eq!(filter.values(), vec![QueryValues::Ids(vec![1, 2, 3])]);
+
+		let filter = Permission::Read(vec![
+			Scope::Person(666),
+			Scope::Equipment(1),
+			Scope::Person(42),
+			Scope::Equipment(2),
+		])
+		.get_query_filter(Dimension::Equipment, "foo");
 		assert_eq!(
-			Permission::Read(vec![
-				Scope::Person(666),
-				Scope::Person(42),
-				Scope::Equipment(1),
-				Scope::Equipment(2)
-			])
-			.get_query_select("equipment"),
-			String::from(" WHERE equipment IN (1,2) OR person IN (666,42)")
+			filter.where_clause(1),
+			String::from(" WHERE (foo = ANY($1) OR foo IN (SELECT id FROM equipment WHERE person = ANY($2)))")
 		);
 		assert_eq!(
-			Permission::Read(vec![Scope::Person(1), Scope::Equipment(1),]).get_query_select("id"),
-			String::from(" WHERE id IN (1) OR person IN (1)")
+			filter.and_clause(2),
+			String::from(" AND (foo = ANY($2) OR foo IN (SELECT id FROM equipment WHERE person = ANY($3)))")
 		);
+		assert_eq!(filter.values(), vec![QueryValues::Ids(vec![1, 2]), QueryValues::Ids(vec![666, 42])]);
 
+		// The SQL only depends on the kinds of scopes, not on their values
 		assert_eq!(
-			Permission::Read(vec![
-				Scope::Person(1),
-				Scope::Equipment(1),
-				Scope::Person(2),
-				Scope::Equipment(2)
-			])
-			.get_query_select("id"),
-			String::from(" WHERE id IN (1,2) OR person IN (1,2)")
+			Permission::Read(vec![Scope::Equipment(7), Scope::Person(8)])
+				.get_query_filter(Dimension::Equipment, "foo")
+				.and_clause(2),
+			filter.and_clause(2)
 		);
-	}
 
-	#[test]
-	fn get_query_select_without_where_test() {
+		let filter = Permission::ReadAny.get_query_filter(Dimension::Equipment, "id");
+		assert_eq!(filter.where_clause(1), String::new());
+		assert_eq!(filter.and_clause(1), String::new());
+		assert_eq!(filter.values(), Vec::new());
 		assert_eq!(
-			Permission::Read(vec![Scope::Equipment(1), Scope::Equipment(2), Scope::Equipment(3)])
-				.get_query_select_without_where("id"),
-			String::from(" AND id IN (1,2,3)"),
+			Permission::Read(vec![Scope::Person(1), Scope::Any]).get_query_filter(Dimension::Equipment, "id").and_clause(1),
+			String::new()
 		);
+
+		let filter = Permission::Read(Vec::new()).get_query_filter(Dimension::Equipment, "id");
+		assert_eq!(filter.and_clause(1), String::from(" AND FALSE"));
+		assert_eq!(filter.values(), Vec::new());
 	}
 
 	#[test]
 	fn has_permission_test() {
-		assert_eq!(Permission::Write(vec![Scope::Equipment(1)]).has_permission("write", 1, -1), true);
-		assert_eq!(Permission::WriteAny.has_permission("write", 2, -1), true);
-		assert_eq!(Permission::Write(vec![Scope::Equipment(1), Scope::Equipment(2)]).has_permission("write", 1, -1), true);
-		assert_eq!(Permission::Write(vec![Scope::Equipment(1), Scope::Equipment(2)]).has_permission("write", 2, -1), true);
-		assert_eq!(Permission::Write(vec![Scope::Equipment(1), Scope::Equipment(3)]).has_permission("write", 3, -1), true);
-		assert_eq!(Permission::Write(vec![Scope::Equipment(1), Scope::Equipment(2)]).has_permission("write", 4, -1), false);
-		assert_eq!(Permission::Write(vec![Scope::Equipment(1), Scope::Equipment(3)]).has_permission("write", 2, -1), false);
-		assert_eq!(Permission::Write(vec![Scope::Person(2)]).has_permission("write", 2, -1), false);
-		assert_eq!(Permission::ReadAny.has_permission("write", 2, -1), false);
-		assert_eq!(Permission::Read(vec![Scope::Equipment(2)]).has_permission("write", 2, -1), false);
-		assert_eq!(Permission::Create(true).has_permission("write", 2, -1), false);
-
-		assert_eq!(Permission::Write(vec![Scope::Person(1)]).has_permission("write", -1, 1), true);
-		assert_eq!(Permission::WriteAny.has_permission("write", -1, 2), true);
-		assert_eq!(Permission::Write(vec![Scope::Person(1), Scope::Person(2)]).has_permission("write", -1, 1), true);
-		assert_eq!(Permission::Write(vec![Scope::Person(1), Scope::Person(2)]).has_permission("write", -1, 2), true);
-		assert_eq!(Permission::Write(vec![Scope::Person(1), Scope::Person(3)]).has_permission("write", -1, 3), true);
-		assert_eq!(Permission::Write(vec![Scope::Person(1), Scope::Person(2)]).has_permission("write", -1, 4), false);
-		assert_eq!(Permission::Write(vec![Scope::Person(1), Scope::Person(3)]).has_permission("write", -1, 2), false);
-		assert_eq!(Permission::Write(vec![Scope::Equipment(2)]).has_permission("write", -1, 2), false);
-		assert_eq!(Permission::ReadAny.has_permission("write", -1, 2), false);
-		assert_eq!(Permission::Read(vec![Scope::Person(2)]).has_permission("write", -1, 2), false);
-		assert_eq!(Permission::Create(true).has_permission("write", -1, 2), false);
-		assert_eq!(Permission::Read(vec![Scope::Person(5), Scope::Equipment(20)]).has_permission("write", 20, 55), false);
-		assert_eq!(Permission::Read(vec![Scope::Person(5), Scope::Equipment(20)]).has_permission("write", 2, 5), false);
-
-		assert_eq!(Permission::Write(vec![Scope::Person(5), Scope::Equipment(20)]).has_permission("write", 20, 55), true);
-		assert_eq!(Permission::Write(vec![Scope::Person(5), Scope::Equipment(20)]).has_permission("write", 2, 5), true);
-
-		assert_eq!(Permission::Read(vec![Scope::Equipment(1)]).has_permission("read", 1, -1), true);
-		assert_eq!(Permission::Read(vec![Scope::Equipment(1)]).has_permission("read", 2, -1), false);
-		assert_eq!(Permission::Read(vec![Scope::Person(1)]).has_permission("read", 2, 1), true);
+		assert!(Permission::Write(vec![Scope::Equipment(1)]).has_permission("write", 1, -1));
+		assert!(Permission::WriteAny.has_permission("write", 2, -1));
+		assert!(Permission::Write(vec![Scope::Equipment(1), Scope::Equipment(2)]).has_permission("write", 1, -1));
+		assert!(Permission::Write(vec![Scope::Equipment(1), Scope::Equipment(2)]).has_permission("write", 2, -1));
+		assert!(Permission::Write(vec![Scope::Equipment(1), Scope::Equipment(3)]).has_permission("write", 3, -1));
+		assert!(!Permission::Write(vec![Scope::Equipment(1), Scope::Equipment(2)]).has_permission("write", 4, -1));
+		assert!(!Permission::Write(vec![Scope::Equipment(1), Scope::Equipment(3)]).has_permission("write", 2, -1));
+		assert!(!Permission::Write(vec![Scope::Person(2)]).has_permission("write", 2, -1));
+		assert!(!Permission::ReadAny.has_permission("write", 2, -1));
+		assert!(!Permission::Read(vec![Scope::Equipment(2)]).has_permission("write", 2, -1));
+		assert!(!Permission::Create(true).has_permission("write", 2, -1));
+
+		assert!(Permission::Write(vec![Scope::Person(1)]).has_permission("write", -1, 1));
+		assert!(Permission::WriteAny.has_permission("write", -1, 2));
+		assert!(Permission::Write(vec![Scope::Person(1), Scope::Person(2)]).has_permission("write", -1, 1));
+		assert!(Permission::Write(vec![Scope::Person(1), Scope::Person(2)]).has_permission("write", -1, 2));
+		assert!(Permission::Write(vec![Scope::Person(1), Scope::Person(3)]).has_permission("write", -1, 3));
+		assert!(!Permission::Write(vec![Scope::Person(1), Scope::Person(2)]).has_permission("write", -1, 4));
+		assert!(!Permission::Write(vec![Scope::Person(1), Scope::Person(3)]).has_permission("write", -1, 2));
+		assert!(!Permission::Write(vec![Scope::Equipment(2)]).has_permission("write", -1, 2));
+		assert!(!Permission::ReadAny.has_permission("write", -1, 2));
+		assert!(!Permission::Read(vec![Scope::Person(2)]).has_permission("write", -1, 2));
+		assert!(!Permission::Create(true).has_permission("write", -1, 2));
+		assert!(!Permission::Read(vec![Scope::Person(5), Scope::Equipment(20)]).has_permission("write", 20, 55));
+		assert!(!Permission::Read(vec![Scope::Person(5), Scope::Equipment(20)]).has_permission("write", 2, 5));
+
+		assert!(Permission::Write(vec![Scope::Person(5), Scope::Equipment(20)]).has_permission("write", 20, 55));
+		assert!(Permission::Write(vec![Scope::Person(5), Scope::Equipment(20)]).has_permission("write", 2, 5));
+
+		assert!(Permission::Read(vec![Scope::Equipment(1)]).has_permission("read", 1, -1));
+		assert!(!Permission::Read(vec![Scope::Equipment(1)]).has_permission("read", 2, -1));
+		assert!(Permission::Read(vec![Scope::Person(1)]).has_permission("read", 2, 1));
 
 		// User has write access to all equipment that has id 5,6,7 and all equipment that was created by user 12,13
 		// WRITE(equipment[5],equipment[6],equipment[7],person[12],person[13])
@@ -510,4 +1128,187 @@ mod tests {
 		// Permission::Write(vec![Scope::Equipment(5), Scope::Equipment(6), Scope::Equipment(7), Scope::Person(12), Scope::Person(13)]
 		// has_permission needs to see if the equipment is either within the allowed ids or person within the allowed person
 	}
+
+	#[test]
+	fn permissions_union_test() {
+		let technician =
+			Permission::parse(String::from("READ(equipment[1],person[2])|WRITE(equipment[1])|CREATE(false)")).unwrap();
+		let manager = Permission::parse(String::from("READ(equipment[3])|WRITE(person[2])|CREATE(true)")).unwrap();
+
+		assert_eq!(
+			technician.union(&manager),
+			Permissions::All {
+				read: Permission::Read(vec![Scope::Equipment(1), Scope::Person(2), Scope::Equipment(3)]),
+				write: Permission::Write(vec![Scope::Equipment(1), Scope::Person(2)]),
+				create: Permission::Create(true),
+			}
+		);
+		assert_eq!(technician.union(&technician), technician);
+		assert_eq!(
+			Permissions::none().union(&Permission::parse(String::from("READ(*)|WRITE(person[4])|CREATE(false)")).unwrap()),
+			Permissions::All {
+				read: Permission::ReadAny,
+				write: Permission::Write(vec![Scope::Equipment(-1), Scope::Person(4)]),
+				create: Permission::Create(false),
+			}
+		);
+		assert_eq!(
+			technician.union(&Permission::parse(String::from("READ(*)|WRITE(*)|CREATE(false)")).unwrap()),
+			Permissions::All {
+				read: Permission::ReadAny,
+				write: Permission::WriteAny,
+				create: Permission::Create(false),
+			}
+		);
+
+		let Permissions::All { read, write, create: _ } = technician.union(&manager);
+		assert!(read.has_permission("read", 3, -1));
+		assert!(write.has_permission("write", 9, 2));
+		assert!(!write.has_permission("write", 3, -1));
+		let filter = read.get_query_filter(Dimension::Equipment, "id");
+		assert_eq!(
+			filter.where_clause(1),
+			" WHERE (id = ANY($1) OR id IN (SELECT id FROM equipment WHERE person = ANY($2)))"
+		);
+		assert_eq!(filter.values(), vec![QueryValues::Ids(vec![1, 3]), QueryValues::Ids(vec![2])]);
+	}
+
+	#[test]
+	fn attribute_scope_test() {
+		let perm = Permission::parse(String::from(
+			r#"READ(type["IncubationCabinet"],location["Lab 2"],department["Gen\"etics"])|WRITE(location["Lab 2"])|CREATE(false)"#,
+		))
+		.unwrap();
+		let Permissions::All { read, write, create: _ } = perm.clone();
+
+		assert_eq!(
+			read,
+			Permission::Read(vec![
+				Scope::EquipmentType(String::from("IncubationCabinet")),
+				Scope::Location(String::from("Lab 2")),
+				Scope::Department(String::from("Gen\"etics")),
+			])
+		);
+		assert_eq!(Permission::parse(perm.to_string()), Ok(perm));
+
+		let cabinet = Subject {
+			id: 4,
+			person: 9,
+			equipment_type: Some(String::from("IncubationCabinet")),
+			location: Some(String::from("Lab 1")),
+			department: None,
+		};
+		let flask_in_lab = Subject {
+			id: 5,
+			person: 9,
+			equipment_type: Some(String::from("Flask")),
+			location: Some(String::from("Lab 2")),
+			department: None,
+		};
+		let flask_elsewhere = Subject {
+			location: Some(String::from("Lab 3")),
+			department: Some(String::from("Gen\"etics")),
+			..flask_in_lab.clone()
+		};
+
+		assert!(read.has_permission_on("read", &cabinet));
+		assert!(!write.has_permission_on("write", &cabinet));
+		assert!(read.has_permission_on("read", &flask_in_lab));
+		assert!(write.has_permission_on("write", &flask_in_lab));
+		assert!(read.has_permission_on("read", &flask_elsewhere));
+		assert!(!write.has_permission_on("write", &flask_elsewhere));
+		// Without attributes only ids and owners can match
+		assert!(!read.has_permission("read", 4, 9));
+
+		let filter = read.get_query_filter(Dimension::Equipment, "equipment.id");
+		assert_eq!(
+			filter.where_clause(1),
+			String::from(
+				" WHERE (equipment.id IN (SELECT id FROM equipment WHERE equipment_type = ANY($1)) \
+				OR equipment.id IN (SELECT id FROM equipment WHERE location = ANY($2)) \
+				OR equipment.id IN (SELECT equipment.id FROM equipment JOIN people ON people.id = equipment.person WHERE people.department = ANY($3)))"
+			)
+		);
+		assert_eq!(
+			filter.values(),
+			vec![
+				QueryValues::Text(vec![String::from("IncubationCabinet")]),
+				QueryValues::Text(vec![String::from("Lab 2")]),
+				QueryValues::Text(vec![String::from("Gen\"etics")]),
+			]
+		);
+
+		let filter = Permission::Read(vec![Scope::Person(4)]).get_query_filter(Dimension::People, "people.id");
+		assert_eq!(filter.where_clause(1), String::from(" WHERE (people.id = ANY($1))"));
+
+		let filter = read.get_query_filter(Dimension::People, "id");
+		assert_eq!(filter.and_clause(1), String::from(" AND (id IN (SELECT id FROM people WHERE department = ANY($1)))"));
+		assert_eq!(filter.values(), vec![QueryValues::Text(vec![String::from("Gen\"etics")])]);
+
+		let filter = Permission::Read(vec![Scope::EquipmentType(String::from("Flask"))])
+			.get_query_filter(Dimension::Culture, "culture.id");
+		assert_eq!(filter.where_clause(1), String::from(" WHERE FALSE"));
+		assert_eq!(filter.values(), Vec::new());
+
+		// Values are bound as they are and never end up in the SQL
+		let filter = Permission::Read(vec![Scope::Location(String::from("Lab 'B'); DROP TABLE culture; --"))])
+			.get_query_filter(Dimension::Culture, "culture.id");
+		assert_eq!(
+			filter.where_clause(1),
+			String::from(" WHERE (culture.id IN (SELECT id FROM culture WHERE location = ANY($1)))")
+		);
+		assert_eq!(
+			filter.values(),
+			vec![QueryValues::Text(vec![String::from(
+				"Lab 'B'); DROP TABLE culture; --"
+			)])]
+		);
+
+		assert_eq!(
+			Permission::parse(String::from(r#"READ(location["Lab 2)|WRITE(*)|CREATE(false)"#)).unwrap_err(),
+			PermissionError {
+				column: 15,
+				reason: PermissionErrorReason::UnterminatedValue,
+			}
+		);
+		assert_eq!(
+			Permission::parse(String::from("READ(location[2])|WRITE(*)|CREATE(false)")).unwrap_err(),
+			PermissionError {
+				column: 15,
+				reason: PermissionErrorReason::Expected("a quoted value"),
+			}
+		);
+	}
+
+	#[test]
+	fn permissions_is_subset_of_test() {
+		let manager =
+			Permission::parse(String::from("READ(equipment[1],type[\"Flask\"])|WRITE(equipment[1])|CREATE(true)")).unwrap();
+		let admin = Permission::parse(String::from("READ(*)|WRITE(*)|CREATE(true)")).unwrap();
+
+		assert!(Permissions::none().is_subset_of(&manager));
+		assert!(manager.is_subset_of(&manager));
+		assert!(manager.is_subset_of(&admin));
+		assert!(!admin.is_subset_of(&manager));
+		assert!(
+			Permission::parse(String::from("READ(type[\"Flask\"])|WRITE(equipment[-1])|CREATE(false)"))
+				.unwrap()
+				.is_subset_of(&manager)
+		);
+		assert!(
+			!Permission::parse(String::from("READ(equipment[2])|WRITE(equipment[-1])|CREATE(false)"))
+				.unwrap()
+				.is_subset_of(&manager)
+		);
+		assert!(
+			!Permission::parse(String::from("READ(equipment[1])|WRITE(type[\"Flask\"])|CREATE(false)"))
+				.unwrap()
+				.is_subset_of(&manager)
+		);
+		assert!(
+			!Permission::parse(String::from("READ(equipment[1])|WRITE(equipment[1])|CREATE(true)")).unwrap().is_subset_of(
+				&Permissions::none().union(&Permission::parse(String::from("READ(*)|WRITE(*)|CREATE(false)")).unwrap())
+			)
+		);
+	}
 }
@@ -26,6 +26,24 @@ fn test_get_equipment_base_folder() {
 	);
 }
 
+pub fn get_culture_base_folder(id: i32) -> String {
+	let (lower_bound, upper_bound) = get_bounds(id);
+	format!("/upload_media/culture/{lower_bound}-{upper_bound}k/{id}/")
+}
+
+#[test]
+fn test_get_culture_base_folder() {
+	assert_eq!(get_culture_base_folder(2), String::from("/upload_media/culture/0-5k/2/"));
+	assert_eq!(get_culture_base_folder(50), String::from("/upload_media/culture/0-5k/50/"));
+	assert_eq!(get_culture_base_folder(4999), String::from("/upload_media/culture/0-5k/4999/"));
+	assert_eq!(get_culture_base_folder(5000), String::from("/upload_media/culture/5-10k/5000/"));
+	assert_eq!(get_culture_base_folder(28_999), String::from("/upload_media/culture/25-30k/28999/"));
+	assert_eq!(
+		get_culture_base_folder(2_147_483_647),
+		String::from("/upload_media/culture/2147480-2147485k/2147483647/")
+	);
+}
+
 pub fn get_equipment_notes_folder(id: i32) -> String {
 	let (lower_bound, upper_bound) = get_bounds(id);
 	format!("notes/{lower_bound}-{upper_bound}k/")
@@ -0,0 +1,268 @@
+use crate::{
+	equipment::{EquipmentLogData, EquipmentLogSQLData, EquipmentLogType, EquipmentNotesData, EquipmentNotesSQLData},
+	jobs::retry_backoff,
+	webhooks::WebhookEvent,
+};
+
+use chrono::prelude::*;
+use hmac::{Hmac, Mac};
+use serde::Serialize;
+use sha2::Sha256;
+use sqlx::{PgConnection, PgPool};
+
+/// A delivery that failed this many times is given up on
+pub const MAX_DELIVERY_ATTEMPTS: i32 = 8;
+
+/// How many deliveries one run of the job sends at most, small enough that a run of timeouts ends well inside the job lock
+const DELIVERY_BATCH: i64 = 50;
+
+const DELIVERY_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// How long claimed deliveries stay out of other runs, longer than a whole batch of timeouts can take
+const CLAIM_SECONDS: i32 = 30 * 60;
+
+/// The value of the `X-Codon-Signature` header, receivers recompute it over the raw body with their secret
+pub fn sign_payload(secret: &str, payload: &str) -> String {
+	let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+	mac.update(payload.as_bytes());
+	format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// The JSON body sent for an event, `data` is the log entry or note it was caused by
+pub fn webhook_payload<T: Serialize>(event: WebhookEvent, equipment: i32, data: &T) -> String {
+	serde_json::json!({
+		"event": event.to_string(),
+		"equipment": equipment,
+		"timestamp": Utc::now(),
+		"data": data,
+	})
+	.to_string()
+}
+
+/// Which event a log entry stands for, maintenance records aren't sent
+pub fn log_event(log_type: &EquipmentLogType) -> Option<WebhookEvent> {
+	match log_type {
+		EquipmentLogType::Edit => Some(WebhookEvent::FieldEdit),
+		EquipmentLogType::Create => Some(WebhookEvent::EquipmentCreated),
+		EquipmentLogType::Maintenance => None,
+		EquipmentLogType::Cleaning
+		| EquipmentLogType::Preparation
+		| EquipmentLogType::Sterilization
+		| EquipmentLogType::Using
+		| EquipmentLogType::Dirty
+		| EquipmentLogType::Archive => Some(WebhookEvent::StatusChange),
+	}
+}
+
+async fn queue_webhooks(
+	connection: &mut PgConnection,
+	event: WebhookEvent,
+	payload: String,
+) -> Result<(), sqlx::Error> {
+	sqlx::query(
+		r#"
+		INSERT INTO webhook_deliveries (webhook, event, payload)
+		SELECT id, $1, $2 FROM webhooks WHERE enabled AND $1 = ANY(events)
+		"#,
+	)
+	.bind(event.to_string())
+	.bind(payload)
+	.execute(connection)
+	.await
+	.map(|_| ())
+}
+
+/// Queues a delivery for every webhook interested in a log entry.
+/// Called with the connection that wrote the entry so a rolled back change doesn't send anything.
+pub async fn queue_log_webhooks(connection: &mut PgConnection, log_id: i32) -> Result<(), sqlx::Error> {
+	let log: EquipmentLogData = sqlx::query_as::<_, EquipmentLogSQLData>(
+		r#"SELECT
+			equipment_log.*,
+			people.id AS person_id,
+			people.status AS person_status,
+			people.preferred_name AS person_preferred_name,
+			people.picture AS person_picture
+		FROM
+			equipment_log
+			JOIN people ON equipment_log.person = people.id
+		WHERE equipment_log.id = $1"#,
+	)
+	.bind(log_id)
+	.fetch_one(&mut *connection)
+	.await?
+	.into();
+
+	match log_event(&log.log_type) {
+		Some(event) => queue_webhooks(connection, event, webhook_payload(event, log.equipment, &log)).await,
+		None => Ok(()),
+	}
+}
+
+/// Queues a delivery for every webhook interested in a note, deletions have to call this before the note is gone
+pub async fn queue_note_webhooks(
+	connection: &mut PgConnection,
+	note_id: i32,
+	event: WebhookEvent,
+) -> Result<(), sqlx::Error> {
+	let note: EquipmentNotesData = sqlx::query_as::<_, EquipmentNotesSQLData>(
+		r#"SELECT
+			equipment_notes.*,
+			people.id AS person_id,
+			people.status AS person_status,
+			people.preferred_name AS person_preferred_name,
+			people.picture AS person_picture
+		FROM
+			equipment_notes
+			JOIN people ON equipment_notes.person = people.id
+		WHERE equipment_notes.id = $1"#,
+	)
+	.bind(note_id)
+	.fetch_one(&mut *connection)
+	.await?
+	.into();
+
+	queue_webhooks(connection, event, webhook_payload(event, note.equipment, &note)).await
+}
+
+/// Sends due deliveries of enabled webhooks, failures are retried with a growing backoff
+pub async fn deliver_webhooks(pool: PgPool) -> Result<(), String> {
+	// Claiming moves `next_attempt` past the end of this run so no other run picks the same deliveries up,
+	// a crashed instance leaves them pending and they are sent again once the claim runs out
+	let deliveries: Vec<(i32, String, String, String, String, i32)> = sqlx::query_as(
+		r#"
+		WITH claimed AS (
+			UPDATE webhook_deliveries SET next_attempt = NOW() + $2 * INTERVAL '1 second'
+			WHERE id IN (
+				SELECT webhook_deliveries.id
+				FROM
+					webhook_deliveries
+					JOIN webhooks ON webhook_deliveries.webhook = webhooks.id
+				WHERE
+					webhook_deliveries.status = 'pending'
+					AND webhook_deliveries.next_attempt <= NOW()
+					AND webhooks.enabled
+				ORDER BY webhook_deliveries.id
+				LIMIT $1
+				FOR UPDATE OF webhook_deliveries SKIP LOCKED
+			)
+			RETURNING webhook_deliveries.*
+		)
+		SELECT claimed.id, webhooks.url, webhooks.secret, claimed.event, claimed.payload, claimed.attempts
+		FROM
+			claimed
+			JOIN webhooks ON claimed.webhook = webhooks.id
+		ORDER BY claimed.id
+		"#,
+	)
+	.bind(DELIVERY_BATCH)
+	.bind(CLAIM_SECONDS)
+	.fetch_all(&pool)
+	.await
+	.map_err(|error| error.to_string())?;
+
+	if deliveries.is_empty() {
+		return Ok(());
+	}
+
+	let client = reqwest::Client::builder().timeout(DELIVERY_TIMEOUT).build().map_err(|error| error.to_string())?;
+
+	for (id, url, secret, event, payload, attempts) in deliveries {
+		let response = client
+			.post(&url)
+			.header("Content-Type", "application/json")
+			.header("X-Codon-Event", &event)
+			.header("X-Codon-Delivery", id.to_string())
+			.header("X-Codon-Signature", sign_payload(&secret, &payload))
+			.body(payload)
+			.send()
+			.await;
+
+		let (response_status, error) = match response {
+			Ok(response) if response.status().is_success() => (Some(response.status().as_u16() as i32), None),
+			Ok(response) => (Some(response.status().as_u16() as i32), Some(format!("Responded with {}", response.status()))),
+			Err(error) => (None, Some(error.to_string())),
+		};
+
+		match error {
+			None => {
+				sqlx::query(
+					r#"
+					UPDATE webhook_deliveries
+					SET status = 'delivered', attempts = attempts + 1, response_status = $2, error = NULL, delivered_at = NOW()
+					WHERE id = $1
+					"#,
+				)
+				.bind(id)
+				.bind(response_status)
+				.execute(&pool)
+				.await
+				.map_err(|error| error.to_string())?;
+			},
+			Some(error) => {
+				let attempt = attempts + 1;
+				let status = if attempt >= MAX_DELIVERY_ATTEMPTS {
+					"failed"
+				} else {
+					"pending"
+				};
+				sqlx::query(
+					r#"
+					UPDATE webhook_deliveries
+					SET status = $2, attempts = $3, next_attempt = $4, response_status = $5, error = $6
+					WHERE id = $1
+					"#,
+				)
+				.bind(id)
+				.bind(status)
+				.bind(attempt)
+				.bind(Utc::now() + retry_backoff(attempt))
+				.bind(response_status)
+				.bind(error)
+				.execute(&pool)
+				.await
+				.map_err(|error| error.to_string())?;
+			},
+		}
+	}
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn sign_payload_test() {
+		// RFC 4231 test case 2
+		assert_eq!(
+			sign_payload("Jefe", "what do ya want for nothing?"),
+			"sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+		);
+	}
+
+	#[test]
+	fn log_event_test() {
+		assert_eq!(log_event(&EquipmentLogType::Edit), Some(WebhookEvent::FieldEdit));
+		assert_eq!(log_event(&EquipmentLogType::Create), Some(WebhookEvent::EquipmentCreated));
+		assert_eq!(log_event(&EquipmentLogType::Cleaning), Some(WebhookEvent::StatusChange));
+		assert_eq!(log_event(&EquipmentLogType::Maintenance), None);
+	}
+
+	#[test]
+	fn webhook_payload_test() {
+		let log = EquipmentLogData {
+			id: 4,
+			equipment: 12,
+			field: Some(String::from("name")),
+			new_value: Some(String::from("Spore Spa")),
+			..Default::default()
+		};
+		let payload: serde_json::Value = serde_json::from_str(&webhook_payload(WebhookEvent::FieldEdit, 12, &log)).unwrap();
+
+		assert_eq!(payload["event"], "field_edit");
+		assert_eq!(payload["equipment"], 12);
+		assert_eq!(payload["data"]["field"], "name");
+		assert_eq!(payload["data"]["new_value"], "Spore Spa");
+	}
+}
@@ -0,0 +1,10 @@
+#[cfg(feature = "ssr")]
+pub mod delivery;
+#[cfg(feature = "ssr")]
+pub use delivery::*;
+
+pub mod schema;
+pub use schema::*;
+
+pub mod webhooks_view;
+pub use webhooks_view::*;
@@ -0,0 +1,163 @@
+use chrono::prelude::*;
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "ssr")]
+use sqlx::Row;
+
+/// Something that happened to equipment which webhooks can subscribe to
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum WebhookEvent {
+	StatusChange,
+	FieldEdit,
+	NoteCreated,
+	NoteDeleted,
+	EquipmentCreated,
+}
+
+impl WebhookEvent {
+	pub fn all() -> Vec<Self> {
+		vec![
+			WebhookEvent::StatusChange,
+			WebhookEvent::FieldEdit,
+			WebhookEvent::NoteCreated,
+			WebhookEvent::NoteDeleted,
+			WebhookEvent::EquipmentCreated,
+		]
+	}
+
+	pub fn parse(input: &str) -> Option<Self> {
+		match input {
+			"status_change" => Some(WebhookEvent::StatusChange),
+			"field_edit" => Some(WebhookEvent::FieldEdit),
+			"note_created" => Some(WebhookEvent::NoteCreated),
+			"note_deleted" => Some(WebhookEvent::NoteDeleted),
+			"equipment_created" => Some(WebhookEvent::EquipmentCreated),
+			_ => None,
+		}
+	}
+
+	pub fn label(&self) -> &'static str {
+		match self {
+			WebhookEvent::StatusChange => "Status change",
+			WebhookEvent::FieldEdit => "Field edit",
+			WebhookEvent::NoteCreated => "Note created",
+			WebhookEvent::NoteDeleted => "Note deleted",
+			WebhookEvent::EquipmentCreated => "Equipment created",
+		}
+	}
+}
+
+impl std::fmt::Display for WebhookEvent {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			WebhookEvent::StatusChange => write!(f, "status_change"),
+			WebhookEvent::FieldEdit => write!(f, "field_edit"),
+			WebhookEvent::NoteCreated => write!(f, "note_created"),
+			WebhookEvent::NoteDeleted => write!(f, "note_deleted"),
+			WebhookEvent::EquipmentCreated => write!(f, "equipment_created"),
+		}
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum WebhookDeliveryStatus {
+	#[default]
+	Pending,
+	Delivered,
+	Failed,
+}
+
+impl WebhookDeliveryStatus {
+	pub fn parse(input: String) -> Self {
+		match input.to_lowercase().as_str() {
+			"delivered" => WebhookDeliveryStatus::Delivered,
+			"failed" => WebhookDeliveryStatus::Failed,
+			_ => WebhookDeliveryStatus::Pending,
+		}
+	}
+}
+
+impl std::fmt::Display for WebhookDeliveryStatus {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			WebhookDeliveryStatus::Pending => write!(f, "pending"),
+			WebhookDeliveryStatus::Delivered => write!(f, "delivered"),
+			WebhookDeliveryStatus::Failed => write!(f, "failed"),
+		}
+	}
+}
+
+/// An endpoint that gets told about the events it subscribed to
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WebhookData {
+	pub id: i32,
+	pub url: String,
+	pub secret: String,
+	pub events: Vec<WebhookEvent>,
+	pub enabled: bool,
+	pub create_date: DateTime<Utc>,
+}
+
+#[cfg(feature = "ssr")]
+impl sqlx::FromRow<'_, sqlx::postgres::PgRow> for WebhookData {
+	fn from_row(row: &sqlx::postgres::PgRow) -> Result<Self, sqlx::Error> {
+		let events: Vec<String> = row.try_get("events")?;
+		Ok(WebhookData {
+			id: row.try_get("id")?,
+			url: row.try_get("url")?,
+			secret: row.try_get("secret")?,
+			events: events.iter().filter_map(|event| WebhookEvent::parse(event)).collect(),
+			enabled: row.try_get("enabled")?,
+			create_date: row.try_get("create_date")?,
+		})
+	}
+}
+
+/// One payload queued for one webhook
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WebhookDeliveryData {
+	pub id: i32,
+	pub webhook: i32,
+	pub url: String,
+	pub event: String,
+	pub payload: String,
+	pub status: WebhookDeliveryStatus,
+	pub attempts: i32,
+	pub next_attempt: DateTime<Utc>,
+	pub response_status: Option<i32>,
+	pub error: Option<String>,
+	pub create_date: DateTime<Utc>,
+	pub delivered_at: Option<DateTime<Utc>>,
+}
+
+#[cfg(feature = "ssr")]
+impl sqlx::FromRow<'_, sqlx::postgres::PgRow> for WebhookDeliveryData {
+	fn from_row(row: &sqlx::postgres::PgRow) -> Result<Self, sqlx::Error> {
+		Ok(WebhookDeliveryData {
+			id: row.try_get("id")?,
+			webhook: row.try_get("webhook")?,
+			url: row.try_get("url")?,
+			event: row.try_get("event")?,
+			payload: row.try_get("payload")?,
+			status: WebhookDeliveryStatus::parse(row.try_get("status")?),
+			attempts: row.try_get("attempts")?,
+			next_attempt: row.try_get("next_attempt")?,
+			response_status: row.try_get("response_status")?,
+			error: row.try_get("error")?,
+			create_date: row.try_get("create_date")?,
+			delivered_at: row.try_get("delivered_at")?,
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn webhook_event_round_trip_test() {
+		for event in WebhookEvent::all() {
+			assert_eq!(WebhookEvent::parse(&event.to_string()), Some(event));
+		}
+		assert_eq!(WebhookEvent::parse("unknown"), None);
+	}
+}
@@ -0,0 +1,405 @@
+use crate::{
+	app::LoginAction,
+	components::{
+		button::{Button, ButtonVariant},
+		checkbox::{CheckboxGroup, CheckboxItem},
+		input::Input,
+	},
+	error_template::ErrorTemplate,
+	icons::PeopleLogo,
+	login::Login,
+	people::Heading,
+	webhooks::{WebhookData, WebhookDeliveryData, WebhookDeliveryStatus, WebhookEvent},
+};
+
+use leptos::*;
+use leptos_router::*;
+use std::collections::HashSet;
+
+stylance::import_style!(css, "webhooks.module.css");
+
+/// How many deliveries the delivery log lists
+pub const WEBHOOK_DELIVERIES_SHOWN: i64 = 50;
+
+/// Lets people admins register webhooks and follow what was delivered to them
+#[component]
+pub fn Webhooks() -> impl IntoView {
+	let add_webhook_action = create_server_action::<AddWebhook>();
+	let toggle_webhook_action = create_server_action::<ToggleWebhook>();
+	let delete_webhook_action = create_server_action::<DeleteWebhook>();
+	let retry_delivery_action = create_server_action::<RetryWebhookDelivery>();
+
+	let login_action = use_context::<LoginAction>().expect("No login action found in context");
+
+	let webhooks_data = create_resource(
+		move || {
+			(
+				login_action.version().get(),
+				add_webhook_action.version().get(),
+				toggle_webhook_action.version().get(),
+				delete_webhook_action.version().get(),
+			)
+		},
+		move |_| get_webhooks(),
+	);
+	let deliveries_data = create_resource(
+		move || {
+			(login_action.version().get(), retry_delivery_action.version().get(), delete_webhook_action.version().get())
+		},
+		move |_| get_webhook_deliveries(),
+	);
+
+	let events = create_rw_signal(HashSet::<String>::new());
+	let selected_events = move || events.get().into_iter().collect::<Vec<_>>().join(",");
+
+	let error = move || {
+		[
+			add_webhook_action.value().get(),
+			toggle_webhook_action.value().get(),
+			delete_webhook_action.value().get(),
+			retry_delivery_action.value().get(),
+		]
+		.into_iter()
+		.find_map(|value| value.and_then(Result::err))
+		.map(|error| {
+			view! {
+				<span class=css::error>
+					{error.to_string().replace("error reaching server to call server function: ", "")}
+				</span>
+			}
+		})
+	};
+
+	view! {
+		<Heading hide_new=true>
+			<PeopleLogo />
+			" Webhooks"
+		</Heading>
+
+		<Suspense fallback=move || view! { <p>Loading webhooks...</p> }>
+			<ErrorBoundary fallback=|errors| {
+				view! { <ErrorTemplate errors=errors /> }
+			}>
+				{move || {
+					webhooks_data
+						.get()
+						.map(|data| match data {
+							Err(error) => {
+								let error = error.to_string();
+								if error.contains("User not authenticated") {
+									view! { <Login redirect="/webhooks" /> }.into_view()
+								} else {
+									view! { <pre class="error">Server Error: {error}</pre> }.into_view()
+								}
+							}
+							Ok(webhooks) if webhooks.is_empty() => {
+								view! { <p class=css::muted>No webhooks registered yet</p> }.into_view()
+							}
+							Ok(webhooks) => {
+								view! {
+									<div class=css::webhooks>
+										{webhooks
+											.into_iter()
+											.map(|webhook| view! { <WebhookItem webhook toggle_webhook_action delete_webhook_action /> })
+											.collect_view()}
+									</div>
+								}
+									.into_view()
+							}
+						})
+				}}
+			</ErrorBoundary>
+		</Suspense>
+
+		<ActionForm action=add_webhook_action class=css::form>
+			<Input name="url" placeholder="https://example.com/hooks/codon" kind="url" required=true />
+			<CheckboxGroup value=events>
+				{WebhookEvent::all()
+					.into_iter()
+					.map(|event| view! { <CheckboxItem label=event.label() key=event.to_string() /> })
+					.collect_view()}
+			</CheckboxGroup>
+			<input type="hidden" name="events" prop:value=selected_events />
+			<Button kind="submit" variant=ButtonVariant::Outlined>
+				Add webhook
+			</Button>
+		</ActionForm>
+		{error}
+
+		<h2>Deliveries</h2>
+		<Transition>
+			{move || {
+				deliveries_data
+					.get()
+					.and_then(Result::ok)
+					.map(|deliveries| {
+						view! {
+							<table class=css::deliveries>
+								{deliveries
+									.into_iter()
+									.map(|delivery| view! { <DeliveryRow delivery retry_delivery_action /> })
+									.collect_view()}
+							</table>
+						}
+					})
+			}}
+		</Transition>
+	}
+}
+
+#[component]
+fn WebhookItem(
+	webhook: WebhookData,
+	toggle_webhook_action: Action<ToggleWebhook, Result<(), ServerFnError>>,
+	delete_webhook_action: Action<DeleteWebhook, Result<(), ServerFnError>>,
+) -> impl IntoView {
+	let id = webhook.id;
+	let enabled = webhook.enabled;
+
+	view! {
+		<div class=css::webhook>
+			<div class=css::webhook_heading>
+				<code class=css::url>{webhook.url}</code>
+				<span class=if enabled { css::enabled } else { css::muted }>
+					{if enabled { "enabled" } else { "disabled" }}
+				</span>
+				<Button
+					variant=ButtonVariant::Text
+					on_click=Callback::new(move |_: ev::MouseEvent| {
+						toggle_webhook_action.dispatch(ToggleWebhook { id, enabled: !enabled });
+					})
+				>
+					{if enabled { "Disable" } else { "Enable" }}
+				</Button>
+				<Button
+					variant=ButtonVariant::Text
+					on_click=Callback::new(move |_: ev::MouseEvent| {
+						delete_webhook_action.dispatch(DeleteWebhook { id });
+					})
+				>
+					Remove
+				</Button>
+			</div>
+			<p class=css::muted>
+				{webhook.events.iter().map(WebhookEvent::label).collect::<Vec<_>>().join(", ")}
+			</p>
+			<p class=css::muted>"Secret " <code>{webhook.secret}</code></p>
+		</div>
+	}
+}
+
+#[component]
+fn DeliveryRow(
+	delivery: WebhookDeliveryData,
+	retry_delivery_action: Action<RetryWebhookDelivery, Result<(), ServerFnError>>,
+) -> impl IntoView {
+	let id = delivery.id;
+	let status_class = match delivery.status {
+		WebhookDeliveryStatus::Pending => css::pending,
+		WebhookDeliveryStatus::Delivered => css::delivered,
+		WebhookDeliveryStatus::Failed => css::failed,
+	};
+	let can_retry = delivery.status != WebhookDeliveryStatus::Delivered;
+
+	view! {
+		<tr>
+			<td>{delivery.create_date.format("%d %b %Y %H:%M:%S").to_string()}</td>
+			<td>
+				<code>{delivery.event}</code>
+			</td>
+			<td class=css::url>{delivery.url}</td>
+			<td class=status_class>{delivery.status.to_string()}</td>
+			<td>{format!("{} attempts", delivery.attempts)}</td>
+			<td>{delivery.response_status.map(|status| status.to_string()).unwrap_or_default()}</td>
+			<td class=css::error>{delivery.error.unwrap_or_default()}</td>
+			<td>
+				<Show when=move || can_retry>
+					<Button
+						variant=ButtonVariant::Text
+						on_click=Callback::new(move |_: ev::MouseEvent| {
+							retry_delivery_action.dispatch(RetryWebhookDelivery { id });
+						})
+					>
+						Retry now
+					</Button>
+				</Show>
+			</td>
+		</tr>
+	}
+}
+
+/// Reads the comma separated event names the add form sends
+pub fn parse_webhook_events(input: &str) -> Result<Vec<WebhookEvent>, String> {
+	let mut events = Vec::new();
+	for name in input.split(',').map(str::trim).filter(|name| !name.is_empty()) {
+		match WebhookEvent::parse(name) {
+			Some(event) if !events.contains(&event) => events.push(event),
+			Some(_) => {},
+			None => return Err(format!("Unknown event \"{name}\"")),
+		}
+	}
+
+	if events.is_empty() {
+		return Err(String::from("Pick at least one event"));
+	}
+	Ok(events)
+}
+
+#[server(prefix = "/api")]
+pub async fn get_webhooks() -> Result<Vec<WebhookData>, ServerFnError> {
+	use crate::{auth::get_user, jobs::check_jobs_admin};
+
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	check_jobs_admin(user)?;
+
+	Ok(sqlx::query_as::<_, WebhookData>("SELECT * FROM webhooks ORDER BY id").fetch_all(&pool).await?)
+}
+
+#[server(prefix = "/api")]
+pub async fn get_webhook_deliveries() -> Result<Vec<WebhookDeliveryData>, ServerFnError> {
+	use crate::{auth::get_user, jobs::check_jobs_admin};
+
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	check_jobs_admin(user)?;
+
+	Ok(
+		sqlx::query_as::<_, WebhookDeliveryData>(
+			r#"
+			SELECT webhook_deliveries.*, webhooks.url
+			FROM
+				webhook_deliveries
+				JOIN webhooks ON webhook_deliveries.webhook = webhooks.id
+			ORDER BY webhook_deliveries.id DESC
+			LIMIT $1
+			"#,
+		)
+		.bind(WEBHOOK_DELIVERIES_SHOWN)
+		.fetch_all(&pool)
+		.await?,
+	)
+}
+
+#[server(prefix = "/api")]
+pub async fn add_webhook(url: String, events: String) -> Result<(), ServerFnError> {
+	use crate::{auth::get_user, jobs::check_jobs_admin};
+
+	use rand::Rng;
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	let user = check_jobs_admin(user)?;
+
+	let url = url.trim().to_string();
+	if !url.starts_with("http://") && !url.starts_with("https://") {
+		return Err(ServerFnError::Request(String::from("The URL has to start with http:// or https://")));
+	}
+	let events: Vec<String> = parse_webhook_events(&events)
+		.map_err::<ServerFnError, _>(ServerFnError::Request)?
+		.iter()
+		.map(ToString::to_string)
+		.collect();
+	let mut secret = [0u8; 32];
+	rand::thread_rng().fill(&mut secret);
+	let secret = hex::encode(secret);
+
+	sqlx::query("INSERT INTO webhooks (url, secret, events, person) VALUES ($1, $2, $3, $4)")
+		.bind(url)
+		.bind(secret)
+		.bind(events)
+		.bind(user.id)
+		.execute(&pool)
+		.await?;
+
+	Ok(())
+}
+
+#[server(prefix = "/api")]
+pub async fn toggle_webhook(id: i32, enabled: bool) -> Result<(), ServerFnError> {
+	use crate::{auth::get_user, jobs::check_jobs_admin};
+
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	check_jobs_admin(user)?;
+
+	sqlx::query("UPDATE webhooks SET enabled = $1 WHERE id = $2").bind(enabled).bind(id).execute(&pool).await?;
+
+	Ok(())
+}
+
+/// Removes a webhook together with its delivery log
+#[server(prefix = "/api")]
+pub async fn delete_webhook(id: i32) -> Result<(), ServerFnError> {
+	use crate::{auth::get_user, jobs::check_jobs_admin};
+
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	check_jobs_admin(user)?;
+
+	sqlx::query("DELETE FROM webhooks WHERE id = $1").bind(id).execute(&pool).await?;
+
+	Ok(())
+}
+
+/// Puts a delivery back in the queue and makes the delivery job due right away
+#[server(prefix = "/api")]
+pub async fn retry_webhook_delivery(id: i32) -> Result<(), ServerFnError> {
+	use crate::{auth::get_user, jobs::check_jobs_admin};
+
+	use sqlx::PgPool;
+
+	let pool = use_context::<PgPool>()
+		.ok_or_else::<ServerFnError, _>(|| ServerFnError::ServerError(String::from("Database not initialized")))?;
+	let user = get_user().await?;
+
+	check_jobs_admin(user)?;
+
+	let result = sqlx::query(
+		"UPDATE webhook_deliveries SET status = 'pending', next_attempt = NOW() WHERE id = $1 AND status <> 'delivered'",
+	)
+	.bind(id)
+	.execute(&pool)
+	.await?;
+	if result.rows_affected() == 0 {
+		return Err(ServerFnError::Request(String::from("This delivery can't be retried")));
+	}
+
+	sqlx::query("UPDATE jobs SET next_run = NOW() WHERE name = 'webhook_delivery'").execute(&pool).await?;
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn parse_webhook_events_test() {
+		assert_eq!(
+			parse_webhook_events("note_created, status_change,note_created"),
+			Ok(vec![WebhookEvent::NoteCreated, WebhookEvent::StatusChange])
+		);
+		assert!(parse_webhook_events("").is_err());
+		assert!(parse_webhook_events("status_change,exploded").is_err());
+	}
+}
@@ -16,16 +16,10 @@ struct GetUserCallVisitor {
 
 impl<'ast> Visit<'ast> for GetUserCallVisitor {
 	fn visit_expr_call(&mut self, node: &'ast ExprCall) {
-		match *node.func {
-			ExprPath(ref expr_path) => match expr_path.path.segments.last() {
-				Some(segment) => {
-					if segment.ident == "get_user" {
-						self.found = true;
-					}
-				},
-				_ => {},
-			},
-			_ => {},
+		if let ExprPath(ref expr_path) = *node.func {
+			if expr_path.path.segments.last().is_some_and(|segment| segment.ident == "get_user") {
+				self.found = true;
+			}
 		}
 	}
 }
@@ -85,20 +79,17 @@ fn all_server_functions_call_get_user() {
 		let syntax = syn::parse_file(&src).expect("Failed to parse file");
 
 		for item in syntax.items {
-			match item {
-				Fn(ref func) => {
-					if is_server_function(&func.attrs) {
-						let mut visitor = GetUserCallVisitor { found: false };
-						visitor.visit_item_fn(func);
+			if let Fn(ref func) = item {
+				if is_server_function(&func.attrs) {
+					let mut visitor = GetUserCallVisitor { found: false };
+					visitor.visit_item_fn(func);
 
-						let opt_out = has_doc_comment(&func.attrs, "![allow_no_get_user]");
+					let opt_out = has_doc_comment(&func.attrs, "![allow_no_get_user]");
 
-						if !visitor.found && !opt_out {
-							server_functions_missing_get_user.push(format!("{} in file {:?}", func.sig.ident, file_path));
-						}
+					if !visitor.found && !opt_out {
+						server_functions_missing_get_user.push(format!("{} in file {:?}", func.sig.ident, file_path));
 					}
-				},
-				_ => {},
+				}
 			}
 		}
 	}